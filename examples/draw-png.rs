@@ -2,29 +2,31 @@
 //!
 //! This example demonstrates how to use the `gem` crate to create a simple PNG image.
 
-use gem::prelude::*;
+use gem::{composite::PorterDuff, image::ImageViewMut, prelude::*};
 
 fn main() {
     let temp_dir = std::env::temp_dir();
     let file_path = temp_dir.join("red_box.png");
     let mut red_box_50x50 = vec![Abgr8888::from_abgr(0xFF, 0x00, 0x00, 0xFF); 50 * 50];
 
-    // Make a semi-transparent blue box in the middle of the image
-    for y in 0..50 {
-        for x in 0..50 {
-            if (10..40).contains(&x) && (10..40).contains(&y) {
-                red_box_50x50[y * 50 + x] = Abgr8888::from_abgr(0x7F, 0x7F, 0, 0xFF);
-            }
-        }
+    // Composite a semi-transparent blue box over the middle of the image
+    let blue = Abgr8888::from_abgr(0x80, 0xFF, 0x00, 0x00);
+    let mut image = ImageViewMut::new(&mut red_box_50x50, 50, 50).unwrap();
+    for row in image.view_mut(10, 10, 30, 30).unwrap().rows_mut() {
+        PorterDuff::SrcOver.composite_fill(blue, row);
     }
 
+    // PNG expects bytes in the order red, green, blue, alpha
+    let bytes: Vec<u8> = red_box_50x50
+        .iter()
+        .flat_map(|color| [color.red(), color.green(), color.blue(), color.alpha()])
+        .collect();
+
     let mut encoder = png::Encoder::new(std::fs::File::create(&file_path).unwrap(), 50_u32, 50_u32);
     encoder.set_color(png::ColorType::Rgba);
     encoder.set_depth(png::BitDepth::Eight);
     let mut writer = encoder.write_header().unwrap();
-    writer
-        .write_image_data(bytemuck::cast_slice(&red_box_50x50))
-        .unwrap();
+    writer.write_image_data(&bytes).unwrap();
 
     println!("Red box image created at: {}", file_path.display());
     open::that(file_path).unwrap_or_else(|_| {
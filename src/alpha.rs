@@ -28,9 +28,19 @@
 //! - [`Alpha<T>`]; a generic Alpha channel representation with a single component
 //! - [`AlphaFirst<A, C>`]; a generic Alpha channel representation with alpha first,
 //! - [`AlphaLast<A, C>`]; a generic Alpha channel representation with alpha last
+//!
+//! ## Premultiplied Alpha
+//!
+//! The types above store _straight_ alpha. [`Premultiplied<C>`] wraps a color whose components
+//! are premultiplied by alpha, and converts to and from straight alpha with correct rounding.
+
+use crate::component::Component;
 
 mod has_alpha;
+mod premultiplied;
+
 pub use has_alpha::HasAlpha;
+pub use premultiplied::Premultiplied;
 
 /// Alpha-only color type.
 ///
@@ -39,6 +49,11 @@ pub use has_alpha::HasAlpha;
 /// The layout of this type is always the same as the underlying type `T` (`#[repr(transparent)]`).
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(transparent)]
 pub struct Alpha<T> {
     alpha: T,
@@ -69,7 +84,7 @@ impl<T> Alpha<T> {
 
 impl<T> HasAlpha for Alpha<T>
 where
-    T: Copy,
+    T: Component,
 {
     type Component = T;
 
@@ -101,6 +116,7 @@ pub type Alpha8 = Alpha<u8>;
 /// }
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct AlphaFirst<A, C> {
     alpha: A,
@@ -157,7 +173,7 @@ where
 
 impl<A, C> HasAlpha for AlphaFirst<A, C>
 where
-    A: Copy,
+    A: Component,
 {
     type Component = A;
 
@@ -182,6 +198,7 @@ where
 /// }
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct AlphaLast<A, C> {
     color: C,
@@ -238,7 +255,7 @@ where
 
 impl<A, C> HasAlpha for AlphaLast<A, C>
 where
-    A: Copy,
+    A: Component,
 {
     type Component = A;
 
@@ -251,6 +268,34 @@ where
     }
 }
 
+/// Implements a `Has{*}` trait for [`AlphaFirst`] and [`AlphaLast`] by delegating to the color.
+macro_rules! impl_alpha_wrappers {
+    ($trait:ident, $get:ident, $set:ident, $with:ident) => {
+        $crate::alpha::impl_alpha_wrappers!(@impl AlphaFirst, $trait, $get, $set, $with);
+        $crate::alpha::impl_alpha_wrappers!(@impl AlphaLast, $trait, $get, $set, $with);
+    };
+    (@impl $wrapper:ident, $trait:ident, $get:ident, $set:ident, $with:ident) => {
+        impl<A, C> $trait for $crate::alpha::$wrapper<A, C>
+        where
+            A: Copy,
+            C: Copy + $trait,
+        {
+            type Component = C::Component;
+
+            fn $get(&self) -> Self::Component {
+                self.color().$get()
+            }
+
+            fn $set(&mut self, value: Self::Component) {
+                let color = self.color().$with(value);
+                *self = $crate::alpha::$wrapper::with_color(self.alpha(), color);
+            }
+        }
+    };
+}
+
+pub(crate) use impl_alpha_wrappers;
+
 #[cfg(test)]
 mod tests {
     use super::*;
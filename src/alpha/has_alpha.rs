@@ -1,7 +1,9 @@
+use crate::component::Component;
+
 /// A trait for types that have an alpha (transparency) component.
 pub trait HasAlpha {
     /// The type of the alpha component.
-    type Component;
+    type Component: Component;
 
     /// Creates a new color with the given alpha component.
     ///
@@ -0,0 +1,286 @@
+use crate::{
+    component::{Component, sealed::Scaled},
+    rgb::RgbaColor,
+};
+
+/// A color with red, green, and blue components premultiplied by its alpha component.
+///
+/// Colors such as [`AlphaFirst<A, C>`][] and [`AlphaLast<A, C>`][] store _straight_ alpha, where
+/// the red, green, and blue components are independent of alpha. Many GPU APIs and compositors
+/// instead expect _premultiplied_ alpha, where each component has already been multiplied by
+/// alpha; filtering or blending straight colors as if they were premultiplied produces dark
+/// fringes around transparent edges.
+///
+/// Wrapping colors in `Premultiplied<C>` records which representation they use, so that the two
+/// cannot be mixed up:
+///
+/// ```rust
+/// use gem::{alpha::Premultiplied, rgb::Abgr8888};
+///
+/// let straight = Abgr8888::from_abgr(128, 0, 0, 255);
+/// let premultiplied = Premultiplied::premultiply(straight);
+/// assert_eq!(premultiplied.color(), Abgr8888::from_abgr(128, 0, 0, 128));
+/// ```
+///
+/// ## Layout
+///
+/// The layout of this type is always the same as the underlying type `C` (`#[repr(transparent)]`).
+///
+/// [`AlphaFirst<A, C>`]: crate::alpha::AlphaFirst
+/// [`AlphaLast<A, C>`]: crate::alpha::AlphaLast
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[repr(transparent)]
+pub struct Premultiplied<C> {
+    color: C,
+}
+
+impl<C> Premultiplied<C> {
+    /// Creates a new instance of `Premultiplied`, asserting that `color` is already premultiplied.
+    ///
+    /// To convert a color with straight alpha, use [`Premultiplied::premultiply`] instead.
+    #[must_use]
+    pub const fn new(color: C) -> Self {
+        Self { color }
+    }
+
+    /// Returns the premultiplied color.
+    #[must_use]
+    pub const fn color(&self) -> C
+    where
+        C: Copy,
+    {
+        self.color
+    }
+
+    /// Consumes and returns the premultiplied color.
+    #[must_use]
+    pub fn into_inner(self) -> C {
+        self.color
+    }
+}
+
+impl<C> Premultiplied<C>
+where
+    C: RgbaColor,
+{
+    /// Multiplies the red, green, and blue components of a straight color by its alpha.
+    ///
+    /// Integer components, including packed formats such as [`Argb4444`][], are rounded to the
+    /// nearest value, so a fully transparent color always becomes transparent black.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{alpha::Premultiplied, rgb::Argb4444};
+    ///
+    /// let color = Premultiplied::premultiply(Argb4444::from_argb(5, 15, 9, 0));
+    /// assert_eq!(color.color(), Argb4444::from_argb(5, 5, 3, 0));
+    /// ```
+    ///
+    /// [`Argb4444`]: crate::rgb::Argb4444
+    #[must_use]
+    pub fn premultiply(color: C) -> Self {
+        let alpha = color.alpha();
+        let (r, g, b) = (color.red(), color.green(), color.blue());
+        let mut color = color;
+        color.set_red(multiply(r, alpha));
+        color.set_green(multiply(g, alpha));
+        color.set_blue(multiply(b, alpha));
+        Self::new(color)
+    }
+
+    /// Divides the red, green, and blue components by alpha, returning a straight color.
+    ///
+    /// Integer components are rounded to the nearest value, and clamped if a component was larger
+    /// than alpha. If alpha is zero, the color is transparent black.
+    ///
+    /// For integer components, premultiplying the result returns the original color, but
+    /// unpremultiplying is lossy for small alpha values.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{alpha::Premultiplied, rgb::Abgr8888};
+    ///
+    /// let color = Premultiplied::new(Abgr8888::from_abgr(128, 0, 0, 128));
+    /// assert_eq!(color.unpremultiply(), Abgr8888::from_abgr(128, 0, 0, 255));
+    ///
+    /// let color = Premultiplied::new(Abgr8888::from_abgr(0, 10, 20, 30));
+    /// assert_eq!(color.unpremultiply(), Abgr8888::from_abgr(0, 0, 0, 0));
+    /// ```
+    #[must_use]
+    pub fn unpremultiply(self) -> C {
+        let mut color = self.color;
+        let alpha = color.alpha();
+        let (r, g, b) = (color.red(), color.green(), color.blue());
+        color.set_red(divide(r, alpha));
+        color.set_green(divide(g, alpha));
+        color.set_blue(divide(b, alpha));
+        color
+    }
+}
+
+/// Returns `value × alpha`, rounded to the nearest value of `T`.
+#[allow(clippy::cast_possible_truncation)]
+fn multiply<T: Component, A: Component>(value: T, alpha: A) -> T {
+    match (value.to_scaled(), alpha.to_scaled()) {
+        (
+            Scaled::Int { value, max },
+            Scaled::Int {
+                value: alpha,
+                max: alpha_max,
+            },
+        ) => {
+            let (value, alpha, alpha_max) =
+                (u128::from(value), u128::from(alpha), u128::from(alpha_max));
+            let value = (2 * value * alpha + alpha_max) / (2 * alpha_max);
+            T::from_scaled(Scaled::Int {
+                value: value as u64,
+                max,
+            })
+        }
+        (value, alpha) => T::from_scaled(Scaled::Float(value.to_float() * alpha.to_float())),
+    }
+}
+
+/// Returns `value / alpha`, rounded to the nearest value of `T`, or zero if `alpha` is zero.
+#[allow(clippy::cast_possible_truncation)]
+fn divide<T: Component, A: Component>(value: T, alpha: A) -> T {
+    match (value.to_scaled(), alpha.to_scaled()) {
+        (_, Scaled::Int { value: 0, .. }) => T::MIN,
+        (
+            Scaled::Int { value, max },
+            Scaled::Int {
+                value: alpha,
+                max: alpha_max,
+            },
+        ) => {
+            let (value, alpha, alpha_max) =
+                (u128::from(value), u128::from(alpha), u128::from(alpha_max));
+            let value = (2 * value * alpha_max + alpha) / (2 * alpha);
+            T::from_scaled(Scaled::Int {
+                value: value.min(u128::from(max)) as u64,
+                max,
+            })
+        }
+        (value, alpha) => {
+            let alpha = alpha.to_float();
+            if alpha <= 0.0 {
+                T::MIN
+            } else {
+                T::from_scaled(Scaled::Float(value.to_float() / alpha))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        alpha::HasAlpha,
+        component::Bits,
+        rgb::{Abgr8888, Argb1555, Argb4444, HasRed, Rgbaf32},
+    };
+
+    #[test]
+    fn repr_transparent() {
+        let color = Premultiplied::new(Abgr8888::from_abgr(1, 2, 3, 4));
+        let bytes = unsafe { core::mem::transmute::<Premultiplied<Abgr8888>, [u8; 4]>(color) };
+        assert_eq!(bytes, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn premultiply_u8_rounds_to_nearest() {
+        for alpha in 0..=255u8 {
+            for value in 0..=255u8 {
+                let color = Premultiplied::premultiply(Abgr8888::from_abgr(alpha, 0, 0, value));
+                let expected = (f64::from(value) * f64::from(alpha) / 255.0).round();
+                assert_eq!(
+                    f64::from(color.color().red()),
+                    expected,
+                    "{value} × {alpha}"
+                );
+                assert_eq!(color.color().alpha(), alpha);
+            }
+        }
+    }
+
+    #[test]
+    fn unpremultiply_u8_round_trips() {
+        for alpha in 1..=255u8 {
+            for value in 0..=alpha {
+                let premultiplied = Premultiplied::new(Abgr8888::from_abgr(alpha, 0, 0, value));
+                let straight = premultiplied.unpremultiply();
+                let expected = (f64::from(value) * 255.0 / f64::from(alpha)).round();
+                assert_eq!(f64::from(straight.red()), expected, "{value} / {alpha}");
+                assert_eq!(Premultiplied::premultiply(straight), premultiplied);
+            }
+        }
+    }
+
+    #[test]
+    fn unpremultiply_u8_zero_alpha_is_transparent_black() {
+        let color = Premultiplied::new(Abgr8888::from_abgr(0, 255, 128, 1));
+        assert_eq!(color.unpremultiply(), Abgr8888::from_abgr(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn unpremultiply_u8_clamps() {
+        let color = Premultiplied::new(Abgr8888::from_abgr(64, 0, 65, 255));
+        assert_eq!(color.unpremultiply(), Abgr8888::from_abgr(64, 0, 255, 255));
+    }
+
+    #[test]
+    fn premultiply_argb4444_uses_4_bit_rounding() {
+        for alpha in 0..=15u8 {
+            for value in 0..=15u8 {
+                let color = Premultiplied::premultiply(Argb4444::from_argb(alpha, value, 0, 0));
+                let expected = (f64::from(value) * f64::from(alpha) / 15.0).round();
+                assert_eq!(f64::from(color.color().red().get()), expected);
+                if value <= alpha && alpha > 0 {
+                    let straight = Premultiplied::new(Argb4444::from_argb(alpha, value, 0, 0));
+                    let straight = straight.unpremultiply();
+                    assert_eq!(Premultiplied::premultiply(straight).color().red(), value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn argb1555() {
+        let opaque = Argb1555::from_rgb(31, 16, 1);
+        assert_eq!(Premultiplied::premultiply(opaque).color(), opaque);
+        assert_eq!(Premultiplied::new(opaque).unpremultiply(), opaque);
+
+        let transparent = opaque.with_alpha(Bits::new(0));
+        assert_eq!(
+            Premultiplied::premultiply(transparent).color(),
+            Argb1555::TRANSPARENT
+        );
+        assert_eq!(
+            Premultiplied::new(transparent).unpremultiply(),
+            Argb1555::TRANSPARENT
+        );
+    }
+
+    #[test]
+    fn rgbaf32() {
+        let straight = Rgbaf32::from_rgba(1.0, 0.5, 0.25, 0.5);
+        let premultiplied = Premultiplied::premultiply(straight);
+        assert_eq!(
+            premultiplied.color(),
+            Rgbaf32::from_rgba(0.5, 0.25, 0.125, 0.5)
+        );
+        assert_eq!(premultiplied.unpremultiply(), straight);
+    }
+
+    #[test]
+    fn rgbaf32_zero_alpha() {
+        let color = Premultiplied::new(Rgbaf32::from_rgba(0.5, 0.5, 0.5, 0.0));
+        assert_eq!(color.unpremultiply(), Rgbaf32::default());
+    }
+}
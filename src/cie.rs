@@ -0,0 +1,433 @@
+//! Colorimetric 🔬 color spaces defined by the CIE, relative to a reference white point.
+//!
+//! This module contains:
+//!
+//! - Reference white points, [`D65`], [`D50`], and [`D60`], and the [`WhitePoint`] trait to
+//!   define others
+//! - Generic types for the CIE XYZ, xyY, CIELAB, and CIE `LCh` color spaces
+//! - Traits that work on the opponent axes of Lab-like color spaces
+//!
+//! Each type has a white point type parameter `W`, which defaults to [`D65`]; colors relative to
+//! different white points are different types, and are converted explicitly with
+//! [`Xyz::adapt`].
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{cie::{D50, Lab}, encoding::Srgb, rgb::Rgbaf32};
+//! use gem::alpha::AlphaLast;
+//!
+//! let red = Srgb::new(Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.5));
+//! let lab: AlphaLast<f32, Lab<f32, D50>> = red.into();
+//!
+//! let (l, a, b) = lab.color().into_lab();
+//! assert!((l - 54.29).abs() < 0.01);
+//! assert!((a - 80.80).abs() < 0.01);
+//! assert!((b - 69.89).abs() < 0.01);
+//! assert_eq!(lab.alpha(), 0.5);
+//! ```
+//!
+//! ## Predefined Types
+//!
+//! Type         | Description
+//! ------------ | -----------
+//! [`Xyzf32`]   | CIE 1931 XYZ tristimulus values, as 32-bit floating-point
+//! [`Yxyf32`]   | CIE 1931 luminance and chromaticity, as 32-bit floating-point
+//! [`Labf32`]   | CIELAB lightness and opponent axes, as 32-bit floating-point
+//! [`Lchf32`]   | CIE `LCh` lightness, chroma, and hue, as 32-bit floating-point
+//!
+//! ## Generic Types
+//!
+//! - [`Xyz<T, W>`]; `X`, `Y`, and `Z` tristimulus values
+//! - [`Yxy<T, W>`]; luminance `Y` with `x` and `y` chromaticity
+//! - [`Lab<T, W>`]; lightness `L*`, and opponent axes `a*` and `b*`
+//! - [`Lch<T, W>`]; lightness `L*`, chroma `C*`, and hue `h°`
+//!
+//! ## Conversions
+//!
+//! Every floating-point type converts to and from [`Srgb<Rgbf32>`] and [`LinearRgb<Rgbf32>`]
+//! using [`From`]; RGB colors are converted to XYZ through linear light, and then adapted to the
+//! white point `W` with the Bradford transform. Colors with alpha, such as [`Rgbaf32`], convert
+//! to and from [`AlphaLast`], keeping alpha unchanged.
+//!
+//! The conversion matrices are derived from the chromaticities of the sRGB primaries and white
+//! points in double precision, rather than copied from rounded published values.
+//!
+//! [`Srgb<Rgbf32>`]: crate::encoding::Srgb
+//! [`LinearRgb<Rgbf32>`]: crate::encoding::LinearRgb
+//! [`Rgbaf32`]: crate::rgb::Rgbaf32
+//! [`AlphaLast`]: crate::alpha::AlphaLast
+
+use crate::{
+    alpha::AlphaLast,
+    encoding::{LinearRgb, Srgb},
+    math::{Matrix3, diagonal, invert, mul_matrix, mul_vector},
+    rgb::{RgbColor, Rgbaf32, Rgbf32},
+};
+
+mod impl_alpha_wrappers;
+mod lab;
+mod lch;
+mod traits;
+mod white_point;
+mod xyz;
+mod yxy;
+
+pub use lab::{Lab, Labf32};
+pub use lch::{Lch, Lchf32};
+pub use traits::*;
+pub use white_point::{D50, D60, D65, WhitePoint};
+pub use xyz::{Xyz, Xyzf32};
+pub use yxy::{Yxy, Yxyf32};
+
+pub(crate) use white_point::chromaticity_to_xyz;
+
+/// Implements a `Has{*}` trait for a type with a white point by reading and writing a field.
+macro_rules! impl_has_field {
+    ($ty:ident, $trait:path { $get:ident, $set:ident, $field:ident }) => {
+        impl<T, W> $trait for $ty<T, W>
+        where
+            T: Copy,
+        {
+            type Component = T;
+
+            fn $get(&self) -> Self::Component {
+                self.$field
+            }
+
+            fn $set(&mut self, value: Self::Component) {
+                self.$field = value;
+            }
+        }
+    };
+}
+
+use impl_has_field;
+
+/// Returns the matrix that converts linear RGB into XYZ, relative to the white point of the space.
+///
+/// The matrix is derived from the `x` and `y` chromaticities of the red, green, and blue primaries,
+/// so that the sum of the three columns is the white point.
+pub(crate) const fn rgb_to_xyz_matrix(primaries: [[f64; 2]; 3], white: [f64; 3]) -> Matrix3 {
+    let [r, g, b] = [
+        chromaticity_to_xyz(primaries[0]),
+        chromaticity_to_xyz(primaries[1]),
+        chromaticity_to_xyz(primaries[2]),
+    ];
+    let matrix = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+    let scale = mul_vector(&invert(&matrix), white);
+    mul_matrix(&matrix, &diagonal(scale))
+}
+
+/// The Bradford cone response matrix.
+#[rustfmt::skip]
+const BRADFORD: Matrix3 = [
+    [ 0.8951,  0.2664, -0.1614],
+    [-0.7502,  1.7135,  0.0367],
+    [ 0.0389, -0.0685,  1.0296],
+];
+
+/// Returns the Bradford matrix that adapts XYZ relative to `src` into XYZ relative to `dst`.
+#[allow(clippy::float_cmp)]
+pub(crate) const fn adaptation_matrix(src: [f64; 3], dst: [f64; 3]) -> Matrix3 {
+    if src[0] == dst[0] && src[1] == dst[1] && src[2] == dst[2] {
+        return diagonal([1.0; 3]);
+    }
+    let (src, dst) = (mul_vector(&BRADFORD, src), mul_vector(&BRADFORD, dst));
+    let scale = diagonal([dst[0] / src[0], dst[1] / src[1], dst[2] / src[2]]);
+    mul_matrix(&invert(&BRADFORD), &mul_matrix(&scale, &BRADFORD))
+}
+
+/// The `x` and `y` chromaticities of the sRGB (and Rec. 709) red, green, and blue primaries.
+const SRGB_PRIMARIES: [[f64; 2]; 3] = [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]];
+
+/// Converts linear sRGB into XYZ relative to [`D65`].
+const LINEAR_SRGB_TO_XYZ: Matrix3 = rgb_to_xyz_matrix(SRGB_PRIMARIES, D65::XYZ);
+
+/// Converts XYZ relative to [`D65`] into linear sRGB.
+pub(crate) const XYZ_TO_LINEAR_SRGB: Matrix3 = invert(&LINEAR_SRGB_TO_XYZ);
+
+impl<W> From<LinearRgb<Rgbf32>> for Xyz<f32, W>
+where
+    W: WhitePoint,
+{
+    fn from(color: LinearRgb<Rgbf32>) -> Self {
+        let matrix =
+            const { mul_matrix(&adaptation_matrix(D65::XYZ, W::XYZ), &LINEAR_SRGB_TO_XYZ) };
+        let (r, g, b) = color.into_inner().into_rgb();
+        let rgb = [f64::from(r), f64::from(g), f64::from(b)];
+        Self::from_f64(mul_vector(&matrix, rgb))
+    }
+}
+
+impl<W> From<Xyz<f32, W>> for LinearRgb<Rgbf32>
+where
+    W: WhitePoint,
+{
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: Xyz<f32, W>) -> Self {
+        let matrix =
+            const { mul_matrix(&XYZ_TO_LINEAR_SRGB, &adaptation_matrix(W::XYZ, D65::XYZ)) };
+        let [r, g, b] = mul_vector(&matrix, color.into_f64());
+        LinearRgb::new(Rgbf32::from_rgb(r as f32, g as f32, b as f32))
+    }
+}
+
+/// Implements conversions between a CIE type and sRGB or linear RGB colors, through XYZ.
+macro_rules! impl_from_rgb {
+    ($($ty:ident),*) => {$(
+        impl<W> From<Srgb<Rgbf32>> for $ty<f32, W>
+        where
+            W: WhitePoint,
+        {
+            fn from(color: Srgb<Rgbf32>) -> Self {
+                Self::from(color.into_linear())
+            }
+        }
+
+        impl<W> From<$ty<f32, W>> for Srgb<Rgbf32>
+        where
+            W: WhitePoint,
+        {
+            fn from(color: $ty<f32, W>) -> Self {
+                LinearRgb::<Rgbf32>::from(color).into_srgb()
+            }
+        }
+
+        impl<W> From<LinearRgb<Rgbaf32>> for AlphaLast<f32, $ty<f32, W>>
+        where
+            W: WhitePoint,
+        {
+            fn from(color: LinearRgb<Rgbaf32>) -> Self {
+                let (rgb, alpha) = color.into_inner().into_inner();
+                AlphaLast::with_color(alpha, $ty::from(LinearRgb::new(rgb)))
+            }
+        }
+
+        impl<W> From<AlphaLast<f32, $ty<f32, W>>> for LinearRgb<Rgbaf32>
+        where
+            W: WhitePoint,
+        {
+            fn from(color: AlphaLast<f32, $ty<f32, W>>) -> Self {
+                let (color, alpha) = color.into_inner();
+                let rgb = LinearRgb::<Rgbf32>::from(color).into_inner();
+                LinearRgb::new(AlphaLast::with_color(alpha, rgb))
+            }
+        }
+
+        impl<W> From<Srgb<Rgbaf32>> for AlphaLast<f32, $ty<f32, W>>
+        where
+            W: WhitePoint,
+        {
+            fn from(color: Srgb<Rgbaf32>) -> Self {
+                Self::from(color.into_linear())
+            }
+        }
+
+        impl<W> From<AlphaLast<f32, $ty<f32, W>>> for Srgb<Rgbaf32>
+        where
+            W: WhitePoint,
+        {
+            fn from(color: AlphaLast<f32, $ty<f32, W>>) -> Self {
+                LinearRgb::<Rgbaf32>::from(color).into_srgb()
+            }
+        }
+    )*};
+}
+
+impl_from_rgb!(Xyz, Yxy, Lab, Lch);
+
+/// Implements conversions between a CIE type and linear RGB colors, through XYZ.
+macro_rules! impl_from_linear_rgb {
+    ($($ty:ident),*) => {$(
+        impl<W> From<LinearRgb<Rgbf32>> for $ty<f32, W>
+        where
+            W: WhitePoint,
+        {
+            fn from(color: LinearRgb<Rgbf32>) -> Self {
+                Self::from(Xyz::<f32, W>::from(color))
+            }
+        }
+
+        impl<W> From<$ty<f32, W>> for LinearRgb<Rgbf32>
+        where
+            W: WhitePoint,
+        {
+            fn from(color: $ty<f32, W>) -> Self {
+                Self::from(Xyz::<f32, W>::from(color))
+            }
+        }
+    )*};
+}
+
+impl_from_linear_rgb!(Yxy, Lab, Lch);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::cylindrical::{HasChroma, HasHue, HasLightness};
+
+    fn assert_near(actual: (f32, f32, f32), expected: (f32, f32, f32), tolerance: f32) {
+        let (a, e) = (actual, expected);
+        assert!(
+            (a.0 - e.0).abs() < tolerance
+                && (a.1 - e.1).abs() < tolerance
+                && (a.2 - e.2).abs() < tolerance,
+            "expected {e:?}, got {a:?}"
+        );
+    }
+
+    fn srgb(r: f32, g: f32, b: f32) -> Srgb<Rgbf32> {
+        Srgb::new(Rgbf32::from_rgb(r, g, b))
+    }
+
+    #[test]
+    fn linear_srgb_to_xyz_matrix() {
+        // IEC 61966-2-1, rounded to four decimal places.
+        #[rustfmt::skip]
+        let expected = [
+            [0.4124, 0.3576, 0.1805],
+            [0.2126, 0.7152, 0.0722],
+            [0.0193, 0.1192, 0.9505],
+        ];
+        for (row, values) in LINEAR_SRGB_TO_XYZ.iter().enumerate() {
+            for (col, value) in values.iter().enumerate() {
+                assert!(
+                    (value - expected[row][col]).abs() < 5e-5,
+                    "{LINEAR_SRGB_TO_XYZ:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bradford_d65_to_d50_matrix() {
+        // Bruce Lindbloom, "Chromatic Adaptation", Bradford D65 to D50.
+        #[rustfmt::skip]
+        let expected = [
+            [ 1.047_811_2,  0.022_886_6, -0.050_127_0],
+            [ 0.029_542_4,  0.990_484_4, -0.017_049_1],
+            [-0.009_234_5,  0.015_043_6,  0.752_131_6],
+        ];
+        let actual = adaptation_matrix(D65::XYZ, D50::XYZ);
+        for (row, values) in actual.iter().enumerate() {
+            for (col, value) in values.iter().enumerate() {
+                assert!((value - expected[row][col]).abs() < 5e-4, "{actual:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn srgb_primaries_to_xyz() {
+        // IEC 61966-2-1; each primary is a column of the matrix.
+        let cases = [
+            (srgb(1.0, 0.0, 0.0), (0.4124, 0.2126, 0.0193)),
+            (srgb(0.0, 1.0, 0.0), (0.3576, 0.7152, 0.1192)),
+            (srgb(0.0, 0.0, 1.0), (0.1805, 0.0722, 0.9505)),
+            (srgb(1.0, 1.0, 1.0), (0.9505, 1.0, 1.0891)),
+        ];
+        for (rgb, xyz) in cases {
+            assert_near(Xyzf32::from(rgb).into_xyz(), xyz, 1e-4);
+        }
+    }
+
+    #[test]
+    fn srgb_to_lab_d65() {
+        // Bruce Lindbloom, "CIE Color Calculator", sRGB with a D65 reference white. The calculator
+        // uses a white point rounded to five digits, which accounts for differences below 0.01.
+        let cases = [
+            (srgb(1.0, 0.0, 0.0), (53.2408, 80.0925, 67.2032)),
+            (srgb(0.0, 1.0, 0.0), (87.7347, -86.1827, 83.1793)),
+            (srgb(0.0, 0.0, 1.0), (32.2970, 79.1875, -107.8602)),
+            (srgb(1.0, 1.0, 1.0), (100.0, 0.0, 0.0)),
+        ];
+        for (rgb, lab) in cases {
+            assert_near(Labf32::from(rgb).into_lab(), lab, 1e-2);
+        }
+    }
+
+    #[test]
+    fn srgb_to_lab_d50() {
+        // CSS Color Module Level 4, sample conversions with Bradford adaptation to D50.
+        let cases = [
+            (srgb(1.0, 0.0, 0.0), (54.2905, 80.8049, 69.8910)),
+            (srgb(0.0, 1.0, 0.0), (87.8185, -79.2711, 80.9946)),
+            (srgb(0.0, 0.0, 1.0), (29.5683, 68.2874, -112.0294)),
+            (srgb(1.0, 1.0, 1.0), (100.0, 0.0, 0.0)),
+        ];
+        for (rgb, lab) in cases {
+            assert_near(Lab::<f32, D50>::from(rgb).into_lab(), lab, 1e-3);
+        }
+    }
+
+    #[test]
+    fn srgb_to_lch_d50() {
+        let lch = Lch::<f32, D50>::from(srgb(1.0, 0.0, 0.0));
+        assert_near(
+            (lch.lightness(), lch.chroma(), lch.hue()),
+            (54.2905, 106.8372, 40.8526),
+            1e-2,
+        );
+    }
+
+    #[test]
+    fn srgb_to_yxy_white_is_white_point() {
+        assert_near(
+            Yxyf32::from(srgb(1.0, 1.0, 1.0)).into_yxy(),
+            (1.0, 0.3127, 0.3290),
+            1e-6,
+        );
+        assert_near(
+            Yxy::<f32, D50>::from(srgb(1.0, 1.0, 1.0)).into_yxy(),
+            (1.0, 0.3457, 0.3585),
+            1e-6,
+        );
+    }
+
+    #[test]
+    fn srgb_round_trips() {
+        for value in 0..=16 {
+            let v = f32::from(u8::try_from(value).unwrap()) / 16.0;
+            let rgb = srgb(v, 1.0 - v, v / 2.0);
+            let expected = rgb.color().into_rgb();
+            let via_lab = Srgb::<Rgbf32>::from(Lab::<f32, D50>::from(rgb));
+            assert_near(via_lab.color().into_rgb(), expected, 1e-5);
+            let via_lch = Srgb::<Rgbf32>::from(Lchf32::from(rgb));
+            assert_near(via_lch.color().into_rgb(), expected, 1e-5);
+            let via_yxy = Srgb::<Rgbf32>::from(Yxy::<f32, D50>::from(rgb));
+            assert_near(via_yxy.color().into_rgb(), expected, 1e-5);
+        }
+    }
+
+    #[test]
+    fn linear_rgb_to_xyz_d50() {
+        let xyz = Xyz::<f32, D50>::from(LinearRgb::new(Rgbf32::from_rgb(1.0, 1.0, 1.0)));
+        #[allow(clippy::cast_possible_truncation)]
+        let expected = (D50::XYZ[0] as f32, 1.0, D50::XYZ[2] as f32);
+        assert_near(xyz.into_xyz(), expected, 1e-6);
+    }
+
+    #[test]
+    fn alpha_last_passes_alpha_through() {
+        let rgba = Srgb::new(Rgbaf32::from_rgba(0.2, 0.4, 0.6, 0.25));
+        let lab = AlphaLast::<f32, Lab<f32, D50>>::from(rgba);
+        assert_eq!(lab.alpha(), 0.25);
+
+        let back = Srgb::<Rgbaf32>::from(lab).into_inner();
+        assert_eq!(back.alpha(), 0.25);
+        assert_near(back.color().into_rgb(), (0.2, 0.4, 0.6), 1e-5);
+    }
+
+    #[test]
+    fn alpha_last_linear_rgb() {
+        let rgba = LinearRgb::new(Rgbaf32::from_rgba(1.0, 1.0, 1.0, 0.5));
+        let xyz = AlphaLast::<f32, Xyz<f32, D65>>::from(rgba);
+        assert_eq!(xyz.alpha(), 0.5);
+        assert_near(xyz.color().into_xyz(), (0.9505, 1.0, 1.0891), 1e-4);
+
+        let back = LinearRgb::<Rgbaf32>::from(xyz).into_inner();
+        assert_eq!(back.alpha(), 0.5);
+    }
+}
@@ -0,0 +1,24 @@
+use crate::{
+    alpha::impl_alpha_wrappers,
+    cie::{HasA, HasB},
+};
+
+impl_alpha_wrappers!(HasA, a, set_a, with_a);
+impl_alpha_wrappers!(HasB, b, set_b, with_b);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::{alpha::AlphaLast, cie::Labf32};
+
+    use super::*;
+
+    #[test]
+    fn alpha_last_set_a_and_b() {
+        let mut color = AlphaLast::<f32, Labf32>::with_color(0.5, Labf32::from_lab(50.0, 0.0, 0.0));
+        color.set_a(10.0);
+        color.set_b(-10.0);
+        assert_eq!(color.color(), Labf32::from_lab(50.0, 10.0, -10.0));
+        assert_eq!(color.alpha(), 0.5);
+    }
+}
@@ -0,0 +1,208 @@
+use core::marker::PhantomData;
+
+use crate::{
+    cie::{D65, HasA, HasB, Lch, WhitePoint, Xyz, impl_has_field},
+    cylindrical::HasLightness,
+    math::{cbrt, sin_cos_degrees},
+};
+
+/// A color representation that contains CIE 1976 `L*`, `a*`, and `b*` components (CIELAB).
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Lab {
+///     T l;
+///     T a;
+///     T b;
+/// };
+/// ```
+///
+/// The white point `W` is a zero-sized marker and does not affect the layout.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Lab<T, W = D65> {
+    l: T,
+    a: T,
+    b: T,
+    white: PhantomData<W>,
+}
+
+impl<T, W> Lab<T, W> {
+    /// Creates a new CIELAB color with the given lightness, `a*`, and `b*` components.
+    #[must_use]
+    pub const fn from_lab(lightness: T, a: T, b: T) -> Self {
+        Self {
+            l: lightness,
+            a,
+            b,
+            white: PhantomData,
+        }
+    }
+
+    /// Consumes the color and returns its lightness, `a*`, and `b*` components.
+    #[must_use]
+    pub fn into_lab(self) -> (T, T, T) {
+        (self.l, self.a, self.b)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, W> bytemuck::Zeroable for Lab<T, W> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, W> bytemuck::Pod for Lab<T, W>
+where
+    T: bytemuck::Pod,
+    W: Copy + 'static,
+{
+}
+
+impl_has_field!(
+    Lab,
+    HasLightness {
+        lightness,
+        set_lightness,
+        l
+    }
+);
+impl_has_field!(Lab, HasA { a, set_a, a });
+impl_has_field!(Lab, HasB { b, set_b, b });
+
+/// 32-bit floating-point CIELAB color, relative to [`D65`].
+///
+/// Lightness is in the range `0.0..=100.0`; `a*` and `b*` are unbounded, but are typically within
+/// `-128.0..=127.0`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::{D50, Lab}, cylindrical::HasLightness, encoding::Srgb, rgb::Rgbf32};
+///
+/// let gray = Lab::<f32, D50>::from(Srgb::new(Rgbf32::from_rgb(0.5, 0.5, 0.5)));
+/// assert!((gray.lightness() - 53.39).abs() < 0.01);
+/// ```
+pub type Labf32 = Lab<f32, D65>;
+
+/// CIE `ε`, the `Y` below which lightness is linear.
+const EPSILON: f64 = 216.0 / 24389.0;
+
+/// CIE `κ`, the slope of lightness below `ε`.
+const KAPPA: f64 = 24389.0 / 27.0;
+
+impl<W> From<Xyz<f32, W>> for Lab<f32, W>
+where
+    W: WhitePoint,
+{
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: Xyz<f32, W>) -> Self {
+        let [x, y, z] = color.into_f64();
+        let [wx, wy, wz] = W::XYZ;
+        let f = |t: f64| {
+            if t > EPSILON {
+                cbrt(t)
+            } else {
+                (KAPPA * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x / wx), f(y / wy), f(z / wz));
+        Self::from_lab(
+            (116.0 * fy - 16.0) as f32,
+            (500.0 * (fx - fy)) as f32,
+            (200.0 * (fy - fz)) as f32,
+        )
+    }
+}
+
+impl<W> From<Lab<f32, W>> for Xyz<f32, W>
+where
+    W: WhitePoint,
+{
+    fn from(color: Lab<f32, W>) -> Self {
+        let (l, a, b) = color.into_lab();
+        let (l, a, b) = (f64::from(l), f64::from(a), f64::from(b));
+        let fy = (l + 16.0) / 116.0;
+        let (fx, fz) = (fy + a / 500.0, fy - b / 200.0);
+        let f_inv = |t: f64| {
+            let cubed = t * t * t;
+            if cubed > EPSILON {
+                cubed
+            } else {
+                (116.0 * t - 16.0) / KAPPA
+            }
+        };
+        let y = if l > KAPPA * EPSILON {
+            fy * fy * fy
+        } else {
+            l / KAPPA
+        };
+        let [wx, wy, wz] = W::XYZ;
+        Self::from_f64([f_inv(fx) * wx, y * wy, f_inv(fz) * wz])
+    }
+}
+
+impl<W> From<Lch<f32, W>> for Lab<f32, W> {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: Lch<f32, W>) -> Self {
+        let (l, c, h) = color.into_lch();
+        let (sin, cos) = sin_cos_degrees(f64::from(h));
+        let c = f64::from(c);
+        Self::from_lab(l, (c * cos) as f32, (c * sin) as f32)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::cie::{D50, Lchf32, Xyzf32};
+
+    #[test]
+    fn from_lab_accessors() {
+        let lab = Labf32::from_lab(50.0, -20.0, 30.0);
+        assert_eq!(lab.lightness(), 50.0);
+        assert_eq!(lab.a(), -20.0);
+        assert_eq!(lab.b(), 30.0);
+        assert_eq!(lab.into_lab(), (50.0, -20.0, 30.0));
+    }
+
+    #[test]
+    fn white_point_is_neutral() {
+        #[allow(clippy::cast_possible_truncation)]
+        let white = D50::XYZ.map(|value| value as f32);
+        let white = Xyz::<f32, D50>::from_xyz(white[0], white[1], white[2]);
+        let (l, a, b) = Lab::from(white).into_lab();
+        assert!((l - 100.0).abs() < 1e-4);
+        assert!(a.abs() < 1e-4);
+        assert!(b.abs() < 1e-4);
+    }
+
+    #[test]
+    fn black_is_zero() {
+        let lab = Labf32::from(Xyzf32::from_xyz(0.0, 0.0, 0.0));
+        assert_eq!(lab, Labf32::from_lab(0.0, 0.0, 0.0));
+        assert_eq!(Xyzf32::from(lab), Xyzf32::from_xyz(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn round_trips_xyz() {
+        // Includes components on both sides of the linear segment near black.
+        for xyz in [[0.25, 0.5, 0.75], [0.001, 0.002, 0.004], [0.9, 0.005, 0.1]] {
+            let color = Xyzf32::from_xyz(xyz[0], xyz[1], xyz[2]);
+            let (x, y, z) = Xyzf32::from(Labf32::from(color)).into_xyz();
+            assert!((x - xyz[0]).abs() < 1e-6, "{color:?}");
+            assert!((y - xyz[1]).abs() < 1e-6, "{color:?}");
+            assert!((z - xyz[2]).abs() < 1e-6, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn from_lch() {
+        let (l, a, b) = Labf32::from(Lchf32::from_lch(50.0, 10.0, 90.0)).into_lab();
+        assert_eq!(l, 50.0);
+        assert!(a.abs() < 1e-6);
+        assert!((b - 10.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,161 @@
+use core::marker::PhantomData;
+
+use crate::{
+    cie::{D65, Lab, WhitePoint, Xyz, impl_has_field},
+    cylindrical::{HasChroma, HasHue, HasLightness},
+    math::{atan2_degrees, hypot},
+};
+
+/// A color representation that contains CIE 1976 `L*`, `C*`, and `h°` components (CIE `LCh`).
+///
+/// This is the cylindrical form of [`Lab<T>`][crate::cie::Lab].
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Lch {
+///     T l;
+///     T c;
+///     T h;
+/// };
+/// ```
+///
+/// The white point `W` is a zero-sized marker and does not affect the layout.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Lch<T, W = D65> {
+    l: T,
+    c: T,
+    h: T,
+    white: PhantomData<W>,
+}
+
+impl<T, W> Lch<T, W> {
+    /// Creates a new CIE `LCh` color with the given lightness, chroma, and hue components.
+    #[must_use]
+    pub const fn from_lch(lightness: T, chroma: T, hue: T) -> Self {
+        Self {
+            l: lightness,
+            c: chroma,
+            h: hue,
+            white: PhantomData,
+        }
+    }
+
+    /// Consumes the color and returns its lightness, chroma, and hue components.
+    #[must_use]
+    pub fn into_lch(self) -> (T, T, T) {
+        (self.l, self.c, self.h)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, W> bytemuck::Zeroable for Lch<T, W> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, W> bytemuck::Pod for Lch<T, W>
+where
+    T: bytemuck::Pod,
+    W: Copy + 'static,
+{
+}
+
+impl_has_field!(
+    Lch,
+    HasLightness {
+        lightness,
+        set_lightness,
+        l
+    }
+);
+impl_has_field!(
+    Lch,
+    HasChroma {
+        chroma,
+        set_chroma,
+        c
+    }
+);
+impl_has_field!(Lch, HasHue { hue, set_hue, h });
+
+/// 32-bit floating-point CIE `LCh` color, relative to [`D65`].
+///
+/// Lightness is in the range `0.0..=100.0`, chroma is non-negative (typically below `150.0`), and
+/// hue is in degrees.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::{D50, Lch}, cylindrical::HasHue, encoding::Srgb, rgb::Rgbf32};
+///
+/// let red = Lch::<f32, D50>::from(Srgb::new(Rgbf32::from_rgb(1.0, 0.0, 0.0)));
+/// assert!((red.hue() - 40.85).abs() < 0.01);
+/// ```
+pub type Lchf32 = Lch<f32, D65>;
+
+impl<W> From<Lab<f32, W>> for Lch<f32, W> {
+    /// Converts from CIELAB; achromatic colors have a hue of `0.0`.
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: Lab<f32, W>) -> Self {
+        let (l, a, b) = color.into_lab();
+        let (a, b) = (f64::from(a), f64::from(b));
+        Self::from_lch(l, hypot(a, b) as f32, atan2_degrees(b, a) as f32)
+    }
+}
+
+impl<W> From<Xyz<f32, W>> for Lch<f32, W>
+where
+    W: WhitePoint,
+{
+    fn from(color: Xyz<f32, W>) -> Self {
+        Self::from(Lab::from(color))
+    }
+}
+
+impl<W> From<Lch<f32, W>> for Xyz<f32, W>
+where
+    W: WhitePoint,
+{
+    fn from(color: Lch<f32, W>) -> Self {
+        Self::from(Lab::from(color))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::cie::Labf32;
+
+    #[test]
+    fn from_lch_accessors() {
+        let lch = Lchf32::from_lch(50.0, 20.0, 270.0);
+        assert_eq!(lch.lightness(), 50.0);
+        assert_eq!(lch.chroma(), 20.0);
+        assert_eq!(lch.hue(), 270.0);
+        assert_eq!(lch.into_lch(), (50.0, 20.0, 270.0));
+    }
+
+    #[test]
+    fn from_lab() {
+        let lch = Lchf32::from(Labf32::from_lab(50.0, 0.0, -20.0));
+        assert_eq!(lch, Lchf32::from_lch(50.0, 20.0, 270.0));
+    }
+
+    #[test]
+    fn from_lab_achromatic_has_zero_hue() {
+        let lch = Lchf32::from(Labf32::from_lab(50.0, 0.0, 0.0));
+        assert_eq!(lch, Lchf32::from_lch(50.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn round_trips_lab() {
+        let lab = Labf32::from_lab(75.0, -30.0, 40.0);
+        let (l, a, b) = Labf32::from(Lchf32::from(lab)).into_lab();
+        assert_eq!(l, 75.0);
+        assert!((a + 30.0).abs() < 1e-5);
+        assert!((b - 40.0).abs() < 1e-5);
+    }
+}
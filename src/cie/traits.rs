@@ -0,0 +1,5 @@
+mod has_a;
+pub use has_a::HasA;
+
+mod has_b;
+pub use has_b::HasB;
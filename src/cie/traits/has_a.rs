@@ -0,0 +1,56 @@
+/// A trait for types that have an `a` component, the green–red opponent axis.
+pub trait HasA {
+    /// The type of the `a` component.
+    ///
+    /// Negative values are green, positive values are red, and `0.0` is neutral.
+    type Component;
+
+    /// Returns the value of the `a` component.
+    #[must_use]
+    fn a(&self) -> Self::Component;
+
+    /// Sets the `a` component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_a(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the `a` component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_a(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_a(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cie::Labf32;
+
+    use super::*;
+
+    #[test]
+    fn with_a_labf32_get_a() {
+        assert_eq!(Labf32::from_lab(50.0, -40.0, 20.0).a(), -40.0);
+    }
+
+    #[test]
+    fn with_a_labf32_set_a() {
+        let mut color = Labf32::from_lab(50.0, -40.0, 20.0);
+        color.set_a(40.0);
+        assert_eq!(color, Labf32::from_lab(50.0, 40.0, 20.0));
+    }
+
+    #[test]
+    fn with_a_labf32_with_a() {
+        let color = Labf32::from_lab(50.0, -40.0, 20.0);
+        let new_color = color.with_a(40.0);
+        assert_eq!(new_color, Labf32::from_lab(50.0, 40.0, 20.0));
+    }
+}
@@ -0,0 +1,56 @@
+/// A trait for types that have an `b` component, the blue–yellow opponent axis.
+pub trait HasB {
+    /// The type of the `b` component.
+    ///
+    /// Negative values are blue, positive values are yellow, and `0.0` is neutral.
+    type Component;
+
+    /// Returns the value of the `b` component.
+    #[must_use]
+    fn b(&self) -> Self::Component;
+
+    /// Sets the `b` component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_b(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the `b` component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_b(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_b(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cie::Labf32;
+
+    use super::*;
+
+    #[test]
+    fn with_b_labf32_get_b() {
+        assert_eq!(Labf32::from_lab(50.0, 20.0, -40.0).b(), -40.0);
+    }
+
+    #[test]
+    fn with_b_labf32_set_b() {
+        let mut color = Labf32::from_lab(50.0, 20.0, -40.0);
+        color.set_b(40.0);
+        assert_eq!(color, Labf32::from_lab(50.0, 20.0, 40.0));
+    }
+
+    #[test]
+    fn with_b_labf32_with_b() {
+        let color = Labf32::from_lab(50.0, 20.0, -40.0);
+        let new_color = color.with_b(40.0);
+        assert_eq!(new_color, Labf32::from_lab(50.0, 20.0, 40.0));
+    }
+}
@@ -0,0 +1,92 @@
+/// A reference white, used to interpret and adapt colorimetric values.
+///
+/// White points are zero-sized marker types used as a type parameter, for example `Lab<f32, D50>`,
+/// so that colors relative to different whites cannot be mixed by accident.
+///
+/// ## Examples
+///
+/// Custom white points can be defined by their chromaticity:
+///
+/// ```rust
+/// use gem::cie::WhitePoint;
+///
+/// #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// struct DciP3;
+///
+/// impl WhitePoint for DciP3 {
+///     const CHROMATICITY: [f64; 2] = [0.314, 0.351];
+/// }
+///
+/// assert!((DciP3::XYZ[0] - 0.894_587).abs() < 1e-6);
+/// ```
+pub trait WhitePoint {
+    /// The `x` and `y` chromaticity coordinates of the white point.
+    const CHROMATICITY: [f64; 2];
+
+    /// The `X`, `Y`, and `Z` tristimulus values of the white point, normalized to `Y = 1.0`.
+    const XYZ: [f64; 3] = chromaticity_to_xyz(Self::CHROMATICITY);
+}
+
+/// Returns the tristimulus values, normalized to `Y = 1.0`, of a chromaticity.
+pub(crate) const fn chromaticity_to_xyz([x, y]: [f64; 2]) -> [f64; 3] {
+    [x / y, 1.0, (1.0 - x - y) / y]
+}
+
+/// CIE standard illuminant D65, representing average daylight.
+///
+/// This is the white point of sRGB, Display P3, and Rec. 2020.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct D65;
+
+impl WhitePoint for D65 {
+    const CHROMATICITY: [f64; 2] = [0.3127, 0.3290];
+}
+
+/// CIE standard illuminant D50, representing horizon light.
+///
+/// This is the white point of ICC profile connection spaces and printing.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct D50;
+
+impl WhitePoint for D50 {
+    const CHROMATICITY: [f64; 2] = [0.3457, 0.3585];
+}
+
+/// The white point of the Academy Color Encoding System (ACES), close to CIE illuminant D60.
+///
+/// This is the white point of ACES2065-1 and `ACEScg`, as defined by SMPTE ST 2065-1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct D60;
+
+impl WhitePoint for D60 {
+    const CHROMATICITY: [f64; 2] = [0.32168, 0.33767];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn d65_xyz() {
+        let [x, y, z] = D65::XYZ;
+        assert!((x - 0.950_455_9).abs() < 1e-7);
+        assert!((y - 1.0).abs() < f64::EPSILON);
+        assert!((z - 1.089_057_8).abs() < 1e-7);
+    }
+
+    #[test]
+    fn d50_xyz() {
+        let [x, y, z] = D50::XYZ;
+        assert!((x - 0.964_295_7).abs() < 1e-7);
+        assert!((y - 1.0).abs() < f64::EPSILON);
+        assert!((z - 0.825_104_6).abs() < 1e-7);
+    }
+
+    #[test]
+    fn d60_xyz() {
+        let [x, y, z] = D60::XYZ;
+        assert!((x - 0.952_646_1).abs() < 1e-7);
+        assert!((y - 1.0).abs() < f64::EPSILON);
+        assert!((z - 1.008_825_2).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,176 @@
+use core::marker::PhantomData;
+
+use crate::{
+    cie::{D65, WhitePoint, adaptation_matrix},
+    math::mul_vector,
+};
+
+/// A color representation that contains CIE 1931 `X`, `Y`, and `Z` tristimulus values.
+///
+/// The `Y` component is the relative luminance of the color, with the white point `W` at `1.0`.
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Xyz {
+///     T x;
+///     T y;
+///     T z;
+/// };
+/// ```
+///
+/// The white point `W` is a zero-sized marker and does not affect the layout.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Xyz<T, W = D65> {
+    x: T,
+    y: T,
+    z: T,
+    white: PhantomData<W>,
+}
+
+impl<T, W> Xyz<T, W> {
+    /// Creates a new XYZ color with the given `X`, `Y`, and `Z` components.
+    #[must_use]
+    pub const fn from_xyz(x: T, y: T, z: T) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            white: PhantomData,
+        }
+    }
+
+    /// Returns the `X` component.
+    #[must_use]
+    pub const fn x(&self) -> T
+    where
+        T: Copy,
+    {
+        self.x
+    }
+
+    /// Returns the `Y` component, which is the relative luminance.
+    #[must_use]
+    pub const fn y(&self) -> T
+    where
+        T: Copy,
+    {
+        self.y
+    }
+
+    /// Returns the `Z` component.
+    #[must_use]
+    pub const fn z(&self) -> T
+    where
+        T: Copy,
+    {
+        self.z
+    }
+
+    /// Consumes the color and returns its `X`, `Y`, and `Z` components.
+    #[must_use]
+    pub fn into_xyz(self) -> (T, T, T) {
+        (self.x, self.y, self.z)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, W> bytemuck::Zeroable for Xyz<T, W> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, W> bytemuck::Pod for Xyz<T, W>
+where
+    T: bytemuck::Pod,
+    W: Copy + 'static,
+{
+}
+
+/// 32-bit floating-point XYZ color, relative to [`D65`].
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::Xyzf32, encoding::Srgb, rgb::Rgbf32};
+///
+/// let white = Xyzf32::from(Srgb::new(Rgbf32::from_rgb(1.0, 1.0, 1.0)));
+/// assert!((white.y() - 1.0).abs() < 1e-6);
+/// ```
+pub type Xyzf32 = Xyz<f32, D65>;
+
+impl<W> Xyz<f32, W>
+where
+    W: WhitePoint,
+{
+    /// Converts the color to be relative to another white point, using the Bradford transform.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::cie::{D50, D65, WhitePoint, Xyzf32};
+    ///
+    /// let [x, y, z] = D65::XYZ;
+    /// # #[allow(clippy::cast_possible_truncation)]
+    /// let white = Xyzf32::from_xyz(x as f32, y as f32, z as f32);
+    ///
+    /// let adapted = white.adapt::<D50>();
+    /// assert!((f64::from(adapted.x()) - D50::XYZ[0]).abs() < 1e-6);
+    /// assert!((f64::from(adapted.z()) - D50::XYZ[2]).abs() < 1e-6);
+    /// ```
+    #[must_use]
+    pub fn adapt<V: WhitePoint>(self) -> Xyz<f32, V> {
+        let matrix = const { adaptation_matrix(W::XYZ, V::XYZ) };
+        Xyz::from_f64(mul_vector(&matrix, self.into_f64()))
+    }
+
+    /// Returns the components as double-precision floating-point.
+    pub(crate) fn into_f64(self) -> [f64; 3] {
+        [f64::from(self.x), f64::from(self.y), f64::from(self.z)]
+    }
+
+    /// Creates a color from double-precision floating-point components.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_f64([x, y, z]: [f64; 3]) -> Self {
+        Self::from_xyz(x as f32, y as f32, z as f32)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::cie::D50;
+
+    #[test]
+    fn from_xyz_accessors() {
+        let xyz = Xyzf32::from_xyz(0.25, 0.5, 0.75);
+        assert_eq!(xyz.x(), 0.25);
+        assert_eq!(xyz.y(), 0.5);
+        assert_eq!(xyz.z(), 0.75);
+        assert_eq!(xyz.into_xyz(), (0.25, 0.5, 0.75));
+    }
+
+    #[test]
+    fn repr_c() {
+        let xyz = Xyz::<u8, D50>::from_xyz(1, 2, 3);
+        let bytes = unsafe { core::mem::transmute::<Xyz<u8, D50>, [u8; 3]>(xyz) };
+        assert_eq!(bytes, [1, 2, 3]);
+    }
+
+    #[test]
+    fn adapt_same_white_point_is_identity() {
+        let xyz = Xyzf32::from_xyz(0.25, 0.5, 0.75);
+        assert_eq!(xyz.adapt::<D65>(), xyz);
+    }
+
+    #[test]
+    fn adapt_round_trips() {
+        let xyz = Xyzf32::from_xyz(0.25, 0.5, 0.75);
+        let (x, y, z) = xyz.adapt::<D50>().adapt::<D65>().into_xyz();
+        assert!((x - 0.25).abs() < 1e-6);
+        assert!((y - 0.5).abs() < 1e-6);
+        assert!((z - 0.75).abs() < 1e-6);
+    }
+}
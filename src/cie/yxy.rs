@@ -0,0 +1,166 @@
+use core::marker::PhantomData;
+
+use crate::cie::{D65, WhitePoint, Xyz};
+
+/// A color representation that contains CIE 1931 luminance `Y` and chromaticity `x` and `y`.
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Yxy {
+///     T luminance;
+///     T x;
+///     T y;
+/// };
+/// ```
+///
+/// The white point `W` is a zero-sized marker and does not affect the layout.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Yxy<T, W = D65> {
+    luminance: T,
+    x: T,
+    y: T,
+    white: PhantomData<W>,
+}
+
+impl<T, W> Yxy<T, W> {
+    /// Creates a new xyY color with the given luminance and `x` and `y` chromaticity.
+    #[must_use]
+    pub const fn from_yxy(luminance: T, x: T, y: T) -> Self {
+        Self {
+            luminance,
+            x,
+            y,
+            white: PhantomData,
+        }
+    }
+
+    /// Returns the luminance (`Y`) component.
+    #[must_use]
+    pub const fn luminance(&self) -> T
+    where
+        T: Copy,
+    {
+        self.luminance
+    }
+
+    /// Returns the `x` chromaticity component.
+    #[must_use]
+    pub const fn x(&self) -> T
+    where
+        T: Copy,
+    {
+        self.x
+    }
+
+    /// Returns the `y` chromaticity component.
+    #[must_use]
+    pub const fn y(&self) -> T
+    where
+        T: Copy,
+    {
+        self.y
+    }
+
+    /// Consumes the color and returns its luminance and `x` and `y` chromaticity components.
+    #[must_use]
+    pub fn into_yxy(self) -> (T, T, T) {
+        (self.luminance, self.x, self.y)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, W> bytemuck::Zeroable for Yxy<T, W> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, W> bytemuck::Pod for Yxy<T, W>
+where
+    T: bytemuck::Pod,
+    W: Copy + 'static,
+{
+}
+
+/// 32-bit floating-point xyY color, relative to [`D65`].
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::Yxyf32, encoding::Srgb, rgb::Rgbf32};
+///
+/// let white = Yxyf32::from(Srgb::new(Rgbf32::from_rgb(1.0, 1.0, 1.0)));
+/// assert!((white.x() - 0.3127).abs() < 1e-6);
+/// assert!((white.y() - 0.3290).abs() < 1e-6);
+/// ```
+pub type Yxyf32 = Yxy<f32, D65>;
+
+impl<W> From<Xyz<f32, W>> for Yxy<f32, W>
+where
+    W: WhitePoint,
+{
+    /// Converts from XYZ; black has the chromaticity of the white point.
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: Xyz<f32, W>) -> Self {
+        let [x, y, z] = color.into_f64();
+        let sum = x + y + z;
+        let [cx, cy] = if sum == 0.0 {
+            W::CHROMATICITY
+        } else {
+            [x / sum, y / sum]
+        };
+        Self::from_yxy(y as f32, cx as f32, cy as f32)
+    }
+}
+
+impl<W> From<Yxy<f32, W>> for Xyz<f32, W>
+where
+    W: WhitePoint,
+{
+    fn from(color: Yxy<f32, W>) -> Self {
+        let (luminance, x, y) = color.into_yxy();
+        let (luminance, x, y) = (f64::from(luminance), f64::from(x), f64::from(y));
+        if y == 0.0 {
+            return Self::from_xyz(0.0, 0.0, 0.0);
+        }
+        Self::from_f64([x * luminance / y, luminance, (1.0 - x - y) * luminance / y])
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::cie::{D50, Xyzf32};
+
+    #[test]
+    fn from_yxy_accessors() {
+        let yxy = Yxyf32::from_yxy(0.5, 0.25, 0.75);
+        assert_eq!(yxy.luminance(), 0.5);
+        assert_eq!(yxy.x(), 0.25);
+        assert_eq!(yxy.y(), 0.75);
+        assert_eq!(yxy.into_yxy(), (0.5, 0.25, 0.75));
+    }
+
+    #[test]
+    fn from_xyz_black_uses_white_point() {
+        let yxy = Yxy::<f32, D50>::from(Xyz::from_xyz(0.0, 0.0, 0.0));
+        assert_eq!(yxy, Yxy::from_yxy(0.0, 0.3457, 0.3585));
+    }
+
+    #[test]
+    fn into_xyz_zero_y_is_black() {
+        let xyz = Xyzf32::from(Yxyf32::from_yxy(0.5, 0.25, 0.0));
+        assert_eq!(xyz, Xyzf32::from_xyz(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn round_trips_xyz() {
+        let xyz = Xyzf32::from_xyz(0.25, 0.5, 0.75);
+        let (x, y, z) = Xyzf32::from(Yxyf32::from(xyz)).into_xyz();
+        assert!((x - 0.25).abs() < 1e-6);
+        assert_eq!(y, 0.5);
+        assert!((z - 0.75).abs() < 1e-6);
+    }
+}
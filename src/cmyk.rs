@@ -0,0 +1,273 @@
+//! Subtractive 🖨️ color models for printing with cyan, magenta, yellow, and black inks.
+//!
+//! This module contains:
+//!
+//! - [`Cmyk<T>`], a generic CMYK color, with a naive conversion to and from RGB
+//! - [`Separation`], which separates RGB colors into inks with [`BlackGeneration`], undercolor
+//!   removal or gray component replacement ([`Removal`]), and a total ink limit
+//!
+//! Each component is the coverage of an ink, where `0.0` is no ink and `1.0` is solid ink. The
+//! naive conversion treats inks as ideal filters of the RGB components as stored, which is useful
+//! for previews and device-independent exports; matching a real press requires a profile.
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{
+//!     cmyk::{BlackGeneration, Cmyk8, CmykF32, Removal, Separation},
+//!     rgb::Rgb888,
+//! };
+//!
+//! let orange = Rgb888::from_rgb(255, 128, 0);
+//! let cmyk = Cmyk8::from_rgb_color(orange);
+//! assert_eq!(cmyk, Cmyk8::from_cmyk(0, 127, 255, 0));
+//! assert_eq!(cmyk.into_rgb_color::<Rgb888>(), orange);
+//!
+//! // A dark gray is printed mostly with black, within 300% of total ink.
+//! let separation = Separation::new(BlackGeneration::MEDIUM, Removal::Gcr(1.0), 3.0);
+//! let gray: CmykF32 = separation.separate(Rgb888::from_rgb(64, 64, 64));
+//! assert!(gray.black() > gray.cyan());
+//! ```
+//!
+//! ## Predefined Types
+//!
+//! Type         | Bits per pixel | Description
+//! ------------ | -------------- | -----------
+//! [`Cmyk8`]    | 32             | 8-bit integer coverage of each ink
+//! [`CmykF32`]  | 128            | 32-bit floating-point coverage of each ink, in `0.0..=1.0`
+
+use crate::{component::Component, rgb::RgbColor};
+
+mod separation;
+
+pub use separation::{BlackGeneration, Removal, Separation};
+
+/// A color representation that contains cyan, magenta, yellow, and black ink components.
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Cmyk {
+///     T c;
+///     T m;
+///     T y;
+///     T k;
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Cmyk<T> {
+    c: T,
+    m: T,
+    y: T,
+    k: T,
+}
+
+impl<T> Cmyk<T> {
+    /// Creates a new CMYK color with the given cyan, magenta, yellow, and black components.
+    #[must_use]
+    pub const fn from_cmyk(cyan: T, magenta: T, yellow: T, black: T) -> Self {
+        Self {
+            c: cyan,
+            m: magenta,
+            y: yellow,
+            k: black,
+        }
+    }
+
+    /// Consumes the color and returns its cyan, magenta, yellow, and black components.
+    #[must_use]
+    pub fn into_cmyk(self) -> (T, T, T, T) {
+        (self.c, self.m, self.y, self.k)
+    }
+
+    /// Returns the cyan component.
+    #[must_use]
+    pub const fn cyan(&self) -> T
+    where
+        T: Copy,
+    {
+        self.c
+    }
+
+    /// Returns the magenta component.
+    #[must_use]
+    pub const fn magenta(&self) -> T
+    where
+        T: Copy,
+    {
+        self.m
+    }
+
+    /// Returns the yellow component.
+    #[must_use]
+    pub const fn yellow(&self) -> T
+    where
+        T: Copy,
+    {
+        self.y
+    }
+
+    /// Returns the black component.
+    #[must_use]
+    pub const fn black(&self) -> T
+    where
+        T: Copy,
+    {
+        self.k
+    }
+}
+
+impl<T: Component> Cmyk<T> {
+    /// Converts any RGB color into CMYK, with as much black as possible.
+    ///
+    /// Black is the darkness of the lightest component, and cyan, magenta, and yellow the
+    /// remaining absorption of red, green, and blue. This is the inverse of the naive conversion
+    /// that CSS Color 5 uses for `device-cmyk()`, and is equivalent to a [`Separation`] with
+    /// maximum gray component replacement and no ink limit.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cmyk::CmykF32, rgb::Rgbf32};
+    ///
+    /// let cmyk = CmykF32::from_rgb_color(Rgbf32::from_rgb(0.5, 0.25, 0.0));
+    /// assert_eq!(cmyk, CmykF32::from_cmyk(0.0, 0.5, 1.0, 0.5));
+    /// ```
+    #[must_use]
+    pub fn from_rgb_color<C: RgbColor>(color: C) -> Self {
+        let [r, g, b] = rgb_to_f32(color);
+        let black = 1.0 - r.max(g).max(b);
+        let ink = |c: f32| {
+            if black < 1.0 {
+                (1.0 - c - black) / (1.0 - black)
+            } else {
+                0.0
+            }
+        };
+        Self::from_f32([ink(r), ink(g), ink(b), black])
+    }
+
+    /// Converts this color into any RGB color.
+    ///
+    /// Each RGB component is the light that passes through both its ink and black.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cmyk::Cmyk8, rgb::Rgb888};
+    ///
+    /// let rgb: Rgb888 = Cmyk8::from_cmyk(0, 255, 255, 0).into_rgb_color();
+    /// assert_eq!(rgb, Rgb888::from_rgb(255, 0, 0));
+    /// ```
+    #[must_use]
+    pub fn into_rgb_color<C: RgbColor>(self) -> C {
+        let [c, m, y, k] = self.into_f32();
+        let light = |ink: f32| (1.0 - ink) * (1.0 - k);
+        C::from_rgb(
+            Component::from_normalized_f32(light(c)),
+            Component::from_normalized_f32(light(m)),
+            Component::from_normalized_f32(light(y)),
+        )
+    }
+
+    fn into_f32(self) -> [f32; 4] {
+        [self.c, self.m, self.y, self.k].map(Component::to_normalized_f32)
+    }
+
+    fn from_f32([c, m, y, k]: [f32; 4]) -> Self {
+        Self::from_cmyk(
+            T::from_normalized_f32(c),
+            T::from_normalized_f32(m),
+            T::from_normalized_f32(y),
+            T::from_normalized_f32(k),
+        )
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Cmyk<T> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Cmyk<T> where T: bytemuck::Pod {}
+
+/// 8-bit integer CMYK color, where `255` is solid ink.
+pub type Cmyk8 = Cmyk<u8>;
+
+/// 32-bit floating-point CMYK color, where `1.0` is solid ink.
+pub type CmykF32 = Cmyk<f32>;
+
+/// Returns the normalized red, green, and blue components of `color`.
+fn rgb_to_f32<C: RgbColor>(color: C) -> [f32; 3] {
+    let (r, g, b) = color.into_rgb();
+    [
+        r.to_normalized_f32(),
+        g.to_normalized_f32(),
+        b.to_normalized_f32(),
+    ]
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::rgb::{Rgb888, Rgbf32};
+
+    #[test]
+    fn from_rgb_color_primaries() {
+        let cases = [
+            (
+                Rgb888::from_rgb(255, 0, 0),
+                Cmyk8::from_cmyk(0, 255, 255, 0),
+            ),
+            (
+                Rgb888::from_rgb(0, 255, 0),
+                Cmyk8::from_cmyk(255, 0, 255, 0),
+            ),
+            (
+                Rgb888::from_rgb(0, 0, 255),
+                Cmyk8::from_cmyk(255, 255, 0, 0),
+            ),
+            (
+                Rgb888::from_rgb(0, 255, 255),
+                Cmyk8::from_cmyk(255, 0, 0, 0),
+            ),
+            (
+                Rgb888::from_rgb(255, 255, 255),
+                Cmyk8::from_cmyk(0, 0, 0, 0),
+            ),
+            (Rgb888::from_rgb(0, 0, 0), Cmyk8::from_cmyk(0, 0, 0, 255)),
+        ];
+        for (rgb, cmyk) in cases {
+            assert_eq!(Cmyk8::from_rgb_color(rgb), cmyk, "{rgb:?}");
+            assert_eq!(cmyk.into_rgb_color::<Rgb888>(), rgb, "{cmyk:?}");
+        }
+    }
+
+    #[test]
+    fn grays_are_black_only() {
+        let cmyk = CmykF32::from_rgb_color(Rgbf32::from_rgb(0.25, 0.25, 0.25));
+        assert_eq!(cmyk, CmykF32::from_cmyk(0.0, 0.0, 0.0, 0.75));
+    }
+
+    #[test]
+    fn round_trips_rgb888() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = Rgb888::from_rgb(r, g, b);
+                    let cmyk = CmykF32::from_rgb_color(rgb);
+                    assert_eq!(cmyk.into_rgb_color::<Rgb888>(), rgb, "{cmyk:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn into_rgb_color_overprints() {
+        // Inks multiply: half cyan over half black leaves a quarter of red.
+        let rgb = CmykF32::from_cmyk(0.5, 0.0, 0.0, 0.5).into_rgb_color::<Rgbf32>();
+        assert_eq!(rgb, Rgbf32::from_rgb(0.25, 0.5, 0.5));
+    }
+}
@@ -0,0 +1,399 @@
+use crate::{
+    cmyk::{Cmyk, rgb_to_f32},
+    component::Component,
+    math::pow,
+    rgb::RgbColor,
+};
+
+/// A curve that describes how much black ink replaces the gray component of a color.
+///
+/// The gray component is the coverage that cyan, magenta, and yellow have in common, which
+/// together print a neutral darkness that black ink can print instead. Black starts at the gray
+/// component `start`, and rises along a power curve with `exponent` to `max` where the gray
+/// component is solid.
+///
+/// Black never exceeds the gray component, so that it can always be removed from the other inks.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::cmyk::BlackGeneration;
+///
+/// assert_eq!(BlackGeneration::MEDIUM.black(0.25), 0.0);
+/// assert_eq!(BlackGeneration::MEDIUM.black(1.0), 1.0);
+/// assert_eq!(BlackGeneration::MAXIMUM.black(0.5), 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlackGeneration {
+    start: f32,
+    exponent: f32,
+    max: f32,
+}
+
+impl BlackGeneration {
+    /// No black; colors are printed with cyan, magenta, and yellow only.
+    pub const NONE: Self = Self::new(0.0, 1.0, 0.0);
+
+    /// Black only in the shadows, starting at a gray component of 50%.
+    pub const LIGHT: Self = Self::new(0.5, 1.0, 1.0);
+
+    /// Black in the midtones and shadows, starting at a gray component of 25%.
+    pub const MEDIUM: Self = Self::new(0.25, 1.0, 1.0);
+
+    /// Black almost everywhere, starting at a gray component of 10%.
+    pub const HEAVY: Self = Self::new(0.1, 1.0, 1.0);
+
+    /// Black replaces the whole gray component.
+    pub const MAXIMUM: Self = Self::new(0.0, 1.0, 1.0);
+
+    /// Creates a black generation curve.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `start` is not within `0.0..1.0`, `exponent` is not positive, or `max` is not
+    /// within `0.0..=1.0`.
+    #[must_use]
+    pub const fn new(start: f32, exponent: f32, max: f32) -> Self {
+        assert!(start >= 0.0 && start < 1.0, "start must be within 0.0..1.0");
+        assert!(exponent > 0.0, "exponent must be positive");
+        assert!(max >= 0.0 && max <= 1.0, "max must be within 0.0..=1.0");
+        Self {
+            start,
+            exponent,
+            max,
+        }
+    }
+
+    /// Returns the gray component at which black starts.
+    #[must_use]
+    pub const fn start(&self) -> f32 {
+        self.start
+    }
+
+    /// Returns the exponent of the curve; values above `1.0` keep black lighter in the midtones.
+    #[must_use]
+    pub const fn exponent(&self) -> f32 {
+        self.exponent
+    }
+
+    /// Returns the black that replaces a solid gray component.
+    #[must_use]
+    pub const fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// Returns the black that replaces a gray component of `gray`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn black(&self, gray: f32) -> f32 {
+        if gray <= self.start {
+            return 0.0;
+        }
+        let t = ((gray - self.start) / (1.0 - self.start)).min(1.0);
+        let curve = pow(f64::from(t), f64::from(self.exponent)) as f32;
+        (self.max * curve).min(gray)
+    }
+}
+
+/// How much of cyan, magenta, and yellow is removed under the black that replaces them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Removal {
+    /// Undercolor removal (UCR), which replaces the gray component of neutral colors only.
+    ///
+    /// Black is generated in proportion to how neutral a color is, from the full curve for grays to
+    /// none for fully saturated colors, so that saturated shadows keep their colored inks. The
+    /// amount is the fraction of the replaced gray component that is removed from the other inks,
+    /// within `0.0..=1.0`.
+    Ucr(f32),
+
+    /// Gray component replacement (GCR), which replaces the gray component of every color.
+    ///
+    /// The amount is the fraction of the replaced gray component that is removed from the other
+    /// inks, within `0.0..=1.0`; with `1.0`, the separated color looks the same as without black,
+    /// and with `0.0`, black is only added to darken it.
+    Gcr(f32),
+}
+
+impl Removal {
+    /// Returns the fraction of the replaced gray component that is removed.
+    #[must_use]
+    pub const fn amount(self) -> f32 {
+        match self {
+            Self::Ucr(amount) | Self::Gcr(amount) => amount,
+        }
+    }
+}
+
+/// Separates RGB colors into cyan, magenta, yellow, and black inks.
+///
+/// Colors are first converted into cyan, magenta, and yellow, then part of their gray component
+/// is replaced by black, following a [`BlackGeneration`] curve and a [`Removal`] strategy, and
+/// finally cyan, magenta, and yellow are reduced so that the sum of all inks does not exceed the
+/// total ink limit, such as `3.0` (300%) for coated paper.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{
+///     cmyk::{BlackGeneration, CmykF32, Removal, Separation},
+///     rgb::Rgb888,
+/// };
+///
+/// let separation = Separation::new(BlackGeneration::LIGHT, Removal::Ucr(0.2), 2.8);
+///
+/// // Without black, a light gray is printed with equal cyan, magenta, and yellow.
+/// let light: CmykF32 = separation.separate(Rgb888::from_rgb(192, 192, 192));
+/// assert_eq!(light.black(), 0.0);
+/// assert_eq!(light.cyan(), light.magenta());
+///
+/// // Black is solid, and leaves 180% for the other inks.
+/// let black: CmykF32 = separation.separate(Rgb888::from_rgb(0, 0, 0));
+/// assert_eq!(black.black(), 1.0);
+/// let (c, m, y, _) = black.into_cmyk();
+/// assert!((c + m + y - 1.8).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Separation {
+    black: BlackGeneration,
+    removal: Removal,
+    ink_limit: f32,
+}
+
+impl Separation {
+    /// Creates a separation with the given black generation, removal, and total ink limit.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the amount of `removal` is not within `0.0..=1.0`, or `ink_limit` is not within
+    /// `0.0..=4.0`.
+    #[must_use]
+    pub const fn new(black: BlackGeneration, removal: Removal, ink_limit: f32) -> Self {
+        let amount = removal.amount();
+        assert!(
+            amount >= 0.0 && amount <= 1.0,
+            "removal amount must be within 0.0..=1.0"
+        );
+        assert!(
+            ink_limit >= 0.0 && ink_limit <= 4.0,
+            "ink limit must be within 0.0..=4.0"
+        );
+        Self {
+            black,
+            removal,
+            ink_limit,
+        }
+    }
+
+    /// Returns the black generation curve.
+    #[must_use]
+    pub const fn black(&self) -> BlackGeneration {
+        self.black
+    }
+
+    /// Returns how the gray component is removed under black.
+    #[must_use]
+    pub const fn removal(&self) -> Removal {
+        self.removal
+    }
+
+    /// Returns the maximum sum of all inks, where `4.0` is no limit.
+    #[must_use]
+    pub const fn ink_limit(&self) -> f32 {
+        self.ink_limit
+    }
+
+    /// Returns a copy of this separation with a different black generation curve.
+    #[must_use]
+    pub const fn with_black(self, black: BlackGeneration) -> Self {
+        Self { black, ..self }
+    }
+
+    /// Returns a copy of this separation with a different removal.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the amount of `removal` is not within `0.0..=1.0`.
+    #[must_use]
+    pub const fn with_removal(self, removal: Removal) -> Self {
+        Self::new(self.black, removal, self.ink_limit)
+    }
+
+    /// Returns a copy of this separation with a different total ink limit.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `ink_limit` is not within `0.0..=4.0`.
+    #[must_use]
+    pub const fn with_ink_limit(self, ink_limit: f32) -> Self {
+        Self::new(self.black, self.removal, ink_limit)
+    }
+
+    /// Separates an RGB color into inks.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{
+    ///     cmyk::{BlackGeneration, Cmyk8, Removal, Separation},
+    ///     rgb::Rgb888,
+    /// };
+    ///
+    /// // Without a limit, maximum replacement is the naive conversion.
+    /// let naive = Separation::new(BlackGeneration::MAXIMUM, Removal::Gcr(1.0), 4.0);
+    /// let olive = Rgb888::from_rgb(128, 128, 0);
+    /// assert_eq!(naive.separate::<u8, _>(olive), Cmyk8::from_rgb_color(olive));
+    /// ```
+    #[must_use]
+    pub fn separate<T: Component, C: RgbColor>(&self, color: C) -> Cmyk<T> {
+        let cmy = rgb_to_f32(color).map(|c| 1.0 - c);
+        let [c, m, y] = cmy;
+        let gray = c.min(m).min(y);
+        let mut black = self.black.black(gray);
+        if let Removal::Ucr(_) = self.removal {
+            let max = c.max(m).max(y);
+            if max > 0.0 {
+                black *= gray / max;
+            }
+        }
+
+        // Removing the gray component exactly keeps `(1 - ink) * (1 - black)` unchanged.
+        let amount = self.removal.amount();
+        let mut cmy = cmy.map(|ink| {
+            let removed = if black < 1.0 {
+                (ink - black) / (1.0 - black)
+            } else {
+                0.0
+            };
+            ink * (1.0 - amount) + removed * amount
+        });
+
+        let black = black.min(self.ink_limit);
+        let total: f32 = cmy.iter().sum();
+        if total + black > self.ink_limit {
+            let scale = (self.ink_limit - black) / total;
+            cmy = cmy.map(|ink| ink * scale);
+        }
+        let [c, m, y] = cmy;
+        Cmyk::from_f32([c, m, y, black])
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::{
+        cmyk::{Cmyk8, CmykF32},
+        rgb::{Rgb888, Rgbf32},
+    };
+
+    fn total(color: CmykF32) -> f32 {
+        let (c, m, y, k) = color.into_cmyk();
+        c + m + y + k
+    }
+
+    #[test]
+    fn maximum_gcr_is_naive() {
+        let separation = Separation::new(BlackGeneration::MAXIMUM, Removal::Gcr(1.0), 4.0);
+        for r in (0..=255).step_by(51) {
+            for g in (0..=255).step_by(51) {
+                for b in (0..=255).step_by(51) {
+                    let rgb = Rgb888::from_rgb(r, g, b);
+                    let cmyk: Cmyk8 = separation.separate(rgb);
+                    assert_eq!(cmyk, Cmyk8::from_rgb_color(rgb), "{rgb:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn no_black_is_cmy() {
+        let separation = Separation::new(BlackGeneration::NONE, Removal::Gcr(1.0), 4.0);
+        let cmyk: CmykF32 = separation.separate(Rgbf32::from_rgb(0.25, 0.5, 1.0));
+        assert_eq!(cmyk, CmykF32::from_cmyk(0.75, 0.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn full_removal_preserves_color() {
+        let color = Rgbf32::from_rgb(0.2, 0.3, 0.4);
+        for black in [
+            BlackGeneration::LIGHT,
+            BlackGeneration::MEDIUM,
+            BlackGeneration::new(0.0, 2.0, 0.8),
+        ] {
+            for removal in [Removal::Ucr(1.0), Removal::Gcr(1.0)] {
+                let separation = Separation::new(black, removal, 4.0);
+                let cmyk: CmykF32 = separation.separate(color);
+                assert!(cmyk.black() > 0.0, "{separation:?}");
+                let (r, g, b) = cmyk.into_rgb_color::<Rgbf32>().into_rgb();
+                assert!((r - 0.2).abs() < 1e-6, "{separation:?}");
+                assert!((g - 0.3).abs() < 1e-6, "{separation:?}");
+                assert!((b - 0.4).abs() < 1e-6, "{separation:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn no_removal_adds_black() {
+        let separation = Separation::new(BlackGeneration::MAXIMUM, Removal::Gcr(0.0), 4.0);
+        let cmyk: CmykF32 = separation.separate(Rgbf32::from_rgb(0.5, 0.5, 0.5));
+        assert_eq!(cmyk, CmykF32::from_cmyk(0.5, 0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn ucr_spares_saturated_colors() {
+        // A dark red has a gray component, but is far from neutral.
+        let dark_red = Rgbf32::from_rgb(0.5, 0.0, 0.0);
+        let ucr = Separation::new(BlackGeneration::MAXIMUM, Removal::Ucr(1.0), 4.0);
+        let gcr = ucr.with_removal(Removal::Gcr(1.0));
+        let cmyk: CmykF32 = gcr.separate(dark_red);
+        assert!((cmyk.black() - 0.5).abs() < 1e-6);
+        let cmyk: CmykF32 = ucr.separate(dark_red);
+        assert!((cmyk.black() - 0.25).abs() < 1e-6);
+
+        // Grays are replaced by both.
+        let cmyk: CmykF32 = ucr.separate(Rgbf32::from_rgb(0.5, 0.5, 0.5));
+        assert_eq!(cmyk, CmykF32::from_cmyk(0.0, 0.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn ink_limit() {
+        let black = Rgb888::from_rgb(0, 0, 0);
+        let separation = Separation::new(BlackGeneration::LIGHT, Removal::Gcr(0.0), 3.0);
+        let cmyk: CmykF32 = separation.separate(black);
+        assert_eq!(cmyk.black(), 1.0);
+        assert!((total(cmyk) - 3.0).abs() < 1e-6);
+        assert_eq!(cmyk.cyan(), cmyk.magenta());
+
+        // Black is kept even when it alone exceeds the limit.
+        let cmyk: CmykF32 = separation.with_ink_limit(0.8).separate(black);
+        assert_eq!(cmyk, CmykF32::from_cmyk(0.0, 0.0, 0.0, 0.8));
+
+        // Colors within the limit are unchanged.
+        let red = Rgb888::from_rgb(255, 0, 0);
+        let cmyk: CmykF32 = separation.separate(red);
+        assert_eq!(cmyk, CmykF32::from_cmyk(0.0, 1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn black_generation_curve() {
+        let curve = BlackGeneration::new(0.5, 2.0, 0.8);
+        assert_eq!(curve.black(0.25), 0.0);
+        assert!((curve.black(0.75) - 0.2).abs() < 1e-6);
+        assert!((curve.black(1.0) - 0.8).abs() < 1e-6);
+        // Black never exceeds the gray component.
+        assert_eq!(BlackGeneration::new(0.0, 0.5, 1.0).black(0.25), 0.25);
+    }
+
+    #[test]
+    #[should_panic(expected = "ink limit must be within 0.0..=4.0")]
+    fn invalid_ink_limit() {
+        let _ = Separation::new(BlackGeneration::MEDIUM, Removal::Gcr(1.0), 4.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "removal amount must be within 0.0..=1.0")]
+    fn invalid_removal() {
+        let _ = Separation::new(BlackGeneration::MEDIUM, Removal::Ucr(-0.5), 3.0);
+    }
+}
@@ -0,0 +1,501 @@
+//! Individual color components, such as 🔴 red or 🧊 alpha, and the range of values they hold.
+//!
+//! Every `Component` associated type (for example [`HasRed::Component`][]) implements
+//! [`Component`], which describes the range of values a component can hold and how to convert it
+//! to and from other component types. This allows writing one generic algorithm over all colors:
+//!
+//! ```rust
+//! use gem::{component::Component, rgb::{HasRed, Rgb565, Rgb888, Rgbf32}};
+//!
+//! fn redness<C: HasRed>(color: C) -> f32 {
+//!     color.red().to_normalized_f32()
+//! }
+//!
+//! assert_eq!(redness(Rgb565::from_rgb(31, 0, 0)), 1.0);
+//! assert_eq!(redness(Rgb888::from_rgb(255, 0, 0)), 1.0);
+//! assert_eq!(redness(Rgbf32::from_rgb(1.0, 0.0, 0.0)), 1.0);
+//! ```
+//!
+//! ## Supported Types
+//!
+//! Type         | Range           | Description
+//! ------------ | --------------- | -----------
+//! [`Bits<N>`]  | `0..=2^N - 1`   | An `N`-bit unsigned integer, used by packed formats
+//! [`u8`]       | `0..=255`       | 8-bit unsigned integer
+//! [`u16`]      | `0..=65535`     | 16-bit unsigned integer
+//! [`u32`]      | `0..=2^32 - 1`  | 32-bit unsigned integer
+//! [`f32`]      | `0.0..=1.0`     | 32-bit floating-point, values outside the range are allowed
+//! [`f64`]      | `0.0..=1.0`     | 64-bit floating-point, values outside the range are allowed
+//!
+//! [`HasRed::Component`]: crate::rgb::HasRed::Component
+
+use core::fmt::{self, Debug};
+
+/// A value of a single color component, such as 🔴 red or 🧊 alpha.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Component: Copy + Debug + Default + PartialOrd + sealed::Sealed {
+    /// The value that represents the absence of the component, such as black or transparent.
+    const MIN: Self;
+
+    /// The value that represents the full intensity of the component, such as white or opaque.
+    const MAX: Self;
+
+    /// The number of bits of precision, or `None` for floating-point components.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::component::{Bits, Component};
+    ///
+    /// assert_eq!(Bits::<5>::BITS, Some(5));
+    /// assert_eq!(<u8 as Component>::BITS, Some(8));
+    /// assert_eq!(<f32 as Component>::BITS, None);
+    /// ```
+    const BITS: Option<u32>;
+
+    /// Returns the component as a floating-point value, where [`Self::MIN`] is `0.0` and
+    /// [`Self::MAX`] is `1.0`.
+    ///
+    /// Floating-point components are returned as-is, even if they are outside of `0.0..=1.0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::component::{Bits, Component};
+    ///
+    /// assert_eq!(Bits::<5>::new(31).to_normalized_f32(), 1.0);
+    /// assert_eq!(51u8.to_normalized_f32(), 0.2);
+    /// ```
+    #[must_use]
+    fn to_normalized_f32(self) -> f32;
+
+    /// Creates a component from a floating-point value, where `0.0` is [`Self::MIN`] and `1.0` is
+    /// [`Self::MAX`].
+    ///
+    /// Integer components are clamped to their range and rounded to the nearest value; `NaN` is
+    /// treated as `0.0`. Floating-point components are returned as-is.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::component::Component;
+    ///
+    /// assert_eq!(u8::from_normalized_f32(0.5), 128);
+    /// assert_eq!(u8::from_normalized_f32(2.0), 255);
+    /// ```
+    #[must_use]
+    fn from_normalized_f32(value: f32) -> Self;
+
+    /// Converts the component into another component type, preserving its relative intensity.
+    ///
+    /// Widening an integer component is lossless, and replicates bits so that [`Self::MAX`] always
+    /// becomes [`Component::MAX`] of the target (a 5-bit `31` becomes an 8-bit `255`, not `248`).
+    /// Narrowing an integer component rounds to the nearest value. Floating-point components are
+    /// clamped to `0.0..=1.0` when converted to integers.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::component::{Bits, Component};
+    ///
+    /// assert_eq!(Bits::<5>::new(31).convert::<u8>(), 255);
+    /// assert_eq!(255u8.convert::<Bits<5>>(), Bits::new(31));
+    /// assert_eq!(0xFFFFu16.convert::<u8>(), 0xFF);
+    /// assert_eq!(0.5f32.convert::<u8>(), 128);
+    /// ```
+    #[must_use]
+    fn convert<T: Component>(self) -> T {
+        T::from_scaled(self.to_scaled())
+    }
+}
+
+pub(crate) mod sealed {
+    use core::fmt;
+
+    /// An intermediate representation used to convert between component types.
+    #[derive(Clone, Copy)]
+    pub enum Scaled {
+        /// An unsigned integer in the range `0..=max`.
+        Int { value: u64, max: u64 },
+
+        /// A floating-point value, nominally in the range `0.0..=1.0`.
+        Float(f64),
+    }
+
+    impl Scaled {
+        /// Converts into an unsigned integer in the range `0..=max`.
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        pub fn to_int(self, max: u64) -> u64 {
+            match self {
+                Self::Int { value, max: from } if from == max => value,
+                Self::Int { value, max: from } => {
+                    let (value, from, max) = (u128::from(value), u128::from(from), u128::from(max));
+                    ((value * max + from / 2) / from) as u64
+                }
+                Self::Float(value) => (value.clamp(0.0, 1.0) * max as f64 + 0.5) as u64,
+            }
+        }
+
+        /// Converts into a floating-point value in the range `0.0..=1.0`.
+        #[allow(clippy::cast_precision_loss)]
+        pub fn to_float(self) -> f64 {
+            match self {
+                Self::Int { value, max } => value as f64 / max as f64,
+                Self::Float(value) => value,
+            }
+        }
+    }
+
+    /// Prevents [`super::Component`] from being implemented outside of this crate.
+    pub trait Sealed {
+        fn to_scaled(self) -> Scaled;
+        fn from_scaled(scaled: Scaled) -> Self;
+
+        /// Writes the component as a decimal number, scaled so that its maximum is `scale`.
+        ///
+        /// Integer components are written with the fewest fractional digits that round to the
+        /// same value; floating-point components use the shortest representation that parses to
+        /// the same value, except that NaN is written as `0` and infinities as the largest finite
+        /// value, which CSS can parse.
+        fn write_scaled(self, f: &mut fmt::Formatter<'_>, scale: u32) -> fmt::Result;
+    }
+
+    /// Writes `value / max * scale` with the fewest fractional digits that round back to `value`.
+    ///
+    /// A tie is only accepted if it rounds up and is exact in `f32`, since otherwise a parser that
+    /// stores the number in `f32` may round it down.
+    pub fn write_int(f: &mut fmt::Formatter<'_>, value: u64, max: u64, scale: u32) -> fmt::Result {
+        let (value, max, scale) = (u128::from(value), u128::from(max), u128::from(scale));
+        let mut digits = 0;
+        let mut unit = 1;
+        loop {
+            let denominator = scale * unit;
+            let scaled = (2 * value * denominator + max) / (2 * max);
+            let error = 2 * (scaled * max).abs_diff(value * denominator);
+            let exact_tie = error == denominator
+                && scaled * max < value * denominator
+                && (scaled << 24) % denominator == 0;
+            if error < denominator || exact_tie {
+                let (whole, mut fraction) = (scaled / unit, scaled % unit);
+                while digits > 0 && fraction % 10 == 0 {
+                    (digits, fraction) = (digits - 1, fraction / 10);
+                }
+                return if digits == 0 {
+                    write!(f, "{whole}")
+                } else {
+                    write!(f, "{whole}.{fraction:0digits$}")
+                };
+            }
+            digits += 1;
+            unit *= 10;
+        }
+    }
+}
+
+use sealed::Scaled;
+
+/// An unsigned integer component with `N` bits of precision, where `1 <= N <= 8`.
+///
+/// Packed formats, such as [`Rgb565`][], use this type so that the range of each component is known
+/// at compile time.
+///
+/// ## Layout
+///
+/// This type has the same layout as [`u8`].
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{component::{Bits, Component}, rgb::{HasGreen, HasRed, Rgb565}};
+///
+/// let color = Rgb565::from_rgb(31, 63, 0);
+/// assert_eq!(color.red(), Bits::<5>::MAX);
+/// assert_eq!(color.green(), Bits::<6>::MAX);
+/// ```
+///
+/// [`Rgb565`]: crate::rgb::Rgb565
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct Bits<const N: u32>(u8);
+
+impl<const N: u32> Bits<N> {
+    /// A mask of the `N` lower bits.
+    #[allow(clippy::cast_possible_truncation)]
+    const MASK: u8 = {
+        assert!(N >= 1 && N <= 8, "Bits<N> requires 1 <= N <= 8");
+        ((1u16 << N) - 1) as u8
+    };
+
+    /// Creates a new component from the lower `N` bits of `value`.
+    ///
+    /// This is a **lossy** conversion; the remaining bits are discarded.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::component::Bits;
+    ///
+    /// assert_eq!(Bits::<5>::new(31).get(), 31);
+    /// assert_eq!(Bits::<5>::new(32).get(), 0);
+    /// ```
+    #[must_use]
+    pub const fn new(value: u8) -> Self {
+        Self(value & Self::MASK)
+    }
+
+    /// Returns the value of this component.
+    #[must_use]
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl<const N: u32> From<Bits<N>> for u8 {
+    fn from(value: Bits<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: u32> PartialEq<u8> for Bits<N> {
+    fn eq(&self, other: &u8) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: u32> serde::Serialize for Bits<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: u32> serde::Deserialize<'de> for Bits<N> {
+    /// Deserializes an integer, which must be in the range `0..=2^N - 1`.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        if value > Self::MASK {
+            return Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(value.into()),
+                &"an integer within the range of the component",
+            ));
+        }
+        Ok(Self(value))
+    }
+}
+
+impl<const N: u32> sealed::Sealed for Bits<N> {
+    fn to_scaled(self) -> Scaled {
+        Scaled::Int {
+            value: u64::from(self.0),
+            max: u64::from(Self::MASK),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_scaled(scaled: Scaled) -> Self {
+        Self(scaled.to_int(u64::from(Self::MASK)) as u8)
+    }
+
+    fn write_scaled(self, f: &mut fmt::Formatter<'_>, scale: u32) -> fmt::Result {
+        sealed::write_int(f, u64::from(self.0), u64::from(Self::MASK), scale)
+    }
+}
+
+impl<const N: u32> Component for Bits<N> {
+    const MIN: Self = Self(0);
+    const MAX: Self = Self(Self::MASK);
+    const BITS: Option<u32> = Some(N);
+
+    fn to_normalized_f32(self) -> f32 {
+        f32::from(self.0) / f32::from(Self::MASK)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_normalized_f32(value: f32) -> Self {
+        Self((value.clamp(0.0, 1.0) * f32::from(Self::MASK) + 0.5) as u8)
+    }
+}
+
+macro_rules! impl_component_int {
+    ($($ty:ty),+) => {
+        $(
+            impl sealed::Sealed for $ty {
+                fn to_scaled(self) -> Scaled {
+                    Scaled::Int {
+                        value: u64::from(self),
+                        max: u64::from(<$ty>::MAX),
+                    }
+                }
+
+                #[allow(clippy::cast_possible_truncation)]
+                fn from_scaled(scaled: Scaled) -> Self {
+                    scaled.to_int(u64::from(<$ty>::MAX)) as $ty
+                }
+
+                fn write_scaled(self, f: &mut fmt::Formatter<'_>, scale: u32) -> fmt::Result {
+                    sealed::write_int(f, u64::from(self), u64::from(<$ty>::MAX), scale)
+                }
+            }
+
+            impl Component for $ty {
+                const MIN: Self = <$ty>::MIN;
+                const MAX: Self = <$ty>::MAX;
+                const BITS: Option<u32> = Some(<$ty>::BITS);
+
+                #[allow(clippy::cast_possible_truncation)]
+                fn to_normalized_f32(self) -> f32 {
+                    (f64::from(self) / f64::from(<$ty>::MAX)) as f32
+                }
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                fn from_normalized_f32(value: f32) -> Self {
+                    (f64::from(value).clamp(0.0, 1.0) * f64::from(<$ty>::MAX) + 0.5) as $ty
+                }
+            }
+        )+
+    };
+}
+
+impl_component_int!(u8, u16, u32);
+
+macro_rules! impl_component_float {
+    ($($ty:ty),+) => {
+        $(
+            impl sealed::Sealed for $ty {
+                fn to_scaled(self) -> Scaled {
+                    Scaled::Float(f64::from(self))
+                }
+
+                #[allow(clippy::cast_possible_truncation)]
+                fn from_scaled(scaled: Scaled) -> Self {
+                    scaled.to_float() as $ty
+                }
+
+                #[allow(clippy::cast_precision_loss)]
+                fn write_scaled(self, f: &mut fmt::Formatter<'_>, scale: u32) -> fmt::Result {
+                    let value = self * scale as $ty;
+                    if value.is_nan() {
+                        f.write_str("0")
+                    } else {
+                        write!(f, "{}", value.clamp(-<$ty>::MAX, <$ty>::MAX))
+                    }
+                }
+            }
+
+            impl Component for $ty {
+                const MIN: Self = 0.0;
+                const MAX: Self = 1.0;
+                const BITS: Option<u32> = None;
+
+                #[allow(clippy::cast_possible_truncation)]
+                fn to_normalized_f32(self) -> f32 {
+                    self as f32
+                }
+
+                fn from_normalized_f32(value: f32) -> Self {
+                    value.into()
+                }
+            }
+        )+
+    };
+}
+
+impl_component_float!(f32, f64);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_new_masks_value() {
+        assert_eq!(Bits::<1>::new(0xFF).get(), 1);
+        assert_eq!(Bits::<4>::new(0xFF).get(), 15);
+        assert_eq!(Bits::<6>::new(0xFF).get(), 63);
+        assert_eq!(Bits::<8>::new(0xFF).get(), 255);
+    }
+
+    #[test]
+    fn bits_min_max() {
+        assert_eq!(Bits::<1>::MAX, 1);
+        assert_eq!(Bits::<5>::MAX, 31);
+        assert_eq!(Bits::<5>::MIN, 0);
+        assert_eq!(Bits::<8>::MAX, 255);
+    }
+
+    #[test]
+    fn int_normalized_round_trip() {
+        for value in 0..=u8::MAX {
+            assert_eq!(u8::from_normalized_f32(value.to_normalized_f32()), value);
+        }
+        for value in 0..=u16::MAX {
+            assert_eq!(u16::from_normalized_f32(value.to_normalized_f32()), value);
+        }
+        for value in 0..=63 {
+            let value = Bits::<6>::new(value);
+            assert_eq!(
+                Bits::<6>::from_normalized_f32(value.to_normalized_f32()),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn int_from_normalized_clamps() {
+        assert_eq!(u8::from_normalized_f32(-1.0), 0);
+        assert_eq!(u8::from_normalized_f32(f32::NAN), 0);
+        assert_eq!(u16::from_normalized_f32(1.5), u16::MAX);
+        assert_eq!(Bits::<4>::from_normalized_f32(f32::INFINITY), Bits::MAX);
+    }
+
+    #[test]
+    fn float_normalized_is_identity() {
+        assert_eq!(2.5f32.to_normalized_f32(), 2.5);
+        assert_eq!(f32::from_normalized_f32(-0.5), -0.5);
+        assert_eq!(0.25f64.to_normalized_f32(), 0.25);
+    }
+
+    #[test]
+    fn convert_widening_is_lossless() {
+        for value in 0..=31 {
+            let value = Bits::<5>::new(value);
+            assert_eq!(value.convert::<u8>().convert::<Bits<5>>(), value);
+            assert_eq!(value.convert::<Bits<6>>().convert::<Bits<5>>(), value);
+            assert_eq!(value.convert::<f32>().convert::<Bits<5>>(), value);
+        }
+        for value in 0..=u8::MAX {
+            assert_eq!(value.convert::<u16>().convert::<u8>(), value);
+            assert_eq!(value.convert::<u32>().convert::<u8>(), value);
+        }
+    }
+
+    #[test]
+    fn convert_widening_replicates_bits() {
+        assert_eq!(Bits::<5>::MAX.convert::<u8>(), 255);
+        assert_eq!(Bits::<1>::MAX.convert::<u8>(), 255);
+        assert_eq!(u8::MAX.convert::<u16>(), u16::MAX);
+        assert_eq!(0x12u8.convert::<u16>(), 0x1212);
+        assert_eq!(u16::MAX.convert::<u32>(), u32::MAX);
+    }
+
+    #[test]
+    fn convert_narrowing_rounds() {
+        assert_eq!(127u8.convert::<Bits<1>>(), Bits::new(0));
+        assert_eq!(128u8.convert::<Bits<1>>(), Bits::new(1));
+        assert_eq!(0x1280u16.convert::<u8>(), 0x12);
+        assert_eq!(0x12FFu16.convert::<u8>(), 0x13);
+    }
+
+    #[test]
+    fn convert_float_to_int_clamps() {
+        assert_eq!((-1.0f32).convert::<u8>(), 0);
+        assert_eq!(2.0f32.convert::<u8>(), 255);
+        assert_eq!(f32::NAN.convert::<u16>(), 0);
+    }
+}
@@ -0,0 +1,112 @@
+//! Compositing 🖌️ one color over another, such as drawing a semi-transparent shape.
+//!
+//! This module contains:
+//!
+//! - [`PorterDuff`], the twelve Porter-Duff operators, such as [`PorterDuff::SrcOver`]
+//! - [`BlendMode`], the blend modes of [Compositing and Blending Level 1], such as
+//!   [`BlendMode::Multiply`]
+//!
+//! Every operator and blend mode works on any [`RgbaColor`], either a single color at a time or
+//! over slices.
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{composite::PorterDuff, rgb::Abgr8888};
+//!
+//! let blue = Abgr8888::from_abgr(128, 255, 0, 0);
+//! let red = Abgr8888::from_abgr(255, 0, 0, 255);
+//!
+//! let purple = PorterDuff::SrcOver.composite(blue, red);
+//! assert_eq!(purple, Abgr8888::from_abgr(255, 128, 0, 127));
+//! ```
+//!
+//! ## Precision
+//!
+//! Colors with components of 8 bits or fewer, such as [`Abgr8888`] or [`Argb4444`], are
+//! composited with integer arithmetic, and each result is the exact result rounded to the nearest
+//! value. Other colors, such as [`Rgbaf32`], are composited with floating-point arithmetic.
+//! Blend modes always use floating-point arithmetic.
+//!
+//! Components are composited as they are stored, which for the predefined types is sRGB-encoded;
+//! this matches how browsers and most image editors composite. To composite in linear light,
+//! convert colors with [`LinearRgb`] first.
+//!
+//! [Compositing and Blending Level 1]: https://www.w3.org/TR/compositing-1/
+//! [`RgbaColor`]: crate::rgb::RgbaColor
+//! [`Abgr8888`]: crate::rgb::Abgr8888
+//! [`Argb4444`]: crate::rgb::Argb4444
+//! [`Rgbaf32`]: crate::rgb::Rgbaf32
+//! [`LinearRgb`]: crate::encoding::LinearRgb
+
+use crate::{
+    alpha::HasAlpha,
+    component::Component,
+    rgb::{HasBlue, HasGreen, HasRed, RgbaColor},
+};
+
+mod blend_mode;
+mod porter_duff;
+
+pub use blend_mode::BlendMode;
+pub use porter_duff::PorterDuff;
+
+/// Returns whether every component of `C` fits into a [`u8`] without loss.
+fn is_8_bit<C: RgbaColor>() -> bool {
+    fn fits<T: Component>() -> bool {
+        matches!(T::BITS, Some(bits) if bits <= 8)
+    }
+    fits::<<C as HasRed>::Component>()
+        && fits::<<C as HasGreen>::Component>()
+        && fits::<<C as HasBlue>::Component>()
+        && fits::<<C as HasAlpha>::Component>()
+}
+
+/// Applies `int` to the components of colors with 8 bits or fewer, and `float` otherwise.
+///
+/// Both functions receive `[red, green, blue, alpha]` of the source and destination.
+fn apply<C: RgbaColor>(
+    src: C,
+    dst: C,
+    int: impl FnOnce([u8; 4], [u8; 4]) -> [u8; 4],
+    float: impl FnOnce([f32; 4], [f32; 4]) -> [f32; 4],
+) -> C {
+    if is_8_bit::<C>() {
+        let [r, g, b, a] = int(into_u8(src), into_u8(dst));
+        C::from_rgba(r.convert(), g.convert(), b.convert(), a.convert())
+    } else {
+        apply_float(src, dst, float)
+    }
+}
+
+/// Applies `float` to the normalized components of colors of any type.
+///
+/// Integer components are rounded to the nearest value afterwards.
+fn apply_float<C: RgbaColor>(
+    src: C,
+    dst: C,
+    float: impl FnOnce([f32; 4], [f32; 4]) -> [f32; 4],
+) -> C {
+    let [r, g, b, a] = float(into_f32(src), into_f32(dst));
+    C::from_rgba(
+        Component::from_normalized_f32(r),
+        Component::from_normalized_f32(g),
+        Component::from_normalized_f32(b),
+        Component::from_normalized_f32(a),
+    )
+}
+
+fn into_u8<C: RgbaColor>(color: C) -> [u8; 4] {
+    let (r, g, b, a) = color.into_rgba();
+    [r.convert(), g.convert(), b.convert(), a.convert()]
+}
+
+fn into_f32<C: RgbaColor>(color: C) -> [f32; 4] {
+    let (r, g, b, a) = color.into_rgba();
+    [
+        r.to_normalized_f32(),
+        g.to_normalized_f32(),
+        b.to_normalized_f32(),
+        a.to_normalized_f32(),
+    ]
+}
@@ -0,0 +1,442 @@
+use crate::{composite::apply_float, math::sqrt, rgb::RgbaColor};
+
+/// A blend mode of [Compositing and Blending Level 1], which mixes the source color with the
+/// destination (backdrop) color where they overlap.
+///
+/// The blended color is composited over the destination using source-over compositing, so where
+/// the destination is transparent the source is drawn unchanged, and where the source is
+/// transparent the destination is kept:
+///
+/// ```txt
+/// Cs' = (1 − αb) × Cs + αb × B(Cb, Cs)
+/// αo  = αs + αb × (1 − αs)
+/// Co  = (αs × Cs' + (1 − αs) × αb × Cb) / αo
+/// ```
+///
+/// Separable blend modes, such as [`Multiply`][Self::Multiply], apply `B` to each of red, green,
+/// and blue independently. Non-separable blend modes, such as [`Hue`][Self::Hue], combine the
+/// hue, saturation, and luminosity of both colors.
+///
+/// [Compositing and Blending Level 1]: https://www.w3.org/TR/compositing-1/#blending
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{composite::BlendMode, rgb::Rgbaf32};
+///
+/// let src = Rgbaf32::from_rgba(0.5, 1.0, 0.0, 1.0);
+/// let dst = Rgbaf32::from_rgba(0.5, 0.5, 0.5, 1.0);
+///
+/// assert_eq!(BlendMode::Multiply.blend(src, dst), Rgbaf32::from_rgba(0.25, 0.5, 0.0, 1.0));
+/// assert_eq!(BlendMode::Screen.blend(src, dst), Rgbaf32::from_rgba(0.75, 1.0, 0.5, 1.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BlendMode {
+    /// The source color, without mixing.
+    Normal,
+
+    /// The product of the source and destination; the result is always darker.
+    Multiply,
+
+    /// The complement of the product of the complements; the result is always lighter.
+    Screen,
+
+    /// [`Multiply`][Self::Multiply] or [`Screen`][Self::Screen], depending on the destination.
+    Overlay,
+
+    /// The darker of the source and destination.
+    Darken,
+
+    /// The lighter of the source and destination.
+    Lighten,
+
+    /// Brightens the destination to reflect the source.
+    ColorDodge,
+
+    /// Darkens the destination to reflect the source.
+    ColorBurn,
+
+    /// [`Multiply`][Self::Multiply] or [`Screen`][Self::Screen], depending on the source.
+    HardLight,
+
+    /// A softer version of [`HardLight`][Self::HardLight].
+    SoftLight,
+
+    /// The absolute difference of the source and destination.
+    Difference,
+
+    /// Like [`Difference`][Self::Difference], with lower contrast.
+    Exclusion,
+
+    /// The hue of the source, with the saturation and luminosity of the destination.
+    Hue,
+
+    /// The saturation of the source, with the hue and luminosity of the destination.
+    Saturation,
+
+    /// The hue and saturation of the source, with the luminosity of the destination.
+    Color,
+
+    /// The luminosity of the source, with the hue and saturation of the destination.
+    Luminosity,
+}
+
+impl BlendMode {
+    /// Every blend mode, in declaration order.
+    pub const ALL: [Self; 16] = [
+        Self::Normal,
+        Self::Multiply,
+        Self::Screen,
+        Self::Overlay,
+        Self::Darken,
+        Self::Lighten,
+        Self::ColorDodge,
+        Self::ColorBurn,
+        Self::HardLight,
+        Self::SoftLight,
+        Self::Difference,
+        Self::Exclusion,
+        Self::Hue,
+        Self::Saturation,
+        Self::Color,
+        Self::Luminosity,
+    ];
+
+    /// Returns whether the blend mode applies to each of red, green, and blue independently.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::composite::BlendMode;
+    ///
+    /// assert!(BlendMode::Multiply.is_separable());
+    /// assert!(!BlendMode::Luminosity.is_separable());
+    /// ```
+    #[must_use]
+    pub const fn is_separable(self) -> bool {
+        !matches!(
+            self,
+            Self::Hue | Self::Saturation | Self::Color | Self::Luminosity
+        )
+    }
+
+    /// Blends `src` with `dst`, composites the result over `dst`, and returns it.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{composite::BlendMode, rgb::Abgr8888};
+    ///
+    /// let src = Abgr8888::from_abgr(255, 0, 0, 255);
+    /// let dst = Abgr8888::from_abgr(255, 128, 128, 128);
+    /// assert_eq!(BlendMode::Darken.blend(src, dst), Abgr8888::from_abgr(255, 0, 0, 128));
+    /// ```
+    #[must_use]
+    pub fn blend<C: RgbaColor>(self, src: C, dst: C) -> C {
+        apply_float(src, dst, |s, d| self.blend_f32(s, d))
+    }
+
+    /// Blends each color of `src` with the color at the same index of `dst`, in place.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    pub fn blend_slice<C: RgbaColor + Copy>(self, src: &[C], dst: &mut [C]) {
+        assert_eq!(
+            src.len(),
+            dst.len(),
+            "source and destination lengths differ"
+        );
+        for (s, d) in src.iter().zip(dst) {
+            *d = self.blend(*s, *d);
+        }
+    }
+
+    /// Blends a single color, `src`, with every color of `dst`, in place.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{composite::BlendMode, rgb::Rgbaf32};
+    ///
+    /// let mut dst = [Rgbaf32::from_rgba(0.5, 0.5, 0.5, 1.0); 4];
+    /// BlendMode::Difference.blend_fill(Rgbaf32::from_rgba(1.0, 1.0, 1.0, 1.0), &mut dst);
+    /// assert_eq!(dst, [Rgbaf32::from_rgba(0.5, 0.5, 0.5, 1.0); 4]);
+    /// ```
+    pub fn blend_fill<C: RgbaColor + Copy>(self, src: C, dst: &mut [C]) {
+        for d in dst {
+            *d = self.blend(src, *d);
+        }
+    }
+
+    /// Blends and composites normalized `[red, green, blue, alpha]` components.
+    fn blend_f32(self, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+        let [sr, sg, sb, src_alpha] = src;
+        let [dr, dg, db, dst_alpha] = dst;
+        let blended = self.mix([dr, dg, db], [sr, sg, sb]);
+
+        let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+        if out_alpha <= 0.0 {
+            return [0.0; 4];
+        }
+        let channel = |i: usize| {
+            let (cs, cb) = (src[i], dst[i]);
+            let cs = (1.0 - dst_alpha) * cs + dst_alpha * blended[i];
+            (src_alpha * cs + (1.0 - src_alpha) * dst_alpha * cb) / out_alpha
+        };
+        [channel(0), channel(1), channel(2), out_alpha]
+    }
+
+    /// Returns `B(Cb, Cs)`, the blended color of the backdrop `cb` and source `cs`.
+    fn mix(self, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+        match self {
+            Self::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+            Self::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+            Self::Color => set_lum(cs, lum(cb)),
+            Self::Luminosity => set_lum(cb, lum(cs)),
+            _ => [0, 1, 2].map(|i| self.mix_separable(cb[i], cs[i])),
+        }
+    }
+
+    /// Returns `B(Cb, Cs)` for a single component of a separable blend mode.
+    #[allow(clippy::cast_possible_truncation)]
+    fn mix_separable(self, cb: f32, cs: f32) -> f32 {
+        match self {
+            Self::Multiply => cb * cs,
+            Self::Screen => cb + cs - cb * cs,
+            Self::Overlay => Self::HardLight.mix_separable(cs, cb),
+            Self::Darken => cb.min(cs),
+            Self::Lighten => cb.max(cs),
+            Self::ColorDodge => {
+                if cb <= 0.0 {
+                    0.0
+                } else if cs >= 1.0 {
+                    1.0
+                } else {
+                    (cb / (1.0 - cs)).min(1.0)
+                }
+            }
+            Self::ColorBurn => {
+                if cb >= 1.0 {
+                    1.0
+                } else if cs <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - cb) / cs).min(1.0)
+                }
+            }
+            Self::HardLight => {
+                if cs <= 0.5 {
+                    Self::Multiply.mix_separable(cb, 2.0 * cs)
+                } else {
+                    Self::Screen.mix_separable(cb, 2.0 * cs - 1.0)
+                }
+            }
+            Self::SoftLight => {
+                if cs <= 0.5 {
+                    cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+                } else {
+                    let d = if cb <= 0.25 {
+                        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                    } else {
+                        sqrt(f64::from(cb)) as f32
+                    };
+                    cb + (2.0 * cs - 1.0) * (d - cb)
+                }
+            }
+            Self::Difference => (cb - cs).abs(),
+            Self::Exclusion => cb + cs - 2.0 * cb * cs,
+            Self::Normal | Self::Hue | Self::Saturation | Self::Color | Self::Luminosity => cs,
+        }
+    }
+}
+
+/// Returns the luminosity of a color, as defined by the specification.
+fn lum([r, g, b]: [f32; 3]) -> f32 {
+    0.3 * r + 0.59 * g + 0.11 * b
+}
+
+/// Brings the components of a color into `0.0..=1.0`, preserving its luminosity.
+fn clip_color(color: [f32; 3]) -> [f32; 3] {
+    let l = lum(color);
+    let n = color[0].min(color[1]).min(color[2]);
+    let x = color[0].max(color[1]).max(color[2]);
+    let mut color = color;
+    if n < 0.0 {
+        color = color.map(|c| l + (c - l) * l / (l - n));
+    }
+    if x > 1.0 {
+        color = color.map(|c| l + (c - l) * (1.0 - l) / (x - l));
+    }
+    color
+}
+
+/// Returns a color with the given luminosity, preserving its hue and saturation where possible.
+fn set_lum(color: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(color);
+    clip_color(color.map(|c| c + d))
+}
+
+/// Returns the saturation of a color, the difference of its largest and smallest components.
+fn sat(color: [f32; 3]) -> f32 {
+    color[0].max(color[1]).max(color[2]) - color[0].min(color[1]).min(color[2])
+}
+
+/// Returns a color with the given saturation, preserving its hue.
+fn set_sat(color: [f32; 3], s: f32) -> [f32; 3] {
+    let mut order = [0, 1, 2];
+    order.sort_unstable_by(|&a, &b| color[a].total_cmp(&color[b]));
+    let [min, mid, max] = order;
+
+    let mut out = [0.0; 3];
+    if color[max] > color[min] {
+        out[mid] = (color[mid] - color[min]) * s / (color[max] - color[min]);
+        out[max] = s;
+    }
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        composite::PorterDuff,
+        rgb::{Abgr8888, Rgbaf32},
+    };
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn separable_formulas() {
+        // (mode, Cb, Cs, B(Cb, Cs)), computed from the formulas in the specification.
+        let cases = [
+            (BlendMode::Normal, 0.5, 0.4, 0.4),
+            (BlendMode::Multiply, 0.5, 0.4, 0.2),
+            (BlendMode::Screen, 0.5, 0.4, 0.7),
+            (BlendMode::Overlay, 0.25, 0.5, 0.25),
+            (BlendMode::Overlay, 0.75, 0.5, 0.75),
+            (BlendMode::Darken, 0.5, 0.4, 0.4),
+            (BlendMode::Lighten, 0.5, 0.4, 0.5),
+            (BlendMode::ColorDodge, 0.5, 0.4, 0.5 / 0.6),
+            (BlendMode::ColorDodge, 0.0, 1.0, 0.0),
+            (BlendMode::ColorDodge, 0.1, 1.0, 1.0),
+            (BlendMode::ColorDodge, 0.8, 0.5, 1.0),
+            (BlendMode::ColorBurn, 0.8, 0.5, 0.6),
+            (BlendMode::ColorBurn, 0.5, 0.4, 0.0),
+            (BlendMode::ColorBurn, 1.0, 0.0, 1.0),
+            (BlendMode::ColorBurn, 0.9, 0.0, 0.0),
+            (BlendMode::HardLight, 0.5, 0.25, 0.25),
+            (BlendMode::HardLight, 0.5, 0.75, 0.75),
+            (BlendMode::SoftLight, 0.25, 0.25, 0.156_25),
+            (BlendMode::SoftLight, 0.25, 0.75, 0.375),
+            (BlendMode::SoftLight, 0.64, 1.0, 0.8),
+            (BlendMode::Difference, 0.5, 0.4, 0.1),
+            (BlendMode::Difference, 0.4, 0.5, 0.1),
+            (BlendMode::Exclusion, 0.5, 0.4, 0.5),
+        ];
+        for (mode, cb, cs, expected) in cases {
+            let actual = mode.mix([cb; 3], [cs; 3]);
+            assert_close(actual, [expected; 3]);
+        }
+    }
+
+    #[test]
+    fn non_separable_formulas() {
+        let red = [1.0, 0.0, 0.0];
+        let blue = [0.0, 0.0, 1.0];
+        let orange = [1.0, 0.5, 0.0];
+        let gray = [0.5, 0.5, 0.5];
+        let white = [1.0, 1.0, 1.0];
+
+        assert_close(BlendMode::Hue.mix(gray, red), gray);
+        assert_close(
+            BlendMode::Hue.mix(orange, blue),
+            [
+                0.595 - 0.11 * 0.405 / 0.89,
+                0.595 - 0.11 * 0.405 / 0.89,
+                1.0,
+            ],
+        );
+        assert_close(BlendMode::Saturation.mix(orange, gray), [0.595; 3]);
+        assert_close(
+            BlendMode::Color.mix(gray, red),
+            [1.0, 0.5 - 0.3 * 0.5 / 0.7, 0.5 - 0.3 * 0.5 / 0.7],
+        );
+        assert_close(BlendMode::Luminosity.mix(red, white), white);
+        assert_close(BlendMode::Luminosity.mix(red, [0.0; 3]), [0.0; 3]);
+    }
+
+    #[test]
+    fn non_separable_preserves_luminosity() {
+        let cb = [0.2, 0.6, 0.9];
+        let cs = [0.8, 0.3, 0.1];
+        for mode in [BlendMode::Hue, BlendMode::Saturation, BlendMode::Color] {
+            assert!((lum(mode.mix(cb, cs)) - lum(cb)).abs() < 1e-6, "{mode:?}");
+        }
+        assert!((lum(BlendMode::Luminosity.mix(cb, cs)) - lum(cs)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn transparent_src_keeps_dst() {
+        let src = Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.0);
+        let dst = Rgbaf32::from_rgba(0.25, 0.5, 0.75, 0.5);
+        for mode in BlendMode::ALL {
+            assert_eq!(mode.blend(src, dst), dst, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn transparent_dst_keeps_src() {
+        let src = Rgbaf32::from_rgba(0.25, 0.5, 0.75, 0.5);
+        let dst = Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.0);
+        for mode in BlendMode::ALL {
+            assert_eq!(mode.blend(src, dst), src, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn normal_is_src_over() {
+        let src = Rgbaf32::from_rgba(0.2, 0.4, 0.6, 0.5);
+        let dst = Rgbaf32::from_rgba(0.8, 0.6, 0.4, 0.75);
+        let normal = BlendMode::Normal.blend(src, dst).into_rgba();
+        let src_over = PorterDuff::SrcOver.composite(src, dst).into_rgba();
+        assert!((normal.0 - src_over.0).abs() < 1e-6);
+        assert!((normal.1 - src_over.1).abs() < 1e-6);
+        assert!((normal.2 - src_over.2).abs() < 1e-6);
+        assert_eq!(normal.3, src_over.3);
+    }
+
+    #[test]
+    fn semi_transparent_multiply() {
+        // Cs' = 0.5 × 1.0 + 0.5 × 0.5 = 0.75; Co = (0.5 × 0.75 + 0.5 × 0.5 × 0.5) / 0.75.
+        let src = Rgbaf32::from_rgba(1.0, 1.0, 1.0, 0.5);
+        let dst = Rgbaf32::from_rgba(0.5, 0.5, 0.5, 0.5);
+        let (r, _, _, a) = BlendMode::Multiply.blend(src, dst).into_rgba();
+        assert!((r - 0.5 / 0.75).abs() < 1e-6);
+        assert_eq!(a, 0.75);
+    }
+
+    #[test]
+    fn u8_multiply() {
+        let src = Abgr8888::from_abgr(255, 0, 128, 255);
+        let dst = Abgr8888::from_abgr(255, 128, 128, 128);
+        assert_eq!(
+            BlendMode::Multiply.blend(src, dst),
+            Abgr8888::from_abgr(255, 0, 64, 128)
+        );
+    }
+
+    #[test]
+    fn blend_slice() {
+        let src = [Rgbaf32::from_rgba(0.5, 0.5, 0.5, 1.0); 2];
+        let mut dst = [Rgbaf32::from_rgba(0.5, 1.0, 0.0, 1.0); 2];
+        BlendMode::Screen.blend_slice(&src, &mut dst);
+        assert_eq!(dst, [Rgbaf32::from_rgba(0.75, 1.0, 0.5, 1.0); 2]);
+    }
+}
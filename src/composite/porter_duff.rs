@@ -0,0 +1,374 @@
+use core::ops::Sub;
+
+use crate::{composite::apply, rgb::RgbaColor};
+
+/// A Porter-Duff compositing operator, which combines a source color with a destination color.
+///
+/// Each operator keeps a fraction of the source, `Fa`, and a fraction of the destination, `Fb`,
+/// where the fractions depend on the alpha of the source (`αs`) and destination (`αd`):
+///
+/// ```txt
+/// αo = αs × Fa + αd × Fb
+/// Co = (αs × Fa × Cs + αd × Fb × Cd) / αo
+/// ```
+///
+/// If the resulting alpha `αo` is zero, the result is transparent black.
+///
+/// Operator               | `Fa`     | `Fb`
+/// ---------------------- | -------- | --------
+/// [`Clear`][Self::Clear]     | `0`      | `0`
+/// [`Src`][Self::Src]         | `1`      | `0`
+/// [`Dst`][Self::Dst]         | `0`      | `1`
+/// [`SrcOver`][Self::SrcOver] | `1`      | `1 − αs`
+/// [`DstOver`][Self::DstOver] | `1 − αd` | `1`
+/// [`SrcIn`][Self::SrcIn]     | `αd`     | `0`
+/// [`DstIn`][Self::DstIn]     | `0`      | `αs`
+/// [`SrcOut`][Self::SrcOut]   | `1 − αd` | `0`
+/// [`DstOut`][Self::DstOut]   | `0`      | `1 − αs`
+/// [`SrcAtop`][Self::SrcAtop] | `αd`     | `1 − αs`
+/// [`DstAtop`][Self::DstAtop] | `1 − αd` | `αs`
+/// [`Xor`][Self::Xor]         | `1 − αd` | `1 − αs`
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{composite::PorterDuff, rgb::Rgbaf32};
+///
+/// let src = Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.5);
+/// let dst = Rgbaf32::from_rgba(0.0, 0.0, 1.0, 1.0);
+///
+/// assert_eq!(PorterDuff::SrcOver.composite(src, dst), Rgbaf32::from_rgba(0.5, 0.0, 0.5, 1.0));
+/// assert_eq!(PorterDuff::SrcIn.composite(src, dst), src);
+/// assert_eq!(PorterDuff::Xor.composite(src, dst), Rgbaf32::from_rgba(0.0, 0.0, 1.0, 0.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PorterDuff {
+    /// Neither the source nor the destination is kept.
+    Clear,
+
+    /// Only the source is kept.
+    Src,
+
+    /// Only the destination is kept.
+    Dst,
+
+    /// The source is placed over the destination; the most common operator.
+    SrcOver,
+
+    /// The destination is placed over the source.
+    DstOver,
+
+    /// The part of the source inside the destination is kept.
+    SrcIn,
+
+    /// The part of the destination inside the source is kept.
+    DstIn,
+
+    /// The part of the source outside the destination is kept.
+    SrcOut,
+
+    /// The part of the destination outside the source is kept.
+    DstOut,
+
+    /// The part of the source inside the destination is placed over the destination.
+    SrcAtop,
+
+    /// The part of the destination inside the source is placed over the source.
+    DstAtop,
+
+    /// The parts of the source and destination outside of each other are kept.
+    Xor,
+}
+
+impl PorterDuff {
+    /// Every operator, in declaration order.
+    pub const ALL: [Self; 12] = [
+        Self::Clear,
+        Self::Src,
+        Self::Dst,
+        Self::SrcOver,
+        Self::DstOver,
+        Self::SrcIn,
+        Self::DstIn,
+        Self::SrcOut,
+        Self::DstOut,
+        Self::SrcAtop,
+        Self::DstAtop,
+        Self::Xor,
+    ];
+
+    /// Composites `src` with `dst`, and returns the result.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{composite::PorterDuff, rgb::Argb4444};
+    ///
+    /// let src = Argb4444::from_argb(0, 15, 15, 15);
+    /// let dst = Argb4444::from_argb(15, 0, 0, 0);
+    /// assert_eq!(PorterDuff::SrcOver.composite(src, dst), dst);
+    /// ```
+    #[must_use]
+    pub fn composite<C: RgbaColor>(self, src: C, dst: C) -> C {
+        apply(
+            src,
+            dst,
+            |s, d| self.composite_u8(s, d),
+            |s, d| self.composite_f32(s, d),
+        )
+    }
+
+    /// Composites each color of `src` with the color at the same index of `dst`, in place.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{composite::PorterDuff, rgb::Abgr8888};
+    ///
+    /// let src = [Abgr8888::from_abgr(0, 0, 0, 0), Abgr8888::from_abgr(255, 255, 0, 0)];
+    /// let mut dst = [Abgr8888::from_abgr(255, 0, 0, 255); 2];
+    ///
+    /// PorterDuff::SrcOver.composite_slice(&src, &mut dst);
+    /// assert_eq!(dst, [Abgr8888::from_abgr(255, 0, 0, 255), Abgr8888::from_abgr(255, 255, 0, 0)]);
+    /// ```
+    pub fn composite_slice<C: RgbaColor + Copy>(self, src: &[C], dst: &mut [C]) {
+        assert_eq!(
+            src.len(),
+            dst.len(),
+            "source and destination lengths differ"
+        );
+        for (s, d) in src.iter().zip(dst) {
+            *d = self.composite(*s, *d);
+        }
+    }
+
+    /// Composites a single color, `src`, with every color of `dst`, in place.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{composite::PorterDuff, rgb::Rgbaf32};
+    ///
+    /// let mut dst = [Rgbaf32::from_rgba(0.0, 0.0, 0.0, 1.0); 4];
+    /// PorterDuff::SrcOver.composite_fill(Rgbaf32::from_rgba(1.0, 1.0, 1.0, 0.25), &mut dst);
+    /// assert_eq!(dst, [Rgbaf32::from_rgba(0.25, 0.25, 0.25, 1.0); 4]);
+    /// ```
+    pub fn composite_fill<C: RgbaColor + Copy>(self, src: C, dst: &mut [C]) {
+        for d in dst {
+            *d = self.composite(src, *d);
+        }
+    }
+
+    /// Returns the fractions `Fa` and `Fb` of the source and destination, where `one` is opaque.
+    fn factors<T>(self, one: T, src_alpha: T, dst_alpha: T) -> (T, T)
+    where
+        T: Copy + Default + Sub<Output = T>,
+    {
+        let zero = T::default();
+        match self {
+            Self::Clear => (zero, zero),
+            Self::Src => (one, zero),
+            Self::Dst => (zero, one),
+            Self::SrcOver => (one, one - src_alpha),
+            Self::DstOver => (one - dst_alpha, one),
+            Self::SrcIn => (dst_alpha, zero),
+            Self::DstIn => (zero, src_alpha),
+            Self::SrcOut => (one - dst_alpha, zero),
+            Self::DstOut => (zero, one - src_alpha),
+            Self::SrcAtop => (dst_alpha, one - src_alpha),
+            Self::DstAtop => (one - dst_alpha, src_alpha),
+            Self::Xor => (one - dst_alpha, one - src_alpha),
+        }
+    }
+
+    /// Composites 8-bit components, rounding the exact result to the nearest value.
+    #[allow(clippy::cast_possible_truncation)]
+    fn composite_u8(self, src: [u8; 4], dst: [u8; 4]) -> [u8; 4] {
+        let (src_alpha, dst_alpha) = (u32::from(src[3]), u32::from(dst[3]));
+        let (fa, fb) = self.factors(255, src_alpha, dst_alpha);
+
+        // Weights of the source and destination, scaled by 255 × 255.
+        let (ws, wd) = (src_alpha * fa, dst_alpha * fb);
+        let wo = ws + wd;
+        if wo == 0 {
+            return [0; 4];
+        }
+        let channel = |s: u8, d: u8| {
+            let sum = u32::from(s) * ws + u32::from(d) * wd;
+            ((2 * sum + wo) / (2 * wo)) as u8
+        };
+        [
+            channel(src[0], dst[0]),
+            channel(src[1], dst[1]),
+            channel(src[2], dst[2]),
+            ((wo + 127) / 255) as u8,
+        ]
+    }
+
+    /// Composites floating-point components.
+    fn composite_f32(self, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+        let (fa, fb) = self.factors(1.0, src[3], dst[3]);
+        let (ws, wd) = (src[3] * fa, dst[3] * fb);
+        let wo = ws + wd;
+        if wo <= 0.0 {
+            return [0.0; 4];
+        }
+        let channel = |s: f32, d: f32| (s * ws + d * wd) / wo;
+        [
+            channel(src[0], dst[0]),
+            channel(src[1], dst[1]),
+            channel(src[2], dst[2]),
+            wo,
+        ]
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::rgb::{Abgr8888, Argb4444, Rgbaf32};
+
+    /// Returns the exact result of compositing, rounded to the nearest 8-bit value.
+    ///
+    /// Components are kept as integers (stored as `f64`), so that ties are represented exactly.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn reference(op: PorterDuff, src: [u8; 4], dst: [u8; 4]) -> [u8; 4] {
+        let [src, dst] = [src, dst].map(|c| c.map(f64::from));
+        let (fa, fb) = op.factors(255.0, src[3], dst[3]);
+        let (ws, wd) = (src[3] * fa, dst[3] * fb);
+        let wo = ws + wd;
+        if wo == 0.0 {
+            return [0; 4];
+        }
+        let channel = |i: usize| ((src[i] * ws + dst[i] * wd) / wo).round() as u8;
+        [
+            channel(0),
+            channel(1),
+            channel(2),
+            (wo / 255.0).round() as u8,
+        ]
+    }
+
+    #[test]
+    fn src_over_opaque_dst() {
+        let src = Abgr8888::from_abgr(128, 255, 0, 0);
+        let dst = Abgr8888::from_abgr(255, 0, 0, 255);
+        assert_eq!(
+            PorterDuff::SrcOver.composite(src, dst),
+            Abgr8888::from_abgr(255, 128, 0, 127)
+        );
+    }
+
+    #[test]
+    fn u8_matches_reference() {
+        let values = [0, 1, 64, 127, 128, 200, 254, 255];
+        for op in PorterDuff::ALL {
+            for src_alpha in values {
+                for dst_alpha in values {
+                    for value in values {
+                        let src = [value, 255 - value, 255, src_alpha];
+                        let dst = [255 - value, 0, value, dst_alpha];
+                        assert_eq!(
+                            op.composite_u8(src, dst),
+                            reference(op, src, dst),
+                            "{op:?} {src:?} {dst:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn u8_src_and_dst_are_lossless() {
+        for alpha in 1..=255 {
+            let src = [10, 20, 30, alpha];
+            let dst = [40, 50, 60, 255 - alpha / 2];
+            assert_eq!(PorterDuff::Src.composite_u8(src, dst), src);
+            assert_eq!(PorterDuff::Dst.composite_u8(src, dst), dst);
+        }
+    }
+
+    #[test]
+    fn clear_is_transparent() {
+        let src = Rgbaf32::from_rgba(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(PorterDuff::Clear.composite(src, src), Rgbaf32::default());
+    }
+
+    #[test]
+    fn f32_operators() {
+        let src = Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.5);
+        let dst = Rgbaf32::from_rgba(0.0, 0.0, 1.0, 0.5);
+        let cases = [
+            (PorterDuff::Clear, Rgbaf32::from_rgba(0.0, 0.0, 0.0, 0.0)),
+            (PorterDuff::Src, src),
+            (PorterDuff::Dst, dst),
+            (
+                PorterDuff::SrcOver,
+                Rgbaf32::from_rgba(2.0 / 3.0, 0.0, 1.0 / 3.0, 0.75),
+            ),
+            (
+                PorterDuff::DstOver,
+                Rgbaf32::from_rgba(1.0 / 3.0, 0.0, 2.0 / 3.0, 0.75),
+            ),
+            (PorterDuff::SrcIn, Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.25)),
+            (PorterDuff::DstIn, Rgbaf32::from_rgba(0.0, 0.0, 1.0, 0.25)),
+            (PorterDuff::SrcOut, Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.25)),
+            (PorterDuff::DstOut, Rgbaf32::from_rgba(0.0, 0.0, 1.0, 0.25)),
+            (PorterDuff::SrcAtop, Rgbaf32::from_rgba(0.5, 0.0, 0.5, 0.5)),
+            (PorterDuff::DstAtop, Rgbaf32::from_rgba(0.5, 0.0, 0.5, 0.5)),
+            (PorterDuff::Xor, Rgbaf32::from_rgba(0.5, 0.0, 0.5, 0.5)),
+        ];
+        for (op, expected) in cases {
+            assert_eq!(op.composite(src, dst), expected, "{op:?}");
+        }
+    }
+
+    #[test]
+    fn packed_uses_integer_path() {
+        let src = Argb4444::from_argb(8, 15, 0, 0);
+        let dst = Argb4444::from_argb(15, 0, 0, 15);
+        assert_eq!(
+            PorterDuff::SrcOver.composite(src, dst),
+            Argb4444::from_argb(15, 8, 0, 7)
+        );
+    }
+
+    #[test]
+    fn composite_slice() {
+        let src = [Rgbaf32::from_rgba(1.0, 1.0, 1.0, 0.5); 3];
+        let mut dst = [Rgbaf32::from_rgba(0.0, 0.0, 0.0, 1.0); 3];
+        PorterDuff::SrcOver.composite_slice(&src, &mut dst);
+        assert_eq!(dst, [Rgbaf32::from_rgba(0.5, 0.5, 0.5, 1.0); 3]);
+    }
+
+    #[test]
+    #[should_panic = "source and destination lengths differ"]
+    fn composite_slice_different_lengths() {
+        let src = [Rgbaf32::default(); 2];
+        let mut dst = [Rgbaf32::default(); 3];
+        PorterDuff::SrcOver.composite_slice(&src, &mut dst);
+    }
+
+    #[test]
+    fn composite_fill() {
+        let mut dst = [
+            Abgr8888::from_abgr(255, 0, 0, 255),
+            Abgr8888::from_abgr(0, 0, 0, 0),
+        ];
+        PorterDuff::DstOver.composite_fill(Abgr8888::from_abgr(255, 255, 0, 0), &mut dst);
+        assert_eq!(
+            dst,
+            [
+                Abgr8888::from_abgr(255, 0, 0, 255),
+                Abgr8888::from_abgr(255, 255, 0, 0),
+            ]
+        );
+    }
+}
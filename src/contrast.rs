@@ -0,0 +1,227 @@
+//! Checking the 🔆 contrast of text against its background, such as for accessibility.
+//!
+//! This module contains:
+//!
+//! - [`relative_luminance`] and [`contrast_ratio`], as defined by [WCAG 2.1]
+//! - [`Level`] and [`TextSize`], which check a contrast ratio against levels AA and AAA
+//! - [`apca`], the lightness contrast (`Lc`) of the Accessible Perceptual Contrast Algorithm,
+//!   proposed for WCAG 3
+//! - [`adjust_lightness`], which finds the nearest lightness of a color that meets a
+//!   [`Requirement`], keeping its hue
+//!
+//! Every function works on any sRGB-encoded [`RgbColor`], such as [`Rgb888`] or [`Rgbf32`].
+//!
+//! [WCAG 2.1]: https://www.w3.org/TR/WCAG21/
+//! [`RgbColor`]: crate::rgb::RgbColor
+//! [`Rgb888`]: crate::rgb::Rgb888
+//! [`Rgbf32`]: crate::rgb::Rgbf32
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{
+//!     contrast::{Level, Requirement, TextSize, adjust_lightness},
+//!     rgb::Rgb888,
+//! };
+//!
+//! let background = Rgb888::from_rgb(255, 255, 255);
+//! let link = Rgb888::from_rgb(80, 160, 255);
+//! assert!(!Level::Aa.passes(link, background, TextSize::Normal));
+//!
+//! // Darken the link until it passes, keeping its hue.
+//! let ratio = Level::Aa.minimum_ratio(TextSize::Normal);
+//! let link = adjust_lightness(link, background, Requirement::Wcag(ratio)).unwrap();
+//! assert!(Level::Aa.passes(link, background, TextSize::Normal));
+//! ```
+
+use crate::{
+    cylindrical::HasLightness,
+    encoding::LinearRgb,
+    oklab::{Oklabf32, Oklchf32},
+    rgb::{RgbColor, Rgbf32},
+};
+
+mod apca;
+mod wcag;
+
+pub use apca::apca;
+pub use wcag::{Level, TextSize, contrast_ratio, relative_luminance};
+
+/// A minimum contrast between a foreground and a background color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Requirement {
+    /// A minimum WCAG 2.1 contrast ratio, such as `4.5` for text at level AA.
+    Wcag(f32),
+
+    /// A minimum magnitude of APCA lightness contrast, such as `60.0` for body text, for either
+    /// polarity.
+    Apca(f32),
+}
+
+impl Requirement {
+    /// Returns whether `foreground` on `background` meets the requirement.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{contrast::Requirement, rgb::Rgb888};
+    ///
+    /// let (black, white) = (Rgb888::from_rgb(0, 0, 0), Rgb888::from_rgb(255, 255, 255));
+    /// assert!(Requirement::Apca(100.0).is_met(white, black));
+    /// assert!(!Requirement::Wcag(4.5).is_met(white, white));
+    /// ```
+    #[must_use]
+    pub fn is_met<C: RgbColor>(self, foreground: C, background: C) -> bool {
+        match self {
+            Self::Wcag(ratio) => contrast_ratio(foreground, background) >= ratio,
+            Self::Apca(lc) => apca(foreground, background).abs() >= lc,
+        }
+    }
+}
+
+/// Returns the color nearest to `foreground` in Oklch lightness that meets `requirement` on
+/// `background`, or `None` if neither black nor white would.
+///
+/// The hue of `foreground` is kept; its chroma is kept too, unless a lighter or darker color of
+/// that chroma is outside of the sRGB gamut, in which case it is reduced until it fits. Both
+/// darker and lighter colors are considered, and a color that already meets the requirement is
+/// returned unchanged. The contrast is measured after rounding to `C`, so the result always meets
+/// the requirement.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{
+///     contrast::{Requirement, adjust_lightness, contrast_ratio},
+///     rgb::{HasGreen, HasRed, Rgb888},
+/// };
+///
+/// let background = Rgb888::from_rgb(30, 30, 30);
+/// let text = Rgb888::from_rgb(90, 40, 40);
+/// let text = adjust_lightness(text, background, Requirement::Wcag(7.0)).unwrap();
+/// assert!(contrast_ratio(text, background) >= 7.0);
+/// assert!(text.red() > text.green());
+///
+/// // No color has a contrast ratio of 21 against gray.
+/// let gray = Rgb888::from_rgb(128, 128, 128);
+/// assert_eq!(adjust_lightness(text, gray, Requirement::Wcag(21.0)), None);
+/// ```
+#[must_use]
+pub fn adjust_lightness<C: RgbColor + Copy>(
+    foreground: C,
+    background: C,
+    requirement: Requirement,
+) -> Option<C> {
+    if requirement.is_met(foreground, background) {
+        return Some(foreground);
+    }
+    let color = Oklchf32::from_rgb_color(foreground);
+    let lightness = color.lightness();
+    let meets = |color: C| requirement.is_met(color, background);
+
+    [0.0, 1.0]
+        .into_iter()
+        .filter_map(|extreme| {
+            let mut far = (extreme, in_gamut::<C>(color.with_lightness(extreme)));
+            if !meets(far.1) {
+                return None;
+            }
+            // The nearest lightness that meets the requirement is between `near` and `far`.
+            let mut near = lightness;
+            for _ in 0..24 {
+                let mid = f32::midpoint(near, far.0);
+                let candidate = in_gamut::<C>(color.with_lightness(mid));
+                if meets(candidate) {
+                    far = (mid, candidate);
+                } else {
+                    near = mid;
+                }
+            }
+            Some(((far.0 - lightness).abs(), far.1))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, color)| color)
+}
+
+/// Converts an Oklch color into `C`, reducing its chroma until it is inside of the sRGB gamut.
+fn in_gamut<C: RgbColor>(color: Oklchf32) -> C {
+    const EPSILON: f32 = 1e-5;
+    let contains = |color: Oklchf32| {
+        let (red, green, blue) = LinearRgb::<Rgbf32>::from(Oklabf32::from(color))
+            .into_inner()
+            .into_rgb();
+        [red, green, blue]
+            .iter()
+            .all(|c| (-EPSILON..=1.0 + EPSILON).contains(c))
+    };
+    let (lightness, chroma, hue) = color.into_lch();
+    if contains(color) {
+        return color.into_rgb_color();
+    }
+    let (mut inside, mut outside) = (0.0, chroma);
+    for _ in 0..16 {
+        let mid = f32::midpoint(inside, outside);
+        if contains(Oklchf32::from_lch(lightness, mid, hue)) {
+            inside = mid;
+        } else {
+            outside = mid;
+        }
+    }
+    Oklchf32::from_lch(lightness, inside, hue).into_rgb_color()
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::{
+        cylindrical::HasHue,
+        rgb::{HasRed, Rgb888, Rgbf32},
+    };
+
+    #[test]
+    fn unchanged_when_met() {
+        let (black, white) = (Rgb888::from_rgb(0, 0, 0), Rgb888::from_rgb(255, 255, 255));
+        assert_eq!(
+            adjust_lightness(black, white, Requirement::Wcag(20.0)),
+            Some(black)
+        );
+    }
+
+    #[test]
+    fn chooses_the_nearer_direction() {
+        let background = Rgb888::from_rgb(128, 128, 128);
+        let light = Rgb888::from_rgb(150, 150, 150);
+        let dark = Rgb888::from_rgb(100, 100, 100);
+        let requirement = Requirement::Wcag(3.0);
+        let lighter = adjust_lightness(light, background, requirement).unwrap();
+        let darker = adjust_lightness(dark, background, requirement).unwrap();
+        assert!(lighter.red() > 150 && darker.red() < 100);
+        assert!(requirement.is_met(lighter, background));
+        assert!(requirement.is_met(darker, background));
+    }
+
+    #[test]
+    fn result_is_nearest() {
+        // One step less lightness no longer meets the requirement.
+        let background = Rgbf32::from_rgb(1.0, 1.0, 1.0);
+        let text = Rgbf32::from_rgb(0.2, 0.6, 0.9);
+        let requirement = Requirement::Apca(75.0);
+        let result = adjust_lightness(text, background, requirement).unwrap();
+        let lch = Oklchf32::from_rgb_color(result);
+        let lighter = in_gamut::<Rgbf32>(lch.with_lightness(lch.lightness() + 1e-3));
+        assert!(requirement.is_met(result, background));
+        assert!(!requirement.is_met(lighter, background));
+        assert!((lch.hue() - Oklchf32::from_rgb_color(text).hue()).abs() < 0.1);
+    }
+
+    #[test]
+    fn gamut_mapping_keeps_hue() {
+        let red = Oklchf32::from_rgb_color(Rgbf32::from_rgb(1.0, 0.0, 0.0));
+        let light: Rgbf32 = in_gamut(red.with_lightness(0.9));
+        let (r, g, b) = light.into_rgb();
+        assert!([r, g, b].iter().all(|c| (-1e-4..=1.0 + 1e-4).contains(c)));
+        let hue = Oklchf32::from_rgb_color(light).hue();
+        assert!((hue - red.hue()).abs() < 0.1);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{component::Component, math::pow, rgb::RgbColor};
+
+/// Returns the screen luminance of an sRGB-encoded color, as estimated by APCA.
+///
+/// APCA decodes each component with a simple power of `2.4`, rather than the piecewise sRGB
+/// transfer function.
+fn screen_luminance<C: RgbColor>(color: C) -> f64 {
+    let (red, green, blue) = color.into_rgb();
+    let [red, green, blue] = [
+        red.to_normalized_f32(),
+        green.to_normalized_f32(),
+        blue.to_normalized_f32(),
+    ]
+    .map(|c| pow(f64::from(c).max(0.0), 2.4));
+    0.212_672_9 * red + 0.715_152_2 * green + 0.072_175 * blue
+}
+
+/// Returns the APCA lightness contrast (`Lc`) of `text` on `background`, as defined by the
+/// APCA-W3 0.0.98G-4g constants of the [WCAG 3 draft].
+///
+/// The result is positive for dark text on a light background, from `0.0` up to about `106.0`,
+/// and negative for light text on a dark background, down to about `-108.0`. Unlike the WCAG 2
+/// contrast ratio, the order of the colors matters, and contrast near black counts for less.
+///
+/// [WCAG 3 draft]: https://www.w3.org/TR/wcag-3.0/
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{contrast::apca, rgb::Rgb888};
+///
+/// let gray = Rgb888::from_rgb(0x88, 0x88, 0x88);
+/// let white = Rgb888::from_rgb(0xFF, 0xFF, 0xFF);
+/// assert!((apca(gray, white) - 63.056).abs() < 1e-3);
+/// assert!((apca(white, gray) + 68.541).abs() < 1e-3);
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn apca<C: RgbColor>(text: C, background: C) -> f32 {
+    const BLACK_THRESHOLD: f64 = 0.022;
+    const BLACK_CLAMP: f64 = 1.414;
+    const SCALE: f64 = 1.14;
+    const OFFSET: f64 = 0.027;
+    const CLIP: f64 = 0.1;
+
+    // Soften the contrast of very dark colors, where flare makes them indistinguishable.
+    let soft_clamp = |y: f64| {
+        if y < BLACK_THRESHOLD {
+            y + pow(BLACK_THRESHOLD - y, BLACK_CLAMP)
+        } else {
+            y
+        }
+    };
+    let text = soft_clamp(screen_luminance(text));
+    let background = soft_clamp(screen_luminance(background));
+    if (background - text).abs() < 0.0005 {
+        return 0.0;
+    }
+
+    let contrast = if background > text {
+        let contrast = (pow(background, 0.56) - pow(text, 0.57)) * SCALE;
+        if contrast < CLIP {
+            0.0
+        } else {
+            contrast - OFFSET
+        }
+    } else {
+        let contrast = (pow(background, 0.65) - pow(text, 0.62)) * SCALE;
+        if contrast > -CLIP {
+            0.0
+        } else {
+            contrast + OFFSET
+        }
+    };
+    (contrast * 100.0) as f32
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::rgb::Rgb888;
+
+    fn gray(value: u8) -> Rgb888 {
+        Rgb888::from_rgb(value, value, value)
+    }
+
+    #[test]
+    fn reference_values() {
+        // The examples of the APCA-W3 reference implementation.
+        let cases = [
+            (gray(0x88), gray(0xFF), 63.056_47),
+            (gray(0xFF), gray(0x88), -68.541_46),
+            (gray(0x00), gray(0xAA), 58.146_26),
+            (gray(0x00), gray(0xFF), 106.040_67),
+            (gray(0xFF), gray(0x00), -107.884_73),
+            (
+                Rgb888::from_rgb(0x11, 0x22, 0x33),
+                Rgb888::from_rgb(0xDD, 0xEE, 0xFF),
+                91.668_31,
+            ),
+        ];
+        for (text, background, expected) in cases {
+            let lc = apca(text, background);
+            assert!((lc - expected).abs() < 1e-3, "{text:?} {background:?} {lc}");
+        }
+    }
+
+    #[test]
+    fn low_contrast_is_clipped() {
+        assert_eq!(apca(gray(0x80), gray(0x80)), 0.0);
+        assert_eq!(apca(gray(0x80), gray(0x88)), 0.0);
+        assert_eq!(apca(gray(0x00), gray(0x01)), 0.0);
+    }
+}
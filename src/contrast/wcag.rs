@@ -0,0 +1,139 @@
+use crate::{encoding::decode, rgb::RgbColor};
+
+/// Returns the relative luminance of an sRGB-encoded color, from `0.0` for black to `1.0` for
+/// white, as defined by WCAG 2.1.
+///
+/// WCAG 2.1 decodes sRGB with a threshold of `0.03928` instead of `0.04045`; no 8-bit component
+/// falls between the two, and other components differ by less than `1e-6`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{contrast::relative_luminance, rgb::Rgb888};
+///
+/// assert_eq!(relative_luminance(Rgb888::from_rgb(255, 255, 255)), 1.0);
+/// assert!((relative_luminance(Rgb888::from_rgb(255, 0, 0)) - 0.2126).abs() < 1e-4);
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn relative_luminance<C: RgbColor>(color: C) -> f32 {
+    let (red, green, blue) = color.into_rgb();
+    let [red, green, blue] = [decode::<_, f32>(red), decode(green), decode(blue)].map(f64::from);
+    (0.2126 * red + 0.7152 * green + 0.0722 * blue) as f32
+}
+
+/// Returns the WCAG 2.1 contrast ratio between two sRGB-encoded colors, from `1.0` for equal
+/// colors to `21.0` for black and white.
+///
+/// The ratio is symmetric, so the order of the colors does not matter.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{contrast::contrast_ratio, rgb::Rgb888};
+///
+/// let white = Rgb888::from_rgb(255, 255, 255);
+/// let gray = Rgb888::from_rgb(118, 118, 118);
+/// assert!((contrast_ratio(gray, white) - 4.54).abs() < 0.01);
+/// ```
+#[must_use]
+pub fn contrast_ratio<C: RgbColor>(a: C, b: C) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// A conformance level of WCAG 2.1 for the contrast of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Level {
+    /// Level AA, success criterion 1.4.3 (Contrast (Minimum)).
+    Aa,
+
+    /// Level AAA, success criterion 1.4.6 (Contrast (Enhanced)).
+    Aaa,
+}
+
+/// The size of text, which determines the contrast ratio it needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextSize {
+    /// Text smaller than large text.
+    #[default]
+    Normal,
+
+    /// Text of at least 18 points, or at least 14 points and bold.
+    ///
+    /// Graphical objects and user interface components, under success criterion 1.4.11, need the
+    /// same contrast ratio as large text at level AA.
+    Large,
+}
+
+impl Level {
+    /// Returns the minimum contrast ratio of text of `size`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::contrast::{Level, TextSize};
+    ///
+    /// assert_eq!(Level::Aa.minimum_ratio(TextSize::Normal), 4.5);
+    /// assert_eq!(Level::Aa.minimum_ratio(TextSize::Large), 3.0);
+    /// assert_eq!(Level::Aaa.minimum_ratio(TextSize::Normal), 7.0);
+    /// assert_eq!(Level::Aaa.minimum_ratio(TextSize::Large), 4.5);
+    /// ```
+    #[must_use]
+    pub const fn minimum_ratio(self, size: TextSize) -> f32 {
+        match (self, size) {
+            (Self::Aa, TextSize::Normal) | (Self::Aaa, TextSize::Large) => 4.5,
+            (Self::Aa, TextSize::Large) => 3.0,
+            (Self::Aaa, TextSize::Normal) => 7.0,
+        }
+    }
+
+    /// Returns whether text of `size` in the `foreground` color passes this level on the
+    /// `background` color.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{contrast::{Level, TextSize}, rgb::Rgb888};
+    ///
+    /// let white = Rgb888::from_rgb(255, 255, 255);
+    /// let gray = Rgb888::from_rgb(118, 118, 118);
+    /// assert!(Level::Aa.passes(gray, white, TextSize::Normal));
+    /// assert!(!Level::Aaa.passes(gray, white, TextSize::Normal));
+    /// ```
+    #[must_use]
+    pub fn passes<C: RgbColor>(self, foreground: C, background: C, size: TextSize) -> bool {
+        contrast_ratio(foreground, background) >= self.minimum_ratio(size)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::rgb::{Rgb888, Rgbf32};
+
+    #[test]
+    fn black_and_white() {
+        let (black, white) = (Rgb888::from_rgb(0, 0, 0), Rgb888::from_rgb(255, 255, 255));
+        assert_eq!(relative_luminance(black), 0.0);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-5);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 1e-5);
+        assert_eq!(contrast_ratio(white, white), 1.0);
+    }
+
+    #[test]
+    fn float_colors() {
+        let gray = Rgbf32::from_rgb(0.5, 0.5, 0.5);
+        assert!((relative_luminance(gray) - 0.214_041).abs() < 1e-6);
+    }
+
+    #[test]
+    fn threshold_of_aa() {
+        // #767676 is the lightest gray that passes AA on white, and #777777 fails.
+        let white = Rgb888::from_rgb(255, 255, 255);
+        assert!(Level::Aa.passes(Rgb888::from_rgb(0x76, 0x76, 0x76), white, TextSize::Normal));
+        assert!(!Level::Aa.passes(Rgb888::from_rgb(0x77, 0x77, 0x77), white, TextSize::Normal));
+    }
+}
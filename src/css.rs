@@ -0,0 +1,261 @@
+//! Parsing 🎨 colors written in [CSS Color Module Level 4][css-color-4] syntax.
+//!
+//! This module contains:
+//!
+//! - [`CssColor`], a color parsed from CSS, in the color space it was written in
+//! - [`ParseError`], which describes why a string is not a valid CSS color
+//!
+//! Parsing does not allocate, and works without the standard library.
+//!
+//! [css-color-4]: https://www.w3.org/TR/css-color-4/
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{css::CssColor, rgb::Abgr8888};
+//!
+//! let color: CssColor = "rgb(255 128 0 / 50%)".parse().unwrap();
+//! assert_eq!(Abgr8888::from(color), Abgr8888::from_abgr(128, 0, 128, 255));
+//!
+//! let color: CssColor = "oklch(62.8% 0.2577 29.23)".parse().unwrap();
+//! assert_eq!(Abgr8888::from(color), Abgr8888::from_abgr(255, 0, 0, 255));
+//! ```
+//!
+//! ## Syntax
+//!
+//! Keywords and function names are case-insensitive, and every component may be `none`:
+//!
+//! Syntax                          | Example
+//! ------------------------------- | -------
+//! Hex colors                      | `#f80`, `#f808`, `#ff8800`, `#ff880080`
+//! Named colors                    | `rebeccapurple`, `transparent`
+//! `rgb()` and `rgba()`            | `rgb(255 136 0)`, `rgba(100%, 50%, 0%, 0.5)`
+//! `hsl()` and `hsla()`            | `hsl(32deg 100% 50% / 50%)`, `hsla(32, 100%, 50%, 0.5)`
+//! `hwb()`                         | `hwb(32 0% 0%)`
+//! `lab()` and `lch()`             | `lab(70 37 75)`, `lch(70 83 64)`
+//! `oklab()` and `oklch()`         | `oklab(0.76 0.09 0.15)`, `oklch(76% 0.17 60)`
+//! `color()`                       | `color(display-p3 1 0.55 0.1)`, `color(srgb 1 0.5 0)`
+//!
+//! The legacy syntax with commas is only accepted by `rgb()`, `rgba()`, `hsl()`, and `hsla()`.
+
+use core::{fmt, str::FromStr};
+
+use crate::{
+    alpha::AlphaLast,
+    cie::{D50, D65, Lab, Lch, WhitePoint, XYZ_TO_LINEAR_SRGB, rgb_to_xyz_matrix},
+    encoding::{Srgb, linear_to_srgb, srgb_to_linear},
+    math::{Matrix3, mul_matrix, mul_vector},
+    oklab::{Oklabaf32, Oklchaf32},
+    rgb::{Abgr8888, RgbaColor, Rgbaf32},
+};
+
+mod named;
+mod parser;
+
+/// A color parsed from CSS, in the color space it was written in.
+///
+/// Use [`str::parse`] to parse a color, and [`From`] to convert it into [`Rgbaf32`] or
+/// [`Abgr8888`].
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{css::CssColor, rgb::Rgbaf32};
+///
+/// let color: CssColor = "#ff000080".parse().unwrap();
+/// assert_eq!(color, CssColor::Srgb(Rgbaf32::from_rgba(1.0, 0.0, 0.0, 128.0 / 255.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CssColor {
+    /// Gamma-encoded sRGB, from hex colors, named colors, `rgb()`, `hsl()`, `hwb()`, and
+    /// `color(srgb …)`.
+    Srgb(Rgbaf32),
+
+    /// Gamma-encoded Display P3, from `color(display-p3 …)`.
+    DisplayP3(Rgbaf32),
+
+    /// CIELAB relative to [`D50`], from `lab()`.
+    Lab(AlphaLast<f32, Lab<f32, D50>>),
+
+    /// CIE `LCh` relative to [`D50`], from `lch()`.
+    Lch(AlphaLast<f32, Lch<f32, D50>>),
+
+    /// Oklab, from `oklab()`.
+    Oklab(Oklabaf32),
+
+    /// Oklch, from `oklch()`.
+    Oklch(Oklchaf32),
+}
+
+impl FromStr for CssColor {
+    type Err = ParseError;
+
+    /// Parses a color in CSS Color Module Level 4 syntax, ignoring surrounding whitespace.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `s` is not a supported CSS color.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse(s)
+    }
+}
+
+/// The `x` and `y` chromaticities of the Display P3 red, green, and blue primaries.
+const DISPLAY_P3_PRIMARIES: [[f64; 2]; 3] = [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]];
+
+/// Converts linear Display P3 into linear sRGB; both are relative to [`D65`].
+const DISPLAY_P3_TO_LINEAR_SRGB: Matrix3 = mul_matrix(
+    &XYZ_TO_LINEAR_SRGB,
+    &rgb_to_xyz_matrix(DISPLAY_P3_PRIMARIES, D65::XYZ),
+);
+
+/// Converts gamma-encoded Display P3 into gamma-encoded sRGB, which may be out of gamut.
+///
+/// Display P3 uses the same transfer function as sRGB.
+#[allow(clippy::cast_possible_truncation)]
+fn display_p3_to_srgb(color: Rgbaf32) -> Rgbaf32 {
+    let (r, g, b, alpha) = color.into_rgba();
+    let linear = [r, g, b].map(|c| f64::from(srgb_to_linear(c)));
+    let [r, g, b] =
+        mul_vector(&DISPLAY_P3_TO_LINEAR_SRGB, linear).map(|c| linear_to_srgb(c as f32));
+    Rgbaf32::from_rgba(r, g, b, alpha)
+}
+
+impl From<CssColor> for Rgbaf32 {
+    /// Converts the color into gamma-encoded sRGB.
+    ///
+    /// Colors outside of the sRGB gamut are clipped, so every component is within `0.0..=1.0`.
+    fn from(color: CssColor) -> Self {
+        let color = match color {
+            CssColor::Srgb(color) => color,
+            CssColor::DisplayP3(color) => display_p3_to_srgb(color),
+            CssColor::Lab(color) => Srgb::<Rgbaf32>::from(color).into_inner(),
+            CssColor::Lch(color) => Srgb::<Rgbaf32>::from(color).into_inner(),
+            CssColor::Oklab(color) => color.into_rgba_color(),
+            CssColor::Oklch(color) => color.into_rgba_color(),
+        };
+        let (r, g, b, alpha) = color.into_rgba();
+        let [r, g, b, alpha] = [r, g, b, alpha].map(|c| if c > 0.0 { c.min(1.0) } else { 0.0 });
+        Rgbaf32::from_rgba(r, g, b, alpha)
+    }
+}
+
+impl From<CssColor> for Abgr8888 {
+    /// Converts the color into 8-bit sRGB, clipping colors outside of the sRGB gamut.
+    fn from(color: CssColor) -> Self {
+        Abgr8888::from(Rgbaf32::from(color))
+    }
+}
+
+/// An error that occurs when parsing a [`CssColor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParseError {
+    /// The input is empty, or only contains whitespace.
+    Empty,
+
+    /// A hex color does not have 3, 4, 6, or 8 hexadecimal digits.
+    InvalidHex,
+
+    /// A keyword is not a named color.
+    UnknownName,
+
+    /// A function is not a supported color function.
+    UnknownFunction,
+
+    /// The color space of a `color()` function is not supported.
+    UnknownColorSpace,
+
+    /// A number is malformed, or has an unknown unit.
+    InvalidNumber,
+
+    /// An argument has the wrong type, such as an angle where a percentage is expected.
+    InvalidArgument,
+
+    /// The input is not a color, such as a missing parenthesis, the wrong number of arguments, or
+    /// trailing characters.
+    InvalidSyntax,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "empty color",
+            Self::InvalidHex => "hex color must have 3, 4, 6, or 8 hexadecimal digits",
+            Self::UnknownName => "unknown named color",
+            Self::UnknownFunction => "unknown color function",
+            Self::UnknownColorSpace => "unsupported color space",
+            Self::InvalidNumber => "invalid number",
+            Self::InvalidArgument => "invalid argument type",
+            Self::InvalidSyntax => "invalid color syntax",
+        })
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn abgr(input: &str) -> Abgr8888 {
+        input.parse::<CssColor>().unwrap().into()
+    }
+
+    fn assert_near(actual: Rgbaf32, expected: [f32; 4], tolerance: f32) {
+        let (r, g, b, a) = actual.into_rgba();
+        for (actual, expected) in [r, g, b, a].into_iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < tolerance,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn into_abgr8888() {
+        assert_eq!(abgr("#663399"), Abgr8888::from_abgr(255, 0x99, 0x33, 0x66));
+        assert_eq!(abgr("transparent"), Abgr8888::from_abgr(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn lab_reference() {
+        // From the `lab()` examples in CSS Color Module Level 4.
+        assert_eq!(
+            abgr("lab(29.2345% 39.3825 20.0664)"),
+            Abgr8888::from_abgr(255, 41, 35, 125)
+        );
+        assert_eq!(
+            abgr("lch(29.2345% 44.2 27)"),
+            Abgr8888::from_abgr(255, 41, 35, 125)
+        );
+    }
+
+    #[test]
+    fn oklab_reference() {
+        assert_eq!(
+            abgr("oklab(62.8% 0.2249 0.1258)"),
+            Abgr8888::from_abgr(255, 0, 0, 255)
+        );
+        assert_eq!(
+            abgr("oklch(1 0 none)"),
+            Abgr8888::from_abgr(255, 255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn display_p3_into_srgb() {
+        let red = "color(display-p3 0.917488 0.200287 0.138561)".parse::<CssColor>();
+        assert_near(red.unwrap().into(), [1.0, 0.0, 0.0, 1.0], 1e-4);
+
+        let gray = "color(display-p3 0.5 0.5 0.5 / 25%)".parse::<CssColor>();
+        assert_near(gray.unwrap().into(), [0.5, 0.5, 0.5, 0.25], 1e-5);
+    }
+
+    #[test]
+    fn out_of_gamut_is_clipped() {
+        let p3 = "color(display-p3 0 1 0)".parse::<CssColor>().unwrap();
+        let (r, g, b, _) = Rgbaf32::from(p3).into_rgba();
+        assert_eq!((r, g, b), (0.0, 1.0, 0.0));
+    }
+}
@@ -0,0 +1,698 @@
+//! A parser for the color syntax of CSS Color Module Level 4.
+
+use core::f64::consts::PI;
+
+use crate::{
+    alpha::AlphaLast,
+    cie::{Lab, Lch},
+    css::{CssColor, ParseError, named},
+    cylindrical::{Hslf32, Hwbf32},
+    oklab::{Oklabaf32, Oklchaf32},
+    rgb::{Rgbaf32, Rgbf32},
+};
+
+/// Parses a color, ignoring surrounding whitespace.
+pub(super) fn parse(input: &str) -> Result<CssColor, ParseError> {
+    let mut parser = Parser { rest: input };
+    parser.skip_whitespace();
+    if parser.rest.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let color = parser.color()?;
+    parser.skip_whitespace();
+    if parser.rest.is_empty() {
+        Ok(color)
+    } else {
+        Err(ParseError::InvalidSyntax)
+    }
+}
+
+/// A component of a color function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    /// A number without a unit.
+    Number(f64),
+
+    /// A percentage, where `100%` is `100.0`.
+    Percentage(f64),
+
+    /// An angle, in degrees.
+    Angle(f64),
+
+    /// The `none` keyword, which is a missing component and behaves as `0`.
+    None,
+}
+
+impl Value {
+    /// Returns a number, or a percentage scaled so that `100%` is `percent`.
+    ///
+    /// The number is clamped to the range of `f32`, in which components are stored.
+    fn number(self, percent: f64) -> Result<f64, ParseError> {
+        match self {
+            Self::Number(number) => Ok(number),
+            Self::Percentage(percentage) => Ok(clamp_f32(percentage / 100.0 * percent)),
+            Self::None => Ok(0.0),
+            Self::Angle(_) => Err(ParseError::InvalidArgument),
+        }
+    }
+
+    /// Returns a hue in degrees, in the range `0.0..360.0`.
+    fn hue(self) -> Result<f64, ParseError> {
+        let degrees = match self {
+            Self::Number(degrees) | Self::Angle(degrees) => degrees,
+            Self::None => 0.0,
+            Self::Percentage(_) => return Err(ParseError::InvalidArgument),
+        };
+        let degrees = degrees % 360.0;
+        Ok(if degrees < 0.0 {
+            degrees + 360.0
+        } else {
+            degrees
+        })
+    }
+}
+
+/// The components of a color function.
+struct Arguments {
+    /// The three color components, in order.
+    values: [Value; 3],
+
+    /// The alpha component, if any.
+    alpha: Option<Value>,
+
+    /// Whether the components were separated by commas.
+    legacy: bool,
+}
+
+impl Arguments {
+    /// Returns the alpha component, clamped to `0.0..=1.0`; a missing alpha is fully opaque.
+    #[allow(clippy::cast_possible_truncation)]
+    fn alpha(&self) -> Result<f32, ParseError> {
+        let alpha = self.alpha.map_or(Ok(1.0), |alpha| alpha.number(1.0))?;
+        Ok(alpha.clamp(0.0, 1.0) as f32)
+    }
+}
+
+/// A color function, such as `rgb()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Rgb,
+    Hsl,
+    Hwb,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    Color,
+}
+
+/// Every color function, by name.
+const FUNCTIONS: [(&str, Function); 10] = [
+    ("rgb", Function::Rgb),
+    ("rgba", Function::Rgb),
+    ("hsl", Function::Hsl),
+    ("hsla", Function::Hsl),
+    ("hwb", Function::Hwb),
+    ("lab", Function::Lab),
+    ("lch", Function::Lch),
+    ("oklab", Function::Oklab),
+    ("oklch", Function::Oklch),
+    ("color", Function::Color),
+];
+
+/// Consumes the input from the front.
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    /// Skips whitespace and comments.
+    fn skip_whitespace(&mut self) {
+        loop {
+            self.rest = self
+                .rest
+                .trim_start_matches(|c: char| c.is_ascii_whitespace());
+            match self.rest.strip_prefix("/*") {
+                Some(comment) => self.rest = comment.split_once("*/").map_or("", |(_, rest)| rest),
+                None => return,
+            }
+        }
+    }
+
+    /// Consumes `c` if it is next, returning whether it was.
+    fn eat(&mut self, c: char) -> bool {
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consumes `c` after any whitespace, or returns an error if it is not next.
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(ParseError::InvalidSyntax)
+        }
+    }
+
+    /// Consumes the longest prefix whose bytes match `predicate`.
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a str {
+        let len = self
+            .rest
+            .bytes()
+            .take_while(|&byte| predicate(byte))
+            .count();
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    /// Consumes an identifier, such as a keyword or unit, which may be empty.
+    fn ident(&mut self) -> &'a str {
+        if !self.rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return "";
+        }
+        self.take_while(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+    }
+
+    /// Parses a hex color, a named color, or a color function.
+    fn color(&mut self) -> Result<CssColor, ParseError> {
+        if self.eat('#') {
+            return hex(self.take_while(|byte| byte.is_ascii_alphanumeric()));
+        }
+        let name = self.ident();
+        if name.is_empty() {
+            return Err(ParseError::InvalidSyntax);
+        }
+        if !self.eat('(') {
+            return keyword(name);
+        }
+        let function = FUNCTIONS
+            .iter()
+            .find(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+            .map(|&(_, function)| function)
+            .ok_or(ParseError::UnknownFunction)?;
+        self.function(function)
+    }
+
+    /// Parses the arguments of a color function, after the opening parenthesis.
+    #[allow(clippy::cast_possible_truncation)]
+    fn function(&mut self, function: Function) -> Result<CssColor, ParseError> {
+        if function == Function::Color {
+            return self.color_function();
+        }
+        let legacy = matches!(function, Function::Rgb | Function::Hsl);
+        let args = self.arguments(legacy)?;
+        let alpha = args.alpha()?;
+        let [first, second, third] = args.values;
+        let color = match function {
+            Function::Rgb => CssColor::Srgb(rgb(&args)?),
+            Function::Hsl => {
+                let percentages = [second, third].map(|v| matches!(v, Value::Percentage(_)));
+                if args.legacy && percentages != [true; 2] {
+                    return Err(ParseError::InvalidArgument);
+                }
+                let hue = first.hue()? as f32;
+                let hsl = Hslf32::from_hsl(hue, fraction(second)?, fraction(third)?);
+                CssColor::Srgb(Rgbaf32::with_color(alpha, hsl.into_rgb_color::<Rgbf32>()))
+            }
+            Function::Hwb => {
+                let hue = first.hue()? as f32;
+                let hwb = Hwbf32::from_hwb(hue, fraction(second)?, fraction(third)?);
+                CssColor::Srgb(Rgbaf32::with_color(alpha, hwb.into_rgb_color::<Rgbf32>()))
+            }
+            Function::Lab => {
+                let lightness = first.number(100.0)?.clamp(0.0, 100.0) as f32;
+                let [a, b] = [second, third].map(|v| v.number(125.0));
+                let lab = Lab::from_lab(lightness, a? as f32, b? as f32);
+                CssColor::Lab(AlphaLast::with_color(alpha, lab))
+            }
+            Function::Lch => {
+                let lightness = first.number(100.0)?.clamp(0.0, 100.0) as f32;
+                let chroma = second.number(150.0)?.max(0.0) as f32;
+                let lch = Lch::from_lch(lightness, chroma, third.hue()? as f32);
+                CssColor::Lch(AlphaLast::with_color(alpha, lch))
+            }
+            Function::Oklab => {
+                let lightness = first.number(1.0)?.clamp(0.0, 1.0) as f32;
+                let [a, b] = [second, third].map(|v| v.number(0.4));
+                CssColor::Oklab(Oklabaf32::from_laba(lightness, a? as f32, b? as f32, alpha))
+            }
+            Function::Oklch => {
+                let lightness = first.number(1.0)?.clamp(0.0, 1.0) as f32;
+                let chroma = second.number(0.4)?.max(0.0) as f32;
+                let hue = third.hue()? as f32;
+                CssColor::Oklch(Oklchaf32::from_lcha(lightness, chroma, hue, alpha))
+            }
+            Function::Color => unreachable!("parsed by color_function"),
+        };
+        Ok(color)
+    }
+
+    /// Parses the arguments of `color()`, after the opening parenthesis.
+    #[allow(clippy::cast_possible_truncation)]
+    fn color_function(&mut self) -> Result<CssColor, ParseError> {
+        self.skip_whitespace();
+        let space = self.ident();
+        let srgb = if space.eq_ignore_ascii_case("srgb") {
+            true
+        } else if space.eq_ignore_ascii_case("display-p3") {
+            false
+        } else if space.is_empty() {
+            return Err(ParseError::InvalidSyntax);
+        } else {
+            return Err(ParseError::UnknownColorSpace);
+        };
+        let args = self.arguments(false)?;
+        let [r, g, b] = args.values;
+        let (r, g, b) = (r.number(1.0)?, g.number(1.0)?, b.number(1.0)?);
+        let color = Rgbaf32::from_rgba(r as f32, g as f32, b as f32, args.alpha()?);
+        Ok(if srgb {
+            CssColor::Srgb(color)
+        } else {
+            CssColor::DisplayP3(color)
+        })
+    }
+
+    /// Parses three components, an optional alpha, and the closing parenthesis.
+    ///
+    /// Components are separated by whitespace, with `/` before alpha. If `legacy` is `true`, they
+    /// may instead be separated by commas, in which case none of them may be `none`.
+    fn arguments(&mut self, legacy: bool) -> Result<Arguments, ParseError> {
+        self.skip_whitespace();
+        let first = self.value()?;
+        self.skip_whitespace();
+        let legacy = legacy && self.rest.starts_with(',');
+        let separator = if legacy { ',' } else { '/' };
+
+        let mut values = [first; 3];
+        for value in &mut values[1..] {
+            if legacy {
+                self.expect(',')?;
+            }
+            self.skip_whitespace();
+            *value = self.value()?;
+        }
+
+        self.skip_whitespace();
+        let alpha = if self.eat(separator) {
+            self.skip_whitespace();
+            Some(self.value()?)
+        } else {
+            None
+        };
+        self.expect(')')?;
+
+        let args = Arguments {
+            values,
+            alpha,
+            legacy,
+        };
+        if legacy
+            && args
+                .values
+                .iter()
+                .chain(&args.alpha)
+                .any(|&v| v == Value::None)
+        {
+            return Err(ParseError::InvalidArgument);
+        }
+        Ok(args)
+    }
+
+    /// Parses a number, percentage, angle, or `none`.
+    fn value(&mut self) -> Result<Value, ParseError> {
+        let keyword = self.ident();
+        if !keyword.is_empty() {
+            return if keyword.eq_ignore_ascii_case("none") {
+                Ok(Value::None)
+            } else {
+                Err(ParseError::InvalidArgument)
+            };
+        }
+        if !self
+            .rest
+            .starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.'))
+        {
+            return Err(ParseError::InvalidSyntax);
+        }
+        let number = self.number()?;
+        if self.eat('%') {
+            return Ok(Value::Percentage(number));
+        }
+        let unit = self.ident();
+        let degrees = [
+            ("", None),
+            ("deg", Some(1.0)),
+            ("grad", Some(0.9)),
+            ("rad", Some(180.0 / PI)),
+            ("turn", Some(360.0)),
+        ]
+        .into_iter()
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(unit))
+        .ok_or(ParseError::InvalidNumber)?
+        .1;
+        Ok(degrees.map_or(Value::Number(number), |scale| Value::Angle(number * scale)))
+    }
+
+    /// Parses a number, such as `-1`, `.5`, or `1e3`.
+    ///
+    /// As CSS requires of numbers that overflow, they are clamped to the range of `f32`, in which
+    /// components are stored.
+    fn number(&mut self) -> Result<f64, ParseError> {
+        let bytes = self.rest.as_bytes();
+        let digits = |start: usize| {
+            bytes.get(start..).map_or(0, |rest| {
+                rest.iter().take_while(|b| b.is_ascii_digit()).count()
+            })
+        };
+
+        let mut len = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+        let integer = digits(len);
+        len += integer;
+        let mut fraction = 0;
+        if bytes.get(len) == Some(&b'.') {
+            fraction = digits(len + 1);
+            if fraction > 0 {
+                len += 1 + fraction;
+            }
+        }
+        if integer == 0 && fraction == 0 {
+            return Err(ParseError::InvalidNumber);
+        }
+        if matches!(bytes.get(len), Some(b'e' | b'E')) {
+            let sign = usize::from(matches!(bytes.get(len + 1), Some(b'+' | b'-')));
+            let exponent = digits(len + 1 + sign);
+            if exponent > 0 {
+                len += 1 + sign + exponent;
+            }
+        }
+
+        let (number, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        let number: f64 = number.parse().map_err(|_| ParseError::InvalidNumber)?;
+        Ok(clamp_f32(number))
+    }
+}
+
+/// Clamps a number to the finite range of `f32`.
+fn clamp_f32(number: f64) -> f64 {
+    number.clamp(f64::from(f32::MIN), f64::from(f32::MAX))
+}
+
+/// Parses the digits of a hex color, after `#`.
+fn hex(digits: &str) -> Result<CssColor, ParseError> {
+    let digit = |byte: u8| char::from(byte).to_digit(16).ok_or(ParseError::InvalidHex);
+    let mut components = [u8::MAX; 4];
+    match digits.len() {
+        3 | 4 => {
+            for (component, &byte) in components.iter_mut().zip(digits.as_bytes()) {
+                *component = u8::try_from(digit(byte)? * 17).unwrap_or(u8::MAX);
+            }
+        }
+        6 | 8 => {
+            for (component, pair) in components.iter_mut().zip(digits.as_bytes().chunks(2)) {
+                *component =
+                    u8::try_from(digit(pair[0])? * 16 + digit(pair[1])?).unwrap_or(u8::MAX);
+            }
+        }
+        _ => return Err(ParseError::InvalidHex),
+    }
+    let [r, g, b, a] = components;
+    Ok(from_u8(r, g, b, a))
+}
+
+/// Looks up a named color or `transparent`.
+fn keyword(name: &str) -> Result<CssColor, ParseError> {
+    if name.eq_ignore_ascii_case("transparent") {
+        return Ok(from_u8(0, 0, 0, 0));
+    }
+    let [r, g, b] = named::lookup(name).ok_or(ParseError::UnknownName)?;
+    Ok(from_u8(r, g, b, u8::MAX))
+}
+
+/// Creates an sRGB color from 8-bit components.
+fn from_u8(r: u8, g: u8, b: u8, a: u8) -> CssColor {
+    let [r, g, b, a] = [r, g, b, a].map(|c| f32::from(c) / 255.0);
+    CssColor::Srgb(Rgbaf32::from_rgba(r, g, b, a))
+}
+
+/// Converts the arguments of `rgb()` into a color.
+///
+/// Numbers are in the range `0..=255`; the legacy syntax requires all three components to be
+/// numbers or all three to be percentages.
+#[allow(clippy::cast_possible_truncation)]
+fn rgb(args: &Arguments) -> Result<Rgbaf32, ParseError> {
+    let [r, g, b] = args.values;
+    let same_type = |v: Value| core::mem::discriminant(&v) == core::mem::discriminant(&r);
+    if args.legacy && !(same_type(g) && same_type(b)) {
+        return Err(ParseError::InvalidArgument);
+    }
+    let component = |v: Value| -> Result<f32, ParseError> {
+        Ok((v.number(255.0)?.clamp(0.0, 255.0) / 255.0) as f32)
+    };
+    Ok(Rgbaf32::from_rgba(
+        component(r)?,
+        component(g)?,
+        component(b)?,
+        args.alpha()?,
+    ))
+}
+
+/// Converts a percentage, or a number where `100` is `100%`, into a fraction in `0.0..=1.0`.
+#[allow(clippy::cast_possible_truncation)]
+fn fraction(value: Value) -> Result<f32, ParseError> {
+    let percentage = match value {
+        Value::Number(number) | Value::Percentage(number) => number,
+        Value::None => 0.0,
+        Value::Angle(_) => return Err(ParseError::InvalidArgument),
+    };
+    Ok((percentage / 100.0).clamp(0.0, 1.0) as f32)
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::rgb::{Abgr8888, RgbaColor};
+
+    fn parse_abgr(input: &str) -> Abgr8888 {
+        parse(input).unwrap().into()
+    }
+
+    fn srgb(input: &str) -> Rgbaf32 {
+        match parse(input) {
+            Ok(CssColor::Srgb(color)) => color,
+            other => panic!("{input}: {other:?}"),
+        }
+    }
+
+    fn rgba(r: u8, g: u8, b: u8, a: u8) -> Abgr8888 {
+        Abgr8888::from_abgr(a, b, g, r)
+    }
+
+    #[test]
+    fn hex_lengths() {
+        assert_eq!(parse_abgr("#f80"), rgba(0xFF, 0x88, 0x00, 0xFF));
+        assert_eq!(parse_abgr("#f808"), rgba(0xFF, 0x88, 0x00, 0x88));
+        assert_eq!(parse_abgr("#FF8800"), rgba(0xFF, 0x88, 0x00, 0xFF));
+        assert_eq!(parse_abgr("#ff880080"), rgba(0xFF, 0x88, 0x00, 0x80));
+    }
+
+    #[test]
+    fn hex_errors() {
+        assert_eq!(parse("#"), Err(ParseError::InvalidHex));
+        assert_eq!(parse("#ff"), Err(ParseError::InvalidHex));
+        assert_eq!(parse("#fffff"), Err(ParseError::InvalidHex));
+        assert_eq!(parse("#ffffffffa"), Err(ParseError::InvalidHex));
+        assert_eq!(parse("#ggg"), Err(ParseError::InvalidHex));
+        assert_eq!(parse("#fff fff"), Err(ParseError::InvalidSyntax));
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(parse_abgr("RebeccaPurple"), rgba(0x66, 0x33, 0x99, 0xFF));
+        assert_eq!(parse_abgr("  white\n"), rgba(0xFF, 0xFF, 0xFF, 0xFF));
+        assert_eq!(parse_abgr("Transparent"), rgba(0, 0, 0, 0));
+        assert_eq!(parse("bleu"), Err(ParseError::UnknownName));
+    }
+
+    #[test]
+    fn rgb_modern() {
+        assert_eq!(parse_abgr("rgb(255 136 0)"), rgba(255, 136, 0, 255));
+        assert_eq!(parse_abgr("rgb(100% 50% 0% / 50%)"), rgba(255, 128, 0, 128));
+        assert_eq!(
+            parse_abgr("RGBA(255 50% none / 0.25)"),
+            rgba(255, 128, 0, 64)
+        );
+        assert_eq!(parse_abgr("rgb( 300 -10 1e2 / 2 )"), rgba(255, 0, 100, 255));
+        assert_eq!(srgb("rgb(127.5 0 0)").into_rgba().0, 0.5);
+    }
+
+    #[test]
+    fn rgb_legacy() {
+        assert_eq!(parse_abgr("rgb(255, 136, 0)"), rgba(255, 136, 0, 255));
+        assert_eq!(parse_abgr("rgba(100%,50%,0%,0.5)"), rgba(255, 128, 0, 128));
+        assert_eq!(parse_abgr("rgba(0, 0, 0, 50%)"), rgba(0, 0, 0, 128));
+        assert_eq!(parse("rgb(255, 50%, 0)"), Err(ParseError::InvalidArgument));
+        assert_eq!(parse("rgb(255, none, 0)"), Err(ParseError::InvalidArgument));
+        assert_eq!(parse("rgb(255, 0 0)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse("rgb(255 0, 0)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse("rgb(255, 0, 0 / 1)"), Err(ParseError::InvalidSyntax));
+    }
+
+    #[test]
+    fn hsl() {
+        assert_eq!(parse_abgr("hsl(120 100% 25%)"), rgba(0, 128, 0, 255));
+        assert_eq!(parse_abgr("hsl(120deg 100 25)"), rgba(0, 128, 0, 255));
+        assert_eq!(
+            parse_abgr("hsla(0.5turn, 100%, 50%, 0.5)"),
+            rgba(0, 255, 255, 128)
+        );
+        assert_eq!(parse_abgr("hsl(-120 100% 50%)"), rgba(0, 0, 255, 255));
+        assert_eq!(parse_abgr("hsl(none 0% 100%)"), rgba(255, 255, 255, 255));
+        assert_eq!(parse("hsl(120, 100, 25)"), Err(ParseError::InvalidArgument));
+        assert_eq!(
+            parse("hsl(120 100% 25deg)"),
+            Err(ParseError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn hwb() {
+        assert_eq!(parse_abgr("hwb(0 0% 0%)"), rgba(255, 0, 0, 255));
+        assert_eq!(
+            parse_abgr("hwb(200grad 0% 50% / 0.5)"),
+            rgba(0, 128, 128, 128)
+        );
+        assert_eq!(parse_abgr("hwb(0 60% 60%)"), rgba(128, 128, 128, 255));
+        assert_eq!(parse("hwb(0, 0%, 0%)"), Err(ParseError::InvalidSyntax));
+    }
+
+    #[test]
+    fn angles() {
+        let hue = |input: &str| match parse(input) {
+            Ok(CssColor::Oklch(color)) => color.color().into_lch().2,
+            other => panic!("{input}: {other:?}"),
+        };
+        assert_eq!(hue("oklch(0.5 0.1 90)"), 90.0);
+        assert_eq!(hue("oklch(0.5 0.1 90DEG)"), 90.0);
+        assert_eq!(hue("oklch(0.5 0.1 100grad)"), 90.0);
+        assert!((hue("oklch(0.5 0.1 1.5707963rad)") - 90.0).abs() < 1e-4);
+        assert_eq!(hue("oklch(0.5 0.1 0.25turn)"), 90.0);
+        assert_eq!(hue("oklch(0.5 0.1 -270)"), 90.0);
+        assert_eq!(hue("oklch(0.5 0.1 450)"), 90.0);
+        assert_eq!(parse("oklch(0.5 0.1 90px)"), Err(ParseError::InvalidNumber));
+        assert_eq!(
+            parse("oklch(0.5 0.1 50%)"),
+            Err(ParseError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn lab_and_lch_scale_percentages() {
+        let Ok(CssColor::Lab(lab)) = parse("lab(50% 100% -100% / 0.5)") else {
+            panic!()
+        };
+        assert_eq!(lab.color().into_lab(), (50.0, 125.0, -125.0));
+        assert_eq!(lab.alpha(), 0.5);
+
+        let Ok(CssColor::Lch(lch)) = parse("lch(150 100% 30)") else {
+            panic!()
+        };
+        assert_eq!(lch.color().into_lch(), (100.0, 150.0, 30.0));
+    }
+
+    #[test]
+    fn oklab_and_oklch_scale_percentages() {
+        let Ok(CssColor::Oklab(lab)) = parse("oklab(50% 100% -50%)") else {
+            panic!()
+        };
+        assert_eq!(lab.color().into_lab(), (0.5, 0.4, -0.2));
+        assert_eq!(lab.alpha(), 1.0);
+
+        let Ok(CssColor::Oklch(lch)) = parse("oklch(2 -10% none / none)") else {
+            panic!()
+        };
+        assert_eq!(lch.color().into_lch(), (1.0, 0.0, 0.0));
+        assert_eq!(lch.alpha(), 0.0);
+    }
+
+    #[test]
+    fn color_function() {
+        let Ok(CssColor::DisplayP3(p3)) = parse("color(Display-P3 1 50% 0 / 0.5)") else {
+            panic!()
+        };
+        assert_eq!(p3, Rgbaf32::from_rgba(1.0, 0.5, 0.0, 0.5));
+        assert_eq!(parse_abgr("color(srgb 1 0.5 0)"), rgba(255, 128, 0, 255));
+        assert_eq!(
+            parse("color(rec2020 1 0 0)"),
+            Err(ParseError::UnknownColorSpace)
+        );
+        assert_eq!(parse("color(1 0 0)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(
+            parse("color(srgb, 1, 0, 0)"),
+            Err(ParseError::InvalidSyntax)
+        );
+    }
+
+    #[test]
+    fn whitespace_and_comments() {
+        assert_eq!(
+            parse_abgr(" /* accent */ rgb(\t255 /* red */ 0\n0 ) "),
+            rgba(255, 0, 0, 255)
+        );
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            srgb("color(srgb .5 +0.25 1E-1)").into_rgba(),
+            (0.5, 0.25, 0.1, 1.0)
+        );
+        assert_eq!(parse("rgb(1. 0 0)"), Err(ParseError::InvalidNumber));
+        assert_eq!(parse("rgb(. 0 0)"), Err(ParseError::InvalidNumber));
+        assert_eq!(parse("rgb(- 0 0)"), Err(ParseError::InvalidNumber));
+        assert_eq!(parse("rgb(inf 0 0)"), Err(ParseError::InvalidArgument));
+
+        // Numbers that overflow are clamped, rather than becoming infinite.
+        assert_eq!(parse_abgr("rgb(1e400 0 0)"), rgba(255, 0, 0, 255));
+        for input in ["hsl(1e400 50% 50%)", "hsl(-1e400turn 50% 50%)"] {
+            let (r, g, b, _) = srgb(input).into_rgba();
+            assert!([r, g, b].iter().all(|c| c.is_finite()), "{input}");
+        }
+        let Ok(CssColor::Oklch(oklch)) = parse("oklch(0.5 1e400 30)") else {
+            panic!()
+        };
+        assert_eq!(oklch.color().into_lch(), (0.5, f32::MAX, 30.0));
+        let Ok(CssColor::Lab(lab)) = parse("lab(50 1e400% -1e400)") else {
+            panic!()
+        };
+        assert_eq!(lab.color().into_lab(), (50.0, f32::MAX, f32::MIN));
+        assert_eq!(srgb("color(srgb 1e39 0 0)").into_rgba().0, f32::MAX);
+        assert!(matches!(
+            parse("hsl(1e400turn 0% 0%)"),
+            Ok(CssColor::Srgb(_))
+        ));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert_eq!(parse(""), Err(ParseError::Empty));
+        assert_eq!(parse("  "), Err(ParseError::Empty));
+        assert_eq!(parse("rgb(0 0 0"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse("rgb(0 0)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse("rgb(0 0 0 0)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse("rgb(0 0 0 / 1 / 1)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse("red blue"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse("rgb (0 0 0)"), Err(ParseError::UnknownName));
+        assert_eq!(parse("cmyk(0 0 0 0)"), Err(ParseError::UnknownFunction));
+        assert_eq!(parse("42"), Err(ParseError::InvalidSyntax));
+    }
+}
@@ -0,0 +1,149 @@
+//! Cylindrical color models that describe RGB colors with a 🌈 hue.
+//!
+//! This module contains:
+//!
+//! - Traits that work on all hue-based data types
+//! - Generic types that can be used to define hue-based data types
+//! - Concrete types that define common hue-based data types
+//!
+//! All models follow the definitions in [CSS Color Module Level 4][css-color-4]: hue is measured in
+//! degrees, and every other component is a fraction in the range `0.0..=1.0`. Conversions operate
+//! on the RGB components as stored, without applying or removing a transfer function.
+//!
+//! [css-color-4]: https://www.w3.org/TR/css-color-4/
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{cylindrical::{HasHue, Hslf32}, rgb::Rgb888};
+//!
+//! let orange = Hslf32::from_rgb_color(Rgb888::from_rgb(255, 128, 0));
+//! assert_eq!(orange.hue().round(), 30.0);
+//!
+//! // Rotate the hue by 180 degrees to get the complementary color.
+//! let blue: Rgb888 = orange.with_hue(210.0).into_rgb_color();
+//! assert_eq!(blue, Rgb888::from_rgb(0, 128, 255));
+//! ```
+//!
+//! ## Predefined Types
+//!
+//! Type        | Description
+//! ----------- | -----------
+//! [`Hsvf32`]  | Hue, saturation, and value, as 32-bit floating-point
+//! [`Hslf32`]  | Hue, saturation, and lightness, as 32-bit floating-point
+//! [`Hwbf32`]  | Hue, whiteness, and blackness, as 32-bit floating-point
+//!
+//! ## Generic Types
+//!
+//! - [`Hsv<T>`]; hue, saturation, and value (also known as HSB)
+//! - [`Hsl<T>`]; hue, saturation, and lightness
+//! - [`Hwb<T>`]; hue, whiteness, and blackness
+//!
+//! ## Achromatic Colors
+//!
+//! Grays (including black and white) do not have a meaningful hue. CSS describes their hue as
+//! _powerless_; converting such a color from RGB produces a hue of `0.0`, and any hue is ignored
+//! when converting back into RGB.
+
+use crate::{component::Component, rgb::RgbColor};
+
+mod hsl;
+mod hsv;
+mod hwb;
+mod impl_alpha_wrappers;
+mod traits;
+
+pub use hsl::{Hsl, Hslf32};
+pub use hsv::{Hsv, Hsvf32};
+pub use hwb::{Hwb, Hwbf32};
+pub use traits::*;
+
+/// Implements a `Has{*}` trait for a generic type by reading and writing a field.
+macro_rules! impl_has_field {
+    ($ty:ident<$t:ident>: $bound:path, $trait:ident { $get:ident, $set:ident, $field:ident }) => {
+        impl<$t> crate::cylindrical::$trait for $ty<$t>
+        where
+            $t: $bound,
+        {
+            type Component = $t;
+
+            fn $get(&self) -> Self::Component {
+                self.$field
+            }
+
+            fn $set(&mut self, value: Self::Component) {
+                self.$field = value;
+            }
+        }
+    };
+}
+
+use impl_has_field;
+
+/// Returns the hue, in degrees, of RGB components with the given range (`max - min`).
+///
+/// Returns `0.0` for achromatic colors, where `delta` is zero.
+fn hue_from_rgb(r: f32, g: f32, b: f32, delta: f32) -> f32 {
+    if delta == 0.0 {
+        return 0.0;
+    }
+    let hue = if r >= g && r >= b {
+        (g - b) / delta + if g < b { 6.0 } else { 0.0 }
+    } else if g >= b {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    hue * 60.0
+}
+
+/// Normalizes a hue, in degrees, into the range `0.0..360.0`.
+fn normalize_hue(hue: f32) -> f32 {
+    let hue = hue % 360.0;
+    if hue < 0.0 { hue + 360.0 } else { hue }
+}
+
+/// Returns the normalized red, green, and blue components of a color.
+fn rgb_to_f32<C: RgbColor>(color: C) -> (f32, f32, f32) {
+    let (r, g, b) = color.into_rgb();
+    (
+        r.to_normalized_f32(),
+        g.to_normalized_f32(),
+        b.to_normalized_f32(),
+    )
+}
+
+/// Creates a color from normalized red, green, and blue components.
+fn rgb_from_f32<C: RgbColor>(r: f32, g: f32, b: f32) -> C {
+    C::from_rgb(
+        Component::from_normalized_f32(r),
+        Component::from_normalized_f32(g),
+        Component::from_normalized_f32(b),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hue_primaries() {
+        assert_eq!(hue_from_rgb(1.0, 0.0, 0.0, 1.0), 0.0);
+        assert_eq!(hue_from_rgb(0.0, 1.0, 0.0, 1.0), 120.0);
+        assert_eq!(hue_from_rgb(0.0, 0.0, 1.0, 1.0), 240.0);
+        assert_eq!(hue_from_rgb(1.0, 0.0, 1.0, 1.0), 300.0);
+    }
+
+    #[test]
+    fn hue_achromatic_is_zero() {
+        assert_eq!(hue_from_rgb(0.5, 0.5, 0.5, 0.0), 0.0);
+    }
+
+    #[test]
+    fn normalize_hue_wraps() {
+        assert_eq!(normalize_hue(360.0), 0.0);
+        assert_eq!(normalize_hue(-30.0), 330.0);
+        assert_eq!(normalize_hue(750.0), 30.0);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    component::Component,
+    cylindrical::{
+        Hsvf32, Hwbf32, hue_from_rgb, impl_has_field, normalize_hue, rgb_from_f32, rgb_to_f32,
+    },
+    rgb::RgbColor,
+};
+
+/// A color representation that contains hue, saturation, and lightness components.
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Hsl {
+///     T h;
+///     T s;
+///     T l;
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Hsl<T> {
+    h: T,
+    s: T,
+    l: T,
+}
+
+impl<T> Hsl<T> {
+    /// Creates a new HSL color with the given hue, saturation, and lightness components.
+    #[must_use]
+    pub const fn from_hsl(hue: T, saturation: T, lightness: T) -> Self {
+        Self {
+            h: hue,
+            s: saturation,
+            l: lightness,
+        }
+    }
+
+    /// Consumes the color and returns its hue, saturation, and lightness components.
+    #[must_use]
+    pub fn into_hsl(self) -> (T, T, T) {
+        (self.h, self.s, self.l)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Hsl<T> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Hsl<T> where T: bytemuck::Pod {}
+
+impl_has_field!(Hsl<T>: Copy, HasHue { hue, set_hue, h });
+impl_has_field!(Hsl<T>: Component, HasSaturation { saturation, set_saturation, s });
+impl_has_field!(Hsl<T>: Copy, HasLightness { lightness, set_lightness, l });
+
+/// 32-bit floating-point HSL color.
+///
+/// Hue is in degrees, and saturation and lightness are in the range `0.0..=1.0`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cylindrical::Hslf32, rgb::Rgb888};
+///
+/// let red = Hslf32::from_hsl(0.0, 1.0, 0.5);
+/// assert_eq!(red.into_rgb_color::<Rgb888>(), Rgb888::from_rgb(255, 0, 0));
+/// ```
+pub type Hslf32 = Hsl<f32>;
+
+impl Hslf32 {
+    /// Converts any RGB color into HSL.
+    ///
+    /// The hue of achromatic colors (grays) is `0.0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cylindrical::Hslf32, rgb::Rgbf32};
+    ///
+    /// let hsl = Hslf32::from_rgb_color(Rgbf32::from_rgb(0.0, 0.0, 0.5));
+    /// assert_eq!(hsl, Hslf32::from_hsl(240.0, 1.0, 0.25));
+    /// ```
+    #[must_use]
+    pub fn from_rgb_color<C: RgbColor>(color: C) -> Self {
+        let (r, g, b) = rgb_to_f32(color);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = f32::midpoint(max, min);
+        let saturation = if lightness <= 0.0 || lightness >= 1.0 {
+            0.0
+        } else {
+            (max - lightness) / lightness.min(1.0 - lightness)
+        };
+        Self::from_hsl(hue_from_rgb(r, g, b, max - min), saturation, lightness)
+    }
+
+    /// Converts this color into any RGB color.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cylindrical::Hslf32, rgb::Rgbf32};
+    ///
+    /// let rgb: Rgbf32 = Hslf32::from_hsl(120.0, 1.0, 0.25).into_rgb_color();
+    /// assert_eq!(rgb, Rgbf32::from_rgb(0.0, 0.5, 0.0));
+    /// ```
+    #[must_use]
+    pub fn into_rgb_color<C: RgbColor>(self) -> C {
+        let h = normalize_hue(self.h) / 30.0;
+        let a = self.s * self.l.min(1.0 - self.l);
+        let f = |n: f32| {
+            let k = (n + h) % 12.0;
+            self.l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+        rgb_from_f32(f(0.0), f(8.0), f(4.0))
+    }
+}
+
+impl From<Hsvf32> for Hslf32 {
+    fn from(color: Hsvf32) -> Self {
+        let (h, s, v) = color.into_hsv();
+        let l = v * (1.0 - s / 2.0);
+        let s = if l <= 0.0 || l >= 1.0 {
+            0.0
+        } else {
+            (v - l) / l.min(1.0 - l)
+        };
+        Self::from_hsl(h, s, l)
+    }
+}
+
+impl From<Hwbf32> for Hslf32 {
+    fn from(color: Hwbf32) -> Self {
+        Self::from(Hsvf32::from(color))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        cylindrical::{HasHue, HasLightness, HasSaturation},
+        rgb::{Rgb888, Rgbf32},
+    };
+
+    #[test]
+    fn from_rgb_color_primaries() {
+        let cases = [
+            (
+                Rgb888::from_rgb(0, 255, 0),
+                Hslf32::from_hsl(120.0, 1.0, 0.5),
+            ),
+            (
+                Rgb888::from_rgb(0, 0, 255),
+                Hslf32::from_hsl(240.0, 1.0, 0.5),
+            ),
+            (
+                Rgb888::from_rgb(255, 255, 0),
+                Hslf32::from_hsl(60.0, 1.0, 0.5),
+            ),
+            (Rgb888::from_rgb(0, 0, 0), Hslf32::from_hsl(0.0, 0.0, 0.0)),
+            (
+                Rgb888::from_rgb(255, 255, 255),
+                Hslf32::from_hsl(0.0, 0.0, 1.0),
+            ),
+        ];
+        for (rgb, hsl) in cases {
+            assert_eq!(Hslf32::from_rgb_color(rgb), hsl, "{rgb:?}");
+            assert_eq!(hsl.into_rgb_color::<Rgb888>(), rgb, "{hsl:?}");
+        }
+    }
+
+    #[test]
+    fn from_rgb_color_gray_has_zero_hue() {
+        let hsl = Hslf32::from_rgb_color(Rgbf32::from_rgb(0.25, 0.25, 0.25));
+        assert_eq!(hsl.hue(), 0.0);
+        assert_eq!(hsl.saturation(), 0.0);
+        assert_eq!(hsl.lightness(), 0.25);
+    }
+
+    #[test]
+    fn into_rgb_color_pastel() {
+        let rgb = Hslf32::from_hsl(0.0, 1.0, 0.75).into_rgb_color::<Rgbf32>();
+        assert_eq!(rgb, Rgbf32::from_rgb(1.0, 0.5, 0.5));
+    }
+
+    #[test]
+    fn into_rgb_color_wraps_hue() {
+        let a = Hslf32::from_hsl(480.0, 1.0, 0.5).into_rgb_color::<Rgb888>();
+        let b = Hslf32::from_hsl(120.0, 1.0, 0.5).into_rgb_color::<Rgb888>();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn round_trips_rgb888() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = Rgb888::from_rgb(r, g, b);
+                    let hsl = Hslf32::from_rgb_color(rgb);
+                    assert_eq!(hsl.into_rgb_color::<Rgb888>(), rgb, "{hsl:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_hsv() {
+        let hsl = Hslf32::from(Hsvf32::from_hsv(120.0, 1.0, 0.5));
+        assert_eq!(hsl, Hslf32::from_hsl(120.0, 1.0, 0.25));
+    }
+
+    #[test]
+    fn from_hsv_white() {
+        let hsl = Hslf32::from(Hsvf32::from_hsv(0.0, 0.0, 1.0));
+        assert_eq!(hsl, Hslf32::from_hsl(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn from_hwb() {
+        let hsl = Hslf32::from(Hwbf32::from_hwb(0.0, 0.5, 0.0));
+        assert_eq!(hsl, Hslf32::from_hsl(0.0, 1.0, 0.75));
+    }
+}
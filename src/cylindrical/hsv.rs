@@ -0,0 +1,241 @@
+use crate::{
+    component::Component,
+    cylindrical::{
+        Hslf32, Hwbf32, hue_from_rgb, impl_has_field, normalize_hue, rgb_from_f32, rgb_to_f32,
+    },
+    rgb::RgbColor,
+};
+
+/// A color representation that contains hue, saturation, and value components.
+///
+/// Also known as HSB (hue, saturation, brightness).
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Hsv {
+///     T h;
+///     T s;
+///     T v;
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Hsv<T> {
+    h: T,
+    s: T,
+    v: T,
+}
+
+impl<T> Hsv<T> {
+    /// Creates a new HSV color with the given hue, saturation, and value components.
+    #[must_use]
+    pub const fn from_hsv(hue: T, saturation: T, value: T) -> Self {
+        Self {
+            h: hue,
+            s: saturation,
+            v: value,
+        }
+    }
+
+    /// Consumes the color and returns its hue, saturation, and value components.
+    #[must_use]
+    pub fn into_hsv(self) -> (T, T, T) {
+        (self.h, self.s, self.v)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Hsv<T> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Hsv<T> where T: bytemuck::Pod {}
+
+impl_has_field!(Hsv<T>: Copy, HasHue { hue, set_hue, h });
+impl_has_field!(Hsv<T>: Component, HasSaturation { saturation, set_saturation, s });
+impl_has_field!(Hsv<T>: Component, HasValue { value, set_value, v });
+
+/// 32-bit floating-point HSV color.
+///
+/// Hue is in degrees, and saturation and value are in the range `0.0..=1.0`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cylindrical::Hsvf32, rgb::Rgb888};
+///
+/// let red = Hsvf32::from_hsv(0.0, 1.0, 1.0);
+/// assert_eq!(red.into_rgb_color::<Rgb888>(), Rgb888::from_rgb(255, 0, 0));
+/// ```
+pub type Hsvf32 = Hsv<f32>;
+
+impl Hsvf32 {
+    /// Converts any RGB color into HSV.
+    ///
+    /// The hue of achromatic colors (grays) is `0.0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cylindrical::Hsvf32, rgb::Rgb888};
+    ///
+    /// let hsv = Hsvf32::from_rgb_color(Rgb888::from_rgb(0, 255, 0));
+    /// assert_eq!(hsv, Hsvf32::from_hsv(120.0, 1.0, 1.0));
+    /// ```
+    #[must_use]
+    pub fn from_rgb_color<C: RgbColor>(color: C) -> Self {
+        let (r, g, b) = rgb_to_f32(color);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        Self::from_hsv(hue_from_rgb(r, g, b, delta), s, max)
+    }
+
+    /// Converts this color into any RGB color.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cylindrical::Hsvf32, rgb::Rgbf32};
+    ///
+    /// let rgb: Rgbf32 = Hsvf32::from_hsv(240.0, 1.0, 0.5).into_rgb_color();
+    /// assert_eq!(rgb, Rgbf32::from_rgb(0.0, 0.0, 0.5));
+    /// ```
+    #[must_use]
+    pub fn into_rgb_color<C: RgbColor>(self) -> C {
+        let h = normalize_hue(self.h) / 60.0;
+        let f = |n: f32| {
+            let k = (n + h) % 6.0;
+            self.v - self.v * self.s * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+        rgb_from_f32(f(5.0), f(3.0), f(1.0))
+    }
+}
+
+impl From<Hslf32> for Hsvf32 {
+    fn from(color: Hslf32) -> Self {
+        let (h, s, l) = color.into_hsl();
+        let v = l + s * l.min(1.0 - l);
+        let s = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - l / v) };
+        Self::from_hsv(h, s, v)
+    }
+}
+
+impl From<Hwbf32> for Hsvf32 {
+    fn from(color: Hwbf32) -> Self {
+        let (hue, white, black) = color.into_hwb();
+        let (white, black) = if white + black > 1.0 {
+            (white / (white + black), black / (white + black))
+        } else {
+            (white, black)
+        };
+        let value = 1.0 - black;
+        let saturation = if value == 0.0 {
+            0.0
+        } else {
+            1.0 - white / value
+        };
+        Self::from_hsv(hue, saturation, value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        cylindrical::{HasHue, HasSaturation, HasValue},
+        rgb::{Rgb565, Rgb888, Rgbf32},
+    };
+
+    #[test]
+    fn from_rgb_color_primaries() {
+        let cases = [
+            (Rgb888::from_rgb(255, 0, 0), Hsvf32::from_hsv(0.0, 1.0, 1.0)),
+            (
+                Rgb888::from_rgb(255, 255, 0),
+                Hsvf32::from_hsv(60.0, 1.0, 1.0),
+            ),
+            (
+                Rgb888::from_rgb(0, 255, 255),
+                Hsvf32::from_hsv(180.0, 1.0, 1.0),
+            ),
+            (
+                Rgb888::from_rgb(255, 0, 255),
+                Hsvf32::from_hsv(300.0, 1.0, 1.0),
+            ),
+            (Rgb888::from_rgb(0, 0, 0), Hsvf32::from_hsv(0.0, 0.0, 0.0)),
+            (
+                Rgb888::from_rgb(255, 255, 255),
+                Hsvf32::from_hsv(0.0, 0.0, 1.0),
+            ),
+        ];
+        for (rgb, hsv) in cases {
+            assert_eq!(Hsvf32::from_rgb_color(rgb), hsv, "{rgb:?}");
+            assert_eq!(hsv.into_rgb_color::<Rgb888>(), rgb, "{hsv:?}");
+        }
+    }
+
+    #[test]
+    fn from_rgb_color_gray_has_zero_hue() {
+        let hsv = Hsvf32::from_rgb_color(Rgbf32::from_rgb(0.5, 0.5, 0.5));
+        assert_eq!(hsv.hue(), 0.0);
+        assert_eq!(hsv.saturation(), 0.0);
+        assert_eq!(hsv.value(), 0.5);
+    }
+
+    #[test]
+    fn into_rgb_color_ignores_hue_of_gray() {
+        let gray = Hsvf32::from_hsv(123.0, 0.0, 0.5).into_rgb_color::<Rgbf32>();
+        assert_eq!(gray, Rgbf32::from_rgb(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn into_rgb_color_wraps_hue() {
+        let a = Hsvf32::from_hsv(-120.0, 1.0, 1.0).into_rgb_color::<Rgb888>();
+        let b = Hsvf32::from_hsv(240.0, 1.0, 1.0).into_rgb_color::<Rgb888>();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn round_trips_rgb888() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = Rgb888::from_rgb(r, g, b);
+                    let hsv = Hsvf32::from_rgb_color(rgb);
+                    assert_eq!(hsv.into_rgb_color::<Rgb888>(), rgb, "{hsv:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_rgb565() {
+        for packed in 0..=u16::MAX {
+            let rgb = Rgb565::new(packed);
+            assert_eq!(Hsvf32::from_rgb_color(rgb).into_rgb_color::<Rgb565>(), rgb);
+        }
+    }
+
+    #[test]
+    fn from_hsl() {
+        let hsv = Hsvf32::from(Hslf32::from_hsl(120.0, 1.0, 0.25));
+        assert_eq!(hsv, Hsvf32::from_hsv(120.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn from_hwb() {
+        let hsv = Hsvf32::from(Hwbf32::from_hwb(120.0, 0.25, 0.5));
+        assert_eq!(hsv, Hsvf32::from_hsv(120.0, 0.5, 0.5));
+    }
+
+    #[test]
+    fn from_hwb_normalizes_gray() {
+        let hsv = Hsvf32::from(Hwbf32::from_hwb(120.0, 1.0, 1.0));
+        assert_eq!(hsv, Hsvf32::from_hsv(120.0, 0.0, 0.5));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    component::Component,
+    cylindrical::{Hslf32, Hsvf32, hue_from_rgb, impl_has_field, rgb_to_f32},
+    rgb::RgbColor,
+};
+
+/// A color representation that contains hue, whiteness, and blackness components.
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Hwb {
+///     T h;
+///     T w;
+///     T b;
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Hwb<T> {
+    h: T,
+    w: T,
+    b: T,
+}
+
+impl<T> Hwb<T> {
+    /// Creates a new HWB color with the given hue, whiteness, and blackness components.
+    #[must_use]
+    pub const fn from_hwb(hue: T, whiteness: T, blackness: T) -> Self {
+        Self {
+            h: hue,
+            w: whiteness,
+            b: blackness,
+        }
+    }
+
+    /// Consumes the color and returns its hue, whiteness, and blackness components.
+    #[must_use]
+    pub fn into_hwb(self) -> (T, T, T) {
+        (self.h, self.w, self.b)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Hwb<T> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Hwb<T> where T: bytemuck::Pod {}
+
+impl_has_field!(Hwb<T>: Copy, HasHue { hue, set_hue, h });
+impl_has_field!(Hwb<T>: Component, HasWhiteness { whiteness, set_whiteness, w });
+impl_has_field!(Hwb<T>: Component, HasBlackness { blackness, set_blackness, b });
+
+/// 32-bit floating-point HWB color.
+///
+/// Hue is in degrees, and whiteness and blackness are in the range `0.0..=1.0`. If whiteness and
+/// blackness add up to more than `1.0`, the color is an achromatic gray.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cylindrical::Hwbf32, rgb::Rgb888};
+///
+/// let red = Hwbf32::from_hwb(0.0, 0.0, 0.0);
+/// assert_eq!(red.into_rgb_color::<Rgb888>(), Rgb888::from_rgb(255, 0, 0));
+/// ```
+pub type Hwbf32 = Hwb<f32>;
+
+impl Hwbf32 {
+    /// Converts any RGB color into HWB.
+    ///
+    /// The hue of achromatic colors (grays) is `0.0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cylindrical::Hwbf32, rgb::Rgbf32};
+    ///
+    /// let hwb = Hwbf32::from_rgb_color(Rgbf32::from_rgb(0.75, 0.25, 0.25));
+    /// assert_eq!(hwb, Hwbf32::from_hwb(0.0, 0.25, 0.25));
+    /// ```
+    #[must_use]
+    pub fn from_rgb_color<C: RgbColor>(color: C) -> Self {
+        let (r, g, b) = rgb_to_f32(color);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        Self::from_hwb(hue_from_rgb(r, g, b, max - min), min, 1.0 - max)
+    }
+
+    /// Converts this color into any RGB color.
+    ///
+    /// Whiteness and blackness that add up to more than `1.0` are scaled down proportionally.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cylindrical::Hwbf32, rgb::Rgbf32};
+    ///
+    /// let gray: Rgbf32 = Hwbf32::from_hwb(0.0, 0.75, 0.75).into_rgb_color();
+    /// assert_eq!(gray, Rgbf32::from_rgb(0.5, 0.5, 0.5));
+    /// ```
+    #[must_use]
+    pub fn into_rgb_color<C: RgbColor>(self) -> C {
+        Hsvf32::from(self).into_rgb_color()
+    }
+}
+
+impl From<Hsvf32> for Hwbf32 {
+    fn from(color: Hsvf32) -> Self {
+        let (h, s, v) = color.into_hsv();
+        Self::from_hwb(h, (1.0 - s) * v, 1.0 - v)
+    }
+}
+
+impl From<Hslf32> for Hwbf32 {
+    fn from(color: Hslf32) -> Self {
+        Self::from(Hsvf32::from(color))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        cylindrical::{HasBlackness, HasHue, HasWhiteness},
+        rgb::{Rgb888, Rgbf32},
+    };
+
+    #[test]
+    fn from_rgb_color_primaries() {
+        let cases = [
+            (
+                Rgb888::from_rgb(0, 255, 0),
+                Hwbf32::from_hwb(120.0, 0.0, 0.0),
+            ),
+            (
+                Rgb888::from_rgb(0, 255, 255),
+                Hwbf32::from_hwb(180.0, 0.0, 0.0),
+            ),
+            (Rgb888::from_rgb(0, 0, 0), Hwbf32::from_hwb(0.0, 0.0, 1.0)),
+            (
+                Rgb888::from_rgb(255, 255, 255),
+                Hwbf32::from_hwb(0.0, 1.0, 0.0),
+            ),
+        ];
+        for (rgb, hwb) in cases {
+            assert_eq!(Hwbf32::from_rgb_color(rgb), hwb, "{rgb:?}");
+            assert_eq!(hwb.into_rgb_color::<Rgb888>(), rgb, "{hwb:?}");
+        }
+    }
+
+    #[test]
+    fn from_rgb_color_gray_has_zero_hue() {
+        let hwb = Hwbf32::from_rgb_color(Rgbf32::from_rgb(0.5, 0.5, 0.5));
+        assert_eq!(hwb.hue(), 0.0);
+        assert_eq!(hwb.whiteness(), 0.5);
+        assert_eq!(hwb.blackness(), 0.5);
+    }
+
+    #[test]
+    fn into_rgb_color_normalizes_gray() {
+        let a = Hwbf32::from_hwb(0.0, 2.0, 6.0).into_rgb_color::<Rgbf32>();
+        assert_eq!(a, Rgbf32::from_rgb(0.25, 0.25, 0.25));
+    }
+
+    #[test]
+    fn into_rgb_color_tint() {
+        let rgb = Hwbf32::from_hwb(240.0, 0.5, 0.0).into_rgb_color::<Rgbf32>();
+        assert_eq!(rgb, Rgbf32::from_rgb(0.5, 0.5, 1.0));
+    }
+
+    #[test]
+    fn round_trips_rgb888() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = Rgb888::from_rgb(r, g, b);
+                    let hwb = Hwbf32::from_rgb_color(rgb);
+                    assert_eq!(hwb.into_rgb_color::<Rgb888>(), rgb, "{hwb:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_hsv() {
+        let hwb = Hwbf32::from(Hsvf32::from_hsv(60.0, 0.5, 0.5));
+        assert_eq!(hwb, Hwbf32::from_hwb(60.0, 0.25, 0.5));
+    }
+
+    #[test]
+    fn from_hsl() {
+        let hwb = Hwbf32::from(Hslf32::from_hsl(60.0, 1.0, 0.75));
+        assert_eq!(hwb, Hwbf32::from_hwb(60.0, 0.5, 0.0));
+    }
+}
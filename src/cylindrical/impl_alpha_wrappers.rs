@@ -0,0 +1,41 @@
+use crate::{
+    alpha::impl_alpha_wrappers,
+    cylindrical::{
+        HasBlackness, HasChroma, HasHue, HasLightness, HasSaturation, HasValue, HasWhiteness,
+    },
+};
+
+impl_alpha_wrappers!(HasHue, hue, set_hue, with_hue);
+impl_alpha_wrappers!(HasSaturation, saturation, set_saturation, with_saturation);
+impl_alpha_wrappers!(HasValue, value, set_value, with_value);
+impl_alpha_wrappers!(HasLightness, lightness, set_lightness, with_lightness);
+impl_alpha_wrappers!(HasWhiteness, whiteness, set_whiteness, with_whiteness);
+impl_alpha_wrappers!(HasBlackness, blackness, set_blackness, with_blackness);
+impl_alpha_wrappers!(HasChroma, chroma, set_chroma, with_chroma);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::{
+        alpha::{AlphaFirst, AlphaLast},
+        cylindrical::{Hslf32, Hsvf32},
+    };
+
+    use super::*;
+
+    #[test]
+    fn alpha_first_set_hue() {
+        let mut color = AlphaFirst::<f32, Hsvf32>::with_color(1.0, Hsvf32::from_hsv(0.0, 1.0, 1.0));
+        color.set_hue(120.0);
+        assert_eq!(color.hue(), 120.0);
+        assert_eq!(color.alpha(), 1.0);
+    }
+
+    #[test]
+    fn alpha_last_set_lightness() {
+        let mut color = AlphaLast::<f32, Hslf32>::with_color(0.5, Hslf32::from_hsl(0.0, 1.0, 0.5));
+        color.set_lightness(0.25);
+        assert_eq!(color.lightness(), 0.25);
+        assert_eq!(color.alpha(), 0.5);
+    }
+}
@@ -0,0 +1,20 @@
+mod has_hue;
+pub use has_hue::HasHue;
+
+mod has_saturation;
+pub use has_saturation::HasSaturation;
+
+mod has_value;
+pub use has_value::HasValue;
+
+mod has_lightness;
+pub use has_lightness::HasLightness;
+
+mod has_whiteness;
+pub use has_whiteness::HasWhiteness;
+
+mod has_blackness;
+pub use has_blackness::HasBlackness;
+
+mod has_chroma;
+pub use has_chroma::HasChroma;
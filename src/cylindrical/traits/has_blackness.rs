@@ -0,0 +1,56 @@
+use crate::component::Component;
+
+/// A trait for types that have a blackness component.
+pub trait HasBlackness {
+    /// The type of the blackness component.
+    type Component: Component;
+
+    /// Returns the value of the blackness component.
+    #[must_use]
+    fn blackness(&self) -> Self::Component;
+
+    /// Sets the blackness component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_blackness(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the blackness component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_blackness(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_blackness(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cylindrical::Hwbf32;
+
+    use super::*;
+
+    #[test]
+    fn with_blackness_hwbf32_get_blackness() {
+        assert_eq!(Hwbf32::from_hwb(0.0, 0.25, 0.25).blackness(), 0.25);
+    }
+
+    #[test]
+    fn with_blackness_hwbf32_set_blackness() {
+        let mut color = Hwbf32::from_hwb(0.0, 0.25, 0.25);
+        color.set_blackness(0.5);
+        assert_eq!(color, Hwbf32::from_hwb(0.0, 0.25, 0.5));
+    }
+
+    #[test]
+    fn with_blackness_hwbf32_with_blackness() {
+        let color = Hwbf32::from_hwb(0.0, 0.25, 0.25);
+        let new_color = color.with_blackness(0.5);
+        assert_eq!(new_color, Hwbf32::from_hwb(0.0, 0.25, 0.5));
+    }
+}
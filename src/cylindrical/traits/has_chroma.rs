@@ -0,0 +1,56 @@
+/// A trait for types that have a chroma component.
+pub trait HasChroma {
+    /// The type of the chroma component.
+    ///
+    /// Chroma is `0.0` for achromatic colors; its upper bound depends on the color model.
+    type Component;
+
+    /// Returns the value of the chroma component.
+    #[must_use]
+    fn chroma(&self) -> Self::Component;
+
+    /// Sets the chroma component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_chroma(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the chroma component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_chroma(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_chroma(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cie::Lchf32;
+
+    use super::*;
+
+    #[test]
+    fn with_chroma_lchf32_get_chroma() {
+        assert_eq!(Lchf32::from_lch(50.0, 25.0, 120.0).chroma(), 25.0);
+    }
+
+    #[test]
+    fn with_chroma_lchf32_set_chroma() {
+        let mut color = Lchf32::from_lch(50.0, 25.0, 120.0);
+        color.set_chroma(75.0);
+        assert_eq!(color, Lchf32::from_lch(50.0, 75.0, 120.0));
+    }
+
+    #[test]
+    fn with_chroma_lchf32_with_chroma() {
+        let color = Lchf32::from_lch(50.0, 25.0, 120.0);
+        let new_color = color.with_chroma(75.0);
+        assert_eq!(new_color, Lchf32::from_lch(50.0, 75.0, 120.0));
+    }
+}
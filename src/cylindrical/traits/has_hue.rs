@@ -0,0 +1,57 @@
+/// A trait for types that have a hue component.
+pub trait HasHue {
+    /// The type of the hue component, in degrees.
+    ///
+    /// In HSV, HSL, and HWB, a hue of `0.0` is red, `120.0` is green, and `240.0` is blue. Other
+    /// models, such as CIE `LCh`, place hues at different angles.
+    type Component;
+
+    /// Returns the value of the hue component.
+    #[must_use]
+    fn hue(&self) -> Self::Component;
+
+    /// Sets the hue component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_hue(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the hue component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_hue(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_hue(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cylindrical::Hsvf32;
+
+    use super::*;
+
+    #[test]
+    fn with_hue_hsvf32_get_hue() {
+        assert_eq!(Hsvf32::from_hsv(120.0, 0.5, 0.5).hue(), 120.0);
+    }
+
+    #[test]
+    fn with_hue_hsvf32_set_hue() {
+        let mut color = Hsvf32::from_hsv(120.0, 0.5, 0.5);
+        color.set_hue(240.0);
+        assert_eq!(color, Hsvf32::from_hsv(240.0, 0.5, 0.5));
+    }
+
+    #[test]
+    fn with_hue_hsvf32_with_hue() {
+        let color = Hsvf32::from_hsv(120.0, 0.5, 0.5);
+        let new_color = color.with_hue(240.0);
+        assert_eq!(new_color, Hsvf32::from_hsv(240.0, 0.5, 0.5));
+    }
+}
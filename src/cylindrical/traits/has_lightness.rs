@@ -0,0 +1,56 @@
+/// A trait for types that have a lightness component.
+pub trait HasLightness {
+    /// The type of the lightness component.
+    ///
+    /// The range of lightness depends on the color model.
+    type Component;
+
+    /// Returns the value of the lightness component.
+    #[must_use]
+    fn lightness(&self) -> Self::Component;
+
+    /// Sets the lightness component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_lightness(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the lightness component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_lightness(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_lightness(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cylindrical::Hslf32;
+
+    use super::*;
+
+    #[test]
+    fn with_lightness_hslf32_get_lightness() {
+        assert_eq!(Hslf32::from_hsl(0.0, 0.5, 0.25).lightness(), 0.25);
+    }
+
+    #[test]
+    fn with_lightness_hslf32_set_lightness() {
+        let mut color = Hslf32::from_hsl(0.0, 0.5, 0.25);
+        color.set_lightness(0.75);
+        assert_eq!(color, Hslf32::from_hsl(0.0, 0.5, 0.75));
+    }
+
+    #[test]
+    fn with_lightness_hslf32_with_lightness() {
+        let color = Hslf32::from_hsl(0.0, 0.5, 0.25);
+        let new_color = color.with_lightness(0.75);
+        assert_eq!(new_color, Hslf32::from_hsl(0.0, 0.5, 0.75));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::component::Component;
+
+/// A trait for types that have a saturation component.
+pub trait HasSaturation {
+    /// The type of the saturation component.
+    type Component: Component;
+
+    /// Returns the value of the saturation component.
+    #[must_use]
+    fn saturation(&self) -> Self::Component;
+
+    /// Sets the saturation component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_saturation(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the saturation component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_saturation(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_saturation(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cylindrical::Hsvf32;
+
+    use super::*;
+
+    #[test]
+    fn with_saturation_hsvf32_get_saturation() {
+        assert_eq!(Hsvf32::from_hsv(0.0, 0.25, 0.5).saturation(), 0.25);
+    }
+
+    #[test]
+    fn with_saturation_hsvf32_set_saturation() {
+        let mut color = Hsvf32::from_hsv(0.0, 0.25, 0.5);
+        color.set_saturation(0.75);
+        assert_eq!(color, Hsvf32::from_hsv(0.0, 0.75, 0.5));
+    }
+
+    #[test]
+    fn with_saturation_hsvf32_with_saturation() {
+        let color = Hsvf32::from_hsv(0.0, 0.25, 0.5);
+        let new_color = color.with_saturation(0.75);
+        assert_eq!(new_color, Hsvf32::from_hsv(0.0, 0.75, 0.5));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::component::Component;
+
+/// A trait for types that have a value component.
+pub trait HasValue {
+    /// The type of the value component.
+    type Component: Component;
+
+    /// Returns the value of the value component.
+    #[must_use]
+    fn value(&self) -> Self::Component;
+
+    /// Sets the value component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_value(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the value component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_value(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_value(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cylindrical::Hsvf32;
+
+    use super::*;
+
+    #[test]
+    fn with_value_hsvf32_get_value() {
+        assert_eq!(Hsvf32::from_hsv(0.0, 0.5, 0.25).value(), 0.25);
+    }
+
+    #[test]
+    fn with_value_hsvf32_set_value() {
+        let mut color = Hsvf32::from_hsv(0.0, 0.5, 0.25);
+        color.set_value(0.75);
+        assert_eq!(color, Hsvf32::from_hsv(0.0, 0.5, 0.75));
+    }
+
+    #[test]
+    fn with_value_hsvf32_with_value() {
+        let color = Hsvf32::from_hsv(0.0, 0.5, 0.25);
+        let new_color = color.with_value(0.75);
+        assert_eq!(new_color, Hsvf32::from_hsv(0.0, 0.5, 0.75));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::component::Component;
+
+/// A trait for types that have a whiteness component.
+pub trait HasWhiteness {
+    /// The type of the whiteness component.
+    type Component: Component;
+
+    /// Returns the value of the whiteness component.
+    #[must_use]
+    fn whiteness(&self) -> Self::Component;
+
+    /// Sets the whiteness component to the given value.
+    ///
+    /// If the color has other components, they are left unchanged.
+    fn set_whiteness(&mut self, value: Self::Component);
+
+    /// Converts the color into a new type with the whiteness component set to the given value.
+    ///
+    /// The other components are left unchanged.
+    #[must_use]
+    fn with_whiteness(self, value: Self::Component) -> Self
+    where
+        Self: Copy + Clone,
+    {
+        let mut color = self;
+        color.set_whiteness(value);
+        color
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::cylindrical::Hwbf32;
+
+    use super::*;
+
+    #[test]
+    fn with_whiteness_hwbf32_get_whiteness() {
+        assert_eq!(Hwbf32::from_hwb(0.0, 0.25, 0.25).whiteness(), 0.25);
+    }
+
+    #[test]
+    fn with_whiteness_hwbf32_set_whiteness() {
+        let mut color = Hwbf32::from_hwb(0.0, 0.25, 0.25);
+        color.set_whiteness(0.5);
+        assert_eq!(color, Hwbf32::from_hwb(0.0, 0.5, 0.25));
+    }
+
+    #[test]
+    fn with_whiteness_hwbf32_with_whiteness() {
+        let color = Hwbf32::from_hwb(0.0, 0.25, 0.25);
+        let new_color = color.with_whiteness(0.5);
+        assert_eq!(new_color, Hwbf32::from_hwb(0.0, 0.5, 0.25));
+    }
+}
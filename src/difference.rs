@@ -0,0 +1,282 @@
+//! Measuring 📏 how different two colors look, such as for comparing rendered images.
+//!
+//! This module contains:
+//!
+//! - [`DeltaE`], which compares any two sRGB-encoded [`RgbColor`]s with a chosen metric
+//! - A function for each metric, over the color space it is defined in:
+//!   - [`cie76`], the distance in CIELAB
+//!   - [`cie94`], which weights chroma and hue differences by chroma
+//!   - [`ciede2000`], which also corrects blue hues and near-neutral colors
+//!   - [`hyab`], which combines the difference in lightness with the distance in chroma
+//!   - [`ok`], the distance in [Oklab]
+//!   - [`itp`], the distance in `ICtCp` defined by [ITU-R BT.2124], designed for HDR and wide
+//!     gamut content
+//!
+//! [`RgbColor`]: crate::rgb::RgbColor
+//! [Oklab]: crate::oklab
+//! [ITU-R BT.2124]: https://www.itu.int/rec/R-REC-BT.2124
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{difference::DeltaE, rgb::Rgb888};
+//!
+//! let red = Rgb888::from_rgb(255, 0, 0);
+//! let darker = Rgb888::from_rgb(253, 0, 0);
+//!
+//! // A difference of about 1.0 is just noticeable.
+//! assert!(DeltaE::Ciede2000.between(red, darker) < 1.0);
+//! assert!(DeltaE::Ciede2000.between(red, Rgb888::from_rgb(0, 0, 255)) > 50.0);
+//! ```
+//!
+//! ## Scales
+//!
+//! Metric                 | Just noticeable difference
+//! ---------------------- | --------------------------
+//! [`DeltaE::Cie76`]      | About 2.3
+//! [`DeltaE::Cie94`]      | About 1.0
+//! [`DeltaE::Ciede2000`]  | About 1.0
+//! [`DeltaE::HyAb`]       | About 2.3, but larger for large differences
+//! [`DeltaE::Ok`]         | About 0.02, because Oklab lightness is `0.0..=1.0`
+//! [`DeltaE::Itp`]        | About 1.0
+//!
+//! CIE94 is not symmetric; the first color is the reference, whose chroma weights the difference,
+//! and the second is the sample. The other metrics are symmetric.
+
+use crate::{
+    cie::{D50, D65, Lab, Xyz},
+    encoding::{LinearRgb, decode},
+    math::{hypot, sqrt},
+    oklab::Oklabf32,
+    rgb::{RgbColor, Rgbf32},
+};
+
+mod ciede2000;
+mod itp;
+
+pub use ciede2000::ciede2000;
+pub use itp::itp;
+
+/// A metric of the difference between two colors.
+///
+/// Colors are converted from sRGB into the space of the metric: CIELAB relative to [`D50`], as
+/// used by CSS and ICC profiles, for [`Self::Cie76`], [`Self::Cie94`], [`Self::Ciede2000`], and
+/// [`Self::HyAb`]; Oklab for [`Self::Ok`]; and `ICtCp`, with sRGB white at 203 cd/m², for
+/// [`Self::Itp`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeltaE {
+    /// CIE76, see [`cie76`].
+    Cie76,
+
+    /// CIE94 with the weights for graphic arts, see [`cie94`].
+    Cie94,
+
+    /// CIEDE2000, see [`ciede2000`].
+    Ciede2000,
+
+    /// `HyAB`, see [`hyab`].
+    HyAb,
+
+    /// ΔE OK, see [`ok`].
+    Ok,
+
+    /// ΔE ITP, see [`itp`].
+    Itp,
+}
+
+impl DeltaE {
+    /// Returns the difference between two sRGB-encoded colors.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{difference::DeltaE, rgb::Rgb888};
+    ///
+    /// let white = Rgb888::from_rgb(255, 255, 255);
+    /// let black = Rgb888::from_rgb(0, 0, 0);
+    /// assert!((DeltaE::Cie76.between(white, black) - 100.0).abs() < 1e-3);
+    /// assert!((DeltaE::Ok.between(white, black) - 1.0).abs() < 1e-3);
+    /// ```
+    #[must_use]
+    pub fn between<C: RgbColor>(self, reference: C, sample: C) -> f32 {
+        let (reference, sample) = (linear(reference), linear(sample));
+        let lab = |color: LinearRgb<Rgbf32>| Lab::<f32, D50>::from(Xyz::<f32, D50>::from(color));
+        match self {
+            Self::Cie76 => cie76(lab(reference), lab(sample)),
+            Self::Cie94 => cie94(lab(reference), lab(sample)),
+            Self::Ciede2000 => ciede2000(lab(reference), lab(sample)),
+            Self::HyAb => hyab(lab(reference), lab(sample)),
+            Self::Ok => ok(Oklabf32::from(reference), Oklabf32::from(sample)),
+            Self::Itp => itp(Xyz::<f32, D65>::from(reference), Xyz::from(sample)),
+        }
+    }
+}
+
+/// Decodes an sRGB-encoded color into linear light.
+fn linear<C: RgbColor>(color: C) -> LinearRgb<Rgbf32> {
+    let (red, green, blue) = color.into_rgb();
+    LinearRgb::new(Rgbf32::from_rgb(decode(red), decode(green), decode(blue)))
+}
+
+/// Returns the components of a CIELAB color in double precision.
+fn lab_f64<W>(color: Lab<f32, W>) -> [f64; 3] {
+    let (lightness, a, b) = color.into_lab();
+    [lightness, a, b].map(f64::from)
+}
+
+/// Returns the CIE76 difference, the Euclidean distance between two CIELAB colors.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::Labf32, difference::cie76};
+///
+/// let a = Labf32::from_lab(50.0, 0.0, 0.0);
+/// let b = Labf32::from_lab(50.0, 3.0, 4.0);
+/// assert_eq!(cie76(a, b), 5.0);
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn cie76<W>(reference: Lab<f32, W>, sample: Lab<f32, W>) -> f32 {
+    let ([l1, a1, b1], [l2, a2, b2]) = (lab_f64(reference), lab_f64(sample));
+    sqrt((l1 - l2) * (l1 - l2) + (a1 - a2) * (a1 - a2) + (b1 - b2) * (b1 - b2)) as f32
+}
+
+/// Returns the CIE94 difference between a reference and a sample CIELAB color, with the weights
+/// for graphic arts (`kL = 1`, `K1 = 0.045`, `K2 = 0.015`).
+///
+/// Differences in chroma and hue count for less as the chroma of the reference grows.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::Labf32, difference::cie94};
+///
+/// // A hue difference of a saturated color is less visible than the same distance in lightness.
+/// let red = Labf32::from_lab(50.0, 60.0, 0.0);
+/// assert!(cie94(red, Labf32::from_lab(50.0, 60.0, 5.0)) < 3.0);
+/// assert_eq!(cie94(red, Labf32::from_lab(55.0, 60.0, 0.0)), 5.0);
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn cie94<W>(reference: Lab<f32, W>, sample: Lab<f32, W>) -> f32 {
+    let ([l1, a1, b1], [l2, a2, b2]) = (lab_f64(reference), lab_f64(sample));
+    let (c1, c2) = (hypot(a1, b1), hypot(a2, b2));
+    let (dl, dc) = (l1 - l2, c1 - c2);
+    let (da, db) = (a1 - a2, b1 - b2);
+    // Rounding can make the squared hue difference slightly negative for equal hues.
+    let dh_squared = (da * da + db * db - dc * dc).max(0.0);
+    let (sc, sh) = (1.0 + 0.045 * c1, 1.0 + 0.015 * c1);
+    sqrt(dl * dl + (dc / sc) * (dc / sc) + dh_squared / (sh * sh)) as f32
+}
+
+/// Returns the `HyAB` difference, the sum of the difference in lightness and the Euclidean
+/// distance in `a*` and `b*` between two CIELAB colors.
+///
+/// Unlike the other metrics, `HyAB` stays accurate for large differences, such as when choosing
+/// the most distinct colors of a palette ([Abasi et al., 2020]).
+///
+/// [Abasi et al., 2020]: https://doi.org/10.1002/col.22451
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::Labf32, difference::hyab};
+///
+/// let a = Labf32::from_lab(50.0, 0.0, 0.0);
+/// let b = Labf32::from_lab(60.0, 3.0, 4.0);
+/// assert_eq!(hyab(a, b), 15.0);
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn hyab<W>(reference: Lab<f32, W>, sample: Lab<f32, W>) -> f32 {
+    let ([l1, a1, b1], [l2, a2, b2]) = (lab_f64(reference), lab_f64(sample));
+    ((l1 - l2).abs() + hypot(a1 - a2, b1 - b2)) as f32
+}
+
+/// Returns ΔE OK, the Euclidean distance between two Oklab colors.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{difference::ok, oklab::Oklabf32};
+///
+/// let a = Oklabf32::from_lab(0.5, 0.0, 0.0);
+/// let b = Oklabf32::from_lab(0.5, 0.03, 0.04);
+/// assert!((ok(a, b) - 0.05).abs() < 1e-6);
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn ok(reference: Oklabf32, sample: Oklabf32) -> f32 {
+    let (l1, a1, b1) = reference.into_lab();
+    let (l2, a2, b2) = sample.into_lab();
+    let [dl, da, db] = [l1 - l2, a1 - a2, b1 - b2].map(f64::from);
+    sqrt(dl * dl + da * da + db * db) as f32
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::{cie::Labf32, rgb::Rgb888};
+
+    const METRICS: [DeltaE; 6] = [
+        DeltaE::Cie76,
+        DeltaE::Cie94,
+        DeltaE::Ciede2000,
+        DeltaE::HyAb,
+        DeltaE::Ok,
+        DeltaE::Itp,
+    ];
+
+    #[test]
+    fn equal_colors_have_no_difference() {
+        let color = Rgb888::from_rgb(12, 200, 99);
+        for metric in METRICS {
+            assert_eq!(metric.between(color, color), 0.0, "{metric:?}");
+        }
+    }
+
+    #[test]
+    fn symmetric_metrics() {
+        let (a, b) = (
+            Rgb888::from_rgb(12, 200, 99),
+            Rgb888::from_rgb(100, 50, 220),
+        );
+        for metric in [DeltaE::Cie76, DeltaE::HyAb, DeltaE::Ok, DeltaE::Itp] {
+            assert_eq!(metric.between(a, b), metric.between(b, a), "{metric:?}");
+        }
+    }
+
+    #[test]
+    fn cie94_is_weighted_by_reference_chroma() {
+        let (gray, red) = (
+            Labf32::from_lab(50.0, 0.0, 0.0),
+            Labf32::from_lab(50.0, 50.0, 0.0),
+        );
+        // Only chroma differs: 50 / (1 + 0.045 × C1).
+        assert!((cie94(gray, red) - 50.0).abs() < 1e-4);
+        assert!((cie94(red, gray) - 50.0 / 3.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ok_matches_oklab_distance() {
+        let (white, black) = (Rgb888::from_rgb(255, 255, 255), Rgb888::from_rgb(0, 0, 0));
+        assert!((DeltaE::Ok.between(white, black) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn lab_is_relative_to_d50() {
+        // Without adaptation, sRGB white would not be neutral in CIELAB D50.
+        let white = Rgb888::from_rgb(255, 255, 255);
+        let gray = Rgb888::from_rgb(119, 119, 119);
+        let lab = Lab::<f32, D50>::from(Xyz::<f32, D50>::from(linear(white)));
+        let (lightness, a, b) = lab.into_lab();
+        assert!((lightness - 100.0).abs() < 1e-3);
+        assert!(a.abs() < 1e-3 && b.abs() < 1e-3);
+        // Only lightness differs between grays, so HyAB and CIE76 agree.
+        assert!(
+            (DeltaE::HyAb.between(white, gray) - DeltaE::Cie76.between(white, gray)).abs() < 1e-3
+        );
+    }
+}
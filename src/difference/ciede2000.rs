@@ -0,0 +1,157 @@
+use crate::{
+    cie::Lab,
+    difference::lab_f64,
+    math::{atan2_degrees, exp, hypot, pow, sin_cos_degrees, sqrt},
+};
+
+/// Returns the CIEDE2000 difference between a reference and a sample CIELAB color, with the
+/// parametric weights `kL`, `kC`, and `kH` set to `1`.
+///
+/// This follows the formulas of [Sharma, Wu, and Dalal (2005)][sharma], including the mean hue of
+/// colors on opposite sides of `0°`, and is tested against their reference data.
+///
+/// [sharma]: https://hajim.rochester.edu/ece/sites/gsharma/ciede2000/
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::Labf32, difference::ciede2000};
+///
+/// let a = Labf32::from_lab(50.0, 2.6772, -79.7751);
+/// let b = Labf32::from_lab(50.0, 0.0, -82.7485);
+/// assert!((ciede2000(a, b) - 2.0425).abs() < 1e-4);
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::similar_names)]
+pub fn ciede2000<W>(reference: Lab<f32, W>, sample: Lab<f32, W>) -> f32 {
+    let ([l1, a1, b1], [l2, a2, b2]) = (lab_f64(reference), lab_f64(sample));
+
+    // Stretch a* of near-neutral colors, where the eye is more sensitive to hue.
+    let c_mean = f64::midpoint(hypot(a1, b1), hypot(a2, b2));
+    let c_mean_7 = pow(c_mean, 7.0);
+    let g = 0.5 * (1.0 - sqrt(c_mean_7 / (c_mean_7 + pow(25.0, 7.0))));
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (hypot(a1, b1), hypot(a2, b2));
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            atan2_degrees(b, a)
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let dh = 2.0 * sqrt(c1 * c2) * sin_cos_degrees(dh / 2.0).0;
+
+    let l_mean = f64::midpoint(l1, l2);
+    let c_mean = f64::midpoint(c1, c2);
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        f64::midpoint(h1, h2)
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * sin_cos_degrees(h_mean - 30.0).1
+        + 0.24 * sin_cos_degrees(2.0 * h_mean).1
+        + 0.32 * sin_cos_degrees(3.0 * h_mean + 6.0).1
+        - 0.20 * sin_cos_degrees(4.0 * h_mean - 63.0).1;
+    let l_offset = (l_mean - 50.0) * (l_mean - 50.0);
+    let sl = 1.0 + 0.015 * l_offset / sqrt(20.0 + l_offset);
+    let sc = 1.0 + 0.045 * c_mean;
+    let sh = 1.0 + 0.015 * c_mean * t;
+
+    // Rotate the ellipses of blue hues, where chroma and hue differences interact.
+    let h_offset = (h_mean - 275.0) / 25.0;
+    let rotation = 60.0 * exp(-h_offset * h_offset);
+    let c_mean_7 = pow(c_mean, 7.0);
+    let rc = 2.0 * sqrt(c_mean_7 / (c_mean_7 + pow(25.0, 7.0)));
+    let rt = -sin_cos_degrees(rotation).0 * rc;
+
+    let (dl, dc, dh) = (dl / sl, dc / sc, dh / sh);
+    sqrt(dl * dl + dc * dc + dh * dh + rt * dc * dh) as f32
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::cie::Labf32;
+
+    /// The test data of Sharma, Wu, and Dalal (2005), as `[L1, a1, b1, L2, a2, b2, ΔE00]`.
+    #[rustfmt::skip]
+    const SHARMA: [[f32; 7]; 34] = [
+        [50.0000, 2.6772, -79.7751, 50.0000, 0.0000, -82.7485, 2.0425],
+        [50.0000, 3.1571, -77.2803, 50.0000, 0.0000, -82.7485, 2.8615],
+        [50.0000, 2.8361, -74.0200, 50.0000, 0.0000, -82.7485, 3.4412],
+        [50.0000, -1.3802, -84.2814, 50.0000, 0.0000, -82.7485, 1.0000],
+        [50.0000, -1.1848, -84.8006, 50.0000, 0.0000, -82.7485, 1.0000],
+        [50.0000, -0.9009, -85.5211, 50.0000, 0.0000, -82.7485, 1.0000],
+        [50.0000, 0.0000, 0.0000, 50.0000, -1.0000, 2.0000, 2.3669],
+        [50.0000, -1.0000, 2.0000, 50.0000, 0.0000, 0.0000, 2.3669],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0009, 7.1792],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0010, 7.1792],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0011, 7.2195],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0012, 7.2195],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0009, -2.4900, 4.8045],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0010, -2.4900, 4.8045],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0011, -2.4900, 4.7461],
+        [50.0000, 2.5000, 0.0000, 50.0000, 0.0000, -2.5000, 4.3065],
+        [50.0000, 2.5000, 0.0000, 73.0000, 25.0000, -18.0000, 27.1492],
+        [50.0000, 2.5000, 0.0000, 61.0000, -5.0000, 29.0000, 22.8977],
+        [50.0000, 2.5000, 0.0000, 56.0000, -27.0000, -3.0000, 31.9030],
+        [50.0000, 2.5000, 0.0000, 58.0000, 24.0000, 15.0000, 19.4535],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.1736, 0.5854, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.2972, 0.0000, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 1.8634, 0.5757, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.2592, 0.3350, 1.0000],
+        [60.2574, -34.0099, 36.2677, 60.4626, -34.1751, 39.4387, 1.2644],
+        [63.0109, -31.0961, -5.8663, 62.8187, -29.7946, -4.0864, 1.2630],
+        [61.2901, 3.7196, -5.3901, 61.4292, 2.2480, -4.9620, 1.8731],
+        [35.0831, -44.1164, 3.7933, 35.0232, -40.0716, 1.5901, 1.8645],
+        [22.7233, 20.0904, -46.6940, 23.0331, 14.9730, -42.5619, 2.0373],
+        [36.4612, 47.8580, 18.3852, 36.2715, 50.5065, 21.2231, 1.4146],
+        [90.8027, -2.0831, 1.4410, 91.1528, -1.6435, 0.0447, 1.4441],
+        [90.9257, -0.5406, -0.9208, 88.6381, -0.8985, -0.7239, 1.5381],
+        [6.7747, -0.2908, -2.4247, 5.8714, -0.0985, -2.2286, 0.6377],
+        [2.0776, 0.0795, -1.1350, 0.9033, -0.0636, -0.5514, 0.9082],
+    ];
+
+    #[test]
+    fn sharma_test_data() {
+        for (i, [l1, a1, b1, l2, a2, b2, expected]) in SHARMA.into_iter().enumerate() {
+            let (reference, sample) = (Labf32::from_lab(l1, a1, b1), Labf32::from_lab(l2, a2, b2));
+            let difference = ciede2000(reference, sample);
+            assert!(
+                (difference - expected).abs() < 1e-4,
+                "pair {}: {difference} != {expected}",
+                i + 1
+            );
+            // Unlike CIE94, CIEDE2000 weights differences by the mean of both colors.
+            assert!((ciede2000(sample, reference) - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn neutral_colors_differ_only_in_lightness() {
+        let black = Labf32::from_lab(0.0, 0.0, 0.0);
+        let gray = Labf32::from_lab(50.0, 0.0, 0.0);
+        assert_eq!(ciede2000(gray, gray), 0.0);
+        // The mean lightness is 25, so the lightness weight is 1 + 0.015 × 625 / √645.
+        assert!((ciede2000(black, gray) - 36.5193).abs() < 1e-4);
+    }
+}
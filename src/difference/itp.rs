@@ -0,0 +1,104 @@
+use crate::{
+    cie::{D65, WhitePoint, Xyz, rgb_to_xyz_matrix},
+    math::{Matrix3, invert, mul_matrix, mul_vector, pow, sqrt},
+};
+
+/// The luminance, in cd/m², of `Y = 1.0`, which is the reference white of ITU-R BT.2408.
+const WHITE_LUMINANCE: f64 = 203.0;
+
+/// The `x` and `y` chromaticities of the ITU-R BT.2020 red, green, and blue primaries.
+const REC2020_PRIMARIES: [[f64; 2]; 3] = [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]];
+
+/// Converts linear ITU-R BT.2020 RGB into LMS cone responses, as defined by ITU-R BT.2100.
+#[rustfmt::skip]
+const REC2020_TO_LMS: Matrix3 = [
+    [1688.0 / 4096.0, 2146.0 / 4096.0,  262.0 / 4096.0],
+    [ 683.0 / 4096.0, 2951.0 / 4096.0,  462.0 / 4096.0],
+    [  99.0 / 4096.0,  309.0 / 4096.0, 3688.0 / 4096.0],
+];
+
+/// Converts XYZ relative to [`D65`] into LMS cone responses.
+const XYZ_TO_LMS: Matrix3 = mul_matrix(
+    &REC2020_TO_LMS,
+    &invert(&rgb_to_xyz_matrix(REC2020_PRIMARIES, D65::XYZ)),
+);
+
+/// Converts PQ-encoded LMS into `ICtCp`, as defined by ITU-R BT.2100.
+#[rustfmt::skip]
+const LMS_TO_ICTCP: Matrix3 = [
+    [ 2048.0 / 4096.0,   2048.0 / 4096.0,     0.0 / 4096.0],
+    [ 6610.0 / 4096.0, -13613.0 / 4096.0,  7003.0 / 4096.0],
+    [17933.0 / 4096.0, -17390.0 / 4096.0,  -543.0 / 4096.0],
+];
+
+/// Encodes a luminance, where `1.0` is 10,000 cd/m², with the perceptual quantizer of SMPTE ST
+/// 2084.
+fn pq(luminance: f64) -> f64 {
+    const M1: f64 = 2610.0 / 16384.0;
+    const M2: f64 = 2523.0 / 4096.0 * 128.0;
+    const C1: f64 = 3424.0 / 4096.0;
+    const C2: f64 = 2413.0 / 4096.0 * 32.0;
+    const C3: f64 = 2392.0 / 4096.0 * 32.0;
+    let power = pow(luminance.max(0.0), M1);
+    pow((C1 + C2 * power) / (1.0 + C3 * power), M2)
+}
+
+/// Converts XYZ relative to [`D65`], where `Y = 1.0` is [`WHITE_LUMINANCE`], into `ICtCp`.
+fn ictcp(color: Xyz<f32, D65>) -> [f64; 3] {
+    let scale = WHITE_LUMINANCE / 10_000.0;
+    let lms = mul_vector(&XYZ_TO_LMS, color.into_f64()).map(|c| pq(c * scale));
+    mul_vector(&LMS_TO_ICTCP, lms)
+}
+
+/// Returns ΔE ITP, the difference between two colors in `ICtCp`, as defined by ITU-R BT.2124.
+///
+/// Colors are relative to [`D65`], where `Y = 1.0` is 203 cd/m², the reference white of SDR
+/// content in HDR defined by ITU-R BT.2408. A difference of `1.0` is just noticeable.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cie::Xyzf32, difference::itp};
+///
+/// let white = Xyzf32::from_xyz(0.950_456, 1.0, 1.089_058);
+/// let gray = Xyzf32::from_xyz(0.950_456 * 0.98, 0.98, 1.089_058 * 0.98);
+/// assert!(itp(white, gray) < 3.0);
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn itp(reference: Xyz<f32, D65>, sample: Xyz<f32, D65>) -> f32 {
+    let ([i1, ct1, cp1], [i2, ct2, cp2]) = (ictcp(reference), ictcp(sample));
+    let (di, dt, dp) = (i1 - i2, 0.5 * (ct1 - ct2), cp1 - cp2);
+    (720.0 * sqrt(di * di + dt * dt + dp * dp)) as f32
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pq_reference_values() {
+        assert!(pq(0.0) < 1e-6);
+        assert!((pq(1.0) - 1.0).abs() < 1e-12);
+        // 100 cd/m² is about 50.8% of the PQ signal, and 203 cd/m² about 58%.
+        assert!((pq(0.01) - 0.508_078).abs() < 1e-6);
+        assert!((pq(0.0203) - 0.580_688).abs() < 1e-6);
+    }
+
+    #[test]
+    fn white_is_neutral() {
+        let white = Xyz::from_f64(D65::XYZ);
+        let [intensity, ct, cp] = ictcp(white);
+        assert!((intensity - pq(0.0203)).abs() < 1e-6);
+        assert!(ct.abs() < 1e-6 && cp.abs() < 1e-6);
+    }
+
+    #[test]
+    fn lightness_difference() {
+        let white = Xyz::from_f64(D65::XYZ);
+        let black = Xyz::from_xyz(0.0, 0.0, 0.0);
+        let expected = 720.0 * (pq(0.0203) - pq(0.0));
+        assert!((f64::from(itp(white, black)) - expected).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,383 @@
+//! Dithering 🏁 colors into formats or palettes with fewer colors, without banding.
+//!
+//! This module contains:
+//!
+//! - [`ErrorDiffusion`], which spreads the error of each pixel to its neighbors with a [`Kernel`],
+//!   such as [`Kernel::FloydSteinberg`]
+//! - [`Bayer`], which adds a repeating threshold pattern of 2×2 up to 16×16 pixels
+//! - [`Target`], the colors that dithering chooses from, implemented by:
+//!   - [`Narrow<P>`], every color of a format with alpha, such as [`Argb4444`]
+//!   - [`NarrowRgb<P>`], every color of a format without alpha, such as [`Rgb565`]
+//!   - [`Palette<C>`], a palette of up to 256 colors, producing indices
+//!
+//! Both algorithms read an [`ImageView`] of any [`RgbaColor`] and write an [`ImageViewMut`] of
+//! the pixels of the target, so they work with borrowed slices as well as an [`ImageBuf`].
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{
+//!     dither::{Bayer, NarrowRgb},
+//!     image::{ImageView, ImageViewMut},
+//!     rgb::{Abgr8888, HasRed, Rgb565},
+//! };
+//!
+//! let src = [Abgr8888::from_abgr(255, 100, 100, 100); 16];
+//! let mut dst = [Rgb565::default(); 16];
+//!
+//! Bayer::new(4).dither(
+//!     ImageView::new(&src, 4, 4).unwrap(),
+//!     &mut ImageViewMut::new(&mut dst, 4, 4).unwrap(),
+//!     &NarrowRgb::<Rgb565>::new(),
+//! );
+//!
+//! // 100 is between two 5-bit red levels, so both are used.
+//! assert!(dst.iter().any(|c| c.red() == 12));
+//! assert!(dst.iter().any(|c| c.red() == 13));
+//! ```
+//!
+//! ## Error Diffusion or Ordered Dithering
+//!
+//! [`ErrorDiffusion`] produces the most accurate results, but each pixel depends on the pixels
+//! before it, so a small change to an image can change many pixels, and it needs a buffer of a few
+//! rows of errors; [`ErrorDiffusion::dither`] allocates one (requires the `alloc` feature), and
+//! [`ErrorDiffusion::dither_with_buffer`] accepts one, such as an array on the stack.
+//!
+//! [`Bayer`] is faster, needs no memory, and each pixel depends only on its own color and
+//! position, which suits animation and tiled rendering, but the pattern is more visible.
+//!
+//! Components are dithered as they are stored, which for the predefined types is sRGB-encoded.
+//!
+//! [`Argb4444`]: crate::rgb::Argb4444
+//! [`Rgb565`]: crate::rgb::Rgb565
+//! [`RgbaColor`]: crate::rgb::RgbaColor
+//! [`ImageView`]: crate::image::ImageView
+//! [`ImageViewMut`]: crate::image::ImageViewMut
+//! [`ImageBuf`]: crate::image::ImageBuf
+
+use core::marker::PhantomData;
+
+use crate::{
+    alpha::HasAlpha,
+    component::Component,
+    math,
+    rgb::{HasBlue, HasGreen, HasRed, RgbColor, RgbaColor},
+};
+
+mod diffusion;
+mod ordered;
+
+pub use diffusion::{ErrorDiffusion, Kernel};
+pub use ordered::Bayer;
+
+/// The colors that dithering chooses from, such as every color of a format or a palette.
+///
+/// Colors are `[red, green, blue, alpha]` arrays of normalized components, where `0.0` is
+/// [`Component::MIN`] and `1.0` is [`Component::MAX`].
+pub trait Target {
+    /// The type of pixel that is written for each color.
+    type Pixel: Copy;
+
+    /// Returns the pixel nearest to `color`, and the color it represents.
+    fn nearest(&self, color: [f32; 4]) -> (Self::Pixel, [f32; 4]);
+
+    /// Returns the distance between adjacent colors of each component, or `0.0` for components
+    /// that should not be dithered.
+    ///
+    /// [`Bayer`] varies each component by up to half of this distance.
+    fn step(&self) -> [f32; 4];
+}
+
+/// Every color of a format with alpha, such as [`Argb4444`] or [`Argb1555`].
+///
+/// [`Argb4444`]: crate::rgb::Argb4444
+/// [`Argb1555`]: crate::rgb::Argb1555
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{dither::{Narrow, Target}, rgb::Argb4444};
+///
+/// let (pixel, color) = Narrow::<Argb4444>::new().nearest([1.0, 0.0, 0.0, 0.78]);
+/// assert_eq!(pixel, Argb4444::from_argb(12, 15, 0, 0));
+/// assert_eq!(color, [1.0, 0.0, 0.0, 0.8]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Narrow<P> {
+    format: PhantomData<fn() -> P>,
+}
+
+impl<P> Narrow<P> {
+    /// Creates a target for every color of `P`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            format: PhantomData,
+        }
+    }
+}
+
+impl<P: RgbaColor + Copy> Target for Narrow<P> {
+    type Pixel = P;
+
+    fn nearest(&self, color: [f32; 4]) -> (P, [f32; 4]) {
+        let [red, green, blue, alpha] = color;
+        let pixel = P::from_rgba(
+            Component::from_normalized_f32(red),
+            Component::from_normalized_f32(green),
+            Component::from_normalized_f32(blue),
+            Component::from_normalized_f32(alpha),
+        );
+        (pixel, into_f32(pixel))
+    }
+
+    fn step(&self) -> [f32; 4] {
+        [
+            step::<<P as HasRed>::Component>(),
+            step::<<P as HasGreen>::Component>(),
+            step::<<P as HasBlue>::Component>(),
+            step::<<P as HasAlpha>::Component>(),
+        ]
+    }
+}
+
+/// Every color of a format without alpha, such as [`Rgb565`] or [`Rgb888`].
+///
+/// Alpha is ignored.
+///
+/// [`Rgb565`]: crate::rgb::Rgb565
+/// [`Rgb888`]: crate::rgb::Rgb888
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{dither::{NarrowRgb, Target}, rgb::Rgb565};
+///
+/// let (pixel, _) = NarrowRgb::<Rgb565>::new().nearest([1.0, 0.5, 0.0, 1.0]);
+/// assert_eq!(pixel, Rgb565::from_rgb(31, 32, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NarrowRgb<P> {
+    format: PhantomData<fn() -> P>,
+}
+
+impl<P> NarrowRgb<P> {
+    /// Creates a target for every color of `P`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            format: PhantomData,
+        }
+    }
+}
+
+impl<P: RgbColor + Copy> Target for NarrowRgb<P> {
+    type Pixel = P;
+
+    fn nearest(&self, color: [f32; 4]) -> (P, [f32; 4]) {
+        let [red, green, blue, alpha] = color;
+        let pixel = P::from_rgb(
+            Component::from_normalized_f32(red),
+            Component::from_normalized_f32(green),
+            Component::from_normalized_f32(blue),
+        );
+        let (red, green, blue) = pixel.into_rgb();
+        let color = [
+            red.to_normalized_f32(),
+            green.to_normalized_f32(),
+            blue.to_normalized_f32(),
+            alpha,
+        ];
+        (pixel, color)
+    }
+
+    fn step(&self) -> [f32; 4] {
+        [
+            step::<<P as HasRed>::Component>(),
+            step::<<P as HasGreen>::Component>(),
+            step::<<P as HasBlue>::Component>(),
+            0.0,
+        ]
+    }
+}
+
+/// A palette of up to 256 colors, where each pixel is the index of a color.
+///
+/// The nearest color is the one with the smallest squared distance between components, as they
+/// are stored. Palettes can be chosen with [`Quantizer`][].
+///
+/// [`Quantizer`]: crate::quantize::Quantizer
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{dither::{Palette, Target}, rgb::Abgr8888};
+///
+/// let colors = [
+///     Abgr8888::from_abgr(255, 0, 0, 0),
+///     Abgr8888::from_abgr(255, 255, 255, 255),
+/// ];
+/// let palette = Palette::new(&colors);
+/// assert_eq!(palette.nearest([0.75, 0.75, 0.75, 1.0]).0, 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette<'a, C> {
+    colors: &'a [C],
+    spread: f32,
+}
+
+impl<'a, C: RgbaColor + Copy> Palette<'a, C> {
+    /// Creates a palette of `colors`.
+    ///
+    /// The spread of [`Bayer`] dithering defaults to the distance between the colors of an evenly
+    /// spaced palette of the same size, such as `0.2` for 216 colors.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `colors` is empty or has more than 256 colors.
+    #[must_use]
+    pub fn new(colors: &'a [C]) -> Self {
+        assert!(
+            (1..=256).contains(&colors.len()),
+            "palette must have 1 to 256 colors"
+        );
+        #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+        let levels = math::cbrt(colors.len() as f64) as f32;
+        let spread = if levels > 2.0 {
+            1.0 / (levels - 1.0)
+        } else {
+            1.0
+        };
+        Self { colors, spread }
+    }
+
+    /// Returns a copy of the palette, where [`Bayer`] dithering varies red, green, and blue by up to
+    /// half of `spread`.
+    ///
+    /// A larger spread hides more banding, but adds more noise.
+    #[must_use]
+    pub const fn with_spread(self, spread: f32) -> Self {
+        Self { spread, ..self }
+    }
+
+    /// Returns the colors of the palette.
+    #[must_use]
+    pub const fn colors(&self) -> &'a [C] {
+        self.colors
+    }
+
+    /// Returns the spread of [`Bayer`] dithering.
+    #[must_use]
+    pub const fn spread(&self) -> f32 {
+        self.spread
+    }
+}
+
+impl<C: RgbaColor + Copy> Target for Palette<'_, C> {
+    type Pixel = u8;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn nearest(&self, color: [f32; 4]) -> (u8, [f32; 4]) {
+        let mut best = (0, [0.0; 4], f32::INFINITY);
+        for (i, &candidate) in self.colors.iter().enumerate() {
+            let candidate = into_f32(candidate);
+            let distance = candidate
+                .iter()
+                .zip(color)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum();
+            if distance < best.2 {
+                best = (i, candidate, distance);
+            }
+        }
+        (best.0 as u8, best.1)
+    }
+
+    /// Returns the spread for red, green, and blue, and `0.0` for alpha, so that opaque pixels are
+    /// never dithered towards transparent colors.
+    fn step(&self) -> [f32; 4] {
+        [self.spread, self.spread, self.spread, 0.0]
+    }
+}
+
+/// Returns the normalized distance between adjacent values of `T`.
+#[allow(clippy::cast_precision_loss)]
+fn step<T: Component>() -> f32 {
+    match T::BITS {
+        Some(bits) => 1.0 / ((1u64 << bits) - 1) as f32,
+        None => 0.0,
+    }
+}
+
+/// Returns the normalized components of a color.
+fn into_f32<C: RgbaColor>(color: C) -> [f32; 4] {
+    let (red, green, blue, alpha) = color.into_rgba();
+    [
+        red.to_normalized_f32(),
+        green.to_normalized_f32(),
+        blue.to_normalized_f32(),
+        alpha.to_normalized_f32(),
+    ]
+}
+
+/// Returns the normalized components of a color, clamped to `0.0..=1.0`.
+fn into_clamped_f32<C: RgbaColor>(color: C) -> [f32; 4] {
+    into_f32(color).map(|c| c.clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::rgb::{Abgr8888, Argb4444, Rgb565, Rgbaf32};
+
+    #[test]
+    fn narrow_steps() {
+        assert_eq!(
+            Narrow::<Argb4444>::new().step(),
+            [1.0 / 15.0, 1.0 / 15.0, 1.0 / 15.0, 1.0 / 15.0]
+        );
+        assert_eq!(Narrow::<Rgbaf32>::new().step(), [0.0; 4]);
+        assert_eq!(
+            NarrowRgb::<Rgb565>::new().step(),
+            [1.0 / 31.0, 1.0 / 63.0, 1.0 / 31.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn narrow_rgb_keeps_alpha() {
+        let (_, color) = NarrowRgb::<Rgb565>::new().nearest([0.0, 0.0, 0.0, 0.5]);
+        assert_eq!(color, [0.0, 0.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn palette_prefers_first_of_equal_distances() {
+        let colors = [
+            Abgr8888::from_abgr(255, 0, 0, 0),
+            Abgr8888::from_abgr(255, 255, 255, 255),
+        ];
+        let palette = Palette::new(&colors);
+        assert_eq!(
+            palette.nearest([0.5, 0.5, 0.5, 1.0]),
+            (0, [0.0, 0.0, 0.0, 1.0])
+        );
+        assert_eq!(palette.nearest([0.6, 0.5, 0.5, 1.0]), (1, [1.0; 4]));
+    }
+
+    #[test]
+    fn palette_spread() {
+        let colors = [Abgr8888::default(); 216];
+        assert!((Palette::new(&colors).spread() - 0.2).abs() < 1e-6);
+        assert_eq!(Palette::new(&colors[..2]).spread(), 1.0);
+        assert_eq!(
+            Palette::new(&colors[..2]).with_spread(0.5).step(),
+            [0.5, 0.5, 0.5, 0.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "palette must have 1 to 256 colors")]
+    fn empty_palette() {
+        let _ = Palette::<Abgr8888>::new(&[]);
+    }
+}
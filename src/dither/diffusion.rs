@@ -0,0 +1,416 @@
+#[cfg(feature = "alloc")]
+use alloc::vec;
+
+use crate::{
+    dither::{Target, into_clamped_f32},
+    image::{ImageView, ImageViewMut},
+    rgb::RgbaColor,
+};
+
+/// The number of rows of errors, which is one more than the number of rows a kernel spreads to.
+const ROWS: usize = 3;
+
+/// The number of columns a kernel spreads to on either side.
+const PAD: usize = 2;
+
+/// A matrix of weights that spreads the error of a pixel to the pixels after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kernel {
+    /// Floyd–Steinberg, which spreads to 4 pixels over 2 rows.
+    ///
+    /// ```text
+    ///       *  7
+    ///    3  5  1    (÷ 16)
+    /// ```
+    FloydSteinberg,
+
+    /// Atkinson, which spreads only three quarters of the error to 6 pixels over 3 rows, for more
+    /// contrast at the cost of detail in highlights and shadows.
+    ///
+    /// ```text
+    ///       *  1  1
+    ///    1  1  1
+    ///       1       (÷ 8)
+    /// ```
+    Atkinson,
+
+    /// Sierra, which spreads to 10 pixels over 3 rows.
+    ///
+    /// ```text
+    ///          *  5  3
+    ///    2  4  5  4  2
+    ///       2  3  2       (÷ 32)
+    /// ```
+    Sierra,
+
+    /// Stucki, which spreads to 12 pixels over 3 rows.
+    ///
+    /// ```text
+    ///          *  8  4
+    ///    2  4  8  4  2
+    ///    1  2  4  2  1    (÷ 42)
+    /// ```
+    Stucki,
+
+    /// Jarvis, Judice, and Ninke, which spreads to 12 pixels over 3 rows.
+    ///
+    /// ```text
+    ///          *  7  5
+    ///    3  5  7  5  3
+    ///    1  3  5  3  1    (÷ 48)
+    /// ```
+    JarvisJudiceNinke,
+}
+
+impl Kernel {
+    /// Returns each `(x, y, weight)` of the kernel, relative to the current pixel, and the sum that
+    /// weights are divided by.
+    const fn weights(self) -> (&'static [(isize, usize, u8)], f32) {
+        match self {
+            Self::FloydSteinberg => (&[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)], 16.0),
+            Self::Atkinson => (
+                &[
+                    (1, 0, 1),
+                    (2, 0, 1),
+                    (-1, 1, 1),
+                    (0, 1, 1),
+                    (1, 1, 1),
+                    (0, 2, 1),
+                ],
+                8.0,
+            ),
+            Self::Sierra => (
+                &[
+                    (1, 0, 5),
+                    (2, 0, 3),
+                    (-2, 1, 2),
+                    (-1, 1, 4),
+                    (0, 1, 5),
+                    (1, 1, 4),
+                    (2, 1, 2),
+                    (-1, 2, 2),
+                    (0, 2, 3),
+                    (1, 2, 2),
+                ],
+                32.0,
+            ),
+            Self::Stucki => (
+                &[
+                    (1, 0, 8),
+                    (2, 0, 4),
+                    (-2, 1, 2),
+                    (-1, 1, 4),
+                    (0, 1, 8),
+                    (1, 1, 4),
+                    (2, 1, 2),
+                    (-2, 2, 1),
+                    (-1, 2, 2),
+                    (0, 2, 4),
+                    (1, 2, 2),
+                    (2, 2, 1),
+                ],
+                42.0,
+            ),
+            Self::JarvisJudiceNinke => (
+                &[
+                    (1, 0, 7),
+                    (2, 0, 5),
+                    (-2, 1, 3),
+                    (-1, 1, 5),
+                    (0, 1, 7),
+                    (1, 1, 5),
+                    (2, 1, 3),
+                    (-2, 2, 1),
+                    (-1, 2, 3),
+                    (0, 2, 5),
+                    (1, 2, 3),
+                    (2, 2, 1),
+                ],
+                48.0,
+            ),
+        }
+    }
+}
+
+/// Error diffusion, which chooses the nearest color for each pixel and spreads the difference to
+/// the pixels after it.
+///
+/// By default, scanning is serpentine: odd rows are processed from right to left, with the kernel
+/// mirrored, which avoids the diagonal artifacts of always spreading error in one direction.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{
+///     dither::{ErrorDiffusion, Kernel, Narrow},
+///     image::{ImageView, ImageViewMut},
+///     rgb::{Argb4444, Rgbaf32},
+/// };
+///
+/// // A gradient from black to white is smooth in 4 bits.
+/// let src: Vec<Rgbaf32> = (0..64)
+///     .map(|x| Rgbaf32::from_rgba(x as f32 / 63.0, x as f32 / 63.0, x as f32 / 63.0, 1.0))
+///     .collect();
+/// let mut dst = [Argb4444::default(); 64];
+///
+/// let mut buffer = [[0.0; 4]; ErrorDiffusion::buffer_len(64)];
+/// ErrorDiffusion::new(Kernel::FloydSteinberg).dither_with_buffer(
+///     ImageView::new(&src, 64, 1).unwrap(),
+///     &mut ImageViewMut::new(&mut dst, 64, 1).unwrap(),
+///     &Narrow::<Argb4444>::new(),
+///     &mut buffer,
+/// );
+/// assert_eq!(dst[0], Argb4444::from_argb(15, 0, 0, 0));
+/// assert_eq!(dst[63], Argb4444::from_argb(15, 15, 15, 15));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorDiffusion {
+    kernel: Kernel,
+    serpentine: bool,
+}
+
+impl ErrorDiffusion {
+    /// Creates error diffusion with `kernel` and serpentine scanning.
+    #[must_use]
+    pub const fn new(kernel: Kernel) -> Self {
+        Self {
+            kernel,
+            serpentine: true,
+        }
+    }
+
+    /// Returns a copy that processes every row from left to right if `serpentine` is `false`.
+    #[must_use]
+    pub const fn with_serpentine(self, serpentine: bool) -> Self {
+        Self { serpentine, ..self }
+    }
+
+    /// Returns the kernel.
+    #[must_use]
+    pub const fn kernel(&self) -> Kernel {
+        self.kernel
+    }
+
+    /// Returns whether odd rows are processed from right to left.
+    #[must_use]
+    pub const fn serpentine(&self) -> bool {
+        self.serpentine
+    }
+
+    /// Returns the length of the buffer that [`Self::dither_with_buffer`] needs for an image
+    /// `width` pixels wide.
+    #[must_use]
+    pub const fn buffer_len(width: usize) -> usize {
+        ROWS * (width + 2 * PAD)
+    }
+
+    /// Dithers each pixel of `src` into the pixel at the same position in `dst`, allocating a
+    /// buffer of errors.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different sizes.
+    #[cfg(feature = "alloc")]
+    pub fn dither<S, T>(
+        &self,
+        src: ImageView<'_, S>,
+        dst: &mut ImageViewMut<'_, T::Pixel>,
+        target: &T,
+    ) where
+        S: RgbaColor + Copy,
+        T: Target,
+    {
+        let mut buffer = vec![[0.0; 4]; Self::buffer_len(src.width())];
+        self.dither_with_buffer(src, dst, target, &mut buffer);
+    }
+
+    /// Dithers each pixel of `src` into the pixel at the same position in `dst`, using `buffer` to
+    /// store errors.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different sizes, or `buffer` is shorter than
+    /// [`Self::buffer_len`] of the width.
+    pub fn dither_with_buffer<S, T>(
+        &self,
+        src: ImageView<'_, S>,
+        dst: &mut ImageViewMut<'_, T::Pixel>,
+        target: &T,
+        buffer: &mut [[f32; 4]],
+    ) where
+        S: RgbaColor + Copy,
+        T: Target,
+    {
+        assert_eq!(
+            (src.width(), src.height()),
+            (dst.width(), dst.height()),
+            "source and destination sizes differ"
+        );
+        let width = src.width();
+        let padded = width + 2 * PAD;
+        assert!(
+            buffer.len() >= Self::buffer_len(width),
+            "buffer is too small for the width"
+        );
+        let errors = &mut buffer[..Self::buffer_len(width)];
+        errors.fill([0.0; 4]);
+        let (weights, sum) = self.kernel.weights();
+
+        for (y, (src, dst)) in src.rows().zip(dst.rows_mut()).enumerate() {
+            let reverse = self.serpentine && y % 2 == 1;
+            for i in 0..width {
+                let x = if reverse { width - 1 - i } else { i };
+                let error = errors[(y % ROWS) * padded + PAD + x];
+                let mut color = into_clamped_f32(src[x]);
+                for (c, e) in color.iter_mut().zip(error) {
+                    *c = (*c + e).clamp(0.0, 1.0);
+                }
+                let (pixel, actual) = target.nearest(color);
+                dst[x] = pixel;
+
+                for &(dx, dy, weight) in weights {
+                    let dx = if reverse { -dx } else { dx };
+                    let index = ((y + dy) % ROWS) * padded + (PAD + x).wrapping_add_signed(dx);
+                    let weight = f32::from(weight) / sum;
+                    for ((e, c), a) in errors[index].iter_mut().zip(color).zip(actual) {
+                        *e += (c - a) * weight;
+                    }
+                }
+            }
+            errors[(y % ROWS) * padded..][..padded].fill([0.0; 4]);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::{
+        dither::{Narrow, Palette},
+        rgb::{Abgr8888, Argb4444},
+    };
+
+    const KERNELS: [Kernel; 5] = [
+        Kernel::FloydSteinberg,
+        Kernel::Atkinson,
+        Kernel::Sierra,
+        Kernel::Stucki,
+        Kernel::JarvisJudiceNinke,
+    ];
+
+    const BLACK_AND_WHITE: [Abgr8888; 2] = [
+        Abgr8888::from_abgr(255, 0, 0, 0),
+        Abgr8888::from_abgr(255, 255, 255, 255),
+    ];
+
+    fn dither<T: Target>(
+        diffusion: ErrorDiffusion,
+        src: &[Abgr8888],
+        dst: &mut [T::Pixel],
+        width: usize,
+        target: &T,
+    ) {
+        let height = src.len() / width;
+        let mut buffer = [[0.0; 4]; ErrorDiffusion::buffer_len(16)];
+        diffusion.dither_with_buffer(
+            ImageView::new(src, width, height).unwrap(),
+            &mut ImageViewMut::new(dst, width, height).unwrap(),
+            target,
+            &mut buffer,
+        );
+    }
+
+    #[test]
+    fn weights_sum_to_divisor() {
+        for kernel in KERNELS {
+            let (weights, sum) = kernel.weights();
+            let total: u8 = weights.iter().map(|w| w.2).sum();
+            let expected = if kernel == Kernel::Atkinson { 6.0 } else { sum };
+            assert_eq!(f32::from(total), expected, "{kernel:?}");
+            assert!(
+                weights
+                    .iter()
+                    .all(|&(x, y, _)| y < ROWS && x.unsigned_abs() <= PAD)
+            );
+        }
+    }
+
+    #[test]
+    fn exact_colors_are_unchanged() {
+        let src: [Abgr8888; 64] = core::array::from_fn(|i| {
+            Abgr8888::from_abgr(255, 0x11 * u8::try_from(i % 16).unwrap(), 0x33, 0)
+        });
+        let target = Narrow::<Argb4444>::new();
+        for kernel in KERNELS {
+            let mut dst = [Argb4444::default(); 64];
+            dither(ErrorDiffusion::new(kernel), &src, &mut dst, 16, &target);
+            for (s, d) in src.iter().zip(dst) {
+                assert_eq!(Abgr8888::from(d), *s, "{kernel:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn preserves_average_intensity() {
+        let palette = Palette::new(&BLACK_AND_WHITE);
+        // Atkinson discards a quarter of the error, so it loses detail in dark and light areas.
+        for kernel in KERNELS.into_iter().filter(|&k| k != Kernel::Atkinson) {
+            for gray in [64, 128, 192] {
+                let src = [Abgr8888::from_abgr(255, gray, gray, gray); 256];
+                let mut dst = [0; 256];
+                dither(ErrorDiffusion::new(kernel), &src, &mut dst, 16, &palette);
+                let white = dst.iter().map(|&i| u32::from(i)).sum::<u32>();
+                let expected = u32::from(gray);
+                assert!(white.abs_diff(expected) <= 12, "{kernel:?} {gray} {white}");
+            }
+        }
+    }
+
+    #[test]
+    fn serpentine_reverses_odd_rows() {
+        // The first gray is slightly nearer to black, so it becomes black, and the second white.
+        let gray = Abgr8888::from_abgr(255, 127, 127, 127);
+        let src = [BLACK_AND_WHITE[0], BLACK_AND_WHITE[0], gray, gray];
+        let palette = Palette::new(&BLACK_AND_WHITE);
+        let diffusion = ErrorDiffusion::new(Kernel::FloydSteinberg);
+
+        let mut dst = [0; 4];
+        dither(
+            diffusion.with_serpentine(false),
+            &src,
+            &mut dst,
+            2,
+            &palette,
+        );
+        assert_eq!(dst, [0, 0, 0, 1]);
+        dither(diffusion, &src, &mut dst, 2, &palette);
+        assert_eq!(dst, [0, 0, 1, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer is too small for the width")]
+    fn small_buffer() {
+        let src = [Abgr8888::default(); 4];
+        let mut dst = [Abgr8888::default(); 4];
+        ErrorDiffusion::new(Kernel::Atkinson).dither_with_buffer(
+            ImageView::new(&src, 4, 1).unwrap(),
+            &mut ImageViewMut::new(&mut dst, 4, 1).unwrap(),
+            &Narrow::<Abgr8888>::new(),
+            &mut [[0.0; 4]; 4],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "source and destination sizes differ")]
+    fn different_sizes() {
+        let src = [Abgr8888::default(); 4];
+        let mut dst = [Abgr8888::default(); 4];
+        ErrorDiffusion::new(Kernel::Atkinson).dither_with_buffer(
+            ImageView::new(&src, 4, 1).unwrap(),
+            &mut ImageViewMut::new(&mut dst, 2, 2).unwrap(),
+            &Narrow::<Abgr8888>::new(),
+            &mut [[0.0; 4]; 24],
+        );
+    }
+}
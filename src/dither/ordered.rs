@@ -0,0 +1,184 @@
+use crate::{
+    dither::{Target, into_clamped_f32},
+    image::{ImageView, ImageViewMut},
+    rgb::RgbaColor,
+};
+
+/// Ordered dithering with a Bayer matrix, which offsets each pixel by a threshold that repeats
+/// every `size` × `size` pixels.
+///
+/// Each component is offset by up to half of the [`Target::step`] in either direction, so colors
+/// that the target represents exactly are never changed.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::dither::Bayer;
+///
+/// let bayer = Bayer::new(2);
+/// assert_eq!(bayer.threshold(0, 0), 0.125);
+/// assert_eq!(bayer.threshold(1, 0), 0.625);
+/// assert_eq!(bayer.threshold(0, 1), 0.875);
+/// assert_eq!(bayer.threshold(1, 1), 0.375);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bayer {
+    size: usize,
+}
+
+impl Bayer {
+    /// Creates a Bayer matrix of `size` × `size` thresholds.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` is not 2, 4, 8, or 16.
+    #[must_use]
+    pub const fn new(size: usize) -> Self {
+        assert!(
+            matches!(size, 2 | 4 | 8 | 16),
+            "size must be 2, 4, 8, or 16"
+        );
+        Self { size }
+    }
+
+    /// Returns the width and height of the matrix.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the threshold of the pixel at `(x, y)`, between `0.0` and `1.0`.
+    ///
+    /// Every threshold of the matrix is different, and evenly spaced.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn threshold(&self, x: usize, y: usize) -> f32 {
+        let bits = self.size.trailing_zeros();
+        let (x, y) = (x % self.size, y % self.size);
+        // Interleaving the bits of `x ^ y` and `y`, from the least significant, in reverse order.
+        let mut index = 0;
+        for bit in 0..bits {
+            let shift = 2 * (bits - 1 - bit);
+            index |= (((x ^ y) >> bit) & 1) << (shift + 1);
+            index |= ((y >> bit) & 1) << shift;
+        }
+        (index as f32 + 0.5) / (self.size * self.size) as f32
+    }
+
+    /// Dithers each pixel of `src` into the pixel at the same position in `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different sizes.
+    pub fn dither<S, T>(
+        &self,
+        src: ImageView<'_, S>,
+        dst: &mut ImageViewMut<'_, T::Pixel>,
+        target: &T,
+    ) where
+        S: RgbaColor + Copy,
+        T: Target,
+    {
+        assert_eq!(
+            (src.width(), src.height()),
+            (dst.width(), dst.height()),
+            "source and destination sizes differ"
+        );
+        let step = target.step();
+        for (y, (src, dst)) in src.rows().zip(dst.rows_mut()).enumerate() {
+            for (x, (&s, d)) in src.iter().zip(dst).enumerate() {
+                let offset = self.threshold(x, y) - 0.5;
+                let mut color = into_clamped_f32(s);
+                for (c, step) in color.iter_mut().zip(step) {
+                    *c = (*c + offset * step).clamp(0.0, 1.0);
+                }
+                *d = target.nearest(color).0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::{
+        alpha::HasAlpha,
+        dither::{Narrow, Palette},
+        rgb::{Abgr8888, Argb4444, HasBlue, HasGreen, HasRed},
+    };
+
+    #[test]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn thresholds_are_a_permutation() {
+        for size in [2, 4, 8, 16] {
+            let bayer = Bayer::new(size);
+            let mut seen = [false; 256];
+            for y in 0..size {
+                for x in 0..size {
+                    let index = bayer.threshold(x, y) * (size * size) as f32 - 0.5;
+                    let index = index as usize;
+                    assert!(!seen[index], "{size} {x} {y}");
+                    seen[index] = true;
+                }
+            }
+            assert_eq!(bayer.threshold(size, size), bayer.threshold(0, 0));
+        }
+    }
+
+    #[test]
+    fn matrix_of_4() {
+        let bayer = Bayer::new(4);
+        let expected: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+        for (y, row) in expected.iter().enumerate() {
+            for (x, &index) in row.iter().enumerate() {
+                assert_eq!(bayer.threshold(x, y), (f32::from(index) + 0.5) / 16.0);
+            }
+        }
+    }
+
+    #[test]
+    fn half_gray_is_half_white() {
+        let colors = [
+            Abgr8888::from_abgr(255, 0, 0, 0),
+            Abgr8888::from_abgr(255, 255, 255, 255),
+        ];
+        let src = [Abgr8888::from_abgr(255, 128, 128, 128); 64];
+        let mut dst = [0; 64];
+        Bayer::new(8).dither(
+            ImageView::new(&src, 8, 8).unwrap(),
+            &mut ImageViewMut::new(&mut dst, 8, 8).unwrap(),
+            &Palette::new(&colors),
+        );
+        // Indices are 0 for black and 1 for white.
+        assert_eq!(dst.iter().map(|&i| u32::from(i)).sum::<u32>(), 32);
+    }
+
+    #[test]
+    fn narrowing_preserves_average() {
+        // 0x88 is 8.0 in 4 bits, and 0x80 is 7.53, so about half of the pixels round up.
+        let src = [Abgr8888::from_abgr(0xFF, 0x88, 0x80, 0x00); 16];
+        let mut dst = [Argb4444::default(); 16];
+        Bayer::new(4).dither(
+            ImageView::new(&src, 4, 4).unwrap(),
+            &mut ImageViewMut::new(&mut dst, 4, 4).unwrap(),
+            &Narrow::<Argb4444>::new(),
+        );
+        assert!(
+            dst.iter()
+                .all(|c| c.alpha() == 15 && c.blue() == 8 && c.red() == 0)
+        );
+        let sum: u32 = dst.iter().map(|c| u32::from(c.green().get())).sum();
+        assert_eq!(sum, 7 * 16 + 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "size must be 2, 4, 8, or 16")]
+    fn invalid_size() {
+        let _ = Bayer::new(3);
+    }
+}
@@ -0,0 +1,280 @@
+//! Type-level tags for how 🔴 🟢 🔵 components are encoded, either as sRGB or linear light.
+//!
+//! Most 8-bit images, and colors written in CSS, store gamma-encoded sRGB components, which are
+//! perceptually uniform but cannot be blended or averaged directly. Those operations must happen in
+//! linear light, where components are proportional to physical intensity.
+//!
+//! Wrapping colors in [`Srgb<C>`] or [`LinearRgb<C>`] records which encoding they use, so that
+//! accidentally mixing the two is a compile error instead of a visual bug:
+//!
+//! ```rust
+//! use gem::{encoding::{LinearRgb, Srgb}, rgb::{Rgb888, Rgbf32}};
+//!
+//! fn average(a: LinearRgb<Rgbf32>, b: LinearRgb<Rgbf32>) -> LinearRgb<Rgbf32> {
+//!     let (a, b) = (a.color(), b.color());
+//!     LinearRgb::new(Rgbf32::from_rgb(
+//!         (a.red() + b.red()) / 2.0,
+//!         (a.green() + b.green()) / 2.0,
+//!         (a.blue() + b.blue()) / 2.0,
+//!     ))
+//! }
+//!
+//! # use gem::rgb::{HasBlue, HasGreen, HasRed};
+//! let black = Srgb::new(Rgb888::from_rgb(0, 0, 0)).into_linear_as::<Rgbf32>();
+//! let white = Srgb::new(Rgb888::from_rgb(255, 255, 255)).into_linear_as::<Rgbf32>();
+//!
+//! // Averaging in linear light and then encoding produces a lighter gray than 128.
+//! let gray = average(black, white).into_srgb_as::<Rgb888>();
+//! assert_eq!(gray.color(), Rgb888::from_rgb(188, 188, 188));
+//! ```
+//!
+//! ## Transfer Functions
+//!
+//! The transfer functions are also available for individual components:
+//!
+//! - [`srgb_to_linear`] and [`linear_to_srgb`] for floating-point components
+//! - [`srgb_u8_to_linear`], which decodes 8-bit components using a lookup table
+//! - [`linear_to_srgb_u8`], which encodes 8-bit components with exact rounding
+
+use crate::{component::Component, rgb::RgbColor};
+
+mod srgb;
+pub use srgb::{linear_to_srgb, linear_to_srgb_u8, srgb_to_linear, srgb_u8_to_linear};
+
+/// A color with gamma-encoded sRGB components.
+///
+/// Only the red, green, and blue components are encoded; alpha, if any, is always linear.
+///
+/// ## Layout
+///
+/// The layout of this type is always the same as the underlying type `C` (`#[repr(transparent)]`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[repr(transparent)]
+pub struct Srgb<C> {
+    color: C,
+}
+
+impl<C> Srgb<C> {
+    /// Creates a new instance of `Srgb`, asserting that `color` is gamma-encoded sRGB.
+    #[must_use]
+    pub const fn new(color: C) -> Self {
+        Self { color }
+    }
+
+    /// Returns the gamma-encoded color.
+    #[must_use]
+    pub const fn color(&self) -> C
+    where
+        C: Copy,
+    {
+        self.color
+    }
+
+    /// Consumes and returns the gamma-encoded color.
+    #[must_use]
+    pub fn into_inner(self) -> C {
+        self.color
+    }
+}
+
+impl<C> Srgb<C>
+where
+    C: RgbColor,
+{
+    /// Decodes the color into linear light, keeping the same representation.
+    ///
+    /// Decoding into an 8-bit representation loses precision in dark colors; consider
+    /// [`Srgb::into_linear_as`] with a floating-point representation instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{encoding::Srgb, rgb::Rgbf32};
+    ///
+    /// let linear = Srgb::new(Rgbf32::from_rgb(1.0, 0.0, 0.0)).into_linear();
+    /// assert_eq!(linear.color(), Rgbf32::from_rgb(1.0, 0.0, 0.0));
+    /// ```
+    #[must_use]
+    pub fn into_linear(self) -> LinearRgb<C> {
+        self.into_linear_as()
+    }
+
+    /// Decodes the color into linear light, converting it into another representation.
+    ///
+    /// Any other components (such as alpha) are converted using [`From`]. Decoding 8-bit components
+    /// uses a lookup table.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{encoding::Srgb, rgb::{Abgr8888, Rgbaf32}};
+    ///
+    /// let linear = Srgb::new(Abgr8888::from_abgr(128, 0, 0, 255)).into_linear_as::<Rgbaf32>();
+    /// assert_eq!(linear.color(), Rgbaf32::from_rgba(1.0, 0.0, 0.0, 128.0 / 255.0));
+    /// ```
+    #[must_use]
+    pub fn into_linear_as<D>(self) -> LinearRgb<D>
+    where
+        D: RgbColor + From<C>,
+    {
+        let (r, g, b) = (self.color.red(), self.color.green(), self.color.blue());
+        let mut color = D::from(self.color);
+        color.set_red(decode(r));
+        color.set_green(decode(g));
+        color.set_blue(decode(b));
+        LinearRgb::new(color)
+    }
+}
+
+/// A color with linear light components.
+///
+/// ## Layout
+///
+/// The layout of this type is always the same as the underlying type `C` (`#[repr(transparent)]`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[repr(transparent)]
+pub struct LinearRgb<C> {
+    color: C,
+}
+
+impl<C> LinearRgb<C> {
+    /// Creates a new instance of `LinearRgb`, asserting that `color` is in linear light.
+    #[must_use]
+    pub const fn new(color: C) -> Self {
+        Self { color }
+    }
+
+    /// Returns the linear color.
+    #[must_use]
+    pub const fn color(&self) -> C
+    where
+        C: Copy,
+    {
+        self.color
+    }
+
+    /// Consumes and returns the linear color.
+    #[must_use]
+    pub fn into_inner(self) -> C {
+        self.color
+    }
+}
+
+impl<C> LinearRgb<C>
+where
+    C: RgbColor,
+{
+    /// Encodes the color into sRGB, keeping the same representation.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{encoding::LinearRgb, rgb::Rgbf32};
+    ///
+    /// let srgb = LinearRgb::new(Rgbf32::from_rgb(1.0, 0.0, 0.0)).into_srgb();
+    /// assert_eq!(srgb.color(), Rgbf32::from_rgb(1.0, 0.0, 0.0));
+    /// ```
+    #[must_use]
+    pub fn into_srgb(self) -> Srgb<C> {
+        self.into_srgb_as()
+    }
+
+    /// Encodes the color into sRGB, converting it into another representation.
+    ///
+    /// Any other components (such as alpha) are converted using [`From`]. Encoding into 8-bit
+    /// components rounds exactly, without evaluating the transfer function.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{encoding::LinearRgb, rgb::{Rgb888, Rgbf32}};
+    ///
+    /// let srgb = LinearRgb::new(Rgbf32::from_rgb(0.5, 0.0, 1.0)).into_srgb_as::<Rgb888>();
+    /// assert_eq!(srgb.color(), Rgb888::from_rgb(188, 0, 255));
+    /// ```
+    #[must_use]
+    pub fn into_srgb_as<D>(self) -> Srgb<D>
+    where
+        D: RgbColor + From<C>,
+    {
+        let (r, g, b) = (self.color.red(), self.color.green(), self.color.blue());
+        let mut color = D::from(self.color);
+        color.set_red(encode(r));
+        color.set_green(encode(g));
+        color.set_blue(encode(b));
+        Srgb::new(color)
+    }
+}
+
+/// Decodes a single sRGB component into a linear component, possibly of a different type.
+pub(crate) fn decode<T: Component, U: Component>(value: T) -> U {
+    let linear = if T::BITS == Some(8) {
+        srgb_u8_to_linear(value.convert())
+    } else {
+        srgb_to_linear(value.to_normalized_f32())
+    };
+    U::from_normalized_f32(linear)
+}
+
+/// Encodes a single linear component into an sRGB component, possibly of a different type.
+pub(crate) fn encode<T: Component, U: Component>(value: T) -> U {
+    let linear = value.to_normalized_f32();
+    if U::BITS == Some(8) {
+        linear_to_srgb_u8(linear).convert()
+    } else {
+        U::from_normalized_f32(linear_to_srgb(linear))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::rgb::{Abgr8888, HasRed, Rgb565, Rgb888, Rgbaf32, Rgbf32};
+
+    #[test]
+    fn srgb_rgb888_round_trips_through_rgbf32() {
+        for value in 0..=u8::MAX {
+            let srgb = Srgb::new(Rgb888::from_rgb(value, value / 2, 255 - value));
+            let linear = srgb.into_linear_as::<Rgbf32>();
+            assert_eq!(linear.into_srgb_as::<Rgb888>(), srgb);
+        }
+    }
+
+    #[test]
+    fn srgb_rgbaf32_alpha_is_not_encoded() {
+        let srgb = Srgb::new(Rgbaf32::from_rgba(0.5, 0.5, 0.5, 0.5));
+        let linear = srgb.into_linear();
+        assert!((linear.color().red() - 0.214_041_14).abs() < 1e-6);
+        assert_eq!(linear.color().alpha(), 0.5);
+    }
+
+    #[test]
+    fn srgb_abgr8888_alpha_is_not_encoded() {
+        let srgb = Srgb::new(Abgr8888::from_abgr(64, 128, 128, 128));
+        let linear = srgb.into_linear_as::<Rgbaf32>();
+        assert_eq!(linear.color().alpha(), 64.0 / 255.0);
+        assert_eq!(linear.into_srgb_as::<Abgr8888>(), srgb);
+    }
+
+    #[test]
+    fn srgb_rgb565_uses_transfer_function() {
+        let srgb = Srgb::new(Rgb565::from_rgb(16, 32, 0));
+        let linear = srgb.into_linear_as::<Rgbf32>().color();
+        assert!((linear.red() - srgb_to_linear(16.0 / 31.0)).abs() < 1e-6);
+        assert_eq!(linear.into_rgb().2, 0.0);
+        assert_eq!(
+            LinearRgb::new(linear).into_srgb_as::<Rgb565>(),
+            Srgb::new(Rgb565::from_rgb(16, 32, 0))
+        );
+    }
+
+    #[test]
+    fn linear_into_srgb_same_representation() {
+        let linear = LinearRgb::new(Rgb888::from_rgb(0, 55, 255));
+        assert_eq!(linear.into_srgb().color(), Rgb888::from_rgb(0, 128, 255));
+    }
+}
@@ -32,6 +32,18 @@
 //! [`GrayAlpha16`]  | 32             | 16-bit grayscale color with alpha
 //! [`GrayAlphaF32`] | 64             | 32-bit floating-point grayscale color with alpha
 //!
+//! ## Formatting
+//!
+//! Like the types in [`crate::rgb`], every type implements [`Display`][core::fmt::Display] as CSS
+//! functional notation, and [`LowerHex`][core::fmt::LowerHex] as `#rrggbbaa`:
+//!
+//! ```rust
+//! use gem::gray::GrayAlpha8;
+//!
+//! let gray = GrayAlpha8::new(128, 255);
+//! assert_eq!(format!("{gray} {gray:x}"), "rgb(128 128 128) #808080ff");
+//! ```
+//!
 //! ## Generic Types
 //!
 //! Structs with generic types allow easily creating custom Grayscale types:
@@ -39,7 +51,7 @@
 //! - [`Gray<T>`]; a generic Grayscale color representation with a single component
 //! - [`GrayAlpha<T>`]; a generic Grayscale color representation with an alpha channel
 
-use crate::alpha::AlphaLast;
+use crate::{alpha::AlphaLast, component::Component, rgb::display::impl_display};
 
 mod has_gray;
 pub use has_gray::HasGray;
@@ -51,6 +63,11 @@ pub use has_gray::HasGray;
 /// The layout of this type is always the same as the underlying type `T` (`#[repr(transparent)]`).
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(transparent)]
 pub struct Gray<T> {
     gray: T,
@@ -75,7 +92,7 @@ impl<T> Gray<T> {
 
 impl<T> HasGray for Gray<T>
 where
-    T: Copy,
+    T: Component,
 {
     type Component = T;
 
@@ -117,7 +134,7 @@ pub type GrayAlpha<T> = AlphaLast<T, Gray<T>>;
 
 impl<T> HasGray for GrayAlpha<T>
 where
-    T: Copy,
+    T: Component,
 {
     type Component = T;
 
@@ -197,6 +214,22 @@ impl GrayAlphaF32 {
     }
 }
 
+/// Returns the components of a grayscale color as red, green, blue, and a fully opaque alpha.
+fn opaque<T: Component>(color: Gray<T>) -> (T, T, T, u8) {
+    (color.gray, color.gray, color.gray, u8::MAX)
+}
+
+/// Returns the components of a grayscale color with alpha as red, green, blue, and alpha.
+fn with_alpha<T: Component>(color: GrayAlpha<T>) -> (T, T, T, T) {
+    let gray = color.color().gray;
+    (gray, gray, gray, color.alpha())
+}
+
+impl_display! {
+    [T: Component] Gray<T> => opaque;
+    [T: Component] GrayAlpha<T> => with_alpha;
+}
+
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod tests {
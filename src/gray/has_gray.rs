@@ -1,7 +1,9 @@
+use crate::component::Component;
+
 /// A trait for types that have a ⚫ ⚪ gray component.
 pub trait HasGray {
     /// The type of the gray component.
-    type Component;
+    type Component: Component;
 
     /// Creates a new color with the given gray component.
     ///
@@ -0,0 +1,767 @@
+//! Reading and writing 🎨 ICC color profiles, such as those embedded in PNG, JPEG, and TIFF images.
+//!
+//! This module contains:
+//!
+//! - [`Profile`], a parsed ICC v2 or v4 profile of the matrix/TRC kind, for RGB or grayscale
+//!   colors, which is also created from an [`RgbSpace`] or a gray gamma and written as ICC v4
+//! - [`Curve`], a tone reproduction curve of a profile, from a gamma, table, or parametric curve
+//! - [`Transform`], which decodes colors in the space of a profile into CIE XYZ or linear sRGB
+//!
+//! Only profiles that describe a color space with a tone reproduction curve per component and a
+//! matrix of colorants are supported, which covers most display and working space profiles, such
+//! as sRGB, Display P3, and Adobe RGB (1998). Profiles that describe their color space with
+//! lookup tables, such as most printer profiles, return [`ParseError::LutBased`].
+//!
+//! This module requires the `alloc` feature.
+//!
+//! [`RgbSpace`]: crate::space::RgbSpace
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{
+//!     encoding::LinearRgb,
+//!     icc::{ParseError, Profile, Transform},
+//!     rgb::{HasRed, Rgb888, Rgbf32},
+//!     space::RgbSpace,
+//! };
+//!
+//! /// Decodes the pixels of an image with an embedded profile into linear sRGB.
+//! fn decode(icc: &[u8], pixels: &[Rgb888]) -> Result<Vec<LinearRgb<Rgbf32>>, ParseError> {
+//!     let transform = Transform::new(&Profile::parse(icc)?);
+//!     let mut linear = vec![LinearRgb::default(); pixels.len()];
+//!     transform.convert_rgb_slice(pixels, &mut linear);
+//!     Ok(linear)
+//! }
+//!
+//! // A profile to embed in images exported in Display P3.
+//! let icc = Profile::from_rgb_space(&RgbSpace::DISPLAY_P3)
+//!     .with_description("Display P3")
+//!     .to_bytes();
+//!
+//! // Pure Display P3 red is outside of the sRGB gamut.
+//! let red = decode(&icc, &[Rgb888::from_rgb(255, 0, 0)])?;
+//! assert!(red[0].into_inner().red() > 1.0);
+//!
+//! assert_eq!(decode(b"not a profile", &[]), Err(ParseError::Truncated));
+//! # Ok::<(), ParseError>(())
+//! ```
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::{
+    cie::{adaptation_matrix, chromaticity_to_xyz},
+    math::{Matrix3, mul_matrix},
+    space::RgbSpace,
+};
+
+mod curve;
+mod parse;
+mod transform;
+mod write;
+
+pub use curve::Curve;
+pub use transform::{Output, Transform};
+
+/// The white point of the profile connection space, D50 as ICC.1 rounds it.
+const PCS_WHITE: [f64; 3] = [0.9642, 1.0, 0.8249];
+
+/// The version of created and written profiles.
+const VERSION: (u8, u8) = (4, 3);
+
+/// The size of the profile header, in bytes.
+const HEADER_SIZE: usize = 128;
+
+/// A matrix/TRC color profile.
+///
+/// Colorants and the white point are in the profile connection space, CIE XYZ relative to D50,
+/// into which the white of the medium has been adapted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    version: (u8, u8),
+    model: Model,
+    white_point: [f64; 3],
+    adaptation: Option<Matrix3>,
+    description: String,
+}
+
+impl Profile {
+    /// Creates an RGB profile for a color space, with the curve of its transfer function.
+    ///
+    /// The colorants are adapted into D50 with the Bradford transform, which is recorded as the
+    /// `chad` tag, and the description is empty.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{icc::Profile, space::RgbSpace};
+    ///
+    /// let profile = Profile::from_rgb_space(&RgbSpace::SRGB).with_description("sRGB");
+    /// assert_eq!(profile.version(), (4, 3));
+    /// assert!(profile.to_bytes().len() < 600);
+    /// ```
+    #[must_use]
+    pub fn from_rgb_space(space: &RgbSpace) -> Self {
+        let adaptation = adaptation_matrix(chromaticity_to_xyz(space.white()), PCS_WHITE);
+        let curve = Curve::from(space.transfer());
+        Self {
+            version: VERSION,
+            model: Model::Rgb {
+                colorants: mul_matrix(&adaptation, &space.to_xyz_matrix()),
+                curves: [curve.clone(), curve.clone(), curve],
+            },
+            white_point: PCS_WHITE,
+            adaptation: Some(adaptation),
+            description: String::new(),
+        }
+    }
+
+    /// Creates a grayscale profile, where linear light is the component raised to `gamma`.
+    ///
+    /// Grays are relative to the D50 white of the PCS, and the description is empty.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::icc::{Curve, Model, Profile};
+    ///
+    /// let profile = Profile::from_gray_gamma(2.2).with_description("Gray Gamma 2.2");
+    /// assert_eq!(profile.model(), &Model::Gray { curve: Curve::Gamma(2.2) });
+    /// ```
+    #[must_use]
+    pub fn from_gray_gamma(gamma: f64) -> Self {
+        Self {
+            version: VERSION,
+            model: Model::Gray {
+                curve: Curve::Gamma(gamma),
+            },
+            white_point: PCS_WHITE,
+            adaptation: None,
+            description: String::new(),
+        }
+    }
+
+    /// Parses a profile from its bytes, such as an `iCCP` chunk of a PNG image once decompressed.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `data` is not an ICC v2 or v4 profile, or does not describe RGB or
+    /// grayscale colors with tone reproduction curves and colorants.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        parse::parse(data)
+    }
+
+    /// Serializes the profile as an ICC v4.3 display profile, such as for an `iCCP` chunk.
+    ///
+    /// Fixed-point numbers are rounded to 16 fractional bits, and identical curves share their
+    /// data. A v2 profile without a `chad` tag is written with the adaptation of its media white
+    /// point into D50, which v4 requires.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::icc::Profile;
+    ///
+    /// let profile = Profile::from_gray_gamma(2.2);
+    /// let parsed = Profile::parse(&profile.to_bytes()).unwrap();
+    /// assert_eq!(parsed.version(), (4, 3));
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> alloc::vec::Vec<u8> {
+        write::write(self)
+    }
+
+    /// Returns the major and minor version of the profile, such as `(4, 3)` for ICC v4.3.
+    #[must_use]
+    pub const fn version(&self) -> (u8, u8) {
+        self.version
+    }
+
+    /// Returns the curves and colorants of the profile.
+    #[must_use]
+    pub const fn model(&self) -> &Model {
+        &self.model
+    }
+
+    /// Returns the media white point (`wtpt`), in XYZ.
+    ///
+    /// This is D50 in v4 profiles, and usually the white of the device in v2 profiles.
+    #[must_use]
+    pub const fn white_point(&self) -> [f64; 3] {
+        self.white_point
+    }
+
+    /// Returns the chromatic adaptation (`chad`) from the white of the medium into D50, if any.
+    ///
+    /// The matrix is in row-major order, and is required in v4 profiles whose medium is not D50.
+    #[must_use]
+    pub const fn adaptation(&self) -> Option<[[f64; 3]; 3]> {
+        self.adaptation
+    }
+
+    /// Returns the description (`desc`), such as `"sRGB IEC61966-2.1"`.
+    ///
+    /// This is empty if the profile has no readable description.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns a copy of this profile with a different description.
+    #[must_use]
+    pub fn with_description(self, description: &str) -> Self {
+        Self {
+            description: description.to_string(),
+            ..self
+        }
+    }
+}
+
+/// The color space of a [`Profile`], and how components are decoded into XYZ.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Model {
+    /// An RGB profile, with a curve and a colorant per component.
+    Rgb {
+        /// The XYZ of the red, green, and blue colorants, as the columns of a matrix.
+        colorants: [[f64; 3]; 3],
+
+        /// The red, green, and blue tone reproduction curves.
+        curves: [Curve; 3],
+    },
+
+    /// A grayscale profile, with achromatic colors that scale the white of the PCS.
+    Gray {
+        /// The gray tone reproduction curve.
+        curve: Curve,
+    },
+}
+
+/// An error that occurs when parsing a [`Profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParseError {
+    /// The data is shorter than the profile header, tag table, or declared size.
+    Truncated,
+
+    /// The header does not contain the `acsp` signature.
+    InvalidSignature,
+
+    /// The major version is not 2 or 4.
+    UnsupportedVersion(u8),
+
+    /// The profile is a device link, abstract, or named color profile.
+    UnsupportedClass([u8; 4]),
+
+    /// The color space of the profile is not RGB or grayscale.
+    UnsupportedColorSpace([u8; 4]),
+
+    /// The profile connection space is not XYZ.
+    UnsupportedPcs([u8; 4]),
+
+    /// The profile describes its color space with lookup tables, such as `A2B0`, instead of
+    /// curves and colorants.
+    LutBased,
+
+    /// A tag that matrix/TRC profiles require is missing.
+    MissingTag([u8; 4]),
+
+    /// A tag has an unexpected type, is out of bounds, or is malformed.
+    InvalidTag([u8; 4]),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => f.write_str("truncated profile"),
+            Self::InvalidSignature => f.write_str("missing profile signature"),
+            Self::UnsupportedVersion(major) => write!(f, "unsupported profile version {major}"),
+            Self::UnsupportedClass(class) => {
+                write!(f, "unsupported profile class `{}`", sig(class))
+            }
+            Self::UnsupportedColorSpace(space) => {
+                write!(f, "unsupported color space `{}`", sig(space))
+            }
+            Self::UnsupportedPcs(pcs) => write!(f, "unsupported connection space `{}`", sig(pcs)),
+            Self::LutBased => f.write_str("LUT-based profiles are not supported"),
+            Self::MissingTag(tag) => write!(f, "missing `{}` tag", sig(tag)),
+            Self::InvalidTag(tag) => write!(f, "invalid `{}` tag", sig(tag)),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Returns a signature as text, without the trailing spaces of shorter signatures.
+fn sig(sig: &[u8; 4]) -> &str {
+    core::str::from_utf8(sig).unwrap_or("????").trim_end()
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::{
+        cie::{D50, D65, WhitePoint, Xyz, rgb_to_xyz_matrix},
+        encoding::LinearRgb,
+        gray::{Gray8, GrayF32},
+        rgb::{Rgb888, RgbColor, Rgbf32},
+    };
+    use alloc::{vec, vec::Vec};
+
+    /// Builds a display profile from its tags.
+    #[allow(clippy::cast_possible_truncation)]
+    fn build(version: u8, space: [u8; 4], tags: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![0; 128];
+        data[8] = version;
+        data[12..16].copy_from_slice(b"mntr");
+        data[16..20].copy_from_slice(&space);
+        data[20..24].copy_from_slice(b"XYZ ");
+        data[36..40].copy_from_slice(b"acsp");
+        data.extend((tags.len() as u32).to_be_bytes());
+
+        let start = data.len() + tags.len() * 12;
+        let mut body = Vec::new();
+        for (sig, bytes) in tags {
+            data.extend(*sig);
+            data.extend(((start + body.len()) as u32).to_be_bytes());
+            data.extend((bytes.len() as u32).to_be_bytes());
+            body.extend(bytes);
+            body.resize(body.len().next_multiple_of(4), 0);
+        }
+        data.extend(body);
+        let size = data.len() as u32;
+        data[..4].copy_from_slice(&size.to_be_bytes());
+        data
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn fixed(value: f64) -> [u8; 4] {
+        ((value * 65536.0).round() as i32).to_be_bytes()
+    }
+
+    fn tag(ty: [u8; 4], values: &[f64]) -> Vec<u8> {
+        let mut bytes = [ty, [0; 4]].concat();
+        bytes.extend(values.iter().flat_map(|&value| fixed(value)));
+        bytes
+    }
+
+    fn curv(entries: &[u16]) -> Vec<u8> {
+        let mut bytes = [*b"curv", [0; 4]].concat();
+        bytes.extend(u32::try_from(entries.len()).unwrap().to_be_bytes());
+        bytes.extend(entries.iter().flat_map(|entry| entry.to_be_bytes()));
+        bytes
+    }
+
+    fn srgb_trc() -> Vec<u8> {
+        let mut bytes = [*b"para", [0; 4], [0, 3, 0, 0]].concat();
+        let params = [2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045];
+        bytes.extend(params.iter().flat_map(|&value| fixed(value)));
+        bytes
+    }
+
+    /// Builds an sRGB profile, with a `chad` tag if `version` is 4.
+    fn srgb(version: u8) -> Vec<u8> {
+        let primaries = [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]];
+        let chad = adaptation_matrix(D65::XYZ, PCS_WHITE);
+        let colorants = mul_matrix(&chad, &rgb_to_xyz_matrix(primaries, D65::XYZ));
+        let column = |index: usize| colorants.map(|row| row[index]);
+        let mut tags = vec![
+            (b"rXYZ", tag(*b"XYZ ", &column(0))),
+            (b"gXYZ", tag(*b"XYZ ", &column(1))),
+            (b"bXYZ", tag(*b"XYZ ", &column(2))),
+            (b"rTRC", srgb_trc()),
+            (b"gTRC", srgb_trc()),
+            (b"bTRC", srgb_trc()),
+        ];
+        if version == 4 {
+            tags.push((b"wtpt", tag(*b"XYZ ", &PCS_WHITE)));
+            tags.push((b"chad", tag(*b"sf32", chad.as_flattened())));
+        } else {
+            tags.push((b"wtpt", tag(*b"XYZ ", &D65::XYZ)));
+        }
+        build(version, *b"RGB ", &tags)
+    }
+
+    fn assert_close(actual: [f32; 3], expected: [f64; 3], tolerance: f64) {
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            let difference = (f64::from(actual) - expected).abs();
+            assert!(difference < tolerance, "{actual} is not {expected}");
+        }
+    }
+
+    fn linear(color: LinearRgb<Rgbf32>) -> [f32; 3] {
+        let (r, g, b) = color.into_inner().into_rgb();
+        [r, g, b]
+    }
+
+    fn xyz<W: WhitePoint>(color: &Xyz<f32, W>) -> [f32; 3] {
+        [color.x(), color.y(), color.z()]
+    }
+
+    #[test]
+    fn parses_srgb_v4() {
+        let profile = Profile::parse(&srgb(4)).unwrap();
+        assert_eq!(profile.version(), (4, 0));
+        assert!(profile.adaptation().is_some());
+        assert!((profile.white_point()[2] - 0.8249).abs() < 1e-4);
+        let Model::Rgb { curves, .. } = profile.model() else {
+            panic!("expected an RGB profile");
+        };
+        assert!(matches!(curves[0], Curve::Parametric(_)));
+        assert!((curves[1].eval(0.5) - 0.214_041).abs() < 1e-4);
+    }
+
+    #[test]
+    fn srgb_into_linear_srgb() {
+        for version in [2, 4] {
+            let transform = Transform::new(&Profile::parse(&srgb(version)).unwrap());
+            let cases = [
+                (Rgb888::from_rgb(255, 0, 0), [1.0, 0.0, 0.0]),
+                (Rgb888::from_rgb(0, 255, 0), [0.0, 1.0, 0.0]),
+                (Rgb888::from_rgb(255, 255, 255), [1.0; 3]),
+                (Rgb888::from_rgb(128, 128, 128), [0.215_861; 3]),
+            ];
+            for (rgb, expected) in cases {
+                let actual: LinearRgb<Rgbf32> = transform.convert_rgb(rgb);
+                assert_close(linear(actual), expected, 5e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn srgb_white_into_xyz() {
+        let profile = Profile::parse(&srgb(4)).unwrap();
+        let white = Rgbf32::from_rgb(1.0, 1.0, 1.0);
+        let d65 = Transform::<Xyz<f32, D65>>::new(&profile).convert_rgb(white);
+        assert_close(xyz(&d65), D65::XYZ, 5e-4);
+        let d50 = Transform::<Xyz<f32, D50>>::new(&profile).convert_rgb(white);
+        assert_close(xyz(&d50), D50::XYZ, 5e-4);
+    }
+
+    #[test]
+    fn rgb_profile_converts_gray() {
+        let transform = Transform::new(&Profile::parse(&srgb(4)).unwrap());
+        let gray: LinearRgb<Rgbf32> = transform.convert_gray(Gray8::new(128));
+        assert_close(linear(gray), [0.215_861; 3], 5e-4);
+    }
+
+    #[test]
+    fn gray_gamma_profile() {
+        let data = build(
+            2,
+            *b"GRAY",
+            &[
+                (b"kTRC", curv(&[563])),
+                (b"wtpt", tag(*b"XYZ ", &PCS_WHITE)),
+            ],
+        );
+        let profile = Profile::parse(&data).unwrap();
+        assert_eq!(
+            profile.model(),
+            &Model::Gray {
+                curve: Curve::Gamma(563.0 / 256.0)
+            }
+        );
+
+        let transform = Transform::<Xyz<f32, D50>>::new(&profile);
+        let src = [0.0, 0.5, 1.0].map(GrayF32::new);
+        let mut dst = [Xyz::default(); 3];
+        transform.convert_gray_slice(&src, &mut dst);
+        let expected = crate::math::pow(0.5, 563.0 / 256.0);
+        assert_close(xyz(&dst[0]), [0.0; 3], 1e-6);
+        assert_close(xyz(&dst[1]), D50::XYZ.map(|c| c * expected), 5e-4);
+        assert_close(xyz(&dst[2]), D50::XYZ, 5e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "profile is not an RGB profile")]
+    fn gray_profile_rejects_rgb() {
+        let data = build(
+            4,
+            *b"GRAY",
+            &[(b"kTRC", curv(&[])), (b"wtpt", tag(*b"XYZ ", &PCS_WHITE))],
+        );
+        let transform = Transform::<Xyz<f32, D50>>::new(&Profile::parse(&data).unwrap());
+        let _ = transform.convert_rgb(Rgb888::from_rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn table_curves() {
+        let linear = build(
+            2,
+            *b"GRAY",
+            &[
+                (b"kTRC", curv(&[0, 65535])),
+                (b"wtpt", tag(*b"XYZ ", &PCS_WHITE)),
+            ],
+        );
+        let profile = Profile::parse(&linear).unwrap();
+        assert_eq!(
+            profile.model(),
+            &Model::Gray {
+                curve: Curve::Table(vec![0, 65535])
+            }
+        );
+    }
+
+    fn assert_matrix_close(actual: Matrix3, expected: Matrix3) {
+        for (actual, expected) in actual.as_flattened().iter().zip(expected.as_flattened()) {
+            assert!(
+                (actual - expected).abs() < 1e-4,
+                "{actual} is not {expected}"
+            );
+        }
+    }
+
+    /// Returns the offset of the data of each tag of a profile.
+    fn tag_offsets(data: &[u8]) -> Vec<([u8; 4], u32)> {
+        let count = u32::from_be_bytes(data[128..132].try_into().unwrap()) as usize;
+        (0..count)
+            .map(|index| {
+                let entry = &data[132 + index * 12..];
+                let offset = u32::from_be_bytes(entry[4..8].try_into().unwrap());
+                (entry[..4].try_into().unwrap(), offset)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn writes_rgb_spaces() {
+        let spaces = [
+            ("sRGB", RgbSpace::SRGB),
+            ("Display P3", RgbSpace::DISPLAY_P3),
+            ("Rec. 2020", RgbSpace::REC2020),
+            ("Adobe RGB (1998)", RgbSpace::ADOBE_RGB),
+            ("ProPhoto RGB", RgbSpace::PROPHOTO_RGB),
+            ("ACEScg", RgbSpace::ACESCG),
+        ];
+        for (name, space) in spaces {
+            let profile = Profile::from_rgb_space(&space).with_description(name);
+            let data = profile.to_bytes();
+            let parsed = Profile::parse(&data).unwrap();
+            assert_eq!(parsed.version(), (4, 3));
+            assert_eq!(parsed.description(), name);
+            assert_matrix_close(parsed.adaptation().unwrap(), profile.adaptation().unwrap());
+
+            let (
+                Model::Rgb { colorants, curves },
+                Model::Rgb {
+                    colorants: expected,
+                    ..
+                },
+            ) = (parsed.model(), profile.model())
+            else {
+                panic!("expected RGB profiles");
+            };
+            assert_matrix_close(*colorants, *expected);
+            let transfer = Curve::from(space.transfer());
+            for value in [0.0, 0.01, 0.2, 0.5, 1.0] {
+                assert!(
+                    (curves[1].eval(value) - transfer.eval(value)).abs() < 1e-4,
+                    "{name}"
+                );
+            }
+
+            // Writing a parsed profile again is lossless.
+            assert_eq!(parsed.to_bytes(), data, "{name}");
+
+            let color = Rgbf32::from_rgb(0.25, 0.5, 0.75);
+            let actual = Transform::<Xyz<f32, D65>>::new(&parsed).convert_rgb(color);
+            let expected: Xyz<f32, D65> = space.to_xyz(color);
+            assert_close(xyz(&actual), expected.into_f64(), 5e-4);
+        }
+    }
+
+    #[test]
+    fn writes_gray_gamma() {
+        let profile = Profile::from_gray_gamma(2.2).with_description("Gray Gamma 2.2");
+        let parsed = Profile::parse(&profile.to_bytes()).unwrap();
+        assert_eq!(parsed.description(), "Gray Gamma 2.2");
+        assert_eq!(parsed.adaptation(), None);
+        let Model::Gray {
+            curve: Curve::Gamma(gamma),
+        } = parsed.model()
+        else {
+            panic!("expected a gray gamma profile");
+        };
+        assert!((gamma - 2.2).abs() < 1e-4);
+
+        // Gray is relative to the white of the output.
+        let transform = Transform::new(&parsed);
+        let gray: LinearRgb<Rgbf32> = transform.convert_gray(Gray8::new(128));
+        let expected = crate::math::pow(128.0 / 255.0, 2.2);
+        assert_close(linear(gray), [expected; 3], 5e-4);
+    }
+
+    #[test]
+    fn writes_every_curve() {
+        let profile = Profile {
+            version: VERSION,
+            model: Model::Rgb {
+                colorants: [[0.5, 0.25, 0.125], [0.25, 0.5, 0.25], [0.0, 0.125, 0.75]],
+                curves: [
+                    Curve::Linear,
+                    Curve::Table(vec![0, 1000, 65535]),
+                    Curve::Parametric([2.5, 0.5, 0.25, 0.125, 0.0625, 0.031_25, 0.015_625]),
+                ],
+            },
+            white_point: PCS_WHITE,
+            adaptation: None,
+            description: "Curves ✓".to_string(),
+        };
+        let parsed = Profile::parse(&profile.to_bytes()).unwrap();
+        assert_eq!(parsed.model(), profile.model());
+        assert_eq!(parsed.description(), profile.description());
+    }
+
+    #[test]
+    fn writes_compact_profiles() {
+        let data = Profile::from_rgb_space(&RgbSpace::SRGB).to_bytes();
+        assert_eq!(data.len() % 4, 0);
+        assert!(data.len() < 600, "{}", data.len());
+        // The creation date is 2025-01-01 00:00:00.
+        assert_eq!(data[24..36], [0x07, 0xE9, 0, 1, 0, 1, 0, 0, 0, 0, 0, 0]);
+
+        let offsets = tag_offsets(&data);
+        let offset = |sig: &[u8; 4]| offsets.iter().find(|(tag, _)| tag == sig).unwrap().1;
+        assert_eq!(offset(b"rTRC"), offset(b"gTRC"));
+        assert_eq!(offset(b"rTRC"), offset(b"bTRC"));
+        assert_ne!(offset(b"rXYZ"), offset(b"gXYZ"));
+    }
+
+    #[test]
+    fn rewrites_v2_as_v4() {
+        let original = Profile::parse(&srgb(2)).unwrap();
+        assert_eq!(original.adaptation(), None);
+        let rewritten = Profile::parse(&original.to_bytes()).unwrap();
+        assert_eq!(rewritten.version(), (4, 3));
+        assert!(rewritten.adaptation().is_some());
+        let white = rewritten.white_point().into_iter().zip(PCS_WHITE);
+        assert!(
+            white
+                .into_iter()
+                .all(|(actual, expected)| (actual - expected).abs() < 1e-4)
+        );
+
+        let (original, rewritten) = (Transform::new(&original), Transform::new(&rewritten));
+        for rgb in [
+            Rgb888::from_rgb(255, 0, 0),
+            Rgb888::from_rgb(32, 128, 224),
+            Rgb888::from_rgb(255, 255, 255),
+        ] {
+            let expected: LinearRgb<Rgbf32> = original.convert_rgb(rgb);
+            let actual: LinearRgb<Rgbf32> = rewritten.convert_rgb(rgb);
+            assert_close(linear(actual), linear(expected).map(f64::from), 1e-4);
+        }
+    }
+
+    #[test]
+    fn parses_v2_descriptions() {
+        let mut desc = [*b"desc", [0; 4], 5_u32.to_be_bytes(), *b"sRGB", [0; 4]].concat();
+        desc.extend([0; 79]);
+        let data = build(
+            2,
+            *b"GRAY",
+            &[
+                (b"desc", desc),
+                (b"kTRC", curv(&[])),
+                (b"wtpt", tag(*b"XYZ ", &PCS_WHITE)),
+            ],
+        );
+        assert_eq!(Profile::parse(&data).unwrap().description(), "sRGB");
+    }
+
+    #[test]
+    fn lut_based() {
+        let data = build(
+            4,
+            *b"RGB ",
+            &[
+                (b"A2B0", vec![b'm', b'A', b'B', b' ', 0, 0, 0, 0]),
+                (b"wtpt", tag(*b"XYZ ", &PCS_WHITE)),
+            ],
+        );
+        assert_eq!(Profile::parse(&data), Err(ParseError::LutBased));
+
+        // A CMYK printer profile, with a Lab PCS.
+        let lut = || vec![b'm', b'f', b't', b'2', 0, 0, 0, 0];
+        let mut printer = build(
+            2,
+            *b"CMYK",
+            &[(b"A2B1", lut()), (b"wtpt", tag(*b"XYZ ", &PCS_WHITE))],
+        );
+        printer[12..16].copy_from_slice(b"prtr");
+        printer[20..24].copy_from_slice(b"Lab ");
+        assert_eq!(Profile::parse(&printer), Err(ParseError::LutBased));
+
+        // An RGB profile with a Lab PCS.
+        let mut lab = build(
+            4,
+            *b"RGB ",
+            &[(b"A2B0", lut()), (b"wtpt", tag(*b"XYZ ", &PCS_WHITE))],
+        );
+        lab[20..24].copy_from_slice(b"Lab ");
+        assert_eq!(Profile::parse(&lab), Err(ParseError::LutBased));
+
+        assert_eq!(
+            ParseError::LutBased.to_string(),
+            "LUT-based profiles are not supported"
+        );
+    }
+
+    #[test]
+    fn singular_adaptation() {
+        let data = build(
+            4,
+            *b"GRAY",
+            &[
+                (b"kTRC", curv(&[])),
+                (b"wtpt", tag(*b"XYZ ", &PCS_WHITE)),
+                (b"chad", tag(*b"sf32", &[0.0; 9])),
+            ],
+        );
+        assert_eq!(Profile::parse(&data), Err(ParseError::InvalidTag(*b"chad")));
+    }
+
+    #[test]
+    fn invalid_profiles() {
+        let wtpt = || (b"wtpt", tag(*b"XYZ ", &PCS_WHITE));
+        assert_eq!(Profile::parse(&[]), Err(ParseError::Truncated));
+        assert_eq!(Profile::parse(&[0; 132]), Err(ParseError::Truncated));
+
+        let mut unsigned = srgb(4);
+        unsigned[36..40].fill(0);
+        assert_eq!(Profile::parse(&unsigned), Err(ParseError::InvalidSignature));
+
+        let mut truncated = srgb(2);
+        truncated.pop();
+        assert_eq!(Profile::parse(&truncated), Err(ParseError::Truncated));
+        assert_eq!(
+            Profile::parse(&build(5, *b"RGB ", &[])),
+            Err(ParseError::UnsupportedVersion(5))
+        );
+        assert_eq!(
+            Profile::parse(&build(4, *b"CMYK", &[wtpt()])),
+            Err(ParseError::UnsupportedColorSpace(*b"CMYK"))
+        );
+        assert_eq!(
+            Profile::parse(&build(4, *b"RGB ", &[wtpt()])),
+            Err(ParseError::MissingTag(*b"rXYZ"))
+        );
+        assert_eq!(
+            Profile::parse(&build(4, *b"GRAY", &[(b"kTRC", tag(*b"XYZ ", &[1.0]))])),
+            Err(ParseError::InvalidTag(*b"kTRC"))
+        );
+
+        let mut lab = srgb(4);
+        lab[20..24].copy_from_slice(b"Lab ");
+        assert_eq!(
+            Profile::parse(&lab),
+            Err(ParseError::UnsupportedPcs(*b"Lab "))
+        );
+        assert_eq!(
+            ParseError::UnsupportedPcs(*b"Lab ").to_string(),
+            "unsupported connection space `Lab`"
+        );
+    }
+}
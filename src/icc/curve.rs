@@ -0,0 +1,217 @@
+use alloc::vec::Vec;
+
+use crate::{
+    math::pow,
+    space::{PROPHOTO_END, REC709_ALPHA, REC709_BETA, TransferFunction},
+};
+
+/// A tone reproduction curve, which decodes a device component into linear light.
+///
+/// Curves are defined on `0.0..=1.0`; inputs and outputs outside of it are clamped, as ICC.1
+/// requires.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Curve {
+    /// The identity, stored as a `curv` tag without entries.
+    Linear,
+
+    /// A pure power function, where linear light is the component raised to the gamma.
+    Gamma(f64),
+
+    /// Equally spaced samples, interpolated linearly, where `65535` is `1.0`.
+    Table(Vec<u16>),
+
+    /// A parametric curve, as the parameters `[g, a, b, c, d, e, f]` of ICC function type 4:
+    /// `(a·x + b)^g + e` where `x >= d`, and `c·x + f` below.
+    ///
+    /// Function types 1 to 3 are special cases of this one, to which they are converted, while
+    /// type 0 is a [`Curve::Gamma`].
+    Parametric([f64; 7]),
+}
+
+impl Curve {
+    /// Returns the parameters of an ICC `para` curve of `function` type `0..=4`, or `None` if the
+    /// type is unknown or `params` has the wrong length.
+    #[allow(clippy::many_single_char_names)]
+    pub(super) fn parametric(function: u16, params: &[f64]) -> Option<Self> {
+        // Types 1 and 2 start their power segment where it crosses zero.
+        let start = |a: f64, b: f64| if a == 0.0 { 0.0 } else { -b / a };
+        let params = match (function, params) {
+            (0, &[g]) => return Some(Self::Gamma(g)),
+            (1, &[g, a, b]) => [g, a, b, 0.0, start(a, b), 0.0, 0.0],
+            (2, &[g, a, b, c]) => [g, a, b, 0.0, start(a, b), c, c],
+            (3, &[g, a, b, c, d]) => [g, a, b, c, d, 0.0, 0.0],
+            (4, &[g, a, b, c, d, e, f]) => [g, a, b, c, d, e, f],
+            _ => return None,
+        };
+        Some(Self::Parametric(params))
+    }
+
+    /// Decodes a component into linear light.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::icc::Curve;
+    ///
+    /// assert_eq!(Curve::Gamma(2.0).eval(0.5), 0.25);
+    /// assert_eq!(Curve::Table(vec![0, 65535]).eval(0.25), 0.25);
+    /// assert_eq!(Curve::Linear.eval(1.5), 1.0);
+    /// ```
+    #[must_use]
+    #[allow(
+        clippy::many_single_char_names,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn eval(&self, x: f64) -> f64 {
+        let x = x.clamp(0.0, 1.0);
+        let y = match self {
+            Self::Linear => x,
+            Self::Gamma(gamma) => pow(x, *gamma),
+            Self::Table(table) => match table.as_slice() {
+                [] => x,
+                [only] => f64::from(*only) / 65535.0,
+                _ => {
+                    let position = x * (table.len() - 1) as f64;
+                    let index = (position as usize).min(table.len() - 2);
+                    let t = position - index as f64;
+                    let (a, b) = (f64::from(table[index]), f64::from(table[index + 1]));
+                    (a + (b - a) * t) / 65535.0
+                }
+            },
+            Self::Parametric([g, a, b, c, d, e, f]) => {
+                if x >= *d {
+                    pow((a * x + b).max(0.0), *g) + e
+                } else {
+                    c * x + f
+                }
+            }
+        };
+        y.clamp(0.0, 1.0)
+    }
+}
+
+impl From<TransferFunction> for Curve {
+    /// Returns the curve that decodes components like `transfer`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{icc::Curve, space::TransferFunction};
+    ///
+    /// let curve = Curve::from(TransferFunction::Srgb);
+    /// assert!((curve.eval(0.5) - 0.214_041).abs() < 1e-6);
+    /// ```
+    fn from(transfer: TransferFunction) -> Self {
+        match transfer {
+            TransferFunction::Linear => Self::Linear,
+            TransferFunction::Gamma(gamma) => Self::Gamma(gamma),
+            TransferFunction::Srgb => Self::Parametric([
+                2.4,
+                1.0 / 1.055,
+                0.055 / 1.055,
+                1.0 / 12.92,
+                0.040_45,
+                0.0,
+                0.0,
+            ]),
+            TransferFunction::Rec709 => Self::Parametric([
+                1.0 / 0.45,
+                1.0 / REC709_ALPHA,
+                (REC709_ALPHA - 1.0) / REC709_ALPHA,
+                1.0 / 4.5,
+                4.5 * REC709_BETA,
+                0.0,
+                0.0,
+            ]),
+            TransferFunction::ProPhoto => {
+                Self::Parametric([1.8, 1.0, 0.0, 1.0 / 16.0, 16.0 * PROPHOTO_END, 0.0, 0.0])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// The sRGB transfer function, as ICC function type 3.
+    fn srgb() -> Curve {
+        Curve::parametric(3, &[2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045]).unwrap()
+    }
+
+    #[test]
+    fn srgb_reference_values() {
+        let curve = srgb();
+        assert_eq!(curve.eval(0.0), 0.0);
+        assert!((curve.eval(1.0) - 1.0).abs() < 1e-12);
+        assert!((curve.eval(0.5) - 0.214_041_1).abs() < 1e-7);
+        assert!((curve.eval(0.04) - 0.04 / 12.92).abs() < 1e-12);
+    }
+
+    #[test]
+    fn parametric_types() {
+        let x = 0.6;
+        assert_eq!(Curve::parametric(0, &[2.2]), Some(Curve::Gamma(2.2)));
+
+        // CIE 122-1996, which is zero below where the power segment crosses zero.
+        let cie = Curve::parametric(1, &[2.0, 2.0, -0.5]).unwrap();
+        assert_eq!(cie.eval(0.2), 0.0);
+        assert!((cie.eval(x) - 0.49).abs() < 1e-12);
+
+        // IEC 61966-3, which is offset by `c`.
+        let iec = Curve::parametric(2, &[2.0, 2.0, -0.5, 0.1]).unwrap();
+        assert_eq!(iec.eval(0.2), 0.1);
+        assert!((iec.eval(x) - 0.59).abs() < 1e-12);
+
+        let full = Curve::parametric(4, &[1.0, 0.5, 0.0, 0.25, 0.5, 0.1, 0.05]).unwrap();
+        assert!((full.eval(0.4) - 0.15).abs() < 1e-12);
+        assert!((full.eval(x) - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn from_transfer_function() {
+        let transfers = [
+            TransferFunction::Linear,
+            TransferFunction::Srgb,
+            TransferFunction::Gamma(2.2),
+            TransferFunction::Rec709,
+            TransferFunction::ProPhoto,
+        ];
+        for transfer in transfers {
+            let curve = Curve::from(transfer);
+            for value in [0.0, 0.01, 0.03, 0.2, 0.5, 0.8, 1.0] {
+                #[allow(clippy::cast_possible_truncation)]
+                let expected = transfer.decode(value as f32);
+                let difference = (curve.eval(value) - f64::from(expected)).abs();
+                assert!(difference < 1e-6, "{transfer:?} at {value}");
+            }
+        }
+    }
+
+    #[test]
+    fn parametric_invalid() {
+        assert_eq!(Curve::parametric(5, &[1.0]), None);
+        assert_eq!(Curve::parametric(3, &[1.0, 2.0]), None);
+    }
+
+    #[test]
+    fn table_interpolates() {
+        let curve = Curve::Table(vec![0, 16384, 65535]);
+        assert_eq!(curve.eval(0.0), 0.0);
+        assert_eq!(curve.eval(0.25), 8192.0 / 65535.0);
+        assert_eq!(curve.eval(1.0), 1.0);
+        assert_eq!(Curve::Table(vec![32768]).eval(0.0), 32768.0 / 65535.0);
+    }
+
+    #[test]
+    fn clamps() {
+        assert_eq!(Curve::Gamma(2.0).eval(-1.0), 0.0);
+        assert_eq!(Curve::Gamma(2.0).eval(2.0), 1.0);
+        let offset = Curve::parametric(2, &[1.0, 1.0, 0.0, 0.5]).unwrap();
+        assert_eq!(offset.eval(0.75), 1.0);
+    }
+}
@@ -0,0 +1,239 @@
+use alloc::string::String;
+
+use crate::{
+    icc::{Curve, HEADER_SIZE, Model, ParseError, Profile},
+    math::{Matrix3, determinant},
+};
+
+/// Parses a profile from its bytes.
+pub(super) fn parse(data: &[u8]) -> Result<Profile, ParseError> {
+    let size = read_u32(data, 0)? as usize;
+    let data = data.get(..size).ok_or(ParseError::Truncated)?;
+    if data.len() < HEADER_SIZE + 4 {
+        return Err(ParseError::Truncated);
+    }
+    if &data[36..40] != b"acsp" {
+        return Err(ParseError::InvalidSignature);
+    }
+    let version = (data[8], data[9] >> 4);
+    if !matches!(version.0, 2 | 4) {
+        return Err(ParseError::UnsupportedVersion(version.0));
+    }
+
+    // Check for lookup tables first, since they are what printer and Lab profiles use instead.
+    let tags = Tags::new(data)?;
+    let space = signature(data, 16);
+    let matrix_trc: &[[u8; 4]] = match &space {
+        b"RGB " => &[*b"rXYZ", *b"gXYZ", *b"bXYZ", *b"rTRC", *b"gTRC", *b"bTRC"],
+        b"GRAY" => &[*b"kTRC"],
+        _ => &[],
+    };
+    let has_matrix_trc = !matrix_trc.is_empty() && matrix_trc.iter().all(|&sig| tags.contains(sig));
+    let has_lut = [*b"A2B0", *b"A2B1", *b"A2B2"]
+        .into_iter()
+        .any(|sig| tags.contains(sig));
+    if has_lut && !has_matrix_trc {
+        return Err(ParseError::LutBased);
+    }
+
+    let class = signature(data, 12);
+    if matches!(&class, b"link" | b"abst" | b"nmcl") {
+        return Err(ParseError::UnsupportedClass(class));
+    }
+    let pcs = signature(data, 20);
+    if &pcs != b"XYZ " {
+        return Err(ParseError::UnsupportedPcs(pcs));
+    }
+
+    let model = match &space {
+        b"RGB " => {
+            let colorant = |sig: [u8; 4]| xyz(tags.required(sig)?, sig);
+            let trc = |sig: [u8; 4]| curve(tags.required(sig)?, sig);
+            let [r, g, b] = [
+                colorant(*b"rXYZ")?,
+                colorant(*b"gXYZ")?,
+                colorant(*b"bXYZ")?,
+            ];
+            Model::Rgb {
+                colorants: [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]],
+                curves: [trc(*b"rTRC")?, trc(*b"gTRC")?, trc(*b"bTRC")?],
+            }
+        }
+        b"GRAY" => Model::Gray {
+            curve: curve(tags.required(*b"kTRC")?, *b"kTRC")?,
+        },
+        &other => return Err(ParseError::UnsupportedColorSpace(other)),
+    };
+    let white_point = xyz(tags.required(*b"wtpt")?, *b"wtpt")?;
+    let adaptation = match tags.get(*b"chad")? {
+        // Transforms undo the adaptation, so it must be invertible.
+        Some(bytes) => match matrix(bytes, *b"chad")? {
+            chad if determinant(&chad).abs() > f64::EPSILON => Some(chad),
+            _ => return Err(ParseError::InvalidTag(*b"chad")),
+        },
+        None => None,
+    };
+    // The description is informational, so a malformed one is ignored.
+    let description = tags.get(*b"desc")?.and_then(text).unwrap_or_default();
+    Ok(Profile {
+        version,
+        model,
+        white_point,
+        adaptation,
+        description,
+    })
+}
+
+/// The tag table of a profile.
+struct Tags<'a> {
+    data: &'a [u8],
+    count: usize,
+}
+
+impl<'a> Tags<'a> {
+    fn new(data: &'a [u8]) -> Result<Self, ParseError> {
+        let count = read_u32(data, HEADER_SIZE)? as usize;
+        let end = count
+            .checked_mul(12)
+            .and_then(|size| size.checked_add(HEADER_SIZE + 4))
+            .ok_or(ParseError::Truncated)?;
+        if end > data.len() {
+            return Err(ParseError::Truncated);
+        }
+        Ok(Self { data, count })
+    }
+
+    /// Returns whether there is a tag with signature `sig`.
+    fn contains(&self, sig: [u8; 4]) -> bool {
+        (0..self.count).any(|index| signature(self.data, HEADER_SIZE + 4 + index * 12) == sig)
+    }
+
+    /// Returns the data of the tag with signature `sig`, if there is one.
+    fn get(&self, sig: [u8; 4]) -> Result<Option<&'a [u8]>, ParseError> {
+        for index in 0..self.count {
+            let entry = HEADER_SIZE + 4 + index * 12;
+            if signature(self.data, entry) != sig {
+                continue;
+            }
+            let [offset, size] = [entry + 4, entry + 8].map(|at| read_u32(self.data, at));
+            let (offset, size) = (offset? as usize, size? as usize);
+            let bytes = offset
+                .checked_add(size)
+                .and_then(|end| self.data.get(offset..end))
+                .ok_or(ParseError::InvalidTag(sig))?;
+            return Ok(Some(bytes));
+        }
+        Ok(None)
+    }
+
+    /// Returns the data of a tag that a matrix/TRC profile requires.
+    fn required(&self, sig: [u8; 4]) -> Result<&'a [u8], ParseError> {
+        self.get(sig)?.ok_or(ParseError::MissingTag(sig))
+    }
+}
+
+/// Parses an `XYZType` tag with a single value.
+fn xyz(bytes: &[u8], sig: [u8; 4]) -> Result<[f64; 3], ParseError> {
+    let values = typed(bytes, *b"XYZ ", sig)?;
+    let value = |index: usize| s15_fixed16(values, index * 4).ok_or(ParseError::InvalidTag(sig));
+    Ok([value(0)?, value(1)?, value(2)?])
+}
+
+/// Parses an `s15Fixed16ArrayType` tag of nine values, in row-major order.
+fn matrix(bytes: &[u8], sig: [u8; 4]) -> Result<Matrix3, ParseError> {
+    let values = typed(bytes, *b"sf32", sig)?;
+    let mut matrix = [[0.0; 3]; 3];
+    for (index, value) in matrix.as_flattened_mut().iter_mut().enumerate() {
+        *value = s15_fixed16(values, index * 4).ok_or(ParseError::InvalidTag(sig))?;
+    }
+    Ok(matrix)
+}
+
+/// Parses a `curveType` or `parametricCurveType` tag.
+fn curve(bytes: &[u8], sig: [u8; 4]) -> Result<Curve, ParseError> {
+    let invalid = ParseError::InvalidTag(sig);
+    if let Ok(values) = typed(bytes, *b"curv", sig) {
+        let count = read_u32(values, 0).map_err(|_| invalid)? as usize;
+        let entries = count
+            .checked_mul(2)
+            .and_then(|size| values.get(4..)?.get(..size))
+            .ok_or(invalid)?;
+        let mut entries = entries
+            .chunks_exact(2)
+            .map(|entry| u16::from_be_bytes([entry[0], entry[1]]));
+        return Ok(match count {
+            0 => Curve::Linear,
+            1 => Curve::Gamma(f64::from(entries.next().unwrap_or_default()) / 256.0),
+            _ => Curve::Table(entries.collect()),
+        });
+    }
+    let values = typed(bytes, *b"para", sig)?;
+    let function = values
+        .get(..2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or(invalid)?;
+    let count = match function {
+        0 => 1,
+        1 => 3,
+        2 => 4,
+        3 => 5,
+        4 => 7,
+        _ => return Err(invalid),
+    };
+    let mut params = [0.0; 7];
+    for (index, param) in params[..count].iter_mut().enumerate() {
+        *param = s15_fixed16(values, 4 + index * 4).ok_or(invalid)?;
+    }
+    Curve::parametric(function, &params[..count]).ok_or(invalid)
+}
+
+/// Parses the first record of a `multiLocalizedUnicodeType` tag (v4), or the ASCII text of a
+/// `textDescriptionType` tag (v2).
+fn text(bytes: &[u8]) -> Option<String> {
+    if let Ok(values) = typed(bytes, *b"mluc", *b"desc") {
+        if read_u32(values, 0).ok()? == 0 {
+            return Some(String::new());
+        }
+        let [size, offset] = [12, 16].map(|at| read_u32(values, at).map(|value| value as usize));
+        let (size, offset) = (size.ok()?, offset.ok()?);
+        let units = bytes.get(offset..offset.checked_add(size)?)?;
+        let units = units
+            .chunks_exact(2)
+            .map(|unit| u16::from_be_bytes([unit[0], unit[1]]));
+        return Some(
+            char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+        );
+    }
+    let values = typed(bytes, *b"desc", *b"desc").ok()?;
+    let count = read_u32(values, 0).ok()? as usize;
+    let ascii = values.get(4..4usize.checked_add(count)?)?;
+    let ascii = ascii.split(|&byte| byte == 0).next().unwrap_or_default();
+    Some(String::from_utf8_lossy(ascii).into())
+}
+
+/// Returns the data of a tag after its type signature and reserved bytes, if it has type `ty`.
+fn typed(bytes: &[u8], ty: [u8; 4], sig: [u8; 4]) -> Result<&[u8], ParseError> {
+    match bytes.get(..4) {
+        Some(actual) if actual == ty => bytes.get(8..).ok_or(ParseError::InvalidTag(sig)),
+        _ => Err(ParseError::InvalidTag(sig)),
+    }
+}
+
+fn signature(data: &[u8], at: usize) -> [u8; 4] {
+    [data[at], data[at + 1], data[at + 2], data[at + 3]]
+}
+
+fn read_u32(data: &[u8], at: usize) -> Result<u32, ParseError> {
+    data.get(at..at + 4)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or(ParseError::Truncated)
+}
+
+/// Reads a signed fixed-point number with 16 fractional bits.
+fn s15_fixed16(data: &[u8], at: usize) -> Option<f64> {
+    let bytes = data.get(at..at + 4)?;
+    let value = i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    Some(f64::from(value) / 65536.0)
+}
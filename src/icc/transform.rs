@@ -0,0 +1,179 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{
+    cie::{WhitePoint, Xyz, adaptation_matrix},
+    component::Component,
+    encoding::LinearRgb,
+    gray::Gray,
+    icc::{Curve, Model, PCS_WHITE, Profile},
+    math::{Matrix3, diagonal, invert, mul_matrix, mul_vector},
+    rgb::{RgbColor, Rgbf32},
+};
+
+/// Decodes colors in the space of a [`Profile`] into linear light, as an [`Output`] type.
+///
+/// Components are decoded by the tone reproduction curves of the profile, converted into the
+/// profile connection space by its colorants, and then adapted from the white of the medium into
+/// the white of the output with the Bradford transform, or the `chad` tag of the profile, if any.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{
+///     cie::{D50, Xyz},
+///     gray::Gray8,
+///     icc::{Profile, Transform},
+/// };
+///
+/// /// Returns the luminance of each pixel of a grayscale image with an embedded profile.
+/// fn luminance(icc: &[u8], pixels: &[Gray8]) -> Option<Vec<f32>> {
+///     let transform = Transform::<Xyz<f32, D50>>::new(&Profile::parse(icc).ok()?);
+///     Some(pixels.iter().map(|&pixel| transform.convert_gray(pixel).y()).collect())
+/// }
+///
+/// assert_eq!(luminance(b"not a profile", &[Gray8::new(0)]), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Transform<O> {
+    curves: Vec<Curve>,
+    matrix: Matrix3,
+    output: PhantomData<fn() -> O>,
+}
+
+impl<O: Output> Transform<O> {
+    /// Creates a transform from the space of `profile` into `O`.
+    #[must_use]
+    pub fn new(profile: &Profile) -> Self {
+        let (colorants, curves) = match profile.model() {
+            Model::Rgb { colorants, curves } => (*colorants, curves.to_vec()),
+            Model::Gray { curve } => (diagonal(PCS_WHITE), alloc::vec![curve.clone()]),
+        };
+
+        // Undo the adaptation of the profile into the PCS, then adapt into the output white.
+        let (white, from_pcs) = match profile.adaptation() {
+            Some(chad) => {
+                let inverse = invert(&chad);
+                (mul_vector(&inverse, PCS_WHITE), inverse)
+            }
+            None => (PCS_WHITE, diagonal([1.0; 3])),
+        };
+        let to_output = mul_matrix(&O::FROM_XYZ, &adaptation_matrix(white, O::WHITE));
+        Self {
+            curves,
+            matrix: mul_matrix(&to_output, &mul_matrix(&from_pcs, &colorants)),
+            output: PhantomData,
+        }
+    }
+
+    /// Converts an RGB color in the space of the profile.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the profile is for grayscale colors.
+    #[must_use]
+    pub fn convert_rgb<C: RgbColor>(&self, color: C) -> O {
+        let [r, g, b] = self.curves.as_slice() else {
+            panic!("profile is not an RGB profile");
+        };
+        let (red, green, blue) = color.into_rgb();
+        self.apply([
+            r.eval(f64::from(red.to_normalized_f32())),
+            g.eval(f64::from(green.to_normalized_f32())),
+            b.eval(f64::from(blue.to_normalized_f32())),
+        ])
+    }
+
+    /// Converts a grayscale color in the space of the profile.
+    ///
+    /// The color of an RGB profile is the one with equal red, green, and blue components.
+    #[must_use]
+    pub fn convert_gray<T: Component>(&self, color: Gray<T>) -> O {
+        let gray = f64::from(color.gray().to_normalized_f32());
+        match self.curves.as_slice() {
+            [r, g, b] => self.apply([r.eval(gray), g.eval(gray), b.eval(gray)]),
+            [curve] => self.apply([curve.eval(gray); 3]),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Converts a slice of RGB colors in the space of the profile.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the profile is for grayscale colors, or if `src` and `dst` have different
+    /// lengths.
+    pub fn convert_rgb_slice<C: RgbColor + Copy>(&self, src: &[C], dst: &mut [O]) {
+        assert_eq!(
+            src.len(),
+            dst.len(),
+            "source and destination lengths differ"
+        );
+        for (src, dst) in src.iter().zip(dst) {
+            *dst = self.convert_rgb(*src);
+        }
+    }
+
+    /// Converts a slice of grayscale colors in the space of the profile.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    pub fn convert_gray_slice<T: Component>(&self, src: &[Gray<T>], dst: &mut [O]) {
+        assert_eq!(
+            src.len(),
+            dst.len(),
+            "source and destination lengths differ"
+        );
+        for (src, dst) in src.iter().zip(dst) {
+            *dst = self.convert_gray(*src);
+        }
+    }
+
+    fn apply(&self, linear: [f64; 3]) -> O {
+        O::from_f64(mul_vector(&self.matrix, linear))
+    }
+}
+
+/// A linear-light color that a [`Transform`] converts into.
+///
+/// This trait is sealed, and implemented for CIE XYZ relative to any white point, and for linear
+/// sRGB.
+pub trait Output: sealed::Sealed {}
+
+impl<W: WhitePoint> Output for Xyz<f32, W> {}
+
+impl<W: WhitePoint> sealed::Sealed for Xyz<f32, W> {
+    const WHITE: [f64; 3] = W::XYZ;
+    const FROM_XYZ: Matrix3 = diagonal([1.0; 3]);
+
+    fn from_f64(values: [f64; 3]) -> Self {
+        Self::from_f64(values)
+    }
+}
+
+impl Output for LinearRgb<Rgbf32> {}
+
+impl sealed::Sealed for LinearRgb<Rgbf32> {
+    const WHITE: [f64; 3] = crate::cie::D65::XYZ;
+    const FROM_XYZ: Matrix3 = crate::cie::XYZ_TO_LINEAR_SRGB;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_f64([r, g, b]: [f64; 3]) -> Self {
+        Self::new(Rgbf32::from_rgb(r as f32, g as f32, b as f32))
+    }
+}
+
+mod sealed {
+    use crate::math::Matrix3;
+
+    pub trait Sealed: Sized {
+        /// The white point of the output, in XYZ.
+        const WHITE: [f64; 3];
+
+        /// The matrix from XYZ relative to [`Self::WHITE`] into the output.
+        const FROM_XYZ: Matrix3;
+
+        fn from_f64(values: [f64; 3]) -> Self;
+    }
+}
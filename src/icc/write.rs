@@ -0,0 +1,152 @@
+use alloc::vec::Vec;
+
+use crate::{
+    cie::adaptation_matrix,
+    icc::{Curve, HEADER_SIZE, Model, PCS_WHITE, Profile, VERSION},
+};
+
+/// The copyright notice of written profiles, which v4 requires.
+const COPYRIGHT: &str = "No copyright, use freely";
+
+/// The creation date and time of written profiles, as year, month, day, hours, minutes, and
+/// seconds, which is fixed so that the same profile is always written the same way.
+const CREATED: [u16; 6] = [2025, 1, 1, 0, 0, 0];
+
+/// Serializes a profile as an ICC v4 display profile, of version [`VERSION`].
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn write(profile: &Profile) -> Vec<u8> {
+    // A v2 profile keeps its media white point, from which v4 requires an adaptation.
+    let close = |a: [f64; 3], b: [f64; 3]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4);
+    let adaptation = profile.adaptation.or_else(|| {
+        (!close(profile.white_point, PCS_WHITE))
+            .then(|| adaptation_matrix(profile.white_point, PCS_WHITE))
+    });
+
+    let mut tags = Vec::new();
+    tags.push((*b"desc", text(&profile.description)));
+    tags.push((*b"cprt", text(COPYRIGHT)));
+    tags.push((*b"wtpt", xyz(PCS_WHITE)));
+    if let Some(chad) = adaptation {
+        tags.push((
+            *b"chad",
+            typed(
+                *b"sf32",
+                chad.as_flattened().iter().map(|&value| fixed(value)),
+            ),
+        ));
+    }
+    let space = match &profile.model {
+        Model::Rgb { colorants, curves } => {
+            for (index, sig) in [*b"rXYZ", *b"gXYZ", *b"bXYZ"].into_iter().enumerate() {
+                tags.push((sig, xyz(colorants.map(|row| row[index]))));
+            }
+            for (curve_data, sig) in curves.iter().zip([*b"rTRC", *b"gTRC", *b"bTRC"]) {
+                tags.push((sig, curve(curve_data)));
+            }
+            *b"RGB "
+        }
+        Model::Gray { curve: gray } => {
+            tags.push((*b"kTRC", curve(gray)));
+            *b"GRAY"
+        }
+    };
+
+    let mut data = Vec::with_capacity(512);
+    data.resize(HEADER_SIZE, 0);
+    // The minor version shares its byte with the bug fix level, one digit per nibble.
+    data[8..10].copy_from_slice(&[VERSION.0, VERSION.1 << 4]);
+    data[12..16].copy_from_slice(b"mntr");
+    data[16..20].copy_from_slice(&space);
+    data[20..24].copy_from_slice(b"XYZ ");
+    data[24..36].copy_from_slice(&CREATED.map(u16::to_be_bytes).concat());
+    data[36..40].copy_from_slice(b"acsp");
+    data[68..80].copy_from_slice(&PCS_WHITE.map(fixed).concat());
+    data.extend((tags.len() as u32).to_be_bytes());
+
+    // Identical tags, such as the curves of most RGB profiles, share their data.
+    let start = data.len() + tags.len() * 12;
+    let mut body: Vec<u8> = Vec::new();
+    let mut written: Vec<(usize, &[u8])> = Vec::new();
+    for (sig, bytes) in &tags {
+        let shared = written.iter().find(|(_, other)| *other == bytes.as_slice());
+        let offset = if let Some(&(offset, _)) = shared {
+            offset
+        } else {
+            let offset = start + body.len();
+            body.extend(bytes);
+            body.resize(body.len().next_multiple_of(4), 0);
+            written.push((offset, bytes));
+            offset
+        };
+        data.extend(sig);
+        data.extend((offset as u32).to_be_bytes());
+        data.extend((bytes.len() as u32).to_be_bytes());
+    }
+    data.extend(body);
+    let size = data.len() as u32;
+    data[..4].copy_from_slice(&size.to_be_bytes());
+    data
+}
+
+/// Returns a tag of type `ty`, with its reserved bytes and `values`.
+fn typed<I>(ty: [u8; 4], values: I) -> Vec<u8>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let mut bytes = [ty, [0; 4]].concat();
+    for value in values {
+        bytes.extend(value.as_ref());
+    }
+    bytes
+}
+
+/// Returns an `XYZType` tag with a single value.
+fn xyz(value: [f64; 3]) -> Vec<u8> {
+    typed(*b"XYZ ", value.map(fixed))
+}
+
+/// Returns a `multiLocalizedUnicodeType` tag with a single record, in English.
+#[allow(clippy::cast_possible_truncation)]
+fn text(text: &str) -> Vec<u8> {
+    let units: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let record = [
+        1_u32.to_be_bytes(),
+        12_u32.to_be_bytes(),
+        *b"enUS",
+        (units.len() as u32).to_be_bytes(),
+        28_u32.to_be_bytes(),
+    ];
+    let mut bytes = typed(*b"mluc", record);
+    bytes.extend(units);
+    bytes
+}
+
+/// Returns a `curveType` or `parametricCurveType` tag.
+#[allow(clippy::cast_possible_truncation)]
+fn curve(curve: &Curve) -> Vec<u8> {
+    let curv = |entries: &[u16]| {
+        let mut bytes = typed(*b"curv", [(entries.len() as u32).to_be_bytes()]);
+        bytes.extend(entries.iter().flat_map(|entry| entry.to_be_bytes()));
+        bytes
+    };
+    let para = |function: u16, params: &[f64]| {
+        let mut bytes = typed(*b"para", [function.to_be_bytes(), [0; 2]]);
+        bytes.extend(params.iter().flat_map(|&param| fixed(param)));
+        bytes
+    };
+    match curve {
+        Curve::Linear => curv(&[]),
+        Curve::Table(table) => curv(table),
+        Curve::Gamma(gamma) => para(0, &[*gamma]),
+        Curve::Parametric(params @ [.., e, f]) if *e == 0.0 && *f == 0.0 => para(3, &params[..5]),
+        Curve::Parametric(params) => para(4, params),
+    }
+}
+
+/// Returns a signed fixed-point number with 16 fractional bits.
+#[allow(clippy::cast_possible_truncation)]
+fn fixed(value: f64) -> [u8; 4] {
+    let scaled = value * 65536.0;
+    ((scaled + 0.5_f64.copysign(scaled)) as i32).to_be_bytes()
+}
@@ -0,0 +1,280 @@
+//! Two-dimensional 🖼️ images of pixels, borrowed from slices or owned.
+//!
+//! This module contains:
+//!
+//! - [`ImageView<P>`] and [`ImageViewMut<P>`], which borrow rows of pixels from a slice
+//! - [`ImageBuf<P>`], which owns its pixels (requires the `alloc` feature)
+//!
+//! Pixels can be of any type, such as [`Abgr8888`] or [`Rgbaf32`]. Rows are stored one after
+//! another, each starting `stride` pixels after the previous one; the stride may be larger than
+//! the width to allow padding at the end of each row, or to view a sub-rectangle of a larger
+//! image.
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{image::ImageViewMut, rgb::Abgr8888};
+//!
+//! let mut pixels = [Abgr8888::default(); 4 * 3];
+//! let mut image = ImageViewMut::new(&mut pixels, 4, 3).unwrap();
+//!
+//! // Fill a 2×2 square in the middle of the image.
+//! let mut square = image.view_mut(1, 1, 2, 2).unwrap();
+//! square.fill(Abgr8888::from_abgr(255, 0, 0, 255));
+//!
+//! assert_eq!(image.get(1, 1), Some(&Abgr8888::from_abgr(255, 0, 0, 255)));
+//! assert_eq!(image.get(0, 0), Some(&Abgr8888::default()));
+//! assert_eq!(image.get(4, 0), None);
+//! ```
+//!
+//! ## Raw Bytes
+//!
+//! With the `bytemuck` feature, views can be created from raw bytes, such as a mapped GPU buffer
+//! or a decoded image, with a row stride in bytes:
+//!
+//! ```rust
+//! # #[cfg(feature = "bytemuck")]
+//! # {
+//! use gem::{image::ImageView, rgb::Abgr8888};
+//!
+//! // 2×2 pixels, with 4 bytes of padding at the end of each row.
+//! let bytes = [0xFF_u32; 6];
+//! let bytes: &[u8] = bytemuck::cast_slice(&bytes);
+//! let image = ImageView::<Abgr8888>::from_bytes(bytes, 2, 2, 12).unwrap();
+//! assert_eq!(image.stride(), 3);
+//! # }
+//! ```
+//!
+//! [`Abgr8888`]: crate::rgb::Abgr8888
+//! [`Rgbaf32`]: crate::rgb::Rgbaf32
+
+use core::{fmt, mem::size_of};
+
+#[cfg(feature = "alloc")]
+mod buf;
+mod rows;
+mod view;
+mod view_mut;
+
+#[cfg(feature = "alloc")]
+pub use buf::ImageBuf;
+pub use rows::{Rows, RowsMut};
+pub use view::ImageView;
+pub use view_mut::ImageViewMut;
+
+/// An error that occurs when creating an image from a buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageError {
+    /// The stride, in pixels, is smaller than the width.
+    StrideTooSmall {
+        /// The width of the image, in pixels.
+        width: usize,
+
+        /// The stride of the image, in pixels.
+        stride: usize,
+    },
+
+    /// The stride, in bytes, is not a multiple of the size of a pixel.
+    UnalignedStride {
+        /// The stride of the image, in bytes.
+        byte_stride: usize,
+
+        /// The size of a pixel, in bytes.
+        pixel_size: usize,
+    },
+
+    /// The buffer does not contain enough pixels for the width, height, and stride.
+    BufferTooSmall {
+        /// The number of pixels required.
+        required: usize,
+
+        /// The number of pixels in the buffer.
+        actual: usize,
+    },
+
+    /// The number of pixels required overflows [`usize`].
+    TooLarge,
+
+    /// The bytes are not aligned for the pixel type.
+    Misaligned,
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StrideTooSmall { width, stride } => {
+                write!(
+                    f,
+                    "stride of {stride} pixels is smaller than width of {width}"
+                )
+            }
+            Self::UnalignedStride {
+                byte_stride,
+                pixel_size,
+            } => write!(
+                f,
+                "stride of {byte_stride} bytes is not a multiple of the pixel size ({pixel_size})"
+            ),
+            Self::BufferTooSmall { required, actual } => {
+                write!(f, "buffer has {actual} pixels, but {required} are required")
+            }
+            Self::TooLarge => f.write_str("image dimensions overflow"),
+            Self::Misaligned => f.write_str("bytes are not aligned for the pixel type"),
+        }
+    }
+}
+
+impl core::error::Error for ImageError {}
+
+/// The dimensions of an image, and the distance between the start of each row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Layout {
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl Layout {
+    /// Creates a layout, checking that a buffer of `len` pixels is large enough.
+    fn new(len: usize, width: usize, height: usize, stride: usize) -> Result<Self, ImageError> {
+        if stride < width {
+            return Err(ImageError::StrideTooSmall { width, stride });
+        }
+        let layout = Self {
+            width,
+            height,
+            stride,
+        };
+        let required = layout.required_len().ok_or(ImageError::TooLarge)?;
+        if len < required {
+            return Err(ImageError::BufferTooSmall {
+                required,
+                actual: len,
+            });
+        }
+        Ok(layout)
+    }
+
+    /// Converts a stride in bytes into a stride in pixels of type `P`.
+    fn pixel_stride<P>(byte_stride: usize) -> Result<usize, ImageError> {
+        let pixel_size = size_of::<P>();
+        if pixel_size == 0 || !byte_stride.is_multiple_of(pixel_size) {
+            return Err(ImageError::UnalignedStride {
+                byte_stride,
+                pixel_size,
+            });
+        }
+        Ok(byte_stride / pixel_size)
+    }
+
+    /// Returns the number of pixels from the start of the first row to the end of the last row.
+    fn required_len(&self) -> Option<usize> {
+        if self.height == 0 {
+            return Some(0);
+        }
+        self.stride
+            .checked_mul(self.height - 1)?
+            .checked_add(self.width)
+    }
+
+    /// Returns the index of the pixel at `(x, y)`, if it is within the image.
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.stride + x)
+    }
+
+    /// Returns the index of the first pixel of row `y`, if it is within the image.
+    fn row_start(&self, y: usize) -> Option<usize> {
+        (y < self.height).then(|| y * self.stride)
+    }
+
+    /// Returns the offset and layout of a sub-rectangle, if it is within the image.
+    fn sub(&self, x: usize, y: usize, width: usize, height: usize) -> Option<(usize, Self)> {
+        if x.checked_add(width)? > self.width || y.checked_add(height)? > self.height {
+            return None;
+        }
+        let offset = if width == 0 || height == 0 {
+            0
+        } else {
+            y * self.stride + x
+        };
+        let layout = Self {
+            width,
+            height,
+            stride: self.stride,
+        };
+        Some((offset, layout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_stride_too_small() {
+        assert_eq!(
+            Layout::new(100, 4, 4, 3),
+            Err(ImageError::StrideTooSmall {
+                width: 4,
+                stride: 3
+            })
+        );
+    }
+
+    #[test]
+    fn layout_buffer_too_small() {
+        assert_eq!(
+            Layout::new(10, 3, 3, 4),
+            Err(ImageError::BufferTooSmall {
+                required: 11,
+                actual: 10
+            })
+        );
+        assert!(Layout::new(11, 3, 3, 4).is_ok());
+    }
+
+    #[test]
+    fn layout_too_large() {
+        assert_eq!(
+            Layout::new(usize::MAX, usize::MAX, 3, usize::MAX),
+            Err(ImageError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn layout_empty() {
+        assert!(Layout::new(0, 0, 0, 0).is_ok());
+        assert!(Layout::new(0, 5, 0, 5).is_ok());
+        assert!(Layout::new(0, 0, 5, 0).is_ok());
+    }
+
+    #[test]
+    fn pixel_stride() {
+        assert_eq!(Layout::pixel_stride::<u32>(16), Ok(4));
+        assert_eq!(
+            Layout::pixel_stride::<u32>(10),
+            Err(ImageError::UnalignedStride {
+                byte_stride: 10,
+                pixel_size: 4
+            })
+        );
+    }
+
+    #[test]
+    fn sub_out_of_bounds() {
+        let layout = Layout::new(16, 4, 4, 4).unwrap();
+        assert_eq!(layout.sub(2, 2, 3, 1), None);
+        assert_eq!(layout.sub(usize::MAX, 0, 2, 1), None);
+        assert_eq!(
+            layout.sub(1, 2, 3, 2),
+            Some((
+                9,
+                Layout {
+                    width: 3,
+                    height: 2,
+                    stride: 4
+                }
+            ))
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::vec::Vec;
+
+use crate::image::{ImageError, ImageView, ImageViewMut, Layout, Rows, RowsMut};
+
+/// An owned image of pixels of type `P`, stored row by row without padding.
+///
+/// This type requires the `alloc` feature.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{image::ImageBuf, rgb::Abgr8888};
+///
+/// let mut image = ImageBuf::from_pixel(3, 2, Abgr8888::from_abgr(255, 0, 0, 0));
+/// *image.get_mut(2, 1).unwrap() = Abgr8888::from_abgr(255, 255, 255, 255);
+///
+/// assert_eq!(image.get(2, 1), Some(&Abgr8888::from_abgr(255, 255, 255, 255)));
+/// assert_eq!(image.as_slice().len(), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageBuf<P> {
+    pixels: Vec<P>,
+    layout: Layout,
+}
+
+impl<P> ImageBuf<P> {
+    /// Creates an image of `width` × `height` pixels, each set to `pixel`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `width × height` overflows [`usize`].
+    #[must_use]
+    pub fn from_pixel(width: usize, height: usize, pixel: P) -> Self
+    where
+        P: Clone,
+    {
+        let len = width
+            .checked_mul(height)
+            .expect("image dimensions overflow");
+        let mut pixels = Vec::new();
+        pixels.resize(len, pixel);
+        let layout = Layout {
+            width,
+            height,
+            stride: width,
+        };
+        Self { pixels, layout }
+    }
+
+    /// Creates an image of `width` × `height` pixels, each set to the default value.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `width × height` overflows [`usize`].
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        P: Clone + Default,
+    {
+        Self::from_pixel(width, height, P::default())
+    }
+
+    /// Creates an image of `width` × `height` pixels from a vector, stored row by row.
+    ///
+    /// Any pixels after the first `width × height` are discarded.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `pixels` contains fewer than `width × height` pixels.
+    pub fn from_vec(width: usize, height: usize, mut pixels: Vec<P>) -> Result<Self, ImageError> {
+        let layout = Layout::new(pixels.len(), width, height, width)?;
+        pixels.truncate(width * height);
+        Ok(Self { pixels, layout })
+    }
+
+    /// Returns the width of the image, in pixels.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.layout.width
+    }
+
+    /// Returns the height of the image, in pixels.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.layout.height
+    }
+
+    /// Returns a read-only view of the image.
+    #[must_use]
+    pub fn as_view(&self) -> ImageView<'_, P> {
+        ImageView::from_parts(&self.pixels, self.layout)
+    }
+
+    /// Returns a mutable view of the image.
+    #[must_use]
+    pub fn as_view_mut(&mut self) -> ImageViewMut<'_, P> {
+        ImageViewMut::from_parts(&mut self.pixels, self.layout)
+    }
+
+    /// Returns the pixel at `(x, y)`, or `None` if it is outside of the image.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<&P> {
+        self.layout.index(x, y).map(|index| &self.pixels[index])
+    }
+
+    /// Returns a mutable reference to the pixel at `(x, y)`, or `None` if it is outside of the
+    /// image.
+    #[must_use]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut P> {
+        self.layout.index(x, y).map(|index| &mut self.pixels[index])
+    }
+
+    /// Returns an iterator over the rows of the image, from top to bottom.
+    #[must_use]
+    pub fn rows(&self) -> Rows<'_, P> {
+        self.as_view().rows()
+    }
+
+    /// Returns an iterator over the mutable rows of the image, from top to bottom.
+    #[must_use]
+    pub fn rows_mut(&mut self) -> RowsMut<'_, P> {
+        let Layout {
+            width,
+            height,
+            stride,
+        } = self.layout;
+        RowsMut::new(&mut self.pixels, width, height, stride)
+    }
+
+    /// Returns every pixel of the image, row by row.
+    #[must_use]
+    pub fn as_slice(&self) -> &[P] {
+        &self.pixels
+    }
+
+    /// Returns every pixel of the image mutably, row by row.
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [P] {
+        &mut self.pixels
+    }
+
+    /// Consumes the image and returns its pixels, row by row.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<P> {
+        self.pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn new_is_default() {
+        let image = ImageBuf::<u8>::new(3, 2);
+        assert_eq!(image.width(), 3);
+        assert_eq!(image.height(), 2);
+        assert_eq!(image.as_slice(), [0; 6]);
+    }
+
+    #[test]
+    fn from_vec_truncates() {
+        let image = ImageBuf::from_vec(2, 2, vec![1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(image.into_vec(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn from_vec_too_small() {
+        assert_eq!(
+            ImageBuf::from_vec(2, 2, vec![1, 2, 3]),
+            Err(ImageError::BufferTooSmall {
+                required: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic = "image dimensions overflow"]
+    fn from_pixel_overflow() {
+        let _ = ImageBuf::from_pixel(usize::MAX, 2, 0u8);
+    }
+
+    #[test]
+    fn views() {
+        let mut image = ImageBuf::from_vec(3, 3, vec![0; 9]).unwrap();
+        image.as_view_mut().view_mut(1, 1, 2, 2).unwrap().fill(1);
+        for row in image.rows_mut().take(1) {
+            row.fill(2);
+        }
+        assert_eq!(image.as_slice(), [2, 2, 2, 0, 1, 1, 0, 1, 1]);
+        assert_eq!(
+            image
+                .as_view()
+                .view(1, 1, 2, 2)
+                .unwrap()
+                .pixels()
+                .sum::<i32>(),
+            4
+        );
+        assert_eq!(image.rows().len(), 3);
+    }
+}
@@ -0,0 +1,181 @@
+use core::{iter::FusedIterator, mem};
+
+/// An iterator over the rows of an image, from top to bottom.
+///
+/// Created by [`ImageView::rows`][crate::image::ImageView::rows] and similar methods.
+#[derive(Debug)]
+pub struct Rows<'a, P> {
+    data: &'a [P],
+    width: usize,
+    stride: usize,
+    remaining: usize,
+}
+
+impl<'a, P> Rows<'a, P> {
+    /// Creates an iterator over `height` rows of `data`, where the buffer has been validated.
+    pub(super) fn new(data: &'a [P], width: usize, height: usize, stride: usize) -> Self {
+        Self {
+            data,
+            width,
+            stride,
+            remaining: height,
+        }
+    }
+}
+
+impl<P> Clone for Rows<'_, P> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, P> Iterator for Rows<'a, P> {
+    type Item = &'a [P];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let row = &self.data[..self.width];
+        if self.remaining > 0 {
+            self.data = &self.data[self.stride..];
+        }
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<P> DoubleEndedIterator for Rows<'_, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let start = self.remaining * self.stride;
+        Some(&self.data[start..start + self.width])
+    }
+}
+
+impl<P> ExactSizeIterator for Rows<'_, P> {}
+
+impl<P> FusedIterator for Rows<'_, P> {}
+
+/// An iterator over the mutable rows of an image, from top to bottom.
+///
+/// Created by [`ImageViewMut::rows_mut`][crate::image::ImageViewMut::rows_mut] and similar
+/// methods.
+#[derive(Debug)]
+pub struct RowsMut<'a, P> {
+    data: &'a mut [P],
+    width: usize,
+    stride: usize,
+    remaining: usize,
+}
+
+impl<'a, P> RowsMut<'a, P> {
+    /// Creates an iterator over `height` rows of `data`, where the buffer has been validated.
+    pub(super) fn new(data: &'a mut [P], width: usize, height: usize, stride: usize) -> Self {
+        Self {
+            data,
+            width,
+            stride,
+            remaining: height,
+        }
+    }
+}
+
+impl<'a, P> Iterator for RowsMut<'a, P> {
+    type Item = &'a mut [P];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let data = mem::take(&mut self.data);
+        if self.remaining == 0 {
+            return Some(&mut data[..self.width]);
+        }
+        let (row, rest) = data.split_at_mut(self.stride);
+        self.data = rest;
+        Some(&mut row[..self.width])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<P> DoubleEndedIterator for RowsMut<'_, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let data = mem::take(&mut self.data);
+        let (rest, row) = data.split_at_mut(self.remaining * self.stride);
+        self.data = rest;
+        Some(&mut row[..self.width])
+    }
+}
+
+impl<P> ExactSizeIterator for RowsMut<'_, P> {}
+
+impl<P> FusedIterator for RowsMut<'_, P> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_skip_padding() {
+        let data = [1, 2, 0, 3, 4, 0, 5, 6];
+        let mut rows = Rows::new(&data, 2, 3, 3);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.next(), Some(&[1, 2][..]));
+        assert_eq!(rows.next(), Some(&[3, 4][..]));
+        assert_eq!(rows.next(), Some(&[5, 6][..]));
+        assert_eq!(rows.next(), None);
+    }
+
+    #[test]
+    fn rows_double_ended() {
+        let data = [1, 2, 0, 3, 4, 0, 5, 6];
+        let mut rows = Rows::new(&data, 2, 3, 3);
+        assert_eq!(rows.next_back(), Some(&[5, 6][..]));
+        assert_eq!(rows.next(), Some(&[1, 2][..]));
+        assert_eq!(rows.next_back(), Some(&[3, 4][..]));
+        assert_eq!(rows.next_back(), None);
+        assert_eq!(rows.next(), None);
+    }
+
+    #[test]
+    fn rows_zero_width() {
+        let data: [u8; 0] = [];
+        let rows = Rows::new(&data, 0, 3, 0);
+        assert_eq!(rows.count(), 3);
+    }
+
+    #[test]
+    fn rows_mut_skip_padding() {
+        let mut data = [1, 2, 0, 3, 4, 0, 5, 6];
+        for row in RowsMut::new(&mut data, 2, 3, 3) {
+            row.fill(9);
+        }
+        assert_eq!(data, [9, 9, 0, 9, 9, 0, 9, 9]);
+    }
+
+    #[test]
+    fn rows_mut_double_ended() {
+        let mut data = [1, 2, 0, 3, 4, 0, 5, 6];
+        let mut rows = RowsMut::new(&mut data, 2, 3, 3);
+        assert_eq!(rows.next_back(), Some(&mut [5, 6][..]));
+        assert_eq!(rows.next(), Some(&mut [1, 2][..]));
+        assert_eq!(rows.next_back(), Some(&mut [3, 4][..]));
+        assert_eq!(rows.next(), None);
+    }
+}
@@ -0,0 +1,297 @@
+use core::iter::Flatten;
+
+use crate::image::{ImageError, Layout, Rows};
+
+/// A borrowed, read-only image of pixels of type `P`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{image::ImageView, rgb::Rgb888};
+///
+/// let pixels = [Rgb888::from_rgb(255, 0, 0), Rgb888::from_rgb(0, 255, 0)];
+/// let image = ImageView::new(&pixels, 1, 2).unwrap();
+///
+/// assert_eq!(image.width(), 1);
+/// assert_eq!(image.height(), 2);
+/// assert_eq!(image.get(0, 1), Some(&Rgb888::from_rgb(0, 255, 0)));
+/// ```
+#[derive(Debug)]
+pub struct ImageView<'a, P> {
+    data: &'a [P],
+    layout: Layout,
+}
+
+impl<P> Clone for ImageView<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for ImageView<'_, P> {}
+
+impl<'a, P> ImageView<'a, P> {
+    /// Creates a view from a buffer that has already been validated against `layout`.
+    pub(super) fn from_parts(data: &'a [P], layout: Layout) -> Self {
+        Self { data, layout }
+    }
+
+    /// Creates a view of `width` × `height` pixels, where rows are stored without padding.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `data` contains fewer than `width × height` pixels.
+    pub fn new(data: &'a [P], width: usize, height: usize) -> Result<Self, ImageError> {
+        Self::with_stride(data, width, height, width)
+    }
+
+    /// Creates a view of `width` × `height` pixels, where each row starts `stride` pixels after
+    /// the start of the previous row.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `stride` is smaller than `width`, or if `data` is too small.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::image::ImageView;
+    ///
+    /// let pixels = [1, 2, 0, 3, 4];
+    /// let image = ImageView::with_stride(&pixels, 2, 2, 3).unwrap();
+    /// assert_eq!(image.row(1), Some(&[3, 4][..]));
+    /// ```
+    pub fn with_stride(
+        data: &'a [P],
+        width: usize,
+        height: usize,
+        stride: usize,
+    ) -> Result<Self, ImageError> {
+        let layout = Layout::new(data.len(), width, height, stride)?;
+        Ok(Self { data, layout })
+    }
+
+    /// Creates a view of `width` × `height` pixels, where each row starts `byte_stride` bytes
+    /// after the start of the previous row.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `byte_stride` is not a multiple of the size of `P`, if it is smaller
+    /// than a row, or if `data` is too small.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{image::ImageView, rgb::Abgr8888};
+    ///
+    /// let pixels = [Abgr8888::default(); 8];
+    /// let image = ImageView::with_byte_stride(&pixels, 3, 2, 16).unwrap();
+    /// assert_eq!(image.stride(), 4);
+    /// ```
+    pub fn with_byte_stride(
+        data: &'a [P],
+        width: usize,
+        height: usize,
+        byte_stride: usize,
+    ) -> Result<Self, ImageError> {
+        let stride = Layout::pixel_stride::<P>(byte_stride)?;
+        Self::with_stride(data, width, height, stride)
+    }
+
+    /// Creates a view of `width` × `height` pixels from raw bytes, where each row starts
+    /// `byte_stride` bytes after the start of the previous row.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `bytes` is not aligned for `P`, if `byte_stride` is not a multiple of
+    /// the size of `P` or is smaller than a row, or if `bytes` is too small.
+    #[cfg(feature = "bytemuck")]
+    pub fn from_bytes(
+        bytes: &'a [u8],
+        width: usize,
+        height: usize,
+        byte_stride: usize,
+    ) -> Result<Self, ImageError>
+    where
+        P: bytemuck::Pod,
+    {
+        let stride = Layout::pixel_stride::<P>(byte_stride)?;
+        let len = bytes.len() - bytes.len() % size_of::<P>();
+        let data = bytemuck::try_cast_slice(&bytes[..len]).map_err(|_| ImageError::Misaligned)?;
+        Self::with_stride(data, width, height, stride)
+    }
+
+    /// Returns the width of the image, in pixels.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.layout.width
+    }
+
+    /// Returns the height of the image, in pixels.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.layout.height
+    }
+
+    /// Returns the distance between the start of each row, in pixels.
+    #[must_use]
+    pub const fn stride(&self) -> usize {
+        self.layout.stride
+    }
+
+    /// Returns the pixel at `(x, y)`, or `None` if it is outside of the image.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a P> {
+        let data = self.data;
+        self.layout.index(x, y).map(|index| &data[index])
+    }
+
+    /// Returns row `y`, or `None` if it is outside of the image.
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&'a [P]> {
+        let data = self.data;
+        let width = self.layout.width;
+        self.layout
+            .row_start(y)
+            .map(|start| &data[start..start + width])
+    }
+
+    /// Returns an iterator over the rows of the image, from top to bottom.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::image::ImageView;
+    ///
+    /// let pixels = [1, 2, 3, 4, 5, 6];
+    /// let image = ImageView::new(&pixels, 3, 2).unwrap();
+    /// let sums: Vec<i32> = image.rows().map(|row| row.iter().sum()).collect();
+    /// assert_eq!(sums, [6, 15]);
+    /// ```
+    #[must_use]
+    pub fn rows(&self) -> Rows<'a, P> {
+        let Layout {
+            width,
+            height,
+            stride,
+        } = self.layout;
+        Rows::new(self.data, width, height, stride)
+    }
+
+    /// Returns an iterator over every pixel of the image, row by row.
+    pub fn pixels(&self) -> Flatten<Rows<'a, P>> {
+        self.rows().flatten()
+    }
+
+    /// Returns a view of the `width` × `height` pixels starting at `(x, y)`, or `None` if any of
+    /// it is outside of the image.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::image::ImageView;
+    ///
+    /// let pixels = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    /// let image = ImageView::new(&pixels, 3, 3).unwrap();
+    ///
+    /// let corner = image.view(1, 1, 2, 2).unwrap();
+    /// assert_eq!(corner.pixels().copied().collect::<Vec<_>>(), [5, 6, 8, 9]);
+    /// assert!(image.view(2, 2, 2, 2).is_none());
+    /// ```
+    #[must_use]
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> Option<Self> {
+        let (offset, layout) = self.layout.sub(x, y, width, height)?;
+        Some(Self {
+            data: &self.data[offset..],
+            layout,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::rgb::Abgr8888;
+
+    #[test]
+    fn new_buffer_too_small() {
+        let pixels = [0u8; 5];
+        assert_eq!(
+            ImageView::new(&pixels, 3, 2).unwrap_err(),
+            ImageError::BufferTooSmall {
+                required: 6,
+                actual: 5
+            }
+        );
+    }
+
+    #[test]
+    fn with_stride_allows_short_last_row() {
+        let pixels = [1, 2, 0, 3, 4];
+        let image = ImageView::with_stride(&pixels, 2, 2, 3).unwrap();
+        assert_eq!(image.get(1, 1), Some(&4));
+        assert_eq!(image.get(2, 0), None);
+        assert_eq!(image.get(0, 2), None);
+    }
+
+    #[test]
+    fn with_byte_stride_unaligned() {
+        let pixels = [Abgr8888::default(); 8];
+        assert_eq!(
+            ImageView::with_byte_stride(&pixels, 2, 2, 10).unwrap_err(),
+            ImageError::UnalignedStride {
+                byte_stride: 10,
+                pixel_size: 4
+            }
+        );
+    }
+
+    #[test]
+    fn row_out_of_bounds() {
+        let pixels = [1, 2, 3, 4];
+        let image = ImageView::new(&pixels, 2, 2).unwrap();
+        assert_eq!(image.row(0), Some(&[1, 2][..]));
+        assert_eq!(image.row(2), None);
+    }
+
+    #[test]
+    fn nested_views() {
+        let pixels: [u8; 16] = core::array::from_fn(|i| u8::try_from(i).unwrap());
+        let image = ImageView::new(&pixels, 4, 4).unwrap();
+        let inner = image.view(1, 1, 3, 3).unwrap().view(1, 1, 2, 2).unwrap();
+        assert_eq!(inner.get(0, 0), Some(&10));
+        assert_eq!(inner.get(1, 1), Some(&15));
+        assert_eq!(inner.stride(), 4);
+    }
+
+    #[test]
+    fn empty_view_at_edge() {
+        let pixels = [1, 2, 3, 4];
+        let image = ImageView::new(&pixels, 2, 2).unwrap();
+        let empty = image.view(2, 2, 0, 0).unwrap();
+        assert_eq!(empty.rows().count(), 0);
+        assert_eq!(empty.pixels().count(), 0);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn from_bytes() {
+        let words = [0x0403_0201_u32, 0, 0x0807_0605];
+        let bytes: &[u8] = bytemuck::cast_slice(&words);
+        let image = ImageView::<[u8; 4]>::from_bytes(bytes, 1, 2, 8).unwrap();
+        assert_eq!(image.get(0, 0), Some(&0x0403_0201_u32.to_ne_bytes()));
+        assert_eq!(image.get(0, 1), Some(&0x0807_0605_u32.to_ne_bytes()));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn from_bytes_misaligned() {
+        let words = [0u32; 3];
+        let bytes: &[u8] = bytemuck::cast_slice(&words);
+        assert_eq!(
+            ImageView::<u32>::from_bytes(&bytes[1..], 1, 2, 4).unwrap_err(),
+            ImageError::Misaligned
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::image::{ImageError, ImageView, Layout, Rows, RowsMut};
+
+/// A borrowed, mutable image of pixels of type `P`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{image::ImageViewMut, rgb::Rgb888};
+///
+/// let mut pixels = [Rgb888::default(); 4];
+/// let mut image = ImageViewMut::new(&mut pixels, 2, 2).unwrap();
+///
+/// *image.get_mut(1, 0).unwrap() = Rgb888::from_rgb(255, 255, 255);
+/// assert_eq!(pixels[1], Rgb888::from_rgb(255, 255, 255));
+/// ```
+#[derive(Debug)]
+pub struct ImageViewMut<'a, P> {
+    data: &'a mut [P],
+    layout: Layout,
+}
+
+impl<'a, P> ImageViewMut<'a, P> {
+    /// Creates a view from a buffer that has already been validated against `layout`.
+    pub(super) fn from_parts(data: &'a mut [P], layout: Layout) -> Self {
+        Self { data, layout }
+    }
+
+    /// Creates a view of `width` × `height` pixels, where rows are stored without padding.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `data` contains fewer than `width × height` pixels.
+    pub fn new(data: &'a mut [P], width: usize, height: usize) -> Result<Self, ImageError> {
+        Self::with_stride(data, width, height, width)
+    }
+
+    /// Creates a view of `width` × `height` pixels, where each row starts `stride` pixels after
+    /// the start of the previous row.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `stride` is smaller than `width`, or if `data` is too small.
+    pub fn with_stride(
+        data: &'a mut [P],
+        width: usize,
+        height: usize,
+        stride: usize,
+    ) -> Result<Self, ImageError> {
+        let layout = Layout::new(data.len(), width, height, stride)?;
+        Ok(Self { data, layout })
+    }
+
+    /// Creates a view of `width` × `height` pixels, where each row starts `byte_stride` bytes
+    /// after the start of the previous row.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `byte_stride` is not a multiple of the size of `P`, if it is smaller
+    /// than a row, or if `data` is too small.
+    pub fn with_byte_stride(
+        data: &'a mut [P],
+        width: usize,
+        height: usize,
+        byte_stride: usize,
+    ) -> Result<Self, ImageError> {
+        let stride = Layout::pixel_stride::<P>(byte_stride)?;
+        Self::with_stride(data, width, height, stride)
+    }
+
+    /// Creates a view of `width` × `height` pixels from raw bytes, where each row starts
+    /// `byte_stride` bytes after the start of the previous row.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `bytes` is not aligned for `P`, if `byte_stride` is not a multiple of
+    /// the size of `P` or is smaller than a row, or if `bytes` is too small.
+    #[cfg(feature = "bytemuck")]
+    pub fn from_bytes_mut(
+        bytes: &'a mut [u8],
+        width: usize,
+        height: usize,
+        byte_stride: usize,
+    ) -> Result<Self, ImageError>
+    where
+        P: bytemuck::Pod,
+    {
+        let stride = Layout::pixel_stride::<P>(byte_stride)?;
+        let len = bytes.len() - bytes.len() % size_of::<P>();
+        let data =
+            bytemuck::try_cast_slice_mut(&mut bytes[..len]).map_err(|_| ImageError::Misaligned)?;
+        Self::with_stride(data, width, height, stride)
+    }
+
+    /// Returns the width of the image, in pixels.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.layout.width
+    }
+
+    /// Returns the height of the image, in pixels.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.layout.height
+    }
+
+    /// Returns the distance between the start of each row, in pixels.
+    #[must_use]
+    pub const fn stride(&self) -> usize {
+        self.layout.stride
+    }
+
+    /// Returns a read-only view of the image.
+    #[must_use]
+    pub fn as_view(&self) -> ImageView<'_, P> {
+        ImageView::from_parts(self.data, self.layout)
+    }
+
+    /// Returns the pixel at `(x, y)`, or `None` if it is outside of the image.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<&P> {
+        self.layout.index(x, y).map(|index| &self.data[index])
+    }
+
+    /// Returns a mutable reference to the pixel at `(x, y)`, or `None` if it is outside of the
+    /// image.
+    #[must_use]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut P> {
+        self.layout.index(x, y).map(|index| &mut self.data[index])
+    }
+
+    /// Returns row `y`, or `None` if it is outside of the image.
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&[P]> {
+        let width = self.layout.width;
+        self.layout
+            .row_start(y)
+            .map(|start| &self.data[start..start + width])
+    }
+
+    /// Returns mutable row `y`, or `None` if it is outside of the image.
+    #[must_use]
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [P]> {
+        let width = self.layout.width;
+        self.layout
+            .row_start(y)
+            .map(|start| &mut self.data[start..start + width])
+    }
+
+    /// Returns an iterator over the rows of the image, from top to bottom.
+    #[must_use]
+    pub fn rows(&self) -> Rows<'_, P> {
+        self.as_view().rows()
+    }
+
+    /// Returns an iterator over the mutable rows of the image, from top to bottom.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::image::ImageViewMut;
+    ///
+    /// let mut pixels = [0; 6];
+    /// let mut image = ImageViewMut::new(&mut pixels, 3, 2).unwrap();
+    /// for (y, row) in image.rows_mut().enumerate() {
+    ///     row.fill(y);
+    /// }
+    /// assert_eq!(pixels, [0, 0, 0, 1, 1, 1]);
+    /// ```
+    #[must_use]
+    pub fn rows_mut(&mut self) -> RowsMut<'_, P> {
+        let Layout {
+            width,
+            height,
+            stride,
+        } = self.layout;
+        RowsMut::new(self.data, width, height, stride)
+    }
+
+    /// Returns a read-only view of the `width` × `height` pixels starting at `(x, y)`, or `None`
+    /// if any of it is outside of the image.
+    #[must_use]
+    pub fn view(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Option<ImageView<'_, P>> {
+        self.as_view().view(x, y, width, height)
+    }
+
+    /// Returns a mutable view of the `width` × `height` pixels starting at `(x, y)`, or `None`
+    /// if any of it is outside of the image.
+    #[must_use]
+    pub fn view_mut(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Option<ImageViewMut<'_, P>> {
+        let (offset, layout) = self.layout.sub(x, y, width, height)?;
+        Some(ImageViewMut::from_parts(&mut self.data[offset..], layout))
+    }
+
+    /// Sets every pixel of the image to `pixel`, leaving any padding between rows unchanged.
+    pub fn fill(&mut self, pixel: P)
+    where
+        P: Clone,
+    {
+        for row in self.rows_mut() {
+            row.fill(pixel.clone());
+        }
+    }
+}
+
+impl<'a, P> From<ImageViewMut<'a, P>> for ImageView<'a, P> {
+    fn from(view: ImageViewMut<'a, P>) -> Self {
+        ImageView::from_parts(view.data, view.layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_keeps_padding() {
+        let mut pixels = [0; 8];
+        let mut image = ImageViewMut::with_stride(&mut pixels, 3, 2, 5).unwrap();
+        image.fill(1);
+        assert_eq!(pixels, [1, 1, 1, 0, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn view_mut_sub_rectangle() {
+        let mut pixels = [0; 16];
+        let mut image = ImageViewMut::new(&mut pixels, 4, 4).unwrap();
+        image.view_mut(1, 2, 2, 2).unwrap().fill(1);
+        assert_eq!(pixels, [0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn view_mut_out_of_bounds() {
+        let mut pixels = [0; 4];
+        let mut image = ImageViewMut::new(&mut pixels, 2, 2).unwrap();
+        assert!(image.view_mut(1, 1, 2, 1).is_none());
+        assert!(image.view_mut(2, 2, 0, 0).is_some());
+    }
+
+    #[test]
+    fn get_mut_and_row_mut() {
+        let mut pixels = [0; 6];
+        let mut image = ImageViewMut::new(&mut pixels, 3, 2).unwrap();
+        *image.get_mut(2, 1).unwrap() = 5;
+        image.row_mut(0).unwrap()[1] = 7;
+        assert!(image.get_mut(3, 0).is_none());
+        assert!(image.row_mut(2).is_none());
+        assert_eq!(image.row(1), Some(&[0, 0, 5][..]));
+        assert_eq!(pixels, [0, 7, 0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn into_view() {
+        let mut pixels = [1, 2, 3, 4];
+        let image = ImageViewMut::new(&mut pixels, 2, 2).unwrap();
+        let view = ImageView::from(image);
+        assert_eq!(view.get(1, 1), Some(&4));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn from_bytes_mut() {
+        let mut words = [0u32; 4];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut words);
+        let mut image = ImageViewMut::<u32>::from_bytes_mut(bytes, 1, 2, 8).unwrap();
+        image.fill(7);
+        assert_eq!(words, [7, 0, 7, 0]);
+    }
+}
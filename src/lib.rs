@@ -4,13 +4,56 @@
 //!
 //! By default, this crate uses `#![no_std]` and does not depend on the standard library.
 //!
+//! ### `alloc`
+//!
+//! Enables types that allocate, such as [`image::ImageBuf`], the [`icc`] and [`quantize`] modules,
+//! and [`dither::ErrorDiffusion::dither`].
+//!
+//! ### `std`
+//!
+//! Implies `alloc`, and detects CPU features at runtime, such as AVX2 for [`rgb::convert_slice`].
+//!
 //! ### `bytemuck`
 //!
-//! Derives `bytemuck::Zeroable` and `bytemuck::Pod` for color types.
+//! Derives `bytemuck::Zeroable` and `bytemuck::Pod` for color types, and allows creating images
+//! from raw bytes.
+//!
+//! ### `serde`
+//!
+//! Implements `serde::Serialize` and `serde::Deserialize` for color types, and enables the
+//! [`serde`](mod@serde) module of alternative representations, such as `"#rrggbbaa"` strings.
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod alpha;
+pub mod cie;
+pub mod cmyk;
+pub mod component;
+pub mod composite;
+pub mod contrast;
+pub mod css;
+pub mod cylindrical;
+pub mod difference;
+pub mod dither;
+pub mod encoding;
 pub mod gray;
+#[cfg(feature = "alloc")]
+pub mod icc;
+pub mod image;
+mod math;
+pub mod oklab;
 pub mod prelude;
+#[cfg(feature = "alloc")]
+pub mod quantize;
 pub mod rgb;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod space;
+pub mod vision;
+pub mod ycbcr;
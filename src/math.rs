@@ -0,0 +1,139 @@
+//! Floating-point functions that are not available in `core`.
+//!
+//! These are provided by [`libm`], so that the crate can remain `#![no_std]`.
+
+/// Returns `x` raised to the power of `y`.
+#[inline]
+pub(crate) fn pow(x: f64, y: f64) -> f64 {
+    libm::pow(x, y)
+}
+
+/// Returns the square root of `x`.
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+/// Returns `e` raised to the power of `x`.
+#[inline]
+pub(crate) fn exp(x: f64) -> f64 {
+    libm::exp(x)
+}
+
+/// Returns the cube root of `x`.
+#[inline]
+pub(crate) fn cbrt(x: f64) -> f64 {
+    libm::cbrt(x)
+}
+
+/// Returns the length of the hypotenuse of a right triangle with sides `x` and `y`.
+#[inline]
+pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+    libm::hypot(x, y)
+}
+
+/// Returns the angle, in degrees and in the range `0.0..360.0`, of the point `(x, y)`.
+#[inline]
+pub(crate) fn atan2_degrees(y: f64, x: f64) -> f64 {
+    let degrees = libm::atan2(y, x).to_degrees();
+    if degrees < 0.0 {
+        degrees + 360.0
+    } else {
+        degrees
+    }
+}
+
+/// Returns the sine and cosine of an angle in degrees.
+#[inline]
+pub(crate) fn sin_cos_degrees(degrees: f64) -> (f64, f64) {
+    libm::sincos(degrees.to_radians())
+}
+
+/// A 3×3 matrix, stored in row-major order.
+pub(crate) type Matrix3 = [[f64; 3]; 3];
+
+/// Multiplies a matrix by a column vector.
+#[inline]
+pub(crate) const fn mul_vector(m: &Matrix3, v: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+/// Multiplies two matrices, so that `mul_matrix(a, b)` applies `b` first and then `a`.
+pub(crate) const fn mul_matrix(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    let mut out = [[0.0; 3]; 3];
+    let mut row = 0;
+    while row < 3 {
+        let mut col = 0;
+        while col < 3 {
+            out[row][col] = a[row][0] * b[0][col] + a[row][1] * b[1][col] + a[row][2] * b[2][col];
+            col += 1;
+        }
+        row += 1;
+    }
+    out
+}
+
+/// Returns a matrix with `v` along the diagonal.
+pub(crate) const fn diagonal(v: [f64; 3]) -> Matrix3 {
+    [[v[0], 0.0, 0.0], [0.0, v[1], 0.0], [0.0, 0.0, v[2]]]
+}
+
+/// Returns the determinant of a matrix, which is zero if it is not invertible.
+#[allow(clippy::many_single_char_names)]
+pub(crate) const fn determinant(m: &Matrix3) -> f64 {
+    let [[a, b, c], [d, e, f], [g, h, i]] = *m;
+    a * (e * i - f * h) + b * (f * g - d * i) + c * (d * h - e * g)
+}
+
+/// Returns the inverse of a matrix.
+///
+/// The matrix must be invertible; this is always the case for the color space matrices in this
+/// crate.
+#[allow(clippy::many_single_char_names)]
+pub(crate) const fn invert(m: &Matrix3) -> Matrix3 {
+    let [[a, b, c], [d, e, f], [g, h, i]] = *m;
+    let (ei_fh, fg_di, dh_eg) = (e * i - f * h, f * g - d * i, d * h - e * g);
+    let det = determinant(m);
+    [
+        [ei_fh / det, (c * h - b * i) / det, (b * f - c * e) / det],
+        [fg_di / det, (a * i - c * g) / det, (c * d - a * f) / det],
+        [dh_eg / det, (b * g - a * h) / det, (a * e - b * d) / det],
+    ]
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invert_round_trips() {
+        let m = [[2.0, 0.0, 1.0], [1.0, 3.0, 0.0], [0.0, 1.0, 4.0]];
+        let identity = mul_matrix(&m, &invert(&m));
+        for (row, values) in identity.iter().enumerate() {
+            for (col, value) in values.iter().enumerate() {
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-15, "{identity:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn mul_vector_diagonal() {
+        assert_eq!(
+            mul_vector(&diagonal([1.0, 2.0, 3.0]), [4.0, 5.0, 6.0]),
+            [4.0, 10.0, 18.0]
+        );
+    }
+
+    #[test]
+    fn atan2_degrees_is_positive() {
+        assert_eq!(atan2_degrees(0.0, 1.0), 0.0);
+        assert_eq!(atan2_degrees(1.0, 0.0), 90.0);
+        assert_eq!(atan2_degrees(-1.0, 0.0), 270.0);
+    }
+}
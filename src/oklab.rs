@@ -0,0 +1,142 @@
+//! Perceptual 👁️ color spaces based on Oklab, for adjusting lightness, chroma, and hue.
+//!
+//! [Oklab] is a perceptually uniform color space derived from sRGB; equal steps in lightness,
+//! chroma, or hue look like equal changes, which makes it a good space to interpolate, mix, or
+//! adjust colors in. [`Oklch<T>`] is its cylindrical form.
+//!
+//! [Oklab]: https://bottosson.github.io/posts/oklab/
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{cylindrical::HasLightness, oklab::Oklchf32, rgb::Rgb888};
+//!
+//! let mut color = Oklchf32::from_rgb_color(Rgb888::from_rgb(255, 0, 0));
+//! assert!((color.lightness() - 0.628).abs() < 0.001);
+//!
+//! color.set_lightness(0.4);
+//! let darker: Rgb888 = color.into_rgb_color();
+//! assert_eq!(darker, Rgb888::from_rgb(169, 0, 0));
+//! ```
+//!
+//! ## Predefined Types
+//!
+//! Type            | Description
+//! --------------- | -----------
+//! [`Oklabf32`]    | Oklab lightness and opponent axes, as 32-bit floating-point
+//! [`Oklchf32`]    | Oklch lightness, chroma, and hue, as 32-bit floating-point
+//! [`Oklabaf32`]   | [`Oklabf32`] with alpha, as 32-bit floating-point
+//! [`Oklchaf32`]   | [`Oklchf32`] with alpha, as 32-bit floating-point
+//!
+//! ## Generic Types
+//!
+//! - [`Oklab<T>`]; lightness `L`, and opponent axes `a` and `b`
+//! - [`Oklch<T>`]; lightness `L`, chroma `C`, and hue `h`
+//!
+//! ## Conversions
+//!
+//! Oklab is defined relative to linear sRGB, so every floating-point type converts to and from
+//! [`LinearRgb<Rgbf32>`] using [`From`], and to and from any [`RgbColor`] or [`RgbaColor`], which
+//! are treated as sRGB-encoded. Alpha, if any, is kept unchanged.
+//!
+//! Colors with alpha are [`AlphaLast`] wrappers, so the accessor traits, such as
+//! [`HasLightness`] and [`HasAlpha`], work the same as on the colors without alpha.
+//!
+//! [`LinearRgb<Rgbf32>`]: crate::encoding::LinearRgb
+//! [`RgbColor`]: crate::rgb::RgbColor
+//! [`RgbaColor`]: crate::rgb::RgbaColor
+//! [`AlphaLast`]: crate::alpha::AlphaLast
+//! [`HasLightness`]: crate::cylindrical::HasLightness
+//! [`HasAlpha`]: crate::alpha::HasAlpha
+
+use crate::math::{Matrix3, cbrt, invert, mul_vector};
+
+mod lab;
+mod lch;
+
+pub use lab::{Oklab, Oklabaf32, Oklabf32};
+pub use lch::{Oklch, Oklchaf32, Oklchf32};
+
+/// Implements a `Has{*}` trait for a single-parameter type by reading and writing a field.
+macro_rules! impl_has_field {
+    ($ty:ident, $trait:path { $get:ident, $set:ident, $field:ident }) => {
+        impl<T> $trait for $ty<T>
+        where
+            T: Copy,
+        {
+            type Component = T;
+
+            fn $get(&self) -> Self::Component {
+                self.$field
+            }
+
+            fn $set(&mut self, value: Self::Component) {
+                self.$field = value;
+            }
+        }
+    };
+}
+
+use impl_has_field;
+
+/// Converts linear sRGB into approximate cone responses (`M1` in the reference implementation).
+#[allow(clippy::unreadable_literal)]
+const LINEAR_SRGB_TO_LMS: Matrix3 = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+
+/// Converts non-linear cone responses into Oklab (`M2` in the reference implementation).
+#[allow(clippy::unreadable_literal)]
+const LMS_TO_OKLAB: Matrix3 = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+
+const LMS_TO_LINEAR_SRGB: Matrix3 = invert(&LINEAR_SRGB_TO_LMS);
+const OKLAB_TO_LMS: Matrix3 = invert(&LMS_TO_OKLAB);
+
+/// Converts linear sRGB components into Oklab `L`, `a`, and `b`.
+pub(crate) fn linear_srgb_to_oklab(rgb: [f64; 3]) -> [f64; 3] {
+    let [l, m, s] = mul_vector(&LINEAR_SRGB_TO_LMS, rgb);
+    mul_vector(&LMS_TO_OKLAB, [cbrt(l), cbrt(m), cbrt(s)])
+}
+
+/// Converts Oklab `L`, `a`, and `b` into linear sRGB components.
+pub(crate) fn oklab_to_linear_srgb(lab: [f64; 3]) -> [f64; 3] {
+    let [l, m, s] = mul_vector(&OKLAB_TO_LMS, lab);
+    mul_vector(&LMS_TO_LINEAR_SRGB, [l * l * l, m * m * m, s * s * s])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3], tolerance: f64) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < tolerance, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn linear_srgb_to_oklab_reference() {
+        // Reference values from CSS Color 4.
+        let cases = [
+            ([1.0, 0.0, 0.0], [0.627_955, 0.224_863, 0.125_846]),
+            ([0.0, 1.0, 0.0], [0.866_440, -0.233_888, 0.179_498]),
+            ([0.0, 0.0, 1.0], [0.452_014, -0.032_457, -0.311_528]),
+            ([1.0, 1.0, 1.0], [1.0, 0.0, 0.0]),
+        ];
+        for (rgb, lab) in cases {
+            assert_close(linear_srgb_to_oklab(rgb), lab, 1e-4);
+        }
+    }
+
+    #[test]
+    fn oklab_round_trips() {
+        let rgb = [0.25, 0.5, 0.75];
+        assert_close(oklab_to_linear_srgb(linear_srgb_to_oklab(rgb)), rgb, 1e-12);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{
+    alpha::AlphaLast,
+    cie::{HasA, HasB},
+    component::Component,
+    cylindrical::HasLightness,
+    encoding::{LinearRgb, decode, encode},
+    math::sin_cos_degrees,
+    oklab::{Oklchf32, impl_has_field, linear_srgb_to_oklab, oklab_to_linear_srgb},
+    rgb::{RgbColor, RgbaColor, Rgbf32},
+};
+
+/// A color representation that contains Oklab `L`, `a`, and `b` components.
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Oklab {
+///     T l;
+///     T a;
+///     T b;
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Oklab<T> {
+    l: T,
+    a: T,
+    b: T,
+}
+
+impl<T> Oklab<T> {
+    /// Creates a new Oklab color with the given lightness, `a`, and `b` components.
+    #[must_use]
+    pub const fn from_lab(lightness: T, a: T, b: T) -> Self {
+        Self { l: lightness, a, b }
+    }
+
+    /// Consumes the color and returns its lightness, `a`, and `b` components.
+    #[must_use]
+    pub fn into_lab(self) -> (T, T, T) {
+        (self.l, self.a, self.b)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Oklab<T> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Oklab<T> where T: bytemuck::Pod {}
+
+impl_has_field!(
+    Oklab,
+    HasLightness {
+        lightness,
+        set_lightness,
+        l
+    }
+);
+impl_has_field!(Oklab, HasA { a, set_a, a });
+impl_has_field!(Oklab, HasB { b, set_b, b });
+
+/// 32-bit floating-point Oklab color.
+///
+/// Lightness is in the range `0.0..=1.0`; `a` and `b` are unbounded, but are within `-0.4..=0.4`
+/// for colors inside common RGB gamuts.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{oklab::Oklabf32, rgb::Rgb888};
+///
+/// let white = Oklabf32::from_rgb_color(Rgb888::from_rgb(255, 255, 255));
+/// let (l, a, b) = white.into_lab();
+/// assert!((l - 1.0).abs() < 1e-6 && a.abs() < 1e-6 && b.abs() < 1e-6);
+/// ```
+pub type Oklabf32 = Oklab<f32>;
+
+impl Oklabf32 {
+    /// Converts any sRGB-encoded RGB color into Oklab.
+    ///
+    /// The components of `color` are decoded into linear light first.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cylindrical::HasLightness, oklab::Oklabf32, rgb::Rgb565};
+    ///
+    /// let black = Oklabf32::from_rgb_color(Rgb565::from_rgb(0, 0, 0));
+    /// assert_eq!(black.lightness(), 0.0);
+    /// ```
+    #[must_use]
+    pub fn from_rgb_color<C: RgbColor>(color: C) -> Self {
+        let (r, g, b) = color.into_rgb();
+        Self::from(LinearRgb::new(Rgbf32::from_rgb(
+            decode(r),
+            decode(g),
+            decode(b),
+        )))
+    }
+
+    /// Converts this color into any sRGB-encoded RGB color.
+    ///
+    /// Colors outside of the sRGB gamut are clamped if `C` has integer components.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{oklab::Oklabf32, rgb::Rgb888};
+    ///
+    /// let red = Oklabf32::from_lab(0.627_955, 0.224_863, 0.125_846);
+    /// assert_eq!(red.into_rgb_color::<Rgb888>(), Rgb888::from_rgb(255, 0, 0));
+    /// ```
+    #[must_use]
+    pub fn into_rgb_color<C: RgbColor>(self) -> C {
+        let (r, g, b) = LinearRgb::<Rgbf32>::from(self).into_inner().into_rgb();
+        C::from_rgb(encode(r), encode(g), encode(b))
+    }
+}
+
+impl From<LinearRgb<Rgbf32>> for Oklabf32 {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: LinearRgb<Rgbf32>) -> Self {
+        let (red, green, blue) = color.into_inner().into_rgb();
+        let [l, a, b] = linear_srgb_to_oklab([red, green, blue].map(f64::from));
+        Self::from_lab(l as f32, a as f32, b as f32)
+    }
+}
+
+impl From<Oklabf32> for LinearRgb<Rgbf32> {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: Oklabf32) -> Self {
+        let (lightness, a, b) = color.into_lab();
+        let [r, g, b] = oklab_to_linear_srgb([lightness, a, b].map(f64::from));
+        LinearRgb::new(Rgbf32::from_rgb(r as f32, g as f32, b as f32))
+    }
+}
+
+impl From<Oklchf32> for Oklabf32 {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: Oklchf32) -> Self {
+        let (l, c, h) = color.into_lch();
+        let (sin, cos) = sin_cos_degrees(f64::from(h));
+        let c = f64::from(c);
+        Self::from_lab(l, (c * cos) as f32, (c * sin) as f32)
+    }
+}
+
+/// 32-bit floating-point Oklab color with alpha.
+///
+/// ## Layout
+///
+/// ```c
+/// struct Oklabaf32 {
+///   float l;
+///   float a;
+///   float b;
+///   float alpha;
+/// }
+/// ```
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{oklab::Oklabaf32, rgb::Abgr8888};
+///
+/// let color = Oklabaf32::from_rgba_color(Abgr8888::from_abgr(51, 0, 0, 255));
+/// assert_eq!(color.alpha(), 0.2);
+/// ```
+pub type Oklabaf32 = AlphaLast<f32, Oklabf32>;
+
+impl Oklabaf32 {
+    /// Creates a new Oklab color with alpha from the individual components.
+    #[must_use]
+    pub const fn from_laba(lightness: f32, a: f32, b: f32, alpha: f32) -> Self {
+        Self::with_color(alpha, Oklabf32::from_lab(lightness, a, b))
+    }
+
+    /// Converts any sRGB-encoded RGBA color into Oklab, keeping alpha unchanged.
+    #[must_use]
+    pub fn from_rgba_color<C: RgbaColor>(color: C) -> Self {
+        let (r, g, b, alpha) = color.into_rgba();
+        let linear = LinearRgb::new(Rgbf32::from_rgb(decode(r), decode(g), decode(b)));
+        Self::with_color(alpha.convert(), Oklabf32::from(linear))
+    }
+
+    /// Converts this color into any sRGB-encoded RGBA color, keeping alpha unchanged.
+    #[must_use]
+    pub fn into_rgba_color<C: RgbaColor>(self) -> C {
+        let (color, alpha) = self.into_inner();
+        let (r, g, b) = LinearRgb::<Rgbf32>::from(color).into_inner().into_rgb();
+        C::from_rgba(encode(r), encode(g), encode(b), alpha.convert())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::rgb::{Abgr8888, Argb4444, Rgb888, Rgbaf32};
+
+    #[test]
+    fn from_lab_accessors() {
+        let lab = Oklabf32::from_lab(0.5, -0.1, 0.2);
+        assert_eq!(lab.lightness(), 0.5);
+        assert_eq!(lab.a(), -0.1);
+        assert_eq!(lab.b(), 0.2);
+        assert_eq!(lab.into_lab(), (0.5, -0.1, 0.2));
+    }
+
+    #[test]
+    fn repr_c() {
+        let lab = Oklab::from_lab(1u8, 2, 3);
+        let bytes = unsafe { core::mem::transmute::<Oklab<u8>, [u8; 3]>(lab) };
+        assert_eq!(bytes, [1, 2, 3]);
+    }
+
+    #[test]
+    fn round_trips_rgb888() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = Rgb888::from_rgb(r, g, b);
+                    let lab = Oklabf32::from_rgb_color(rgb);
+                    assert_eq!(lab.into_rgb_color::<Rgb888>(), rgb, "{lab:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_lch() {
+        let (l, a, b) = Oklabf32::from(Oklchf32::from_lch(0.5, 0.1, 180.0)).into_lab();
+        assert_eq!(l, 0.5);
+        assert!((a + 0.1).abs() < 1e-6);
+        assert!(b.abs() < 1e-6);
+    }
+
+    #[test]
+    fn laba_accessors() {
+        let mut color = Oklabaf32::from_laba(0.5, 0.1, -0.1, 0.25);
+        assert_eq!(color.lightness(), 0.5);
+        assert_eq!(color.a(), 0.1);
+        assert_eq!(color.b(), -0.1);
+        assert_eq!(color.alpha(), 0.25);
+
+        color.set_lightness(0.75);
+        assert_eq!(color, Oklabaf32::from_laba(0.75, 0.1, -0.1, 0.25));
+    }
+
+    #[test]
+    fn rgba_color_round_trips_alpha_first() {
+        let abgr = Abgr8888::from_abgr(128, 32, 64, 192);
+        let lab = Oklabaf32::from_rgba_color(abgr);
+        assert_eq!(lab.alpha(), 128.0 / 255.0);
+        assert_eq!(lab.into_rgba_color::<Abgr8888>(), abgr);
+    }
+
+    #[test]
+    fn rgba_color_round_trips_packed() {
+        for packed in (0..=u16::MAX).step_by(7) {
+            let argb = Argb4444::new(packed);
+            let lab = Oklabaf32::from_rgba_color(argb);
+            assert_eq!(lab.into_rgba_color::<Argb4444>(), argb);
+        }
+    }
+
+    #[test]
+    fn rgba_color_float_alpha() {
+        let lab = Oklabaf32::from_rgba_color(Rgbaf32::from_rgba(1.0, 1.0, 1.0, 0.5));
+        assert_eq!(lab.alpha(), 0.5);
+        assert!((lab.lightness() - 1.0).abs() < 1e-6);
+    }
+}
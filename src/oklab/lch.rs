@@ -0,0 +1,230 @@
+use crate::{
+    alpha::AlphaLast,
+    cylindrical::{HasChroma, HasHue, HasLightness},
+    math::{atan2_degrees, hypot},
+    oklab::{Oklabaf32, Oklabf32, impl_has_field},
+    rgb::{RgbColor, RgbaColor},
+};
+
+/// A color representation that contains Oklch `L`, `C`, and `h` components.
+///
+/// This is the cylindrical form of [`Oklab<T>`][crate::oklab::Oklab].
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct Oklch {
+///     T l;
+///     T c;
+///     T h;
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct Oklch<T> {
+    l: T,
+    c: T,
+    h: T,
+}
+
+impl<T> Oklch<T> {
+    /// Creates a new Oklch color with the given lightness, chroma, and hue components.
+    #[must_use]
+    pub const fn from_lch(lightness: T, chroma: T, hue: T) -> Self {
+        Self {
+            l: lightness,
+            c: chroma,
+            h: hue,
+        }
+    }
+
+    /// Consumes the color and returns its lightness, chroma, and hue components.
+    #[must_use]
+    pub fn into_lch(self) -> (T, T, T) {
+        (self.l, self.c, self.h)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Oklch<T> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Oklch<T> where T: bytemuck::Pod {}
+
+impl_has_field!(
+    Oklch,
+    HasLightness {
+        lightness,
+        set_lightness,
+        l
+    }
+);
+impl_has_field!(
+    Oklch,
+    HasChroma {
+        chroma,
+        set_chroma,
+        c
+    }
+);
+impl_has_field!(Oklch, HasHue { hue, set_hue, h });
+
+/// 32-bit floating-point Oklch color.
+///
+/// Lightness is in the range `0.0..=1.0`, chroma is non-negative (typically below `0.4`), and hue
+/// is in degrees.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cylindrical::HasHue, oklab::Oklchf32, rgb::Rgb888};
+///
+/// let blue = Oklchf32::from_rgb_color(Rgb888::from_rgb(0, 0, 255));
+/// assert!((blue.hue() - 264.05).abs() < 0.01);
+/// ```
+pub type Oklchf32 = Oklch<f32>;
+
+impl Oklchf32 {
+    /// Converts any sRGB-encoded RGB color into Oklch.
+    #[must_use]
+    pub fn from_rgb_color<C: RgbColor>(color: C) -> Self {
+        Self::from(Oklabf32::from_rgb_color(color))
+    }
+
+    /// Converts this color into any sRGB-encoded RGB color.
+    ///
+    /// Colors outside of the sRGB gamut are clamped if `C` has integer components.
+    #[must_use]
+    pub fn into_rgb_color<C: RgbColor>(self) -> C {
+        Oklabf32::from(self).into_rgb_color()
+    }
+}
+
+impl From<Oklabf32> for Oklchf32 {
+    /// Converts from Oklab; achromatic colors have a hue of `0.0`.
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(color: Oklabf32) -> Self {
+        let (l, a, b) = color.into_lab();
+        let (a, b) = (f64::from(a), f64::from(b));
+        Self::from_lch(l, hypot(a, b) as f32, atan2_degrees(b, a) as f32)
+    }
+}
+
+/// 32-bit floating-point Oklch color with alpha.
+///
+/// ## Layout
+///
+/// ```c
+/// struct Oklchaf32 {
+///   float l;
+///   float c;
+///   float h;
+///   float alpha;
+/// }
+/// ```
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{cylindrical::HasChroma, oklab::Oklchaf32, rgb::Rgbaf32};
+///
+/// let gray = Oklchaf32::from_rgba_color(Rgbaf32::from_rgba(0.5, 0.5, 0.5, 0.75));
+/// assert!(gray.chroma() < 1e-6);
+/// assert_eq!(gray.alpha(), 0.75);
+/// ```
+pub type Oklchaf32 = AlphaLast<f32, Oklchf32>;
+
+impl Oklchaf32 {
+    /// Creates a new Oklch color with alpha from the individual components.
+    #[must_use]
+    pub const fn from_lcha(lightness: f32, chroma: f32, hue: f32, alpha: f32) -> Self {
+        Self::with_color(alpha, Oklchf32::from_lch(lightness, chroma, hue))
+    }
+
+    /// Converts any sRGB-encoded RGBA color into Oklch, keeping alpha unchanged.
+    #[must_use]
+    pub fn from_rgba_color<C: RgbaColor>(color: C) -> Self {
+        Self::from(Oklabaf32::from_rgba_color(color))
+    }
+
+    /// Converts this color into any sRGB-encoded RGBA color, keeping alpha unchanged.
+    #[must_use]
+    pub fn into_rgba_color<C: RgbaColor>(self) -> C {
+        Oklabaf32::from(self).into_rgba_color()
+    }
+}
+
+impl From<Oklabaf32> for Oklchaf32 {
+    fn from(color: Oklabaf32) -> Self {
+        let (color, alpha) = color.into_inner();
+        Self::with_color(alpha, color.into())
+    }
+}
+
+impl From<Oklchaf32> for Oklabaf32 {
+    fn from(color: Oklchaf32) -> Self {
+        let (color, alpha) = color.into_inner();
+        Self::with_color(alpha, color.into())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::rgb::{Abgr8888, Rgb888};
+
+    #[test]
+    fn from_lch_accessors() {
+        let lch = Oklchf32::from_lch(0.5, 0.1, 270.0);
+        assert_eq!(lch.lightness(), 0.5);
+        assert_eq!(lch.chroma(), 0.1);
+        assert_eq!(lch.hue(), 270.0);
+        assert_eq!(lch.into_lch(), (0.5, 0.1, 270.0));
+    }
+
+    #[test]
+    fn from_rgb_color_red() {
+        // Reference values from CSS Color 4.
+        let (l, c, h) = Oklchf32::from_rgb_color(Rgb888::from_rgb(255, 0, 0)).into_lch();
+        assert!((l - 0.627_955).abs() < 1e-4);
+        assert!((c - 0.257_683).abs() < 1e-4);
+        assert!((h - 29.234).abs() < 1e-2);
+    }
+
+    #[test]
+    fn from_oklab_achromatic_has_zero_hue() {
+        let lch = Oklchf32::from(Oklabf32::from_lab(0.5, 0.0, 0.0));
+        assert_eq!(lch, Oklchf32::from_lch(0.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn round_trips_rgb888() {
+        for r in (0..=255).step_by(17) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(17) {
+                    let rgb = Rgb888::from_rgb(r, g, b);
+                    let lch = Oklchf32::from_rgb_color(rgb);
+                    assert_eq!(lch.into_rgb_color::<Rgb888>(), rgb, "{lch:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lcha_set_hue_keeps_alpha() {
+        let mut color = Oklchaf32::from_lcha(0.7, 0.1, 30.0, 0.5);
+        color.set_hue(120.0);
+        assert_eq!(color, Oklchaf32::from_lcha(0.7, 0.1, 120.0, 0.5));
+    }
+
+    #[test]
+    fn rgba_color_round_trips() {
+        let abgr = Abgr8888::from_abgr(64, 200, 100, 10);
+        let lch = Oklchaf32::from_rgba_color(abgr);
+        assert_eq!(lch.alpha(), 64.0 / 255.0);
+        assert_eq!(lch.into_rgba_color::<Abgr8888>(), abgr);
+    }
+}
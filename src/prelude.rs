@@ -28,6 +28,7 @@
 
 pub use crate::{
     alpha::HasAlpha as _,
+    component::Component as _,
     gray::HasGray as _,
     rgb::{
         Abgr8888, HasBlue as _, HasGreen as _, HasRed as _, Rgb888, RgbColor as _, Rgbaf32, Rgbf32,
@@ -0,0 +1,453 @@
+//! Reducing 🎯 colors to a small palette, such as for GIF images or indexed textures.
+//!
+//! This module contains:
+//!
+//! - [`Quantizer`], which chooses a palette of up to 256 colors for a slice of pixels
+//! - [`Algorithm`], the algorithm that chooses the palette
+//! - [`Space`], the color space in which colors are compared
+//! - [`Quantized`], the palette and the index of the palette color of each pixel
+//!
+//! This module requires the `alloc` feature.
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{quantize::{Algorithm, Quantizer}, rgb::Abgr8888};
+//!
+//! let red = Abgr8888::from_abgr(255, 0, 0, 255);
+//! let blue = Abgr8888::from_abgr(255, 255, 0, 0);
+//! let pixels = [red, blue, red, red];
+//!
+//! let quantized = Quantizer::new(Algorithm::MedianCut, 2).quantize(&pixels);
+//! assert_eq!(quantized.palette().len(), 2);
+//! assert_eq!(quantized.get(0), Some(red));
+//! assert_eq!(quantized.get(1), Some(blue));
+//! ```
+//!
+//! ## Alpha
+//!
+//! Colors are compared with their components premultiplied by alpha, and alpha as a fourth
+//! component. Fully transparent pixels are therefore all the same color, regardless of their red,
+//! green, and blue components, and translucent pixels are grouped with pixels of similar opacity.
+//!
+//! ## Algorithms
+//!
+//! Algorithm                  | Speed   | Description
+//! -------------------------- | ------- | -----------
+//! [`Algorithm::MedianCut`]   | Fast    | Repeatedly splits the group of colors with the most error
+//! [`Algorithm::Octree`]      | Fast    | Merges the least common colors in a tree of color cubes
+//! [`Algorithm::KMeans`]      | Slow    | Refines the median cut palette to minimize error
+//!
+//! Every algorithm is deterministic, so the same pixels always produce the same palette.
+
+use alloc::vec::Vec;
+
+use crate::{
+    alpha::HasAlpha,
+    component::Component,
+    oklab::Oklabf32,
+    rgb::{HasBlue, HasGreen, HasRed, RgbColor, RgbaColor, Rgbf32},
+};
+
+mod kmeans;
+mod median_cut;
+mod octree;
+
+/// An algorithm that chooses the colors of a palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Median cut, which starts with every color in one group, and repeatedly splits the group
+    /// with the most error at the weighted median of its widest component.
+    MedianCut,
+
+    /// Octree, which sorts colors into a tree of nested color cubes, and repeatedly merges the
+    /// least common cubes until there are few enough.
+    ///
+    /// Each node has a child for each half of red, green, blue, and alpha, so the tree has 16
+    /// children per node rather than 8. The palette may have fewer colors than requested.
+    Octree,
+
+    /// K-means clustering, which starts from the median cut palette and moves each palette color
+    /// to the mean of the colors nearest to it, for up to `iterations` rounds.
+    KMeans {
+        /// The maximum number of rounds; clustering stops early if no color changes group.
+        iterations: u32,
+    },
+}
+
+/// The color space in which colors are compared and averaged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Space {
+    /// sRGB-encoded components, as they are stored.
+    #[default]
+    Srgb,
+
+    /// [Oklab](crate::oklab), where distances match perceived differences more closely.
+    Oklab,
+}
+
+/// Chooses a palette for a slice of pixels.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{quantize::{Algorithm, Quantizer, Space}, rgb::Rgbaf32};
+///
+/// let pixels = [
+///     Rgbaf32::from_rgba(1.0, 0.0, 0.0, 1.0),
+///     Rgbaf32::from_rgba(0.9, 0.0, 0.0, 1.0),
+///     Rgbaf32::from_rgba(0.0, 0.0, 1.0, 0.5),
+/// ];
+///
+/// let quantizer = Quantizer::new(Algorithm::KMeans { iterations: 8 }, 2).with_space(Space::Oklab);
+/// let quantized = quantizer.quantize(&pixels);
+/// assert_eq!(quantized.indices()[0], quantized.indices()[1]);
+/// assert_ne!(quantized.indices()[0], quantized.indices()[2]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quantizer {
+    algorithm: Algorithm,
+    space: Space,
+    colors: usize,
+}
+
+impl Quantizer {
+    /// Creates a quantizer that chooses up to `colors` colors, compared in [`Space::Srgb`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `colors` is not within `1..=256`.
+    #[must_use]
+    pub const fn new(algorithm: Algorithm, colors: usize) -> Self {
+        assert!(
+            colors >= 1 && colors <= 256,
+            "colors must be within 1..=256"
+        );
+        Self {
+            algorithm,
+            space: Space::Srgb,
+            colors,
+        }
+    }
+
+    /// Returns a copy of this quantizer that compares colors in `space`.
+    #[must_use]
+    pub const fn with_space(self, space: Space) -> Self {
+        Self { space, ..self }
+    }
+
+    /// Returns the algorithm that chooses the palette.
+    #[must_use]
+    pub const fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Returns the color space in which colors are compared.
+    #[must_use]
+    pub const fn space(&self) -> Space {
+        self.space
+    }
+
+    /// Returns the maximum number of colors in the palette.
+    #[must_use]
+    pub const fn colors(&self) -> usize {
+        self.colors
+    }
+
+    /// Chooses a palette for `pixels`, and the nearest palette color for each pixel.
+    ///
+    /// The palette has no more colors than [`Self::colors`], or than there are distinct pixels.
+    #[must_use]
+    pub fn quantize<C: RgbaColor + Copy>(&self, pixels: &[C]) -> Quantized<C> {
+        let mut points: Vec<Point> = pixels.iter().map(|&p| to_point(p, self.space)).collect();
+        let samples = deduplicate(&mut points);
+
+        let centroids = match self.algorithm {
+            Algorithm::MedianCut => median_cut::median_cut(&mut samples.clone(), self.colors),
+            Algorithm::Octree => octree::octree(&samples, self.colors, self.space),
+            Algorithm::KMeans { iterations } => {
+                let initial = median_cut::median_cut(&mut samples.clone(), self.colors);
+                kmeans::kmeans(&samples, initial, iterations)
+            }
+        };
+
+        let nearest: Vec<u8> = samples
+            .iter()
+            .map(|s| nearest(&centroids, s.point))
+            .collect();
+        let indices = pixels
+            .iter()
+            .map(|&p| {
+                let target = key(to_point(p, self.space));
+                let i = samples.partition_point(|s| key(s.point) < target);
+                nearest[i]
+            })
+            .collect();
+        let palette = centroids
+            .iter()
+            .map(|&c| from_point(c, self.space))
+            .collect();
+        Quantized { palette, indices }
+    }
+}
+
+/// A palette, and the index of the palette color of each pixel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantized<C> {
+    palette: Vec<C>,
+    indices: Vec<u8>,
+}
+
+impl<C> Quantized<C> {
+    /// Returns the colors of the palette.
+    #[must_use]
+    pub fn palette(&self) -> &[C] {
+        &self.palette
+    }
+
+    /// Returns the index into [`Self::palette`] of each pixel, in the same order as the pixels.
+    #[must_use]
+    pub fn indices(&self) -> &[u8] {
+        &self.indices
+    }
+
+    /// Returns the palette color of the pixel at `index`, or `None` if it is out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<C>
+    where
+        C: Copy,
+    {
+        let &i = self.indices.get(index)?;
+        Some(self.palette[usize::from(i)])
+    }
+
+    /// Returns the palette and the indices.
+    #[must_use]
+    pub fn into_parts(self) -> (Vec<C>, Vec<u8>) {
+        (self.palette, self.indices)
+    }
+}
+
+/// A color in the space being quantized, with components premultiplied by alpha, then alpha.
+type Point = [f32; 4];
+
+/// A distinct color, and the number of pixels with that color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sample {
+    point: Point,
+    weight: f64,
+}
+
+/// Returns a key that orders points by their bits, so that equal points are adjacent.
+fn key(point: Point) -> [u32; 4] {
+    point.map(f32::to_bits)
+}
+
+/// Sorts `points` and merges equal points into samples, in the order of their [`key`].
+fn deduplicate(points: &mut [Point]) -> Vec<Sample> {
+    points.sort_unstable_by_key(|&p| key(p));
+    let mut samples: Vec<Sample> = Vec::new();
+    for &point in &*points {
+        match samples.last_mut() {
+            Some(last) if key(last.point) == key(point) => last.weight += 1.0,
+            _ => samples.push(Sample { point, weight: 1.0 }),
+        }
+    }
+    samples
+}
+
+/// Converts a color into a point in `space`.
+fn to_point<C: RgbaColor>(color: C, space: Space) -> Point {
+    let (red, green, blue, alpha) = color.into_rgba();
+    let alpha = alpha.to_normalized_f32().clamp(0.0, 1.0);
+    let rgb = [
+        red.to_normalized_f32(),
+        green.to_normalized_f32(),
+        blue.to_normalized_f32(),
+    ];
+    let [x, y, z] = match space {
+        Space::Srgb => rgb,
+        Space::Oklab => {
+            let (l, a, b) =
+                Oklabf32::from_rgb_color(Rgbf32::from_rgb(rgb[0], rgb[1], rgb[2])).into_lab();
+            [l, a, b]
+        }
+    };
+    [x * alpha, y * alpha, z * alpha, alpha]
+}
+
+/// Converts a point in `space` into a color, clamping it to the sRGB gamut.
+fn from_point<C: RgbaColor>(point: Point, space: Space) -> C {
+    let alpha = point[3];
+    let xyz = if alpha > 0.0 {
+        [point[0] / alpha, point[1] / alpha, point[2] / alpha]
+    } else {
+        [0.0; 3]
+    };
+    let (red, green, blue) = match space {
+        Space::Srgb => (xyz[0], xyz[1], xyz[2]),
+        Space::Oklab => Oklabf32::from_lab(xyz[0], xyz[1], xyz[2])
+            .into_rgb_color::<Rgbf32>()
+            .into_rgb(),
+    };
+    let [red, green, blue, alpha] = [red, green, blue, alpha].map(|c| c.clamp(0.0, 1.0));
+    C::from_rgba(
+        <C as HasRed>::Component::from_normalized_f32(red),
+        <C as HasGreen>::Component::from_normalized_f32(green),
+        <C as HasBlue>::Component::from_normalized_f32(blue),
+        <C as HasAlpha>::Component::from_normalized_f32(alpha),
+    )
+}
+
+/// Returns the squared distance between two points.
+fn distance(a: Point, b: Point) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+/// Returns the index of the centroid nearest to `point`, preferring the first of equal distances.
+#[allow(clippy::cast_possible_truncation)]
+fn nearest(centroids: &[Point], point: Point) -> u8 {
+    let mut best = (0, f32::INFINITY);
+    for (i, &centroid) in centroids.iter().enumerate() {
+        let d = distance(centroid, point);
+        if d < best.1 {
+            best = (i, d);
+        }
+    }
+    best.0 as u8
+}
+
+/// Returns the weighted mean of `samples`, or `None` if their total weight is zero.
+#[allow(clippy::cast_possible_truncation)]
+fn mean<'a>(samples: impl IntoIterator<Item = &'a Sample>) -> Option<Point> {
+    let mut sum = [0.0f64; 4];
+    let mut weight = 0.0;
+    for sample in samples {
+        for (sum, c) in sum.iter_mut().zip(sample.point) {
+            *sum += f64::from(c) * sample.weight;
+        }
+        weight += sample.weight;
+    }
+    (weight > 0.0).then(|| sum.map(|s| (s / weight) as f32))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    use crate::rgb::{Abgr8888, Rgbaf32};
+
+    fn abgr(a: u8, b: u8, g: u8, r: u8) -> Abgr8888 {
+        Abgr8888::from_abgr(a, b, g, r)
+    }
+
+    const ALGORITHMS: [Algorithm; 3] = [
+        Algorithm::MedianCut,
+        Algorithm::Octree,
+        Algorithm::KMeans { iterations: 16 },
+    ];
+
+    #[test]
+    fn empty() {
+        for algorithm in ALGORITHMS {
+            let quantized = Quantizer::new(algorithm, 16).quantize::<Abgr8888>(&[]);
+            assert!(quantized.palette().is_empty());
+            assert!(quantized.indices().is_empty());
+        }
+    }
+
+    #[test]
+    fn few_colors_are_exact() {
+        let colors = [
+            abgr(255, 0, 0, 255),
+            abgr(255, 0, 255, 0),
+            abgr(255, 255, 0, 0),
+            abgr(128, 10, 20, 30),
+        ];
+        let pixels: Vec<Abgr8888> = (0..64).map(|i| colors[i * 7 % 4]).collect();
+        for algorithm in ALGORITHMS {
+            for space in [Space::Srgb, Space::Oklab] {
+                let quantized = Quantizer::new(algorithm, 8)
+                    .with_space(space)
+                    .quantize(&pixels);
+                assert_eq!(quantized.palette().len(), 4, "{algorithm:?} {space:?}");
+                for (i, &pixel) in pixels.iter().enumerate() {
+                    assert_eq!(quantized.get(i), Some(pixel), "{algorithm:?} {space:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transparent_pixels_are_one_color() {
+        let pixels = [
+            abgr(0, 255, 0, 0),
+            abgr(0, 0, 255, 0),
+            abgr(0, 0, 0, 255),
+            abgr(255, 0, 0, 0),
+        ];
+        for algorithm in ALGORITHMS {
+            let quantized = Quantizer::new(algorithm, 2).quantize(&pixels);
+            assert_eq!(quantized.palette().len(), 2, "{algorithm:?}");
+            assert_eq!(quantized.get(0), Some(abgr(0, 0, 0, 0)), "{algorithm:?}");
+            assert_eq!(quantized.indices()[..3], [quantized.indices()[0]; 3]);
+            assert_eq!(quantized.get(3), Some(abgr(255, 0, 0, 0)), "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn alpha_separates_colors() {
+        // Two opaque reds and two translucent reds; alpha must decide the groups.
+        let pixels = [
+            Rgbaf32::from_rgba(1.0, 0.0, 0.0, 1.0),
+            Rgbaf32::from_rgba(0.9, 0.0, 0.0, 1.0),
+            Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.2),
+            Rgbaf32::from_rgba(0.9, 0.0, 0.0, 0.2),
+        ];
+        for algorithm in ALGORITHMS {
+            let quantized = Quantizer::new(algorithm, 2).quantize(&pixels);
+            let indices = quantized.indices();
+            assert_eq!(indices[0], indices[1], "{algorithm:?}");
+            assert_eq!(indices[2], indices[3], "{algorithm:?}");
+            assert_ne!(indices[0], indices[2], "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn gradient_uses_every_color() {
+        let pixels: Vec<Abgr8888> = (0..=255).map(|v| abgr(255, v, v, v)).collect();
+        for algorithm in [Algorithm::MedianCut, Algorithm::KMeans { iterations: 16 }] {
+            let quantized = Quantizer::new(algorithm, 16).quantize(&pixels);
+            assert_eq!(quantized.palette().len(), 16, "{algorithm:?}");
+            let mut used = [false; 16];
+            for &i in quantized.indices() {
+                used[usize::from(i)] = true;
+            }
+            assert_eq!(used, [true; 16], "{algorithm:?}");
+            for (i, &pixel) in pixels.iter().enumerate() {
+                let (r, ..) = quantized.get(i).unwrap().into_rgba();
+                assert!(r.abs_diff(pixel.into_rgba().0) <= 16, "{algorithm:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn oklab_round_trips_palette() {
+        let color = abgr(200, 30, 120, 240);
+        let point = to_point(color, Space::Oklab);
+        assert_eq!(from_point::<Abgr8888>(point, Space::Oklab), color);
+    }
+
+    #[test]
+    #[should_panic = "colors must be within 1..=256"]
+    fn zero_colors_panics() {
+        let _ = Quantizer::new(Algorithm::MedianCut, 0);
+    }
+
+    #[test]
+    #[should_panic = "colors must be within 1..=256"]
+    fn too_many_colors_panics() {
+        let _ = Quantizer::new(Algorithm::Octree, 257);
+    }
+}
@@ -0,0 +1,85 @@
+//! K-means quantization, using Lloyd's algorithm.
+
+use alloc::{vec, vec::Vec};
+
+use super::{Point, Sample, nearest};
+
+/// Refines `centroids` for at most `iterations` rounds.
+///
+/// Each round assigns every sample to its nearest centroid, then moves each centroid to the
+/// weighted mean of its samples. A centroid without samples stays where it is, and refinement
+/// stops early once no sample changes centroid.
+pub(super) fn kmeans(samples: &[Sample], mut centroids: Vec<Point>, iterations: u32) -> Vec<Point> {
+    let mut assignments = vec![u8::MAX; samples.len()];
+    for _ in 0..iterations {
+        let mut changed = false;
+        for (assignment, sample) in assignments.iter_mut().zip(samples) {
+            let i = nearest(&centroids, sample.point);
+            changed |= *assignment != i;
+            *assignment = i;
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0.0f64; 4], 0.0f64); centroids.len()];
+        for (&assignment, sample) in assignments.iter().zip(samples) {
+            let (sum, weight) = &mut sums[usize::from(assignment)];
+            for (sum, c) in sum.iter_mut().zip(sample.point) {
+                *sum += f64::from(c) * sample.weight;
+            }
+            *weight += sample.weight;
+        }
+        for (centroid, (sum, weight)) in centroids.iter_mut().zip(sums) {
+            if weight > 0.0 {
+                #[allow(clippy::cast_possible_truncation)]
+                let mean = sum.map(|s| (s / weight) as f32);
+                *centroid = mean;
+            }
+        }
+    }
+    centroids
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn sample(value: f32, weight: f64) -> Sample {
+        Sample {
+            point: [value, 0.0, 0.0, 1.0],
+            weight,
+        }
+    }
+
+    #[test]
+    fn moves_centroids_to_cluster_means() {
+        let samples = [
+            sample(0.0, 1.0),
+            sample(0.2, 1.0),
+            sample(0.8, 3.0),
+            sample(1.0, 1.0),
+        ];
+        let initial = vec![[0.4, 0.0, 0.0, 1.0], [0.6, 0.0, 0.0, 1.0]];
+        let centroids = kmeans(&samples, initial, 10);
+        assert!((centroids[0][0] - 0.1).abs() < 1e-6);
+        assert!((centroids[1][0] - 0.85).abs() < 1e-6);
+    }
+
+    #[test]
+    fn empty_clusters_keep_their_centroid() {
+        let samples = [sample(0.0, 1.0), sample(0.1, 1.0)];
+        let initial = vec![[0.0, 0.0, 0.0, 1.0], [1.0, 0.0, 0.0, 1.0]];
+        let centroids = kmeans(&samples, initial, 10);
+        assert!((centroids[0][0] - 0.05).abs() < 1e-6);
+        assert_eq!(centroids[1], [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn zero_iterations_keep_initial_centroids() {
+        let samples = [sample(0.0, 1.0)];
+        let initial = vec![[0.5, 0.0, 0.0, 1.0]];
+        assert_eq!(kmeans(&samples, initial.clone(), 0), initial);
+    }
+}
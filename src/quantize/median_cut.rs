@@ -0,0 +1,136 @@
+//! Median cut quantization.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::{Point, Sample, mean};
+
+/// A group of samples, and how much error splitting it along its widest component would remove.
+struct Group {
+    range: Range<usize>,
+    axis: usize,
+    error: f64,
+}
+
+impl Group {
+    /// Measures the samples in `range`.
+    fn new(samples: &[Sample], range: Range<usize>) -> Self {
+        let group = &samples[range.clone()];
+        let mut axis = 0;
+        let mut error = 0.0;
+        if group.len() > 1 {
+            let mean = mean(group).unwrap_or_default();
+            for (i, &m) in mean.iter().enumerate() {
+                let sum: f64 = group
+                    .iter()
+                    .map(|s| {
+                        let d = f64::from(s.point[i] - m);
+                        s.weight * d * d
+                    })
+                    .sum();
+                if sum > error {
+                    (axis, error) = (i, sum);
+                }
+            }
+        }
+        Self { range, axis, error }
+    }
+}
+
+/// Returns up to `colors` centroids, reordering `samples` into groups.
+///
+/// The group with the largest weighted squared error along a single component is split at the
+/// weighted median of that component, until there are `colors` groups or no group can be split.
+#[allow(clippy::float_cmp)]
+pub(super) fn median_cut(samples: &mut [Sample], colors: usize) -> Vec<Point> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+    let mut groups = Vec::with_capacity(colors);
+    groups.push(Group::new(samples, 0..samples.len()));
+
+    while groups.len() < colors {
+        let Some((i, _)) = groups
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| g.error > 0.0)
+            .max_by(|(_, a), (_, b)| a.error.total_cmp(&b.error))
+        else {
+            break;
+        };
+        let Group { range, axis, .. } = groups.swap_remove(i);
+        let group = &mut samples[range.clone()];
+        group.sort_unstable_by(|a, b| a.point[axis].total_cmp(&b.point[axis]));
+
+        // Split after the weighted median, keeping at least one sample on each side.
+        let half = group.iter().map(|s| s.weight).sum::<f64>() / 2.0;
+        let mut total = 0.0;
+        let mut split = 1;
+        for (j, sample) in group.iter().enumerate().take(group.len() - 1) {
+            total += sample.weight;
+            split = j + 1;
+            if total >= half {
+                break;
+            }
+        }
+        // Equal values must not be split apart, or the two groups would overlap.
+        while split > 1 && group[split].point[axis] == group[split - 1].point[axis] {
+            split -= 1;
+        }
+        if group[split].point[axis] == group[split - 1].point[axis] {
+            split = group
+                .iter()
+                .position(|s| s.point[axis] != group[0].point[axis])
+                .unwrap_or(group.len());
+        }
+
+        let mid = range.start + split;
+        groups.push(Group::new(samples, range.start..mid));
+        groups.push(Group::new(samples, mid..range.end));
+    }
+
+    groups
+        .iter()
+        .filter_map(|g| mean(&samples[g.range.clone()]))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn sample(value: f32, weight: f64) -> Sample {
+        Sample {
+            point: [value, 0.0, 0.0, 1.0],
+            weight,
+        }
+    }
+
+    #[test]
+    fn splits_at_weighted_median() {
+        let mut samples = [
+            sample(0.0, 1.0),
+            sample(0.1, 1.0),
+            sample(0.9, 1.0),
+            sample(1.0, 5.0),
+        ];
+        let mut centroids = median_cut(&mut samples, 2);
+        centroids.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        assert!((centroids[0][0] - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(centroids[1][0], 1.0);
+    }
+
+    #[test]
+    fn never_splits_equal_values() {
+        let mut samples = [sample(0.5, 1.0), sample(0.5, 1.0), sample(0.7, 1.0)];
+        let centroids = median_cut(&mut samples, 3);
+        assert_eq!(centroids.len(), 2);
+    }
+
+    #[test]
+    fn stops_when_every_group_is_one_color() {
+        let mut samples = [sample(0.0, 3.0), sample(1.0, 1.0)];
+        assert_eq!(median_cut(&mut samples, 256).len(), 2);
+    }
+}
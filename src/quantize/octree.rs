@@ -0,0 +1,192 @@
+//! Octree quantization, extended with alpha.
+
+use alloc::{vec, vec::Vec};
+
+use super::{Point, Sample, Space};
+
+/// The depth of the leaves of a full tree; each level splits every component in half.
+const DEPTH: usize = 8;
+
+/// A cube of colors, which is either a leaf or has up to 16 smaller cubes.
+#[derive(Clone)]
+struct Node {
+    /// The indices of the children, where `0` (the root) means there is no child.
+    children: [u32; 16],
+
+    /// The weighted sum of the samples in this cube, if it is a leaf.
+    sum: [f64; 4],
+
+    /// The total weight of the samples in this cube, including its children.
+    weight: f64,
+
+    leaf: bool,
+}
+
+impl Node {
+    const EMPTY: Self = Self {
+        children: [0; 16],
+        sum: [0.0; 4],
+        weight: 0.0,
+        leaf: false,
+    };
+}
+
+/// Returns the coordinates of a point in the tree, with each component scaled to 8 bits.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn coordinates(point: Point, space: Space) -> [u8; 4] {
+    let [x, y, z, alpha] = point;
+    let [x, y, z] = match space {
+        Space::Srgb => [x, y, z],
+        // Opponent axes are premultiplied, so they are centered on half of alpha.
+        Space::Oklab => [x, y + alpha * 0.5, z + alpha * 0.5],
+    };
+    [x, y, z, alpha].map(|c| (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8)
+}
+
+/// Returns up to `colors` centroids.
+///
+/// Every sample is inserted into a leaf at the deepest level. Then, while there are too many
+/// leaves, the lightest node at the deepest level with children is merged into a single leaf.
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn octree(samples: &[Sample], colors: usize, space: Space) -> Vec<Point> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+    let mut nodes = vec![Node::EMPTY];
+    let mut levels: [Vec<u32>; DEPTH] = Default::default();
+    let mut leaves = 0;
+
+    for sample in samples {
+        let coordinates = coordinates(sample.point, space);
+        let mut node = 0;
+        for (level, parents) in levels.iter_mut().enumerate() {
+            nodes[node].weight += sample.weight;
+            let shift = DEPTH - 1 - level;
+            let child = coordinates.iter().enumerate().fold(0, |child, (i, &c)| {
+                child | (usize::from((c >> shift) & 1) << i)
+            });
+            if nodes[node].children[child] == 0 {
+                if nodes[node].children == [0; 16] {
+                    parents.push(node as u32);
+                }
+                nodes[node].children[child] = nodes.len() as u32;
+                nodes.push(Node::EMPTY);
+            }
+            node = nodes[node].children[child] as usize;
+        }
+        let leaf = &mut nodes[node];
+        if !leaf.leaf {
+            leaf.leaf = true;
+            leaves += 1;
+        }
+        leaf.weight += sample.weight;
+        for (sum, c) in leaf.sum.iter_mut().zip(sample.point) {
+            *sum += f64::from(c) * sample.weight;
+        }
+    }
+
+    for parents in levels.iter_mut().rev() {
+        if leaves <= colors {
+            break;
+        }
+        // Merge the heaviest nodes last, so that common colors keep the most detail.
+        parents.sort_by(|&a, &b| {
+            let (a, b) = (&nodes[a as usize], &nodes[b as usize]);
+            a.weight.total_cmp(&b.weight)
+        });
+        for &parent in &*parents {
+            if leaves <= colors {
+                break;
+            }
+            let mut sum = [0.0; 4];
+            let mut merged = 0;
+            for child in nodes[parent as usize].children {
+                if child != 0 {
+                    let child = &nodes[child as usize];
+                    for (sum, c) in sum.iter_mut().zip(child.sum) {
+                        *sum += c;
+                    }
+                    merged += 1;
+                }
+            }
+            let parent = &mut nodes[parent as usize];
+            parent.children = [0; 16];
+            parent.sum = sum;
+            parent.leaf = true;
+            leaves -= merged - 1;
+        }
+    }
+
+    let mut centroids = Vec::with_capacity(leaves);
+    let mut stack = vec![0];
+    while let Some(node) = stack.pop() {
+        let node = &nodes[node];
+        if node.leaf {
+            centroids.push(node.sum.map(|s| (s / node.weight) as f32));
+        } else {
+            stack.extend(
+                node.children
+                    .iter()
+                    .rev()
+                    .filter(|&&c| c != 0)
+                    .map(|&c| c as usize),
+            );
+        }
+    }
+    centroids
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn sample(point: Point, weight: f64) -> Sample {
+        Sample { point, weight }
+    }
+
+    #[test]
+    fn keeps_distinct_colors() {
+        let samples = [
+            sample([0.0, 0.0, 0.0, 1.0], 1.0),
+            sample([1.0, 1.0, 1.0, 1.0], 1.0),
+        ];
+        assert_eq!(
+            octree(&samples, 4, Space::Srgb),
+            [[0.0, 0.0, 0.0, 1.0], [1.0, 1.0, 1.0, 1.0]]
+        );
+    }
+
+    #[test]
+    fn merges_lightest_cube_first() {
+        // The two near-whites are rare, so they are averaged together before the near-blacks.
+        let samples = [
+            sample([0.0, 0.0, 0.0, 1.0], 10.0),
+            sample([0.1, 0.0, 0.0, 1.0], 10.0),
+            sample([0.9, 0.9, 0.9, 1.0], 1.0),
+            sample([1.0, 1.0, 1.0, 1.0], 1.0),
+        ];
+        let centroids = octree(&samples, 3, Space::Srgb);
+        assert_eq!(centroids.len(), 3);
+        assert!(centroids.contains(&[0.0, 0.0, 0.0, 1.0]));
+        assert!(centroids.contains(&[0.1, 0.0, 0.0, 1.0]));
+        assert!(centroids.contains(&[0.95, 0.95, 0.95, 1.0]));
+    }
+
+    #[test]
+    fn reduces_to_one_color() {
+        let samples = [
+            sample([0.0, 0.0, 0.0, 0.0], 1.0),
+            sample([1.0, 1.0, 1.0, 1.0], 3.0),
+        ];
+        assert_eq!(octree(&samples, 1, Space::Srgb), [[0.75, 0.75, 0.75, 0.75]]);
+    }
+
+    #[test]
+    fn oklab_coordinates_are_centered() {
+        let white = coordinates([1.0, 0.0, 0.0, 1.0], Space::Oklab);
+        assert_eq!(white, [255, 128, 128, 255]);
+        let clear = coordinates([0.0; 4], Space::Oklab);
+        assert_eq!(clear, [0; 4]);
+    }
+}
@@ -34,6 +34,48 @@
 //! [`Rgbaf32`]  | 128            | 32 bits each for red, green, blue, alpha
 //! [`Rgbf32`]   | 96             | 32 bits each for red, green, blue
 //!
+//! ## Conversions
+//!
+//! Every predefined type implements [`From`] for every other predefined type:
+//!
+//! - Widening integer components scales them, so a 5-bit `31` becomes an 8-bit `255`.
+//! - Narrowing integer components rounds to the nearest representable value.
+//! - Floating-point components are clamped to `0.0..=1.0` when converted to integers.
+//! - Types without an alpha channel are treated as fully opaque; converting to such a type drops
+//!   the alpha channel.
+//!
+//! ```rust
+//! use gem::rgb::{Abgr8888, Rgb565};
+//!
+//! let white = Abgr8888::from(Rgb565::from_rgb(31, 63, 31));
+//! assert_eq!(white, Abgr8888::from_abgr(255, 255, 255, 255));
+//! ```
+//!
+//! To convert many colors at once, [`convert_slice`] produces the same results as [`From`], but
+//! uses SIMD instructions for common pairs, such as [`Rgb565`] into [`Abgr8888`].
+//!
+//! ## Formatting
+//!
+//! Every type implements [`Display`][core::fmt::Display] as CSS functional notation, which can be
+//! parsed by [`crate::css`], and [`LowerHex`][core::fmt::LowerHex] and
+//! [`UpperHex`][core::fmt::UpperHex] as `#rrggbbaa`:
+//!
+//! - Integer components are written as `rgb()`, with alpha as a percentage if it is not opaque.
+//! - Floating-point components are written as `color(srgb …)`, using the shortest number that
+//!   parses to the same value.
+//! - Hex colors round every component to 8 bits, and types without alpha write `ff`.
+//!
+//! ```rust
+//! use gem::rgb::{Abgr8888, Rgbf32};
+//!
+//! let red = Abgr8888::from_abgr(128, 0, 0, 255);
+//! assert_eq!(format!("{red}"), "rgb(255 0 0 / 50%)");
+//! assert_eq!(format!("{red:x}"), "#ff000080");
+//!
+//! let orange = Rgbf32::from_rgb(1.0, 0.6, 0.0);
+//! assert_eq!(format!("{orange}"), "color(srgb 1 0.6 0)");
+//! ```
+//!
 //! ## Generic Types
 //!
 //! Structs with generic types allow easily creating custom RGB types:
@@ -53,11 +95,14 @@
 //!
 //! [`AlphaFirst`]: `crate::alpha::AlphaFirst`
 
+mod convert_slice;
+pub(crate) mod display;
 mod formats;
 mod impl_rgb_alpha_wrappers;
 mod macros;
 mod traits;
 
+pub use convert_slice::{ConvertSlice, convert_slice};
 pub use formats::*;
 pub use traits::*;
 pub use traits::{HasBlue as _, HasGreen as _, HasRed as _, RgbColor as _, RgbaColor as _};
@@ -75,6 +120,7 @@ pub use traits::{HasBlue as _, HasGreen as _, HasRed as _, RgbColor as _, RgbaCo
 /// };
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Rgb<T> {
     r: T,
@@ -115,6 +161,7 @@ macros::impl_rgb_with_fields!(Rgb<T>);
 /// };
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Bgr<T> {
     b: T,
@@ -130,10 +177,11 @@ impl<T> Bgr<T> {
     /// ```rust
     /// use gem::rgb::{HasBlue, HasGreen, HasRed, Bgr};
     ///
-    /// let color = Bgr::from_bgr(0, 255, 0);
+    /// let color = Bgr::from_bgr(0u8, 255, 0);
     /// assert_eq!(color.blue(), 0);
     /// assert_eq!(color.green(), 255);
     /// assert_eq!(color.red(), 0);
+    /// ```
     #[must_use]
     pub const fn from_bgr(blue: T, green: T, red: T) -> Self {
         Self {
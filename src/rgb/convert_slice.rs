@@ -0,0 +1,444 @@
+//! Conversions between slices of predefined formats.
+//!
+//! Most pairs convert each color with [`From`]. Pairs of 8-bit formats, and conversions from
+//! [`Rgb565`] and between 8-bit and floating-point formats with alpha, use specialized kernels that
+//! produce exactly the same results:
+//!
+//! Source                     | Destination                | Kernel
+//! -------------------------- | -------------------------- | ---------------------------
+//! [`Abgr8888`], [`Argb8888`] | [`Argb8888`], [`Abgr8888`] | SSE2, AVX2, NEON
+//! [`Rgb565`]                 | [`Abgr8888`], [`Argb8888`] | SSE2, AVX2, NEON
+//! [`Abgr8888`], [`Argb8888`] | [`Rgbaf32`]                | SSE2, AVX2, NEON
+//! [`Rgbaf32`]                | [`Abgr8888`], [`Argb8888`] | SSE2, AVX2, NEON
+//! Any 8-bit format           | Any other 8-bit format     | Byte shuffle
+//!
+//! On x86-64, SSE2 is always available and AVX2 is detected at runtime with the `std` feature; on
+//! 64-bit ARM, NEON is used when enabled at compile time (the default). Otherwise, or without `std`,
+//! the kernels fall back to the instruction sets enabled at compile time, or to portable code.
+
+use core::{mem, slice};
+
+use crate::rgb::{Abgr8888, Argb1555, Argb4444, Argb8888, Bgr888, Rgb565, Rgb888, Rgbaf32, Rgbf32};
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon;
+mod scalar;
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+use scalar::OPAQUE;
+
+/// Converts a slice of one predefined format into a slice of another predefined format.
+///
+/// This trait is implemented for every pair of predefined formats, including converting a format
+/// into itself, which copies the slice. Use [`convert_slice`] to call it.
+pub trait ConvertSlice<D>: Copy + sealed::Sealed {
+    /// Converts each color in `src` into the color at the same index in `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    fn convert_slice(src: &[Self], dst: &mut [D]);
+}
+
+/// Converts each color in `src` into the color at the same index in `dst`.
+///
+/// The results are identical to converting each color with [`From`], but conversions between
+/// common formats use SIMD instructions when available.
+///
+/// ## Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::rgb::{Abgr8888, Rgb565, convert_slice};
+///
+/// let src = [Rgb565::from_rgb(31, 0, 0); 32];
+/// let mut dst = [Abgr8888::default(); 32];
+/// convert_slice(&src, &mut dst);
+///
+/// assert_eq!(dst, [Abgr8888::from_abgr(255, 0, 0, 255); 32]);
+/// ```
+pub fn convert_slice<S, D>(src: &[S], dst: &mut [D])
+where
+    S: ConvertSlice<D>,
+{
+    S::convert_slice(src, dst);
+}
+
+mod sealed {
+    /// Prevents [`super::ConvertSlice`] from being implemented outside of this crate.
+    pub trait Sealed {}
+}
+
+/// Panics if `src` and `dst` have different lengths.
+fn check_lengths<S, D>(src: &[S], dst: &[D]) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    );
+}
+
+/// A predefined format with exactly the same layout as `Raw`.
+///
+/// ## Safety
+///
+/// `Self` and `Self::Raw` must have the same size and alignment, and every bit pattern must be
+/// valid for both.
+unsafe trait Raw: Sized {
+    type Raw;
+}
+
+unsafe impl Raw for Abgr8888 {
+    type Raw = [u8; 4];
+}
+
+unsafe impl Raw for Argb8888 {
+    type Raw = [u8; 4];
+}
+
+unsafe impl Raw for Bgr888 {
+    type Raw = [u8; 3];
+}
+
+unsafe impl Raw for Rgb888 {
+    type Raw = [u8; 3];
+}
+
+unsafe impl Raw for Rgb565 {
+    type Raw = u16;
+}
+
+unsafe impl Raw for Rgbaf32 {
+    type Raw = [f32; 4];
+}
+
+/// Reinterprets a slice of pixels as their raw representation.
+fn raw<P: Raw>(pixels: &[P]) -> &[P::Raw] {
+    const { assert!(mem::size_of::<P>() == mem::size_of::<P::Raw>()) };
+    const { assert!(mem::align_of::<P>() == mem::align_of::<P::Raw>()) };
+    // SAFETY: `P` and `P::Raw` have the same layout, and every bit pattern is valid for both.
+    unsafe { slice::from_raw_parts(pixels.as_ptr().cast(), pixels.len()) }
+}
+
+/// Reinterprets a mutable slice of pixels as their raw representation.
+fn raw_mut<P: Raw>(pixels: &mut [P]) -> &mut [P::Raw] {
+    const { assert!(mem::size_of::<P>() == mem::size_of::<P::Raw>()) };
+    const { assert!(mem::align_of::<P>() == mem::align_of::<P::Raw>()) };
+    // SAFETY: `P` and `P::Raw` have the same layout, and every bit pattern is valid for both.
+    unsafe { slice::from_raw_parts_mut(pixels.as_mut_ptr().cast(), pixels.len()) }
+}
+
+/// Selects the fastest available implementation of a kernel.
+///
+/// The x86-64 branch uses AVX2 if available, and otherwise SSE2; the 64-bit ARM branch uses NEON;
+/// other targets use the portable kernel.
+macro_rules! dispatch {
+    ($src:ident, $dst:ident, $scalar:expr, x86: $sse2:expr, $avx2:expr, neon: $neon:expr) => {{
+        #[cfg(target_arch = "x86_64")]
+        {
+            if x86::has_avx2() {
+                // SAFETY: The CPU supports AVX2.
+                unsafe { $avx2($src, $dst) }
+            } else {
+                // SAFETY: SSE2 is part of the x86-64 baseline.
+                unsafe { $sse2($src, $dst) }
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            // SAFETY: NEON is enabled at compile time.
+            unsafe { $neon($src, $dst) }
+        }
+        #[cfg(not(any(
+            target_arch = "x86_64",
+            all(target_arch = "aarch64", target_feature = "neon")
+        )))]
+        {
+            $scalar($src, $dst)
+        }
+    }};
+}
+
+/// Swaps the second and fourth bytes of each pixel.
+fn swap_red_blue(src: &[[u8; 4]], dst: &mut [[u8; 4]]) {
+    dispatch!(
+        src,
+        dst,
+        scalar::swap_red_blue,
+        x86: x86::swap_red_blue_sse2, x86::swap_red_blue_avx2,
+        neon: neon::swap_red_blue
+    );
+}
+
+/// Widens packed 5-6-5 pixels into `[a, b, g, r]` if `BGR`, or `[a, r, g, b]` otherwise.
+fn rgb565_to_u8x4<const BGR: bool>(src: &[u16], dst: &mut [[u8; 4]]) {
+    dispatch!(
+        src,
+        dst,
+        scalar::rgb565_to_u8x4::<BGR>,
+        x86: x86::rgb565_to_u8x4_sse2::<BGR>, x86::rgb565_to_u8x4_avx2::<BGR>,
+        neon: neon::rgb565_to_u8x4::<BGR>
+    );
+}
+
+/// Converts `[a, b, g, r]` if `BGR`, or `[a, r, g, b]` otherwise, into `[r, g, b, a]` floats.
+fn u8x4_to_f32x4<const BGR: bool>(src: &[[u8; 4]], dst: &mut [[f32; 4]]) {
+    dispatch!(
+        src,
+        dst,
+        scalar::u8x4_to_f32x4::<BGR>,
+        x86: x86::u8x4_to_f32x4_sse2::<BGR>, x86::u8x4_to_f32x4_avx2::<BGR>,
+        neon: neon::u8x4_to_f32x4::<BGR>
+    );
+}
+
+/// Converts `[r, g, b, a]` floats into `[a, b, g, r]` if `BGR`, or `[a, r, g, b]` otherwise.
+fn f32x4_to_u8x4<const BGR: bool>(src: &[[f32; 4]], dst: &mut [[u8; 4]]) {
+    dispatch!(
+        src,
+        dst,
+        scalar::f32x4_to_u8x4::<BGR>,
+        x86: x86::f32x4_to_u8x4_sse2::<BGR>, x86::f32x4_to_u8x4_avx2::<BGR>,
+        neon: neon::f32x4_to_u8x4::<BGR>
+    );
+}
+
+macro_rules! impl_sealed {
+    ($($ty:ty),+) => {
+        $(impl sealed::Sealed for $ty {})+
+    };
+}
+
+impl_sealed!(
+    Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32
+);
+
+impl<P> ConvertSlice<P> for P
+where
+    P: Copy + sealed::Sealed,
+{
+    fn convert_slice(src: &[Self], dst: &mut [P]) {
+        check_lengths(src, dst);
+        dst.copy_from_slice(src);
+    }
+}
+
+/// Implements [`ConvertSlice`] from the first format into each of the remaining formats.
+///
+/// With `kernel:`, implements it for each pair by calling a kernel on the raw representations; with
+/// `shuffle:`, by rearranging bytes in the given order.
+macro_rules! impl_convert_slice {
+    (kernel: $($src:ty => $dst:ty = $kernel:expr),+ $(,)?) => {
+        $(
+            impl ConvertSlice<$dst> for $src {
+                fn convert_slice(src: &[Self], dst: &mut [$dst]) {
+                    check_lengths(src, dst);
+                    ($kernel)(raw(src), raw_mut(dst));
+                }
+            }
+        )+
+    };
+    (shuffle: $($src:ty => $dst:ty = $order:expr),+ $(,)?) => {
+        $(
+            impl ConvertSlice<$dst> for $src {
+                fn convert_slice(src: &[Self], dst: &mut [$dst]) {
+                    check_lengths(src, dst);
+                    scalar::shuffle(raw(src), raw_mut(dst), $order);
+                }
+            }
+        )+
+    };
+    ($src:ty => $($dst:ty),+ $(,)?) => {
+        $(
+            impl ConvertSlice<$dst> for $src {
+                fn convert_slice(src: &[Self], dst: &mut [$dst]) {
+                    check_lengths(src, dst);
+                    for (s, d) in src.iter().zip(dst) {
+                        *d = (*s).into();
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_convert_slice!(kernel:
+    Abgr8888 => Argb8888 = swap_red_blue,
+    Argb8888 => Abgr8888 = swap_red_blue,
+    Rgb565 => Abgr8888 = rgb565_to_u8x4::<true>,
+    Rgb565 => Argb8888 = rgb565_to_u8x4::<false>,
+    Abgr8888 => Rgbaf32 = u8x4_to_f32x4::<true>,
+    Argb8888 => Rgbaf32 = u8x4_to_f32x4::<false>,
+    Rgbaf32 => Abgr8888 = f32x4_to_u8x4::<true>,
+    Rgbaf32 => Argb8888 = f32x4_to_u8x4::<false>,
+);
+
+impl_convert_slice!(shuffle:
+    Abgr8888 => Rgb888 = [3, 2, 1],
+    Abgr8888 => Bgr888 = [1, 2, 3],
+    Argb8888 => Rgb888 = [1, 2, 3],
+    Argb8888 => Bgr888 = [3, 2, 1],
+    Rgb888 => Abgr8888 = [OPAQUE, 2, 1, 0],
+    Rgb888 => Argb8888 = [OPAQUE, 0, 1, 2],
+    Bgr888 => Abgr8888 = [OPAQUE, 0, 1, 2],
+    Bgr888 => Argb8888 = [OPAQUE, 2, 1, 0],
+    Rgb888 => Bgr888 = [2, 1, 0],
+    Bgr888 => Rgb888 = [2, 1, 0],
+);
+
+impl_convert_slice!(Rgb565 => Argb1555, Argb4444, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+impl_convert_slice!(Argb1555 => Rgb565, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+impl_convert_slice!(Argb4444 => Rgb565, Argb1555, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+impl_convert_slice!(Argb8888 => Rgb565, Argb1555, Argb4444, Rgbf32);
+impl_convert_slice!(Abgr8888 => Rgb565, Argb1555, Argb4444, Rgbf32);
+impl_convert_slice!(Bgr888 => Rgb565, Argb1555, Argb4444, Rgbf32, Rgbaf32);
+impl_convert_slice!(Rgb888 => Rgb565, Argb1555, Argb4444, Rgbf32, Rgbaf32);
+impl_convert_slice!(Rgbf32 => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbaf32);
+impl_convert_slice!(Rgbaf32 => Rgb565, Argb1555, Argb4444, Bgr888, Rgb888, Rgbf32);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use core::fmt::Debug;
+
+    use super::*;
+
+    /// Number of pixels in each sample; not a multiple of any vector width.
+    const LEN: usize = 259;
+
+    /// Floating-point values around the edges of the range and halfway between 8-bit values.
+    const SAMPLES_F32: [f32; 10] = [
+        -1.0,
+        -0.0,
+        0.0,
+        0.5 / 255.0,
+        0.2,
+        0.5,
+        127.5 / 255.0,
+        0.999,
+        1.0,
+        2.0,
+    ];
+
+    fn sample_u8x4() -> [[u8; 4]; LEN] {
+        #[allow(clippy::cast_possible_truncation)]
+        core::array::from_fn(|i| [0, 85, 170, 255].map(|offset| (i * 7 + offset) as u8))
+    }
+
+    fn sample_u8x3() -> [[u8; 3]; LEN] {
+        sample_u8x4().map(|[a, b, c, _]| [a, b, c])
+    }
+
+    fn sample_u16() -> [u16; LEN] {
+        #[allow(clippy::cast_possible_truncation)]
+        core::array::from_fn(|i| (i * 253) as u16)
+    }
+
+    fn sample_f32x4() -> [[f32; 4]; LEN] {
+        core::array::from_fn(|i| {
+            [0, 3, 5, 7].map(|offset| SAMPLES_F32[(i + offset * i / 10) % SAMPLES_F32.len()])
+        })
+    }
+
+    fn from_raw<P: Raw + Default + Copy>(raw: &[P::Raw; LEN]) -> [P; LEN]
+    where
+        P::Raw: Copy,
+    {
+        let mut pixels = [P::default(); LEN];
+        raw_mut(&mut pixels).copy_from_slice(raw);
+        pixels
+    }
+
+    /// Asserts that converting `src` as a slice matches converting each color with [`From`].
+    fn assert_matches_from<S, D>(src: &[S])
+    where
+        S: ConvertSlice<D>,
+        D: From<S> + Default + Copy + PartialEq + Debug,
+    {
+        let mut dst = [D::default(); LEN];
+        for len in (0..=40).chain([LEN]) {
+            convert_slice(&src[..len], &mut dst[..len]);
+            for (&s, &d) in src[..len].iter().zip(&dst) {
+                assert_eq!(d, D::from(s));
+            }
+        }
+    }
+
+    macro_rules! assert_all_match_from {
+        ($src:expr) => {{
+            let src = $src;
+            assert_matches_from::<_, Rgb565>(&src);
+            assert_matches_from::<_, Argb1555>(&src);
+            assert_matches_from::<_, Argb4444>(&src);
+            assert_matches_from::<_, Argb8888>(&src);
+            assert_matches_from::<_, Abgr8888>(&src);
+            assert_matches_from::<_, Bgr888>(&src);
+            assert_matches_from::<_, Rgb888>(&src);
+            assert_matches_from::<_, Rgbf32>(&src);
+            assert_matches_from::<_, Rgbaf32>(&src);
+        }};
+    }
+
+    #[test]
+    fn matches_from_rgb565() {
+        assert_all_match_from!(sample_u16().map(Rgb565::new));
+    }
+
+    #[test]
+    fn matches_from_argb1555() {
+        assert_all_match_from!(sample_u16().map(Argb1555::new));
+    }
+
+    #[test]
+    fn matches_from_argb4444() {
+        assert_all_match_from!(sample_u16().map(Argb4444::new));
+    }
+
+    #[test]
+    fn matches_from_argb8888() {
+        assert_all_match_from!(from_raw::<Argb8888>(&sample_u8x4()));
+    }
+
+    #[test]
+    fn matches_from_abgr8888() {
+        assert_all_match_from!(from_raw::<Abgr8888>(&sample_u8x4()));
+    }
+
+    #[test]
+    fn matches_from_bgr888() {
+        assert_all_match_from!(from_raw::<Bgr888>(&sample_u8x3()));
+    }
+
+    #[test]
+    fn matches_from_rgb888() {
+        assert_all_match_from!(from_raw::<Rgb888>(&sample_u8x3()));
+    }
+
+    #[test]
+    fn matches_from_rgbf32() {
+        assert_all_match_from!(sample_f32x4().map(|[r, g, b, _]| Rgbf32::from_rgb(r, g, b)));
+    }
+
+    #[test]
+    fn matches_from_rgbaf32() {
+        assert_all_match_from!(from_raw::<Rgbaf32>(&sample_f32x4()));
+    }
+
+    #[test]
+    fn rgbaf32_nan_is_zero() {
+        let src = [Rgbaf32::from_rgba(f32::NAN, 1.0, f32::NAN, f32::NAN); 9];
+        let mut dst = [Abgr8888::default(); 9];
+        convert_slice(&src, &mut dst);
+        assert_eq!(dst, [Abgr8888::from_abgr(0, 0, 255, 0); 9]);
+    }
+
+    #[test]
+    #[should_panic = "source and destination lengths differ"]
+    fn panics_on_length_mismatch() {
+        convert_slice(&[Rgb565::default(); 2], &mut [Abgr8888::default(); 3]);
+    }
+}
@@ -0,0 +1,147 @@
+//! NEON kernels for 64-bit ARM.
+//!
+//! NEON is part of the 64-bit ARM baseline, so these kernels are always used when it is enabled at
+//! compile time. Interleaved loads and stores (`vld4`/`vst4`) split pixels into one vector per
+//! component, so byte shuffles become register renames.
+
+use core::arch::aarch64::{
+    float32x4_t, float32x4x4_t, float64x2_t, uint8x8_t, uint8x8x4_t, uint8x16_t, uint8x16x4_t,
+    uint16x8_t, vaddq_f64, vaddq_u16, vandq_u16, vcombine_u16, vcombine_u32, vcvt_f64_f32,
+    vcvt_high_f64_f32, vcvtq_f32_u32, vcvtq_u64_f64, vdivq_f32, vdup_n_u8, vdupq_n_f32,
+    vdupq_n_f64, vdupq_n_u16, vget_high_u8, vget_high_u16, vget_low_f32, vget_low_u8, vget_low_u16,
+    vld1q_u16, vld4q_f32, vld4q_u8, vmaxnmq_f64, vminq_f64, vmovl_u8, vmovl_u16, vmovn_u16,
+    vmovn_u32, vmovn_u64, vmulq_f64, vmulq_u16, vshrq_n_u16, vst4_u8, vst4q_f32, vst4q_u8,
+};
+
+use super::scalar;
+
+/// Swaps the second and fourth bytes of each pixel, 16 pixels at a time.
+#[target_feature(enable = "neon")]
+pub(super) fn swap_red_blue(src: &[[u8; 4]], dst: &mut [[u8; 4]]) {
+    let len = src.len() - src.len() % 16;
+    for (s, d) in src[..len]
+        .chunks_exact(16)
+        .zip(dst[..len].chunks_exact_mut(16))
+    {
+        // SAFETY: Each chunk is 16 pixels of 4 bytes.
+        let uint8x16x4_t(alpha, near, green, far) = unsafe { vld4q_u8(s.as_ptr().cast()) };
+        let pixels = uint8x16x4_t(alpha, far, green, near);
+        // SAFETY: Each chunk is 16 pixels of 4 bytes.
+        unsafe { vst4q_u8(d.as_mut_ptr().cast(), pixels) };
+    }
+    scalar::swap_red_blue(&src[len..], &mut dst[len..]);
+}
+
+/// Widens packed 5-6-5 pixels into 8-bit components, 8 pixels at a time.
+#[target_feature(enable = "neon")]
+pub(super) fn rgb565_to_u8x4<const BGR: bool>(src: &[u16], dst: &mut [[u8; 4]]) {
+    let len = src.len() - src.len() % 8;
+    for (s, d) in src[..len]
+        .chunks_exact(8)
+        .zip(dst[..len].chunks_exact_mut(8))
+    {
+        // SAFETY: Each chunk is 8 pixels of 16 bits.
+        let packed = unsafe { vld1q_u16(s.as_ptr()) };
+        let red = widen::<527, 23>(vshrq_n_u16::<11>(packed));
+        let green = widen::<259, 33>(vandq_u16(vshrq_n_u16::<5>(packed), vdupq_n_u16(0x3F)));
+        let blue = widen::<527, 23>(vandq_u16(packed, vdupq_n_u16(0x1F)));
+        let (near, far) = if BGR { (blue, red) } else { (red, blue) };
+        let pixels = uint8x8x4_t(vdup_n_u8(u8::MAX), near, green, far);
+        // SAFETY: Each chunk is 8 pixels of 4 bytes.
+        unsafe { vst4_u8(d.as_mut_ptr().cast(), pixels) };
+    }
+    scalar::rgb565_to_u8x4::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Scales 5-bit or 6-bit components in 16-bit lanes to 8 bits, like [`scalar::widen5`] and
+/// [`scalar::widen6`].
+#[target_feature(enable = "neon")]
+fn widen<const MUL: u16, const ADD: u16>(value: uint16x8_t) -> uint8x8_t {
+    let scaled = vaddq_u16(vmulq_u16(value, vdupq_n_u16(MUL)), vdupq_n_u16(ADD));
+    vmovn_u16(vshrq_n_u16::<6>(scaled))
+}
+
+/// Converts 8-bit pixels into `[r, g, b, a]` floats, 16 pixels at a time.
+#[target_feature(enable = "neon")]
+pub(super) fn u8x4_to_f32x4<const BGR: bool>(src: &[[u8; 4]], dst: &mut [[f32; 4]]) {
+    let len = src.len() - src.len() % 16;
+    for (s, d) in src[..len]
+        .chunks_exact(16)
+        .zip(dst[..len].chunks_exact_mut(16))
+    {
+        // SAFETY: Each chunk is 16 pixels of 4 bytes.
+        let uint8x16x4_t(alpha, near, green, far) = unsafe { vld4q_u8(s.as_ptr().cast()) };
+        let (red, blue) = if BGR { (far, near) } else { (near, far) };
+        let [red, green, blue, alpha] = [red, green, blue, alpha].map(|c| normalize(c));
+        for (i, d) in d.chunks_exact_mut(4).enumerate() {
+            let pixels = float32x4x4_t(red[i], green[i], blue[i], alpha[i]);
+            // SAFETY: Each chunk is 4 pixels of 4 floats.
+            unsafe { vst4q_f32(d.as_mut_ptr().cast(), pixels) };
+        }
+    }
+    scalar::u8x4_to_f32x4::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Converts 16 bytes into floats in the range `0.0..=1.0`, 4 at a time.
+#[target_feature(enable = "neon")]
+fn normalize(bytes: uint8x16_t) -> [float32x4_t; 4] {
+    let (low, high) = (vmovl_u8(vget_low_u8(bytes)), vmovl_u8(vget_high_u8(bytes)));
+    let scale = vdupq_n_f32(255.0);
+    [
+        vget_low_u16(low),
+        vget_high_u16(low),
+        vget_low_u16(high),
+        vget_high_u16(high),
+    ]
+    .map(|half| vdivq_f32(vcvtq_f32_u32(vmovl_u16(half)), scale))
+}
+
+/// Converts `[r, g, b, a]` floats into 8-bit pixels, 8 pixels at a time.
+///
+/// Components are clamped and rounded in double precision, exactly like the scalar kernel.
+#[target_feature(enable = "neon")]
+pub(super) fn f32x4_to_u8x4<const BGR: bool>(src: &[[f32; 4]], dst: &mut [[u8; 4]]) {
+    let len = src.len() - src.len() % 8;
+    for (s, d) in src[..len]
+        .chunks_exact(8)
+        .zip(dst[..len].chunks_exact_mut(8))
+    {
+        // SAFETY: Each half of the chunk is 4 pixels of 4 floats.
+        let (low, high) = unsafe {
+            (
+                vld4q_f32(s[..4].as_ptr().cast()),
+                vld4q_f32(s[4..].as_ptr().cast()),
+            )
+        };
+        let red = quantize(low.0, high.0);
+        let green = quantize(low.1, high.1);
+        let blue = quantize(low.2, high.2);
+        let alpha = quantize(low.3, high.3);
+        let (near, far) = if BGR { (blue, red) } else { (red, blue) };
+        let pixels = uint8x8x4_t(alpha, near, green, far);
+        // SAFETY: Each chunk is 8 pixels of 4 bytes.
+        unsafe { vst4_u8(d.as_mut_ptr().cast(), pixels) };
+    }
+    scalar::f32x4_to_u8x4::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Clamps and rounds 8 floats to 8 bits, like [`scalar::f32x4_to_u8x4`].
+///
+/// `NaN` becomes `0`, because `vmaxnmq_f64` returns the number if either operand is `NaN`.
+#[target_feature(enable = "neon")]
+fn quantize(low: float32x4_t, high: float32x4_t) -> uint8x8_t {
+    let scale = |value: float64x2_t| {
+        let clamped = vminq_f64(vmaxnmq_f64(value, vdupq_n_f64(0.0)), vdupq_n_f64(1.0));
+        let scaled = vaddq_f64(vmulq_f64(clamped, vdupq_n_f64(255.0)), vdupq_n_f64(0.5));
+        vmovn_u64(vcvtq_u64_f64(scaled))
+    };
+    let low = vcombine_u32(
+        scale(vcvt_f64_f32(vget_low_f32(low))),
+        scale(vcvt_high_f64_f32(low)),
+    );
+    let high = vcombine_u32(
+        scale(vcvt_f64_f32(vget_low_f32(high))),
+        scale(vcvt_high_f64_f32(high)),
+    );
+    vmovn_u16(vcombine_u16(vmovn_u32(low), vmovn_u32(high)))
+}
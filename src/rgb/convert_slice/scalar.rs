@@ -0,0 +1,82 @@
+//! Portable kernels, which are also the reference for the SIMD kernels.
+
+/// An index into the source pixel that is out of range, selecting a fully opaque alpha instead.
+pub(super) const OPAQUE: usize = usize::MAX;
+
+/// Rearranges the bytes of each pixel; `order[i]` is the source byte of destination byte `i`.
+pub(super) fn shuffle<const N: usize, const M: usize>(
+    src: &[[u8; N]],
+    dst: &mut [[u8; M]],
+    order: [usize; M],
+) {
+    for (s, d) in src.iter().zip(dst) {
+        *d = order.map(|i| s.get(i).copied().unwrap_or(u8::MAX));
+    }
+}
+
+/// Swaps the second and fourth bytes of each pixel, converting between `[a, b, g, r]` and
+/// `[a, r, g, b]`.
+pub(super) fn swap_red_blue(src: &[[u8; 4]], dst: &mut [[u8; 4]]) {
+    shuffle(src, dst, [0, 3, 2, 1]);
+}
+
+/// Widens packed 5-6-5 pixels into `[a, b, g, r]` if `BGR`, or `[a, r, g, b]` otherwise.
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn rgb565_to_u8x4<const BGR: bool>(src: &[u16], dst: &mut [[u8; 4]]) {
+    for (&packed, d) in src.iter().zip(dst) {
+        let (r, g, b) = (packed >> 11, (packed >> 5) & 0x3F, packed & 0x1F);
+        let [r, g, b] = [widen5(r), widen6(g), widen5(b)].map(|c| c as u8);
+        *d = if BGR {
+            [u8::MAX, b, g, r]
+        } else {
+            [u8::MAX, r, g, b]
+        };
+    }
+}
+
+/// Scales a 5-bit component to 8 bits, rounding to nearest; equal to `(value * 255 + 15) / 31`.
+pub(super) const fn widen5(value: u16) -> u16 {
+    (value * 527 + 23) >> 6
+}
+
+/// Scales a 6-bit component to 8 bits, rounding to nearest; equal to `(value * 255 + 31) / 63`.
+pub(super) const fn widen6(value: u16) -> u16 {
+    (value * 259 + 33) >> 6
+}
+
+/// Converts `[a, b, g, r]` if `BGR`, or `[a, r, g, b]` otherwise, into `[r, g, b, a]` floats.
+pub(super) fn u8x4_to_f32x4<const BGR: bool>(src: &[[u8; 4]], dst: &mut [[f32; 4]]) {
+    for (&[a, x, g, y], d) in src.iter().zip(dst) {
+        let (r, b) = if BGR { (y, x) } else { (x, y) };
+        *d = [r, g, b, a].map(|c| f32::from(c) / 255.0);
+    }
+}
+
+/// Converts `[r, g, b, a]` floats into `[a, b, g, r]` if `BGR`, or `[a, r, g, b]` otherwise.
+pub(super) fn f32x4_to_u8x4<const BGR: bool>(src: &[[f32; 4]], dst: &mut [[u8; 4]]) {
+    for (s, d) in src.iter().zip(dst) {
+        let [r, g, b, a] = s.map(quantize);
+        *d = if BGR { [a, b, g, r] } else { [a, r, g, b] };
+    }
+}
+
+/// Clamps and rounds a floating-point component to 8 bits; `NaN` becomes `0`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn quantize(value: f32) -> u8 {
+    (f64::from(value).clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widen_rounds_to_nearest() {
+        for value in 0..32 {
+            assert_eq!(widen5(value), (value * 255 + 15) / 31);
+        }
+        for value in 0..64 {
+            assert_eq!(widen6(value), (value * 255 + 31) / 63);
+        }
+    }
+}
@@ -0,0 +1,500 @@
+//! SSE2 and AVX2 kernels for x86-64.
+//!
+//! SSE2 is part of the x86-64 baseline and is always used; AVX2 is used when [`has_avx2`] returns
+//! `true`. Every kernel converts whole vectors at a time and finishes the remaining pixels with the
+//! scalar kernel.
+
+#![allow(clippy::cast_ptr_alignment)]
+
+use core::arch::x86_64::{
+    __m128, __m128d, __m128i, __m256i, _mm_add_epi16, _mm_add_pd, _mm_and_si128, _mm_cvtepi32_ps,
+    _mm_cvtps_pd, _mm_cvttpd_epi32, _mm_div_ps, _mm_loadl_epi64, _mm_loadu_ps, _mm_loadu_si128,
+    _mm_max_pd, _mm_min_pd, _mm_movehl_ps, _mm_mul_pd, _mm_mullo_epi16, _mm_or_si128,
+    _mm_packs_epi32, _mm_packus_epi16, _mm_set1_epi16, _mm_set1_epi32, _mm_set1_pd, _mm_set1_ps,
+    _mm_setr_epi8, _mm_setzero_pd, _mm_setzero_si128, _mm_shuffle_epi8, _mm_shuffle_epi32,
+    _mm_slli_epi16, _mm_slli_epi32, _mm_srli_epi16, _mm_srli_epi32, _mm_storel_epi64,
+    _mm_storeu_ps, _mm_storeu_si128, _mm_unpackhi_epi8, _mm_unpackhi_epi16, _mm_unpacklo_epi8,
+    _mm_unpacklo_epi16, _mm_unpacklo_epi64, _mm256_add_epi16, _mm256_add_pd, _mm256_and_si256,
+    _mm256_cvtepi32_ps, _mm256_cvtepu8_epi32, _mm256_cvtps_pd, _mm256_cvttpd_epi32, _mm256_div_ps,
+    _mm256_loadu_si256, _mm256_max_pd, _mm256_min_pd, _mm256_mul_pd, _mm256_mullo_epi16,
+    _mm256_or_si256, _mm256_permute2x128_si256, _mm256_set1_epi16, _mm256_set1_epi32,
+    _mm256_set1_pd, _mm256_set1_ps, _mm256_setzero_pd, _mm256_slli_epi16, _mm256_slli_epi32,
+    _mm256_srli_epi16, _mm256_srli_epi32, _mm256_storeu_ps, _mm256_storeu_si256,
+    _mm256_unpackhi_epi16, _mm256_unpacklo_epi16,
+};
+
+use super::scalar;
+
+/// Returns whether the AVX2 kernels can be used.
+///
+/// With the `std` feature, the CPU is queried at runtime; otherwise, AVX2 must be enabled at
+/// compile time, such as with `-C target-cpu=native`.
+pub(super) fn has_avx2() -> bool {
+    #[cfg(feature = "std")]
+    {
+        std::is_x86_feature_detected!("avx2")
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "avx2")
+    }
+}
+
+/// Loads 16 bytes from a slice of exactly that size.
+#[target_feature(enable = "sse2")]
+fn load<T>(chunk: &[T]) -> __m128i {
+    assert_eq!(size_of_val(chunk), 16);
+    // SAFETY: The chunk is 16 bytes, and the load is unaligned.
+    unsafe { _mm_loadu_si128(chunk.as_ptr().cast()) }
+}
+
+/// Stores 16 bytes into a slice of exactly that size.
+#[target_feature(enable = "sse2")]
+fn store<T>(chunk: &mut [T], value: __m128i) {
+    assert_eq!(size_of_val(chunk), 16);
+    // SAFETY: The chunk is 16 bytes, and the store is unaligned.
+    unsafe { _mm_storeu_si128(chunk.as_mut_ptr().cast(), value) }
+}
+
+/// Loads 32 bytes from a slice of exactly that size.
+#[target_feature(enable = "avx2")]
+fn load256<T>(chunk: &[T]) -> __m256i {
+    assert_eq!(size_of_val(chunk), 32);
+    // SAFETY: The chunk is 32 bytes, and the load is unaligned.
+    unsafe { _mm256_loadu_si256(chunk.as_ptr().cast()) }
+}
+
+/// Stores 32 bytes into a slice of exactly that size.
+#[target_feature(enable = "avx2")]
+fn store256<T>(chunk: &mut [T], value: __m256i) {
+    assert_eq!(size_of_val(chunk), 32);
+    // SAFETY: The chunk is 32 bytes, and the store is unaligned.
+    unsafe { _mm256_storeu_si256(chunk.as_mut_ptr().cast(), value) }
+}
+
+/// Returns the number of leading pixels that fill whole chunks of `N` pixels.
+const fn whole<const N: usize>(len: usize) -> usize {
+    len - len % N
+}
+
+/// Swaps the second and fourth bytes of each pixel, 4 pixels at a time.
+#[target_feature(enable = "sse2")]
+pub(super) fn swap_red_blue_sse2(src: &[[u8; 4]], dst: &mut [[u8; 4]]) {
+    let len = whole::<4>(src.len());
+    for (s, d) in src[..len]
+        .chunks_exact(4)
+        .zip(dst[..len].chunks_exact_mut(4))
+    {
+        let v = load(s);
+        let keep = _mm_and_si128(v, _mm_set1_epi32(0x00FF_00FF));
+        let second = _mm_slli_epi32::<24>(_mm_srli_epi32::<8>(v));
+        let fourth = _mm_slli_epi32::<8>(_mm_srli_epi32::<24>(v));
+        store(d, _mm_or_si128(keep, _mm_or_si128(second, fourth)));
+    }
+    scalar::swap_red_blue(&src[len..], &mut dst[len..]);
+}
+
+/// Swaps the second and fourth bytes of each pixel, 8 pixels at a time.
+#[target_feature(enable = "avx2")]
+pub(super) fn swap_red_blue_avx2(src: &[[u8; 4]], dst: &mut [[u8; 4]]) {
+    let len = whole::<8>(src.len());
+    for (s, d) in src[..len]
+        .chunks_exact(8)
+        .zip(dst[..len].chunks_exact_mut(8))
+    {
+        let v = load256(s);
+        let keep = _mm256_and_si256(v, _mm256_set1_epi32(0x00FF_00FF));
+        let second = _mm256_slli_epi32::<24>(_mm256_srli_epi32::<8>(v));
+        let fourth = _mm256_slli_epi32::<8>(_mm256_srli_epi32::<24>(v));
+        store256(d, _mm256_or_si256(keep, _mm256_or_si256(second, fourth)));
+    }
+    swap_red_blue_sse2(&src[len..], &mut dst[len..]);
+}
+
+/// Widens packed 5-6-5 pixels into 8-bit components, 8 pixels at a time.
+#[target_feature(enable = "sse2")]
+pub(super) fn rgb565_to_u8x4_sse2<const BGR: bool>(src: &[u16], dst: &mut [[u8; 4]]) {
+    let len = whole::<8>(src.len());
+    for (s, d) in src[..len]
+        .chunks_exact(8)
+        .zip(dst[..len].chunks_exact_mut(8))
+    {
+        let packed = load(s);
+        let red = _mm_srli_epi16::<11>(packed);
+        let green = _mm_and_si128(_mm_srli_epi16::<5>(packed), _mm_set1_epi16(0x3F));
+        let blue = _mm_and_si128(packed, _mm_set1_epi16(0x1F));
+        let red = widen_sse2::<527, 23>(red);
+        let green = widen_sse2::<259, 33>(green);
+        let blue = widen_sse2::<527, 23>(blue);
+        let (near, far) = if BGR { (blue, red) } else { (red, blue) };
+
+        // Each pixel is `[alpha, near, green, far]`, or two little-endian 16-bit halves.
+        let low = _mm_or_si128(_mm_set1_epi16(0xFF), _mm_slli_epi16::<8>(near));
+        let high = _mm_or_si128(green, _mm_slli_epi16::<8>(far));
+        let (first, last) = d.split_at_mut(4);
+        store(first, _mm_unpacklo_epi16(low, high));
+        store(last, _mm_unpackhi_epi16(low, high));
+    }
+    scalar::rgb565_to_u8x4::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Widens packed 5-6-5 pixels into 8-bit components, 16 pixels at a time.
+#[target_feature(enable = "avx2")]
+pub(super) fn rgb565_to_u8x4_avx2<const BGR: bool>(src: &[u16], dst: &mut [[u8; 4]]) {
+    let len = whole::<16>(src.len());
+    for (s, d) in src[..len]
+        .chunks_exact(16)
+        .zip(dst[..len].chunks_exact_mut(16))
+    {
+        let packed = load256(s);
+        let red = _mm256_srli_epi16::<11>(packed);
+        let green = _mm256_and_si256(_mm256_srli_epi16::<5>(packed), _mm256_set1_epi16(0x3F));
+        let blue = _mm256_and_si256(packed, _mm256_set1_epi16(0x1F));
+        let red = widen_avx2::<527, 23>(red);
+        let green = widen_avx2::<259, 33>(green);
+        let blue = widen_avx2::<527, 23>(blue);
+        let (near, far) = if BGR { (blue, red) } else { (red, blue) };
+
+        let low = _mm256_or_si256(_mm256_set1_epi16(0xFF), _mm256_slli_epi16::<8>(near));
+        let high = _mm256_or_si256(green, _mm256_slli_epi16::<8>(far));
+
+        // Unpacking works within each 128-bit lane, so the lanes are reordered afterwards.
+        let (unpacked_low, unpacked_high) = (
+            _mm256_unpacklo_epi16(low, high),
+            _mm256_unpackhi_epi16(low, high),
+        );
+        let (first, last) = d.split_at_mut(8);
+        store256(
+            first,
+            _mm256_permute2x128_si256::<0x20>(unpacked_low, unpacked_high),
+        );
+        store256(
+            last,
+            _mm256_permute2x128_si256::<0x31>(unpacked_low, unpacked_high),
+        );
+    }
+    rgb565_to_u8x4_sse2::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Converts 8-bit pixels into `[r, g, b, a]` floats, 4 pixels at a time.
+#[target_feature(enable = "sse2")]
+pub(super) fn u8x4_to_f32x4_sse2<const BGR: bool>(src: &[[u8; 4]], dst: &mut [[f32; 4]]) {
+    let len = whole::<4>(src.len());
+    let zero = _mm_setzero_si128();
+    let scale = _mm_set1_ps(255.0);
+    for (s, d) in src[..len]
+        .chunks_exact(4)
+        .zip(dst[..len].chunks_exact_mut(4))
+    {
+        let v = load(s);
+
+        // Reorder each pixel into `[r, g, b, a]`, by reversing or rotating its bytes.
+        let rgba = if BGR {
+            let outer = _mm_or_si128(_mm_slli_epi32::<24>(v), _mm_srli_epi32::<24>(v));
+            let inner = _mm_or_si128(
+                _mm_and_si128(_mm_slli_epi32::<8>(v), _mm_set1_epi32(0x00FF_0000)),
+                _mm_and_si128(_mm_srli_epi32::<8>(v), _mm_set1_epi32(0x0000_FF00)),
+            );
+            _mm_or_si128(outer, inner)
+        } else {
+            _mm_or_si128(_mm_srli_epi32::<8>(v), _mm_slli_epi32::<24>(v))
+        };
+
+        let (low, high) = (_mm_unpacklo_epi8(rgba, zero), _mm_unpackhi_epi8(rgba, zero));
+        let pixels = [
+            _mm_unpacklo_epi16(low, zero),
+            _mm_unpackhi_epi16(low, zero),
+            _mm_unpacklo_epi16(high, zero),
+            _mm_unpackhi_epi16(high, zero),
+        ];
+        for (pixel, d) in pixels.into_iter().zip(d) {
+            store_ps(d, _mm_div_ps(_mm_cvtepi32_ps(pixel), scale));
+        }
+    }
+    scalar::u8x4_to_f32x4::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Converts 8-bit pixels into `[r, g, b, a]` floats, 2 pixels at a time.
+#[target_feature(enable = "avx2")]
+pub(super) fn u8x4_to_f32x4_avx2<const BGR: bool>(src: &[[u8; 4]], dst: &mut [[f32; 4]]) {
+    let len = whole::<2>(src.len());
+    let order = if BGR {
+        _mm_setr_epi8(3, 2, 1, 0, 7, 6, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1)
+    } else {
+        _mm_setr_epi8(1, 2, 3, 0, 5, 6, 7, 4, -1, -1, -1, -1, -1, -1, -1, -1)
+    };
+    let scale = _mm256_set1_ps(255.0);
+    for (s, d) in src[..len]
+        .chunks_exact(2)
+        .zip(dst[..len].chunks_exact_mut(2))
+    {
+        assert_eq!(size_of_val(s), 8);
+        // SAFETY: The chunk is 8 bytes, and the load is unaligned.
+        let v = unsafe { _mm_loadl_epi64(s.as_ptr().cast()) };
+        let rgba = _mm256_cvtepu8_epi32(_mm_shuffle_epi8(v, order));
+        let floats = _mm256_div_ps(_mm256_cvtepi32_ps(rgba), scale);
+        // SAFETY: The chunk is 2 pixels of 4 floats, and the store is unaligned.
+        unsafe { _mm256_storeu_ps(d.as_mut_ptr().cast(), floats) };
+    }
+    scalar::u8x4_to_f32x4::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Converts `[r, g, b, a]` floats into 8-bit pixels, 2 pixels at a time.
+///
+/// Components are clamped and rounded in double precision, exactly like the scalar kernel.
+#[target_feature(enable = "sse2")]
+pub(super) fn f32x4_to_u8x4_sse2<const BGR: bool>(src: &[[f32; 4]], dst: &mut [[u8; 4]]) {
+    let len = whole::<2>(src.len());
+    for (s, d) in src[..len]
+        .chunks_exact(2)
+        .zip(dst[..len].chunks_exact_mut(2))
+    {
+        let [first, second] = [&s[0], &s[1]].map(|pixel| {
+            let v = load_ps(pixel);
+            let rg = quantize_sse2(_mm_cvtps_pd(v));
+            let ba = quantize_sse2(_mm_cvtps_pd(_mm_movehl_ps(v, v)));
+            arrange::<BGR>(_mm_unpacklo_epi64(rg, ba))
+        });
+        let packed = _mm_packs_epi32(first, second);
+        assert_eq!(size_of_val(d), 8);
+        // SAFETY: The chunk is 8 bytes, and the store is unaligned.
+        unsafe { _mm_storel_epi64(d.as_mut_ptr().cast(), _mm_packus_epi16(packed, packed)) };
+    }
+    scalar::f32x4_to_u8x4::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Converts `[r, g, b, a]` floats into 8-bit pixels, 4 pixels at a time.
+#[target_feature(enable = "avx2")]
+pub(super) fn f32x4_to_u8x4_avx2<const BGR: bool>(src: &[[f32; 4]], dst: &mut [[u8; 4]]) {
+    let len = whole::<4>(src.len());
+    let (zero, one) = (_mm256_setzero_pd(), _mm256_set1_pd(1.0));
+    let (max, half) = (_mm256_set1_pd(255.0), _mm256_set1_pd(0.5));
+    for (s, d) in src[..len]
+        .chunks_exact(4)
+        .zip(dst[..len].chunks_exact_mut(4))
+    {
+        let [p0, p1, p2, p3] = [&s[0], &s[1], &s[2], &s[3]].map(|pixel| {
+            let v = _mm256_cvtps_pd(load_ps(pixel));
+            let clamped = _mm256_min_pd(_mm256_max_pd(v, zero), one);
+            let rgba = _mm256_cvttpd_epi32(_mm256_add_pd(_mm256_mul_pd(clamped, max), half));
+            arrange::<BGR>(rgba)
+        });
+        let packed = _mm_packus_epi16(_mm_packs_epi32(p0, p1), _mm_packs_epi32(p2, p3));
+        store(d, packed);
+    }
+    scalar::f32x4_to_u8x4::<BGR>(&src[len..], &mut dst[len..]);
+}
+
+/// Scales 5-bit or 6-bit components in 16-bit lanes to 8 bits, like [`scalar::widen5`] and
+/// [`scalar::widen6`].
+#[target_feature(enable = "sse2")]
+fn widen_sse2<const MUL: i16, const ADD: i16>(value: __m128i) -> __m128i {
+    let scaled = _mm_mullo_epi16(value, _mm_set1_epi16(MUL));
+    _mm_srli_epi16::<6>(_mm_add_epi16(scaled, _mm_set1_epi16(ADD)))
+}
+
+/// Scales 5-bit or 6-bit components in 16-bit lanes to 8 bits, like [`scalar::widen5`] and
+/// [`scalar::widen6`].
+#[target_feature(enable = "avx2")]
+fn widen_avx2<const MUL: i16, const ADD: i16>(value: __m256i) -> __m256i {
+    let scaled = _mm256_mullo_epi16(value, _mm256_set1_epi16(MUL));
+    _mm256_srli_epi16::<6>(_mm256_add_epi16(scaled, _mm256_set1_epi16(ADD)))
+}
+
+/// Loads 4 floats from a pixel.
+#[target_feature(enable = "sse2")]
+fn load_ps(pixel: &[f32; 4]) -> __m128 {
+    // SAFETY: The pixel is 4 floats, and the load is unaligned.
+    unsafe { _mm_loadu_ps(pixel.as_ptr()) }
+}
+
+/// Stores 4 floats into a pixel.
+#[target_feature(enable = "sse2")]
+fn store_ps(pixel: &mut [f32; 4], value: __m128) {
+    // SAFETY: The pixel is 4 floats, and the store is unaligned.
+    unsafe { _mm_storeu_ps(pixel.as_mut_ptr(), value) }
+}
+
+/// Clamps, scales, and truncates two components, returning them in the low two 32-bit lanes.
+///
+/// `NaN` becomes `0`, because `_mm_max_pd` returns its second operand if either is `NaN`.
+#[target_feature(enable = "sse2")]
+fn quantize_sse2(value: __m128d) -> __m128i {
+    let clamped = _mm_min_pd(_mm_max_pd(value, _mm_setzero_pd()), _mm_set1_pd(1.0));
+    _mm_cvttpd_epi32(_mm_add_pd(
+        _mm_mul_pd(clamped, _mm_set1_pd(255.0)),
+        _mm_set1_pd(0.5),
+    ))
+}
+
+/// Rearranges the `[r, g, b, a]` 32-bit lanes of a pixel into the destination order.
+#[target_feature(enable = "sse2")]
+fn arrange<const BGR: bool>(rgba: __m128i) -> __m128i {
+    if BGR {
+        _mm_shuffle_epi32::<0b00_01_10_11>(rgba)
+    } else {
+        _mm_shuffle_epi32::<0b10_01_00_11>(rgba)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use core::fmt::Debug;
+
+    use super::*;
+
+    /// Asserts that a SIMD kernel matches the scalar kernel for every prefix of `src` up to 40
+    /// pixels, which covers each remainder after whole vectors, and for all of `src`.
+    fn assert_matches_scalar<S, D, const N: usize>(
+        src: &[S; N],
+        simd: impl Fn(&[S], &mut [D]),
+        scalar: fn(&[S], &mut [D]),
+    ) where
+        D: Default + Copy + PartialEq + Debug,
+    {
+        let (mut expected, mut actual) = ([D::default(); N], [D::default(); N]);
+        scalar(src, &mut expected);
+        for len in (0..=40.min(N)).chain([N]) {
+            actual.fill(D::default());
+            simd(&src[..len], &mut actual[..len]);
+            assert_eq!(actual[..len], expected[..len], "{len} pixels");
+        }
+    }
+
+    /// Runs `test` if the CPU supports AVX2, and reports that it was skipped otherwise.
+    fn with_avx2(test: impl FnOnce()) {
+        if has_avx2() {
+            test();
+        } else {
+            #[cfg(feature = "std")]
+            std::eprintln!("skipping AVX2 kernels, which this CPU does not support");
+        }
+    }
+
+    fn sample_u8x4() -> [[u8; 4]; 256] {
+        #[allow(clippy::cast_possible_truncation)]
+        core::array::from_fn(|i| [0, 85, 170, 255].map(|offset| (i + offset) as u8))
+    }
+
+    fn sample_f32x4() -> [[f32; 4]; 1024] {
+        const SPECIAL: [f32; 8] = [
+            f32::NAN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            -0.0,
+            -f32::MIN_POSITIVE,
+            1.0 + f32::EPSILON,
+            0.5 / 255.0,
+            254.5 / 255.0,
+        ];
+        #[allow(clippy::cast_precision_loss)]
+        core::array::from_fn(|i| {
+            [0, 1, 2, 3].map(|channel| {
+                let i = i * 4 + channel;
+                SPECIAL
+                    .get(i % 64)
+                    .copied()
+                    .unwrap_or((i / 2) as f32 / 1020.0 - 0.5 / 255.0)
+            })
+        })
+    }
+
+    #[test]
+    fn swap_red_blue_matches_scalar() {
+        let src = sample_u8x4();
+        assert_matches_scalar(
+            &src,
+            |s, d| unsafe { swap_red_blue_sse2(s, d) },
+            scalar::swap_red_blue,
+        );
+        with_avx2(|| {
+            assert_matches_scalar(
+                &src,
+                |s, d| unsafe { swap_red_blue_avx2(s, d) },
+                scalar::swap_red_blue,
+            );
+        });
+    }
+
+    #[test]
+    fn rgb565_to_u8x4_matches_scalar() {
+        let src: [u16; 1 << 16] = core::array::from_fn(|i| u16::try_from(i).unwrap());
+        assert_matches_scalar(
+            &src,
+            |s, d| unsafe { rgb565_to_u8x4_sse2::<true>(s, d) },
+            scalar::rgb565_to_u8x4::<true>,
+        );
+        assert_matches_scalar(
+            &src,
+            |s, d| unsafe { rgb565_to_u8x4_sse2::<false>(s, d) },
+            scalar::rgb565_to_u8x4::<false>,
+        );
+        with_avx2(|| {
+            assert_matches_scalar(
+                &src,
+                |s, d| unsafe { rgb565_to_u8x4_avx2::<true>(s, d) },
+                scalar::rgb565_to_u8x4::<true>,
+            );
+            assert_matches_scalar(
+                &src,
+                |s, d| unsafe { rgb565_to_u8x4_avx2::<false>(s, d) },
+                scalar::rgb565_to_u8x4::<false>,
+            );
+        });
+    }
+
+    #[test]
+    fn u8x4_to_f32x4_matches_scalar() {
+        let src = sample_u8x4();
+        assert_matches_scalar(
+            &src,
+            |s, d| unsafe { u8x4_to_f32x4_sse2::<true>(s, d) },
+            scalar::u8x4_to_f32x4::<true>,
+        );
+        assert_matches_scalar(
+            &src,
+            |s, d| unsafe { u8x4_to_f32x4_sse2::<false>(s, d) },
+            scalar::u8x4_to_f32x4::<false>,
+        );
+        with_avx2(|| {
+            assert_matches_scalar(
+                &src,
+                |s, d| unsafe { u8x4_to_f32x4_avx2::<true>(s, d) },
+                scalar::u8x4_to_f32x4::<true>,
+            );
+            assert_matches_scalar(
+                &src,
+                |s, d| unsafe { u8x4_to_f32x4_avx2::<false>(s, d) },
+                scalar::u8x4_to_f32x4::<false>,
+            );
+        });
+    }
+
+    #[test]
+    fn f32x4_to_u8x4_matches_scalar() {
+        let src = sample_f32x4();
+        assert_matches_scalar(
+            &src,
+            |s, d| unsafe { f32x4_to_u8x4_sse2::<true>(s, d) },
+            scalar::f32x4_to_u8x4::<true>,
+        );
+        assert_matches_scalar(
+            &src,
+            |s, d| unsafe { f32x4_to_u8x4_sse2::<false>(s, d) },
+            scalar::f32x4_to_u8x4::<false>,
+        );
+        with_avx2(|| {
+            assert_matches_scalar(
+                &src,
+                |s, d| unsafe { f32x4_to_u8x4_avx2::<true>(s, d) },
+                scalar::f32x4_to_u8x4::<true>,
+            );
+            assert_matches_scalar(
+                &src,
+                |s, d| unsafe { f32x4_to_u8x4_avx2::<false>(s, d) },
+                scalar::f32x4_to_u8x4::<false>,
+            );
+        });
+    }
+}
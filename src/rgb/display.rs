@@ -0,0 +1,305 @@
+//! Formatting colors as CSS functional notation and hex colors.
+//!
+//! [`fmt::Display`] writes `rgb(255 0 0 / 50%)` for integer components, and
+//! `color(srgb 1 0 0 / 0.5)` for floating-point components, so that the output can be parsed by
+//! [`crate::css`] without losing precision. [`fmt::LowerHex`] and [`fmt::UpperHex`] write
+//! `#rrggbbaa`, rounding every component to 8 bits.
+
+use core::fmt::{self, Write as _};
+
+use crate::{
+    alpha::{AlphaFirst, AlphaLast},
+    component::Component,
+    rgb::{Argb1555, Argb4444, Bgr, Rgb, Rgb565, RgbColor, RgbaColor},
+};
+
+/// Writes a color in CSS functional notation, omitting `alpha` if it is fully opaque.
+///
+/// Colors with integer components use `rgb()`, where components are in the range `0..=255` and
+/// alpha is a percentage. Colors with floating-point components use `color(srgb …)`, where every
+/// component, including alpha, is in the range `0.0..=1.0`.
+pub(crate) fn write_css<R, G, B, A>(
+    f: &mut fmt::Formatter<'_>,
+    (red, green, blue, alpha): (R, G, B, A),
+) -> fmt::Result
+where
+    R: Component,
+    G: Component,
+    B: Component,
+    A: Component,
+{
+    let float = R::BITS.is_none();
+    let scale = if float {
+        f.write_str("color(srgb ")?;
+        1
+    } else {
+        f.write_str("rgb(")?;
+        255
+    };
+    red.write_scaled(f, scale)?;
+    f.write_char(' ')?;
+    green.write_scaled(f, scale)?;
+    f.write_char(' ')?;
+    blue.write_scaled(f, scale)?;
+    if alpha != A::MAX {
+        f.write_str(" / ")?;
+        if float {
+            alpha.write_scaled(f, 1)?;
+        } else {
+            alpha.write_scaled(f, 100)?;
+            f.write_char('%')?;
+        }
+    }
+    f.write_char(')')
+}
+
+/// Writes a color as `#rrggbbaa`, rounding every component to 8 bits.
+pub(crate) fn write_hex<R, G, B, A>(
+    f: &mut fmt::Formatter<'_>,
+    (red, green, blue, alpha): (R, G, B, A),
+    upper: bool,
+) -> fmt::Result
+where
+    R: Component,
+    G: Component,
+    B: Component,
+    A: Component,
+{
+    let components: [u8; 4] = [
+        red.convert(),
+        green.convert(),
+        blue.convert(),
+        alpha.convert(),
+    ];
+    f.write_char('#')?;
+    for component in components {
+        if upper {
+            write!(f, "{component:02X}")?;
+        } else {
+            write!(f, "{component:02x}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Implements [`fmt::Display`], [`fmt::LowerHex`], and [`fmt::UpperHex`] for color types.
+///
+/// Each type is given with its generic parameters and a function that returns its red, green,
+/// blue, and alpha components.
+macro_rules! impl_display {
+    ($([$($generics:tt)*] $ty:ty => $components:path;)+) => {
+        $(
+            impl<$($generics)*> ::core::fmt::Display for $ty {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    $crate::rgb::display::write_css(f, $components(*self))
+                }
+            }
+
+            impl<$($generics)*> ::core::fmt::LowerHex for $ty {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    $crate::rgb::display::write_hex(f, $components(*self), false)
+                }
+            }
+
+            impl<$($generics)*> ::core::fmt::UpperHex for $ty {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    $crate::rgb::display::write_hex(f, $components(*self), true)
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use impl_display;
+
+/// Returns the components of a color without alpha, which is fully opaque.
+fn opaque<C: RgbColor>(
+    color: C,
+) -> (
+    <C as crate::rgb::HasRed>::Component,
+    <C as crate::rgb::HasGreen>::Component,
+    <C as crate::rgb::HasBlue>::Component,
+    u8,
+) {
+    let (red, green, blue) = color.into_rgb();
+    (red, green, blue, u8::MAX)
+}
+
+impl_display! {
+    [T: Component] Rgb<T> => opaque;
+    [T: Component] Bgr<T> => opaque;
+    [A: Component, T: Component] AlphaFirst<A, Rgb<T>> => RgbaColor::into_rgba;
+    [A: Component, T: Component] AlphaFirst<A, Bgr<T>> => RgbaColor::into_rgba;
+    [A: Component, T: Component] AlphaLast<A, Rgb<T>> => RgbaColor::into_rgba;
+    [A: Component, T: Component] AlphaLast<A, Bgr<T>> => RgbaColor::into_rgba;
+    [] Rgb565 => opaque;
+    [] Argb1555 => RgbaColor::into_rgba;
+    [] Argb4444 => RgbaColor::into_rgba;
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use super::*;
+    use crate::rgb::{Abgr8888, Argb8888, Bgr888, Rgb888, Rgbaf32, Rgbf32};
+
+    /// A fixed-capacity buffer, since tests cannot allocate.
+    struct Buffer {
+        bytes: [u8; 128],
+        len: usize,
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    /// Asserts that `args` formats as `expected`.
+    fn assert_fmt(args: fmt::Arguments<'_>, expected: &str) {
+        let mut buffer = Buffer {
+            bytes: [0; 128],
+            len: 0,
+        };
+        buffer.write_fmt(args).unwrap();
+        assert_eq!(
+            core::str::from_utf8(&buffer.bytes[..buffer.len]),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn display_integer_components() {
+        assert_fmt(
+            format_args!("{}", Rgb888::from_rgb(255, 0, 0)),
+            "rgb(255 0 0)",
+        );
+        assert_fmt(format_args!("{}", Bgr888::from_bgr(3, 2, 1)), "rgb(1 2 3)");
+        let red = Abgr8888::from_abgr(128, 0, 0, 255);
+        assert_fmt(format_args!("{red}"), "rgb(255 0 0 / 50%)");
+        let blue = Argb8888::from_argb(0, 0, 0, 255);
+        assert_fmt(format_args!("{blue}"), "rgb(0 0 255 / 0%)");
+    }
+
+    #[test]
+    fn display_alpha_is_shortest_percentage() {
+        for alpha in 0..=u8::MAX {
+            let mut buffer = Buffer {
+                bytes: [0; 128],
+                len: 0,
+            };
+            write!(buffer, "{}", Abgr8888::from_abgr(alpha, 0, 0, 0)).unwrap();
+            let text = core::str::from_utf8(&buffer.bytes[..buffer.len]).unwrap();
+            let parsed: Abgr8888 = text.parse::<crate::css::CssColor>().unwrap().into();
+            assert_eq!(parsed.alpha(), alpha, "{text}");
+            if let Some(percentage) = text
+                .strip_prefix("rgb(0 0 0 / ")
+                .and_then(|rest| rest.strip_suffix("%)"))
+            {
+                let fraction = percentage
+                    .split_once('.')
+                    .map_or("", |(_, fraction)| fraction);
+                assert!(fraction.len() <= 2, "{text}");
+            }
+        }
+        assert_fmt(
+            format_args!("{}", Abgr8888::from_abgr(1, 0, 0, 0)),
+            "rgb(0 0 0 / 0.4%)",
+        );
+    }
+
+    #[test]
+    fn display_packed_scales_to_255() {
+        assert_fmt(
+            format_args!("{}", Rgb565::from_rgb(31, 63, 0)),
+            "rgb(255 255 0)",
+        );
+        assert_fmt(
+            format_args!("{}", Rgb565::from_rgb(15, 32, 1)),
+            "rgb(123 130 8)",
+        );
+        let color = Argb1555::new(0x7C00);
+        assert_fmt(format_args!("{color}"), "rgb(255 0 0 / 0%)");
+        let color = Argb4444::from_argb(5, 15, 15, 15);
+        assert_fmt(format_args!("{color}"), "rgb(255 255 255 / 33%)");
+    }
+
+    #[test]
+    fn display_wide_components_round_trip() {
+        let color = Rgb::<u16>::from_rgb(0xFFFF, 0x8000, 1);
+        assert_fmt(format_args!("{color}"), "rgb(255 127.5 0.004)");
+    }
+
+    #[test]
+    fn display_float_components_are_shortest() {
+        let color = Rgbf32::from_rgb(1.0, 0.1, 1.0 / 3.0);
+        assert_fmt(format_args!("{color}"), "color(srgb 1 0.1 0.33333334)");
+        let color = Rgbaf32::from_rgba(1.0, 0.5, 0.0, 0.25);
+        assert_fmt(format_args!("{color}"), "color(srgb 1 0.5 0 / 0.25)");
+        let color = Rgbf32::from_rgb(-0.5, 2.0, 1e-10);
+        assert_fmt(format_args!("{color}"), "color(srgb -0.5 2 0.0000000001)");
+    }
+
+    #[test]
+    fn display_non_finite_components_parse() {
+        let color = Rgbaf32::from_rgba(f32::NAN, f32::INFINITY, f32::NEG_INFINITY, f32::NAN);
+        let mut buffer = Buffer {
+            bytes: [0; 128],
+            len: 0,
+        };
+        write!(buffer, "{color}").unwrap();
+        let text = core::str::from_utf8(&buffer.bytes[..buffer.len]).unwrap();
+        let max = "340282350000000000000000000000000000000";
+        assert_eq!(
+            text.strip_prefix("color(srgb 0 ")
+                .and_then(|rest| rest.strip_prefix(max))
+                .and_then(|rest| rest.strip_prefix(" -"))
+                .and_then(|rest| rest.strip_prefix(max)),
+            Some(" / 0)")
+        );
+        let parsed = text.parse::<crate::css::CssColor>();
+        assert_eq!(
+            parsed,
+            Ok(crate::css::CssColor::Srgb(Rgbaf32::from_rgba(
+                0.0,
+                f32::MAX,
+                f32::MIN,
+                0.0
+            )))
+        );
+    }
+
+    #[test]
+    fn gray_writes_equal_components() {
+        use crate::gray::{Gray8, GrayAlpha16, GrayAlphaF32};
+
+        assert_fmt(format_args!("{}", Gray8::new(128)), "rgb(128 128 128)");
+        assert_fmt(format_args!("{:x}", Gray8::new(128)), "#808080ff");
+        let color = GrayAlpha16::new(0xFFFF, 0x8000);
+        assert_fmt(format_args!("{color}"), "rgb(255 255 255 / 50%)");
+        assert_fmt(format_args!("{color:X}"), "#FFFFFF80");
+        let color = GrayAlphaF32::new(0.5, 0.1);
+        assert_fmt(format_args!("{color}"), "color(srgb 0.5 0.5 0.5 / 0.1)");
+    }
+
+    #[test]
+    fn hex_is_rrggbbaa() {
+        let color = Abgr8888::from_abgr(0x80, 0x0A, 0xBC, 0xFF);
+        assert_fmt(format_args!("{color:x}"), "#ffbc0a80");
+        assert_fmt(format_args!("{color:X}"), "#FFBC0A80");
+        assert_fmt(format_args!("{:x}", Rgb888::from_rgb(1, 2, 3)), "#010203ff");
+        assert_fmt(
+            format_args!("{:x}", Rgb565::from_rgb(31, 0, 15)),
+            "#ff007bff",
+        );
+        let color = Rgbaf32::from_rgba(1.0, 0.5, -1.0, 2.0);
+        assert_fmt(format_args!("{color:x}"), "#ff8000ff");
+    }
+}
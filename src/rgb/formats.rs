@@ -24,3 +24,5 @@ pub use rgb_f32::Rgbf32;
 
 mod rgba_f32;
 pub use rgba_f32::Rgbaf32;
+
+mod convert;
@@ -1,5 +1,3 @@
-use core::mem;
-
 use crate::{alpha::AlphaFirst, rgb::Bgr888};
 
 /// 8-bit ABGR color representation.
@@ -55,7 +53,11 @@ impl Abgr8888 {
     /// ```
     #[must_use]
     pub const fn new(packed: u32) -> Self {
-        unsafe { mem::transmute(packed) }
+        let a = ((packed >> 24) & 0xFF) as u8;
+        let b = ((packed >> 16) & 0xFF) as u8;
+        let g = ((packed >> 8) & 0xFF) as u8;
+        let r = (packed & 0xFF) as u8;
+        Self::from_abgr(a, b, g, r)
     }
 
     /// Creates a new ABGR color from individual component values (a, b, g, r).
@@ -73,8 +75,7 @@ impl Abgr8888 {
     /// ```
     #[must_use]
     pub const fn from_abgr(a: u8, b: u8, g: u8, r: u8) -> Self {
-        let packed = (a as u32) << 24 | (b as u32) << 16 | (g as u32) << 8 | (r as u32);
-        Self::new(packed)
+        Self::with_color(a, Bgr888::from_bgr(b, g, r))
     }
 }
 
@@ -101,4 +102,14 @@ mod tests {
         assert_eq!(color.green(), 0);
         assert_eq!(color.red(), 255);
     }
+
+    #[test]
+    fn test_abgr8888_new_component_order() {
+        let color = Abgr8888::new(0x1122_3344);
+        assert_eq!(color, Abgr8888::from_abgr(0x11, 0x22, 0x33, 0x44));
+        assert_eq!(color.alpha(), 0x11);
+        assert_eq!(color.blue(), 0x22);
+        assert_eq!(color.green(), 0x33);
+        assert_eq!(color.red(), 0x44);
+    }
 }
@@ -2,7 +2,7 @@ use crate::rgb::macros;
 
 /// A 16-bit packed ARGB color representation.
 ///
-/// Each component is represented by 1 bit for alpha, and 4 bits each for red, green, and blue.
+/// Each component is represented by 1 bit for alpha, and 5 bits each for red, green, and blue.
 ///
 /// ## Layout
 ///
@@ -31,6 +31,11 @@ use crate::rgb::macros;
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(transparent)]
 pub struct Argb1555 {
     packed: u16,
@@ -92,12 +97,12 @@ impl Argb1555 {
 
 macros::impl_rgb_packed!(
     Argb1555,
-    red:   { shift: 10, mask: 0x1F, clear: 0xFFE0 },
-    green: { shift: 5, mask: 0x1F, clear: 0xFF9F },
-    blue:  { shift: 0, mask: 0x1F, clear: 0xFFE0 }
+    red:   { bits: 5, shift: 10, mask: 0x1F, clear: 0x83FF },
+    green: { bits: 5, shift: 5, mask: 0x1F, clear: 0xFC1F },
+    blue:  { bits: 5, shift: 0, mask: 0x1F, clear: 0xFFE0 }
 );
 
-macros::impl_with_alpha_packed!(Argb1555, 15, 0x01, 0x7FFF);
+macros::impl_with_alpha_packed!(Argb1555, 1, 15, 0x01, 0x7FFF);
 
 #[cfg(test)]
 mod tests {
@@ -105,6 +110,7 @@ mod tests {
 
     use crate::{
         alpha::HasAlpha,
+        component::Bits,
         rgb::{HasBlue, HasGreen, HasRed},
     };
 
@@ -131,4 +137,12 @@ mod tests {
         assert_eq!(color.green(), 0);
         assert_eq!(color.blue(), 0);
     }
+
+    #[test]
+    fn test_argb1555_set_preserves_other_components() {
+        let mut color = Argb1555::from_rgb(1, 2, 3);
+        color.set_red(Bits::new(31));
+        color.set_green(Bits::new(17));
+        assert_eq!(color, Argb1555::from_rgb(31, 17, 3));
+    }
 }
@@ -31,6 +31,11 @@ use crate::rgb::macros;
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(transparent)]
 pub struct Argb4444 {
     packed: u16,
@@ -87,12 +92,12 @@ impl Argb4444 {
 
 macros::impl_rgb_packed!(
     Argb4444,
-    red:   { shift: 8, mask: 0x0F, clear: 0xF0FF },
-    green: { shift: 4, mask: 0x0F, clear: 0xFF0F },
-    blue:  { shift: 0, mask: 0x0F, clear: 0xFFF0 }
+    red:   { bits: 4, shift: 8, mask: 0x0F, clear: 0xF0FF },
+    green: { bits: 4, shift: 4, mask: 0x0F, clear: 0xFF0F },
+    blue:  { bits: 4, shift: 0, mask: 0x0F, clear: 0xFFF0 }
 );
 
-macros::impl_with_alpha_packed!(Argb4444, 12, 0x0F, 0x0FFF);
+macros::impl_with_alpha_packed!(Argb4444, 4, 12, 0x0F, 0x0FFF);
 
 #[cfg(test)]
 mod tests {
@@ -1,5 +1,3 @@
-use core::mem;
-
 use crate::{alpha::AlphaFirst, rgb::Rgb888};
 
 /// 8-bit ARGB color representation.
@@ -55,7 +53,11 @@ impl Argb8888 {
     /// ```
     #[must_use]
     pub const fn new(packed: u32) -> Self {
-        unsafe { mem::transmute(packed) }
+        let a = ((packed >> 24) & 0xFF) as u8;
+        let r = ((packed >> 16) & 0xFF) as u8;
+        let g = ((packed >> 8) & 0xFF) as u8;
+        let b = (packed & 0xFF) as u8;
+        Self::from_argb(a, r, g, b)
     }
 
     /// Creates a new ARGB color from individual component values (a, r, g, b).
@@ -73,8 +75,7 @@ impl Argb8888 {
     /// ```
     #[must_use]
     pub const fn from_argb(a: u8, r: u8, g: u8, b: u8) -> Self {
-        let packed = (a as u32) << 24 | (r as u32) << 16 | (g as u32) << 8 | (b as u32);
-        Self::new(packed)
+        Self::with_color(a, Rgb888::from_rgb(r, g, b))
     }
 }
 
@@ -101,4 +102,14 @@ mod tests {
         assert_eq!(color.green(), 0);
         assert_eq!(color.blue(), 255);
     }
+
+    #[test]
+    fn test_new_component_order() {
+        let color = Argb8888::new(0x1122_3344);
+        assert_eq!(color, Argb8888::from_argb(0x11, 0x22, 0x33, 0x44));
+        assert_eq!(color.alpha(), 0x11);
+        assert_eq!(color.red(), 0x22);
+        assert_eq!(color.green(), 0x33);
+        assert_eq!(color.blue(), 0x44);
+    }
 }
@@ -0,0 +1,311 @@
+//! Conversions between every pair of predefined formats.
+//!
+//! Each component is converted with [`Component::convert`], which scales integer components with
+//! correct rounding and clamps floating-point components when converting them to integers. Formats
+//! without alpha are treated as fully opaque.
+
+use crate::{
+    alpha::HasAlpha,
+    component::Component,
+    rgb::{
+        Abgr8888, Argb1555, Argb4444, Argb8888, Bgr888, Rgb565, Rgb888, RgbColor, Rgbaf32, Rgbf32,
+    },
+};
+
+/// A predefined format, which may or may not have an alpha channel.
+trait Format: RgbColor + Copy {
+    /// Returns the alpha component, or [`Component::MAX`] (fully opaque) if there is none.
+    fn alpha_or_opaque<T: Component>(&self) -> T;
+
+    /// Sets the alpha component to the alpha of `source`, or does nothing if there is none.
+    fn copy_alpha_from<S: Format>(&mut self, source: &S);
+}
+
+macro_rules! impl_format {
+    (opaque: $($ty:ty),+) => {
+        $(
+            impl Format for $ty {
+                fn alpha_or_opaque<T: Component>(&self) -> T {
+                    T::MAX
+                }
+
+                fn copy_alpha_from<S: Format>(&mut self, _: &S) {}
+            }
+        )+
+    };
+    (alpha: $($ty:ty),+) => {
+        $(
+            impl Format for $ty {
+                fn alpha_or_opaque<T: Component>(&self) -> T {
+                    self.alpha().convert()
+                }
+
+                fn copy_alpha_from<S: Format>(&mut self, source: &S) {
+                    self.set_alpha(source.alpha_or_opaque());
+                }
+            }
+        )+
+    };
+}
+
+impl_format!(opaque: Rgb565, Bgr888, Rgb888, Rgbf32);
+impl_format!(alpha: Argb1555, Argb4444, Argb8888, Abgr8888, Rgbaf32);
+
+/// Converts between two formats, converting each component with [`Component::convert`].
+fn convert<S: Format, D: Format>(color: S) -> D {
+    let mut result = D::from_rgb(
+        color.red().convert(),
+        color.green().convert(),
+        color.blue().convert(),
+    );
+    result.copy_alpha_from(&color);
+    result
+}
+
+/// Implements [`From`] from the first format into each of the remaining formats.
+macro_rules! impl_from_format {
+    ($src:ty => $($dst:ty),+ $(,)?) => {
+        $(
+            impl From<$src> for $dst {
+                fn from(color: $src) -> Self {
+                    convert(color)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_format!(Rgb565 => Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+impl_from_format!(Argb1555 => Rgb565, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+impl_from_format!(Argb4444 => Rgb565, Argb1555, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+impl_from_format!(Argb8888 => Rgb565, Argb1555, Argb4444, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+impl_from_format!(Abgr8888 => Rgb565, Argb1555, Argb4444, Argb8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+impl_from_format!(Bgr888 => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Rgb888, Rgbf32, Rgbaf32);
+impl_from_format!(Rgb888 => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgbf32, Rgbaf32);
+impl_from_format!(Rgbf32 => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbaf32);
+impl_from_format!(Rgbaf32 => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use core::fmt::Debug;
+
+    use super::*;
+    use crate::rgb::{HasBlue, HasGreen, HasRed};
+
+    /// Values used to sample 8-bit channels; includes both endpoints and odd values.
+    const SAMPLES_U8: [u8; 12] = [0, 1, 2, 7, 64, 100, 127, 128, 200, 253, 254, 255];
+
+    /// Values used to sample floating-point channels.
+    const SAMPLES_F32: [f32; 8] = [0.0, 0.001, 0.1, 0.25, 0.5, 0.666, 0.999, 1.0];
+
+    fn samples_rgb565() -> impl Iterator<Item = Rgb565> + Clone {
+        (0..=u16::MAX).map(Rgb565::new)
+    }
+
+    fn samples_argb1555() -> impl Iterator<Item = Argb1555> + Clone {
+        (0..=u16::MAX).map(Argb1555::new)
+    }
+
+    fn samples_argb4444() -> impl Iterator<Item = Argb4444> + Clone {
+        (0..=u16::MAX).map(Argb4444::new)
+    }
+
+    fn samples_rgb888() -> impl Iterator<Item = Rgb888> + Clone {
+        SAMPLES_U8.into_iter().flat_map(|r| {
+            SAMPLES_U8.into_iter().flat_map(move |g| {
+                SAMPLES_U8
+                    .into_iter()
+                    .map(move |b| Rgb888::from_rgb(r, g, b))
+            })
+        })
+    }
+
+    fn samples_bgr888() -> impl Iterator<Item = Bgr888> + Clone {
+        samples_rgb888().map(|c| Bgr888::from_bgr(c.blue(), c.green(), c.red()))
+    }
+
+    fn samples_argb8888() -> impl Iterator<Item = Argb8888> + Clone {
+        samples_rgb888().flat_map(|c| {
+            SAMPLES_U8
+                .into_iter()
+                .map(move |a| Argb8888::from_argb(a, c.red(), c.green(), c.blue()))
+        })
+    }
+
+    fn samples_abgr8888() -> impl Iterator<Item = Abgr8888> + Clone {
+        samples_argb8888().map(|c| Abgr8888::from_abgr(c.alpha(), c.blue(), c.green(), c.red()))
+    }
+
+    fn samples_rgbf32() -> impl Iterator<Item = Rgbf32> + Clone {
+        SAMPLES_F32.into_iter().flat_map(|r| {
+            SAMPLES_F32.into_iter().flat_map(move |g| {
+                SAMPLES_F32
+                    .into_iter()
+                    .map(move |b| Rgbf32::from_rgb(r, g, b))
+            })
+        })
+    }
+
+    fn samples_rgbaf32() -> impl Iterator<Item = Rgbaf32> + Clone {
+        samples_rgbf32().flat_map(|c| {
+            SAMPLES_F32
+                .into_iter()
+                .map(move |a| Rgbaf32::with_color(a, c))
+        })
+    }
+
+    /// Asserts that converting `A -> B -> A -> B` produces the same `B` as `A -> B`.
+    ///
+    /// This holds for every pair of formats: once a color has been narrowed into `B`, widening it
+    /// back into `A` and narrowing it again must be lossless.
+    fn assert_stable<A, B>(samples: impl Iterator<Item = A>)
+    where
+        A: Copy + Debug + From<B>,
+        B: Copy + Debug + PartialEq + From<A>,
+    {
+        for a in samples {
+            let b = B::from(a);
+            assert_eq!(B::from(A::from(b)), b, "{a:?} -> {b:?} is not stable");
+        }
+    }
+
+    /// Asserts that converting `A -> B -> A` is lossless.
+    ///
+    /// This only holds when every channel of `B` is at least as precise as in `A`.
+    fn assert_lossless<A, B>(samples: impl Iterator<Item = A>)
+    where
+        A: Copy + Debug + PartialEq + From<B>,
+        B: Copy + Debug + From<A>,
+    {
+        for a in samples {
+            let b = B::from(a);
+            assert_eq!(A::from(b), a, "{a:?} -> {b:?} is lossy");
+        }
+    }
+
+    /// Runs [`assert_stable`] from `$src` into every listed format.
+    macro_rules! assert_all_stable {
+        ($samples:expr => $($dst:ty),+) => {
+            $(assert_stable::<_, $dst>($samples);)+
+        };
+    }
+
+    #[test]
+    fn round_trip_stable_rgb565() {
+        assert_all_stable!(samples_rgb565() => Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+    }
+
+    #[test]
+    fn round_trip_stable_argb1555() {
+        assert_all_stable!(samples_argb1555() => Rgb565, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+    }
+
+    #[test]
+    fn round_trip_stable_argb4444() {
+        assert_all_stable!(samples_argb4444() => Rgb565, Argb1555, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+    }
+
+    #[test]
+    fn round_trip_stable_argb8888() {
+        assert_all_stable!(samples_argb8888() => Rgb565, Argb1555, Argb4444, Abgr8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+    }
+
+    #[test]
+    fn round_trip_stable_abgr8888() {
+        assert_all_stable!(samples_abgr8888() => Rgb565, Argb1555, Argb4444, Argb8888, Bgr888, Rgb888, Rgbf32, Rgbaf32);
+    }
+
+    #[test]
+    fn round_trip_stable_bgr888() {
+        assert_all_stable!(samples_bgr888() => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Rgb888, Rgbf32, Rgbaf32);
+    }
+
+    #[test]
+    fn round_trip_stable_rgb888() {
+        assert_all_stable!(samples_rgb888() => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgbf32, Rgbaf32);
+    }
+
+    #[test]
+    fn round_trip_stable_rgbf32() {
+        assert_all_stable!(samples_rgbf32() => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbaf32);
+    }
+
+    #[test]
+    fn round_trip_stable_rgbaf32() {
+        assert_all_stable!(samples_rgbaf32() => Rgb565, Argb1555, Argb4444, Argb8888, Abgr8888, Bgr888, Rgb888, Rgbf32);
+    }
+
+    #[test]
+    fn round_trip_lossless_when_widening() {
+        assert_lossless::<_, Argb8888>(samples_rgb565());
+        assert_lossless::<_, Abgr8888>(samples_rgb565());
+        assert_lossless::<_, Rgb888>(samples_rgb565());
+        assert_lossless::<_, Bgr888>(samples_rgb565());
+        assert_lossless::<_, Rgbf32>(samples_rgb565());
+        assert_lossless::<_, Rgbaf32>(samples_rgb565());
+
+        assert_lossless::<_, Argb8888>(samples_argb1555());
+        assert_lossless::<_, Abgr8888>(samples_argb1555());
+        assert_lossless::<_, Rgbaf32>(samples_argb1555());
+
+        assert_lossless::<_, Argb8888>(samples_argb4444());
+        assert_lossless::<_, Abgr8888>(samples_argb4444());
+        assert_lossless::<_, Rgbaf32>(samples_argb4444());
+
+        assert_lossless::<_, Abgr8888>(samples_argb8888());
+        assert_lossless::<_, Rgbaf32>(samples_argb8888());
+        assert_lossless::<_, Argb8888>(samples_abgr8888());
+        assert_lossless::<_, Rgbaf32>(samples_abgr8888());
+
+        assert_lossless::<_, Rgb888>(samples_bgr888());
+        assert_lossless::<_, Argb8888>(samples_bgr888());
+        assert_lossless::<_, Rgbf32>(samples_bgr888());
+        assert_lossless::<_, Bgr888>(samples_rgb888());
+        assert_lossless::<_, Abgr8888>(samples_rgb888());
+        assert_lossless::<_, Rgbaf32>(samples_rgb888());
+
+        assert_lossless::<_, Rgbaf32>(samples_rgbf32());
+    }
+
+    #[test]
+    fn widening_replicates_bits() {
+        let white = Abgr8888::from(Rgb565::from_rgb(31, 63, 31));
+        assert_eq!(white, Abgr8888::from_abgr(255, 255, 255, 255));
+
+        let color = Argb8888::from(Rgb565::from_rgb(16, 32, 1));
+        assert_eq!(color, Argb8888::from_argb(255, 132, 130, 8));
+    }
+
+    #[test]
+    fn narrowing_rounds_to_nearest() {
+        assert_eq!(
+            Rgb565::from(Rgb888::from_rgb(255, 255, 255)),
+            Rgb565::new(0xFFFF)
+        );
+        assert_eq!(
+            Rgb565::from(Rgb888::from_rgb(4, 2, 3)),
+            Rgb565::from_rgb(0, 0, 0)
+        );
+        assert_eq!(
+            Rgb565::from(Rgb888::from_rgb(5, 3, 4)),
+            Rgb565::from_rgb(1, 1, 0)
+        );
+        assert_eq!(Argb1555::from(Abgr8888::from_abgr(127, 0, 0, 0)).alpha(), 0);
+        assert_eq!(Argb1555::from(Abgr8888::from_abgr(128, 0, 0, 0)).alpha(), 1);
+    }
+
+    #[test]
+    fn missing_alpha_is_opaque() {
+        assert_eq!(Argb1555::from(Rgb888::default()).alpha(), 1);
+        assert_eq!(Argb4444::from(Rgb565::default()).alpha(), 15);
+        assert_eq!(Abgr8888::from(Bgr888::default()).alpha(), 255);
+        assert_eq!(Rgbaf32::from(Rgbf32::default()).alpha(), 1.0);
+    }
+
+    #[test]
+    fn floats_are_clamped() {
+        let color = Rgb888::from(Rgbf32::from_rgb(-1.0, 2.0, f32::NAN));
+        assert_eq!(color, Rgb888::from_rgb(0, 255, 0));
+    }
+}
@@ -31,6 +31,11 @@ use crate::rgb::macros;
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(transparent)]
 pub struct Rgb565 {
     packed: u16,
@@ -83,9 +88,9 @@ impl Rgb565 {
 
 macros::impl_rgb_packed!(
     Rgb565,
-    red:   { shift: 11, mask: 0x1F, clear: 0xFFE0 },
-    green: { shift: 5, mask: 0x3F, clear: 0xFF9F },
-    blue:  { shift: 0, mask: 0x1F, clear: 0xFFE0 }
+    red:   { bits: 5, shift: 11, mask: 0x1F, clear: 0x07FF },
+    green: { bits: 6, shift: 5, mask: 0x3F, clear: 0xF81F },
+    blue:  { bits: 5, shift: 0, mask: 0x1F, clear: 0xFFE0 }
 );
 
 #[cfg(test)]
@@ -1,43 +1,46 @@
 macro_rules! impl_rgb_packed {
     (
         $ty:ident,
-        red:  { shift: $rshift:expr, mask: $rmask:expr, clear: $rclear:expr },
-        green:{ shift: $gshift:expr, mask: $gmask:expr, clear: $gclear:expr },
-        blue: { shift: $bshift:expr, mask: $bmask:expr, clear: $bclear:expr }
+        red:  { bits: $rbits:expr, shift: $rshift:expr, mask: $rmask:expr, clear: $rclear:expr },
+        green:{ bits: $gbits:expr, shift: $gshift:expr, mask: $gmask:expr, clear: $gclear:expr },
+        blue: { bits: $bbits:expr, shift: $bshift:expr, mask: $bmask:expr, clear: $bclear:expr }
     ) => {
         impl crate::rgb::HasRed for $ty {
-            type Component = u8;
+            type Component = crate::component::Bits<$rbits>;
 
             fn red(&self) -> Self::Component {
-                ((self.packed >> $rshift) & $rmask) as Self::Component
+                crate::component::Bits::new(((self.packed >> $rshift) & $rmask) as u8)
             }
 
             fn set_red(&mut self, value: Self::Component) {
-                self.packed = (self.packed & $rclear) | ((u16::from(value) & $rmask) << $rshift);
+                self.packed =
+                    (self.packed & $rclear) | ((u16::from(value.get()) & $rmask) << $rshift);
             }
         }
 
         impl crate::rgb::HasGreen for $ty {
-            type Component = u8;
+            type Component = crate::component::Bits<$gbits>;
 
             fn green(&self) -> Self::Component {
-                ((self.packed >> $gshift) & $gmask) as Self::Component
+                crate::component::Bits::new(((self.packed >> $gshift) & $gmask) as u8)
             }
 
             fn set_green(&mut self, value: Self::Component) {
-                self.packed = (self.packed & $gclear) | ((u16::from(value) & $gmask) << $gshift);
+                self.packed =
+                    (self.packed & $gclear) | ((u16::from(value.get()) & $gmask) << $gshift);
             }
         }
 
         impl crate::rgb::HasBlue for $ty {
-            type Component = u8;
+            type Component = crate::component::Bits<$bbits>;
 
             fn blue(&self) -> Self::Component {
-                ((self.packed >> $bshift) & $bmask) as Self::Component
+                crate::component::Bits::new(((self.packed >> $bshift) & $bmask) as u8)
             }
 
             fn set_blue(&mut self, value: Self::Component) {
-                self.packed = (self.packed & $bclear) | ((u16::from(value) & $bmask) << $bshift);
+                self.packed =
+                    (self.packed & $bclear) | ((u16::from(value.get()) & $bmask) << $bshift);
             }
         }
     };
@@ -47,26 +50,40 @@ pub(crate) use impl_rgb_packed;
 
 #[cfg(test)]
 mod tests {
-    use crate::rgb::{HasBlue, HasGreen, HasRed, Rgb565};
+    use crate::{
+        component::Bits,
+        rgb::{HasBlue, HasGreen, HasRed, Rgb565},
+    };
 
     #[test]
     fn set_red() {
         let mut color = Rgb565::from_rgb(0, 0, 0);
-        color.set_red(15);
+        color.set_red(Bits::new(15));
         assert_eq!(color.red(), 15);
     }
 
     #[test]
     fn set_green() {
         let mut color = Rgb565::from_rgb(0, 0, 0);
-        color.set_green(15);
+        color.set_green(Bits::new(15));
         assert_eq!(color.green(), 15);
     }
 
     #[test]
     fn set_blue() {
         let mut color = Rgb565::from_rgb(0, 0, 0);
-        color.set_blue(15);
+        color.set_blue(Bits::new(15));
         assert_eq!(color.blue(), 15);
     }
+
+    #[test]
+    fn set_preserves_other_components() {
+        let mut color = Rgb565::from_rgb(1, 2, 3);
+        color.set_red(Bits::new(31));
+        assert_eq!(color, Rgb565::from_rgb(31, 2, 3));
+        color.set_green(Bits::new(63));
+        assert_eq!(color, Rgb565::from_rgb(31, 63, 3));
+        color.set_blue(Bits::new(0));
+        assert_eq!(color, Rgb565::from_rgb(31, 63, 0));
+    }
 }
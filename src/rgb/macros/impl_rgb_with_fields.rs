@@ -2,7 +2,7 @@ macro_rules! impl_rgb_with_fields {
     ($ty:ident<$t:ident>) => {
         impl<$t> crate::rgb::HasRed for $ty<$t>
         where
-            $t: Copy,
+            $t: crate::component::Component,
         {
             type Component = $t;
 
@@ -17,7 +17,7 @@ macro_rules! impl_rgb_with_fields {
 
         impl<$t> crate::rgb::HasGreen for $ty<$t>
         where
-            $t: Copy,
+            $t: crate::component::Component,
         {
             type Component = $t;
 
@@ -32,7 +32,7 @@ macro_rules! impl_rgb_with_fields {
 
         impl<$t> crate::rgb::HasBlue for $ty<$t>
         where
-            $t: Copy,
+            $t: crate::component::Component,
         {
             type Component = $t;
 
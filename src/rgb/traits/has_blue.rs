@@ -1,7 +1,9 @@
+use crate::component::Component;
+
 /// A trait for types that have a 🔵 blue component.
 pub trait HasBlue {
     /// The type of the blue component.
-    type Component;
+    type Component: Component;
 
     /// Returns the value of the blue component.
     #[must_use]
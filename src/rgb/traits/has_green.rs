@@ -1,7 +1,9 @@
+use crate::component::Component;
+
 /// A trait for types that have a 🟢 green component.
 pub trait HasGreen {
     /// The type of the green component.
-    type Component;
+    type Component: Component;
 
     /// Returns the value of the green component.
     #[must_use]
@@ -1,7 +1,9 @@
+use crate::component::Component;
+
 /// A trait for types that have a 🔴 red component.
 pub trait HasRed {
     /// The type of the red component.
-    type Component;
+    type Component: Component;
 
     /// Returns the value of the red component.
     #[must_use]
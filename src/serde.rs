@@ -0,0 +1,185 @@
+//! Serializing 📦 colors with [`serde`][], enabled by the `serde` feature.
+//!
+//! This module contains:
+//!
+//! - [`hex`], which serializes colors as `"#rrggbbaa"` strings
+//! - [`array`](mod@array), which serializes colors as `[r, g, b, a]` arrays
+//!
+//! Without these modules, colors are serialized as structs, in the order they are stored in
+//! memory; for example, [`Abgr8888`][] is `{ "alpha": 255, "color": { "b": 0, "g": 0, "r": 255 } }`.
+//! Single-component types, such as [`Gray<T>`][] and [`Alpha<T>`][], are serialized as their
+//! component, and packed formats, such as [`Rgb565`][], are serialized as their packed integer.
+//!
+//! [`serde`]: https://serde.rs
+//! [`Abgr8888`]: crate::rgb::Abgr8888
+//! [`Gray<T>`]: crate::gray::Gray
+//! [`Alpha<T>`]: crate::alpha::Alpha
+//! [`Rgb565`]: crate::rgb::Rgb565
+//!
+//! ## Getting Started
+//!
+//! Use `#[serde(with = "…")]` on a field to choose another representation:
+//!
+//! ```rust
+//! use gem::rgb::{Abgr8888, Rgbaf32};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Theme {
+//!     #[serde(with = "gem::serde::hex")]
+//!     accent: Abgr8888,
+//!
+//!     #[serde(with = "gem::serde::array")]
+//!     background: Rgbaf32,
+//! }
+//! ```
+
+pub mod array;
+pub mod hex;
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+    use crate::{
+        alpha::{Alpha8, AlphaFirst},
+        component::Bits,
+        gray::{Gray8, GrayAlphaF32},
+        rgb::{Abgr8888, Argb1555, Bgr, Rgb, Rgb565, Rgbaf32},
+    };
+
+    #[test]
+    fn rgb_and_bgr_are_structs() {
+        assert_tokens(
+            &Rgb::from_rgb(1u8, 2, 3),
+            &[
+                Token::Struct {
+                    name: "Rgb",
+                    len: 3,
+                },
+                Token::Str("r"),
+                Token::U8(1),
+                Token::Str("g"),
+                Token::U8(2),
+                Token::Str("b"),
+                Token::U8(3),
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &Bgr::from_bgr(0.5f32, 0.25, 1.0),
+            &[
+                Token::Struct {
+                    name: "Bgr",
+                    len: 3,
+                },
+                Token::Str("b"),
+                Token::F32(0.5),
+                Token::Str("g"),
+                Token::F32(0.25),
+                Token::Str("r"),
+                Token::F32(1.0),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn alpha_wrappers_are_structs() {
+        assert_tokens(
+            &Abgr8888::from_abgr(128, 3, 2, 1),
+            &[
+                Token::Struct {
+                    name: "AlphaFirst",
+                    len: 2,
+                },
+                Token::Str("alpha"),
+                Token::U8(128),
+                Token::Str("color"),
+                Token::Struct {
+                    name: "Bgr",
+                    len: 3,
+                },
+                Token::Str("b"),
+                Token::U8(3),
+                Token::Str("g"),
+                Token::U8(2),
+                Token::Str("r"),
+                Token::U8(1),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &GrayAlphaF32::new(0.5, 1.0),
+            &[
+                Token::Struct {
+                    name: "AlphaLast",
+                    len: 2,
+                },
+                Token::Str("color"),
+                Token::F32(0.5),
+                Token::Str("alpha"),
+                Token::F32(1.0),
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &AlphaFirst::with_color(Alpha8::new(1), Gray8::new(2)),
+            &[
+                Token::Struct {
+                    name: "AlphaFirst",
+                    len: 2,
+                },
+                Token::Str("alpha"),
+                Token::U8(1),
+                Token::Str("color"),
+                Token::U8(2),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn packed_formats_are_integers() {
+        assert_tokens(&Rgb565::new(0xF800), &[Token::U16(0xF800)]);
+        assert_tokens(&Argb1555::new(0x8001), &[Token::U16(0x8001)]);
+    }
+
+    #[test]
+    fn bits_must_be_in_range() {
+        assert_tokens(&Bits::<5>::new(31), &[Token::U8(31)]);
+        assert_de_tokens_error::<Bits<5>>(
+            &[Token::U8(32)],
+            "invalid value: integer `32`, expected an integer within the range of the component",
+        );
+    }
+
+    #[test]
+    fn float_alpha_is_a_struct() {
+        assert_tokens(
+            &Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.5),
+            &[
+                Token::Struct {
+                    name: "AlphaLast",
+                    len: 2,
+                },
+                Token::Str("color"),
+                Token::Struct {
+                    name: "Rgb",
+                    len: 3,
+                },
+                Token::Str("r"),
+                Token::F32(1.0),
+                Token::Str("g"),
+                Token::F32(0.0),
+                Token::Str("b"),
+                Token::F32(0.0),
+                Token::StructEnd,
+                Token::Str("alpha"),
+                Token::F32(0.5),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Serializes colors as `[r, g, b, a]` arrays.
+//!
+//! Use with `#[serde(with = "gem::serde::array")]` on a field of any [`RgbaColor`], such as
+//! [`Abgr8888`][] or [`Rgbaf32`][]. Components are serialized as-is, in the order red, green,
+//! blue, alpha, regardless of how the color is stored in memory.
+//!
+//! [`Abgr8888`]: crate::rgb::Abgr8888
+//! [`Rgbaf32`]: crate::rgb::Rgbaf32
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use gem::rgb::Rgbaf32;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Theme {
+//!     #[serde(with = "gem::serde::array")]
+//!     background: Rgbaf32,
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alpha::HasAlpha,
+    rgb::{HasBlue, HasGreen, HasRed, RgbaColor},
+};
+
+/// Serializes a color as an `[r, g, b, a]` array.
+///
+/// ## Errors
+///
+/// Returns an error if the serializer fails.
+pub fn serialize<C, S>(color: &C, serializer: S) -> Result<S::Ok, S::Error>
+where
+    C: RgbaColor + Copy,
+    <C as HasRed>::Component: Serialize,
+    <C as HasGreen>::Component: Serialize,
+    <C as HasBlue>::Component: Serialize,
+    <C as HasAlpha>::Component: Serialize,
+    S: serde::Serializer,
+{
+    color.into_rgba().serialize(serializer)
+}
+
+/// Deserializes a color from an `[r, g, b, a]` array.
+///
+/// ## Errors
+///
+/// Returns an error if the input is not an array of four components, or a component is out of
+/// range.
+pub fn deserialize<'de, C, D>(deserializer: D) -> Result<C, D::Error>
+where
+    C: RgbaColor,
+    <C as HasRed>::Component: Deserialize<'de>,
+    <C as HasGreen>::Component: Deserialize<'de>,
+    <C as HasBlue>::Component: Deserialize<'de>,
+    <C as HasAlpha>::Component: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    let (red, green, blue, alpha) = Deserialize::deserialize(deserializer)?;
+    Ok(C::from_rgba(red, green, blue, alpha))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+    use crate::rgb::{Abgr8888, Argb1555, Argb8888, Rgbaf32};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Abgr(#[serde(with = "super")] Abgr8888);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Argb(#[serde(with = "super")] Argb8888);
+
+    /// Returns the tokens of an `[r, g, b, a]` array of 8-bit components.
+    fn tokens(r: u8, g: u8, b: u8, a: u8) -> [Token; 6] {
+        [
+            Token::Tuple { len: 4 },
+            Token::U8(r),
+            Token::U8(g),
+            Token::U8(b),
+            Token::U8(a),
+            Token::TupleEnd,
+        ]
+    }
+
+    #[test]
+    fn order_is_independent_of_layout() {
+        assert_tokens(&Abgr(Abgr8888::from_abgr(4, 3, 2, 1)), &tokens(1, 2, 3, 4));
+        assert_tokens(&Argb(Argb8888::from_argb(4, 1, 2, 3)), &tokens(1, 2, 3, 4));
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Float(#[serde(with = "super")] Rgbaf32);
+
+    #[test]
+    fn floats() {
+        assert_tokens(
+            &Float(Rgbaf32::from_rgba(1.0, 0.5, 0.0, 0.25)),
+            &[
+                Token::Tuple { len: 4 },
+                Token::F32(1.0),
+                Token::F32(0.5),
+                Token::F32(0.0),
+                Token::F32(0.25),
+                Token::TupleEnd,
+            ],
+        );
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Packed(#[serde(with = "super")] Argb1555);
+
+    #[test]
+    fn packed_components_are_checked() {
+        assert_tokens(&Packed(Argb1555::new(0xFFFF)), &tokens(31, 31, 31, 1));
+        assert_de_tokens_error::<Packed>(
+            &tokens(31, 31, 31, 2)[..5],
+            "invalid value: integer `2`, expected an integer within the range of the component",
+        );
+        assert_de_tokens_error::<Packed>(
+            &tokens(32, 0, 0, 0)[..2],
+            "invalid value: integer `32`, expected an integer within the range of the component",
+        );
+    }
+}
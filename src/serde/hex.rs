@@ -0,0 +1,145 @@
+//! Serializes colors as `"#rrggbbaa"` strings.
+//!
+//! Use with `#[serde(with = "gem::serde::hex")]` on a field of any type that converts to and from
+//! [`Abgr8888`], such as every predefined type in [`crate::rgb`]. Components are rounded to 8 bits
+//! when serializing.
+//!
+//! Deserializing also accepts the shorter `"#rgb"`, `"#rgba"`, and `"#rrggbb"` forms; colors
+//! without alpha are fully opaque.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use gem::rgb::Rgb888;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Theme {
+//!     #[serde(with = "gem::serde::hex")]
+//!     accent: Rgb888,
+//! }
+//! ```
+
+use core::fmt;
+
+use serde::de::{self, Unexpected};
+
+use crate::{css::CssColor, rgb::Abgr8888};
+
+/// Serializes a color as a `"#rrggbbaa"` string.
+///
+/// ## Errors
+///
+/// Returns an error if the serializer fails.
+pub fn serialize<C, S>(color: &C, serializer: S) -> Result<S::Ok, S::Error>
+where
+    C: Copy + Into<Abgr8888>,
+    S: serde::Serializer,
+{
+    let color: Abgr8888 = (*color).into();
+    serializer.collect_str(&format_args!("{color:x}"))
+}
+
+/// Deserializes a color from a `"#rgb"`, `"#rgba"`, `"#rrggbb"`, or `"#rrggbbaa"` string.
+///
+/// ## Errors
+///
+/// Returns an error if the input is not a string, or is not a hex color.
+pub fn deserialize<'de, C, D>(deserializer: D) -> Result<C, D::Error>
+where
+    C: From<Abgr8888>,
+    D: serde::Deserializer<'de>,
+{
+    deserializer.deserialize_str(HexVisitor).map(C::from)
+}
+
+/// Visits a hex color string.
+struct HexVisitor;
+
+impl de::Visitor<'_> for HexVisitor {
+    type Value = Abgr8888;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a hex color, such as \"#rrggbbaa\"")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        // Only the hex digits themselves are accepted, without the whitespace or comments that
+        // CSS allows around them.
+        let is_hex = value.strip_prefix('#').is_some_and(|digits| {
+            matches!(digits.len(), 3 | 4 | 6 | 8) && digits.bytes().all(|b| b.is_ascii_hexdigit())
+        });
+        match is_hex.then(|| value.parse::<CssColor>()) {
+            Some(Ok(color)) => Ok(color.into()),
+            _ => Err(E::invalid_value(Unexpected::Str(value), &self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_test::{
+        Token, assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens,
+    };
+
+    use crate::rgb::{Abgr8888, Rgb565, Rgb888, Rgbaf32};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Hex<C: Copy + Into<Abgr8888> + From<Abgr8888>>(#[serde(with = "super")] C);
+
+    #[test]
+    fn round_trip() {
+        let color = Hex(Abgr8888::from_abgr(0x80, 0x03, 0x02, 0xFF));
+        assert_tokens(&color, &[Token::Str("#ff020380")]);
+        assert_tokens(&Hex(Rgb888::from_rgb(1, 2, 3)), &[Token::Str("#010203ff")]);
+        assert_tokens(
+            &Hex(Rgb565::from_rgb(31, 0, 31)),
+            &[Token::Str("#ff00ffff")],
+        );
+    }
+
+    #[test]
+    fn rounds_floats() {
+        let color = Hex(Rgbaf32::from_rgba(1.0, 0.5, 0.0, 0.25));
+        assert_ser_tokens(&color, &[Token::Str("#ff800040")]);
+    }
+
+    #[test]
+    fn accepts_short_forms() {
+        let white = Hex(Rgb888::from_rgb(255, 255, 255));
+        assert_de_tokens(&white, &[Token::Str("#fff")]);
+        assert_de_tokens(&white, &[Token::Str("#FFFFFF")]);
+        let clear = Hex(Abgr8888::from_abgr(0, 0, 0, 0));
+        assert_de_tokens(&clear, &[Token::BorrowedStr("#0000")]);
+    }
+
+    #[test]
+    fn rejects_other_colors() {
+        assert_de_tokens_error::<Hex<Rgb888>>(
+            &[Token::Str("red")],
+            "invalid value: string \"red\", expected a hex color, such as \"#rrggbbaa\"",
+        );
+        assert_de_tokens_error::<Hex<Rgb888>>(
+            &[Token::Str("#ff000")],
+            "invalid value: string \"#ff000\", expected a hex color, such as \"#rrggbbaa\"",
+        );
+        assert_de_tokens_error::<Hex<Rgb888>>(
+            &[Token::Str("#fff /* comment */")],
+            "invalid value: string \"#fff /* comment */\", expected a hex color, such as \"#rrggbbaa\"",
+        );
+        assert_de_tokens_error::<Hex<Rgb888>>(
+            &[Token::Str("#fff ")],
+            "invalid value: string \"#fff \", expected a hex color, such as \"#rrggbbaa\"",
+        );
+        assert_de_tokens_error::<Hex<Rgb888>>(
+            &[Token::Str(" #fff")],
+            "invalid value: string \" #fff\", expected a hex color, such as \"#rrggbbaa\"",
+        );
+        assert_de_tokens_error::<Hex<Rgb888>>(
+            &[Token::U32(0xFF00_00FF)],
+            "invalid type: integer `4278190335`, expected a hex color, such as \"#rrggbbaa\"",
+        );
+    }
+}
@@ -0,0 +1,492 @@
+//! Describing 🌈 RGB color spaces by their primaries, white point, and transfer function.
+//!
+//! The RGB types of this crate, such as [`Rgb888`] and [`Rgbf32`], store components without
+//! recording which color space they are in, and are otherwise assumed to be sRGB. An
+//! [`RgbSpace`] describes a color space, so that colors can be converted between spaces, such as
+//! from Display P3 into sRGB.
+//!
+//! This module contains:
+//!
+//! - [`RgbSpace`], the primaries, white point, and [`TransferFunction`] of a color space
+//! - [`Conversion`], which converts colors from one space into another through CIE XYZ
+//!
+//! Conversions work on any [`RgbColor`], from 8-bit integers to [`Rgbaf32`], and keep alpha, if
+//! any, unchanged. Integer components are clamped to the gamut of the destination space, while
+//! floating-point components may be negative or greater than `1.0` to represent colors outside of
+//! it.
+//!
+//! [`Rgb888`]: crate::rgb::Rgb888
+//! [`Rgbf32`]: crate::rgb::Rgbf32
+//! [`Rgbaf32`]: crate::rgb::Rgbaf32
+//! [`RgbColor`]: crate::rgb::RgbColor
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{
+//!     rgb::{HasGreen, HasRed, Rgb888, Rgbf32},
+//!     space::{Conversion, RgbSpace},
+//! };
+//!
+//! const SRGB_TO_P3: Conversion = Conversion::new(&RgbSpace::SRGB, &RgbSpace::DISPLAY_P3);
+//!
+//! // Pure sRGB red is inside of the larger Display P3 gamut.
+//! let red = SRGB_TO_P3.convert(Rgb888::from_rgb(255, 0, 0));
+//! assert_eq!(red, Rgb888::from_rgb(234, 51, 35));
+//!
+//! // Pure Display P3 red is outside of the sRGB gamut.
+//! let red = Conversion::new(&RgbSpace::DISPLAY_P3, &RgbSpace::SRGB)
+//!     .convert(Rgbf32::from_rgb(1.0, 0.0, 0.0));
+//! assert!(red.red() > 1.0 && red.green() < 0.0);
+//! ```
+//!
+//! ## Predefined Spaces
+//!
+//! Space                      | Primaries        | White point | Transfer function
+//! -------------------------- | ---------------- | ----------- | ------------------------------
+//! [`RgbSpace::SRGB`]         | ITU-R BT.709     | [`D65`]     | [`TransferFunction::Srgb`]
+//! [`RgbSpace::DISPLAY_P3`]   | DCI-P3           | [`D65`]     | [`TransferFunction::Srgb`]
+//! [`RgbSpace::REC709`]       | ITU-R BT.709     | [`D65`]     | [`TransferFunction::Rec709`]
+//! [`RgbSpace::REC2020`]      | ITU-R BT.2020    | [`D65`]     | [`TransferFunction::Rec709`]
+//! [`RgbSpace::ADOBE_RGB`]    | Adobe RGB (1998) | [`D65`]     | A gamma of `563 / 256`
+//! [`RgbSpace::PROPHOTO_RGB`] | ROMM RGB         | [`D50`]     | [`TransferFunction::ProPhoto`]
+//! [`RgbSpace::ACES2065_1`]   | ACES AP0         | [`D60`]     | [`TransferFunction::Linear`]
+//! [`RgbSpace::ACESCG`]       | ACES AP1         | [`D60`]     | [`TransferFunction::Linear`]
+//!
+//! Other spaces, such as linear Display P3, are created with [`RgbSpace::new`] or
+//! [`RgbSpace::with_transfer`].
+//!
+//! [`D65`]: crate::cie::D65
+//! [`D50`]: crate::cie::D50
+//! [`D60`]: crate::cie::D60
+
+use crate::{
+    cie::{
+        D50, D60, D65, WhitePoint, Xyz, adaptation_matrix, chromaticity_to_xyz, rgb_to_xyz_matrix,
+    },
+    component::Component,
+    math::{Matrix3, invert, mul_matrix, mul_vector},
+    rgb::RgbColor,
+};
+
+mod transfer;
+
+pub use transfer::TransferFunction;
+#[cfg(feature = "alloc")]
+pub(crate) use transfer::{PROPHOTO_END, REC709_ALPHA, REC709_BETA};
+
+/// The `x` and `y` chromaticities of the ITU-R BT.709 (and sRGB) primaries.
+const BT709_PRIMARIES: [[f64; 2]; 3] = [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]];
+
+/// An RGB color space, defined by the chromaticities of its primaries and white point, and by the
+/// function that encodes its components.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{
+///     cie::{D65, WhitePoint},
+///     space::{RgbSpace, TransferFunction},
+/// };
+///
+/// let linear_p3 = RgbSpace::DISPLAY_P3.with_transfer(TransferFunction::Linear);
+/// assert_eq!(linear_p3.white(), D65::CHROMATICITY);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RgbSpace {
+    primaries: [[f64; 2]; 3],
+    white: [f64; 2],
+    transfer: TransferFunction,
+}
+
+impl RgbSpace {
+    /// sRGB, as defined by IEC 61966-2-1, and used by CSS and most displays and images.
+    pub const SRGB: Self = Self::new(BT709_PRIMARIES, D65::CHROMATICITY, TransferFunction::Srgb);
+
+    /// Display P3, the DCI-P3 primaries with a [`D65`] white point and the sRGB transfer
+    /// function, as used by CSS and Apple displays.
+    pub const DISPLAY_P3: Self = Self::new(
+        [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]],
+        D65::CHROMATICITY,
+        TransferFunction::Srgb,
+    );
+
+    /// ITU-R BT.709, the space of HDTV, which shares its primaries with sRGB.
+    pub const REC709: Self =
+        Self::new(BT709_PRIMARIES, D65::CHROMATICITY, TransferFunction::Rec709);
+
+    /// ITU-R BT.2020, the wide gamut space of UHDTV.
+    pub const REC2020: Self = Self::new(
+        [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]],
+        D65::CHROMATICITY,
+        TransferFunction::Rec709,
+    );
+
+    /// Adobe RGB (1998), a wide gamut space for photography and printing.
+    pub const ADOBE_RGB: Self = Self::new(
+        [[0.64, 0.33], [0.21, 0.71], [0.15, 0.06]],
+        D65::CHROMATICITY,
+        TransferFunction::Gamma(563.0 / 256.0),
+    );
+
+    /// `ProPhoto` RGB, or ROMM RGB as defined by ISO 22028-2, a very wide gamut space for
+    /// photography, whose green and blue primaries are imaginary.
+    pub const PROPHOTO_RGB: Self = Self::new(
+        [[0.7347, 0.2653], [0.1596, 0.8404], [0.0366, 0.0001]],
+        D50::CHROMATICITY,
+        TransferFunction::ProPhoto,
+    );
+
+    /// ACES2065-1, the linear interchange space of the Academy Color Encoding System, whose AP0
+    /// primaries contain every visible color.
+    pub const ACES2065_1: Self = Self::new(
+        [[0.7347, 0.2653], [0.0, 1.0], [0.0001, -0.0770]],
+        D60::CHROMATICITY,
+        TransferFunction::Linear,
+    );
+
+    /// `ACEScg`, the linear working space of the Academy Color Encoding System, with the AP1
+    /// primaries.
+    pub const ACESCG: Self = Self::new(
+        [[0.713, 0.293], [0.165, 0.830], [0.128, 0.044]],
+        D60::CHROMATICITY,
+        TransferFunction::Linear,
+    );
+
+    /// Creates a color space from the `x` and `y` chromaticities of its red, green, and blue
+    /// primaries and its white point, such as [`D65::CHROMATICITY`], and its transfer function.
+    ///
+    /// [`D65::CHROMATICITY`]: crate::cie::WhitePoint::CHROMATICITY
+    #[must_use]
+    pub const fn new(
+        primaries: [[f64; 2]; 3],
+        white: [f64; 2],
+        transfer: TransferFunction,
+    ) -> Self {
+        Self {
+            primaries,
+            white,
+            transfer,
+        }
+    }
+
+    /// Returns the `x` and `y` chromaticities of the red, green, and blue primaries.
+    #[must_use]
+    pub const fn primaries(&self) -> [[f64; 2]; 3] {
+        self.primaries
+    }
+
+    /// Returns the `x` and `y` chromaticity of the white point.
+    #[must_use]
+    pub const fn white(&self) -> [f64; 2] {
+        self.white
+    }
+
+    /// Returns the transfer function.
+    #[must_use]
+    pub const fn transfer(&self) -> TransferFunction {
+        self.transfer
+    }
+
+    /// Returns a copy of this space with a different transfer function.
+    #[must_use]
+    pub const fn with_transfer(self, transfer: TransferFunction) -> Self {
+        Self { transfer, ..self }
+    }
+
+    /// Returns the matrix that converts linear RGB in this space into XYZ, relative to the white
+    /// point of this space.
+    ///
+    /// The matrix is computed from the chromaticities in double precision.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::space::RgbSpace;
+    ///
+    /// let matrix = RgbSpace::SRGB.to_xyz_matrix();
+    /// assert!((matrix[0][0] - 0.412_390_8).abs() < 1e-7);
+    /// ```
+    #[must_use]
+    pub const fn to_xyz_matrix(&self) -> [[f64; 3]; 3] {
+        rgb_to_xyz_matrix(self.primaries, chromaticity_to_xyz(self.white))
+    }
+
+    /// Returns the matrix that converts XYZ, relative to the white point of this space, into
+    /// linear RGB in this space.
+    #[must_use]
+    pub const fn from_xyz_matrix(&self) -> [[f64; 3]; 3] {
+        invert(&self.to_xyz_matrix())
+    }
+
+    /// Converts a color in this space into XYZ, adapted to the white point `W` with the Bradford
+    /// transform.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{cie::{D50, Xyz}, rgb::Rgb888, space::RgbSpace};
+    ///
+    /// let white: Xyz<f32, D50> = RgbSpace::ACESCG.to_xyz(Rgb888::from_rgb(255, 255, 255));
+    /// assert!((white.x() - 0.964_296).abs() < 1e-5);
+    /// ```
+    #[must_use]
+    pub fn to_xyz<C: RgbColor, W: WhitePoint>(&self, color: C) -> Xyz<f32, W> {
+        let matrix = mul_matrix(
+            &adaptation_matrix(chromaticity_to_xyz(self.white), W::XYZ),
+            &self.to_xyz_matrix(),
+        );
+        let rgb = decode(self.transfer, color.into_rgb());
+        Xyz::from_f64(mul_vector(&matrix, rgb))
+    }
+
+    /// Converts a color from XYZ, adapted from the white point `W` with the Bradford transform,
+    /// into this space.
+    ///
+    /// Any components other than red, green, and blue are set to their default values, as in
+    /// [`RgbColor::from_rgb`].
+    #[must_use]
+    pub fn from_xyz<C: RgbColor, W: WhitePoint>(&self, color: Xyz<f32, W>) -> C {
+        let matrix = mul_matrix(
+            &self.from_xyz_matrix(),
+            &adaptation_matrix(W::XYZ, chromaticity_to_xyz(self.white)),
+        );
+        let rgb = mul_vector(&matrix, color.into_f64());
+        encode(self.transfer, rgb, C::default())
+    }
+}
+
+/// A conversion of colors from one [`RgbSpace`] into another.
+///
+/// Colors are decoded into linear light, converted into XYZ, adapted to the white point of the
+/// destination with the Bradford transform, converted into the destination primaries, and
+/// encoded. All three matrices are combined when the conversion is created, which can be done in a
+/// constant.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{
+///     alpha::HasAlpha,
+///     rgb::{HasRed, Rgbaf32},
+///     space::{Conversion, RgbSpace},
+/// };
+///
+/// let to_acescg = Conversion::new(&RgbSpace::SRGB, &RgbSpace::ACESCG);
+/// let red = to_acescg.convert(Rgbaf32::from_rgba(1.0, 0.0, 0.0, 0.5));
+/// assert!((red.red() - 0.613_097).abs() < 1e-5);
+/// assert_eq!(red.alpha(), 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conversion {
+    matrix: Matrix3,
+    src: TransferFunction,
+    dst: TransferFunction,
+}
+
+impl Conversion {
+    /// Creates a conversion from colors in `src` into colors in `dst`.
+    #[must_use]
+    pub const fn new(src: &RgbSpace, dst: &RgbSpace) -> Self {
+        let adaptation = adaptation_matrix(
+            chromaticity_to_xyz(src.white),
+            chromaticity_to_xyz(dst.white),
+        );
+        let matrix = mul_matrix(
+            &dst.from_xyz_matrix(),
+            &mul_matrix(&adaptation, &src.to_xyz_matrix()),
+        );
+        Self {
+            matrix,
+            src: src.transfer,
+            dst: dst.transfer,
+        }
+    }
+
+    /// Returns the matrix that converts linear RGB in the source space into linear RGB in the
+    /// destination space.
+    #[must_use]
+    pub const fn matrix(&self) -> [[f64; 3]; 3] {
+        self.matrix
+    }
+
+    /// Converts a color, keeping any components other than red, green, and blue.
+    #[must_use]
+    pub fn convert<C: RgbColor>(&self, color: C) -> C {
+        let rgb = (color.red(), color.green(), color.blue());
+        let rgb = mul_vector(&self.matrix, decode(self.src, rgb));
+        encode(self.dst, rgb, color)
+    }
+
+    /// Converts each color of `src` into `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{rgb::Abgr8888, space::{Conversion, RgbSpace}};
+    ///
+    /// let src = [Abgr8888::from_abgr(128, 255, 255, 255); 4];
+    /// let mut dst = [Abgr8888::default(); 4];
+    /// Conversion::new(&RgbSpace::ADOBE_RGB, &RgbSpace::SRGB).convert_slice(&src, &mut dst);
+    /// assert_eq!(dst, src);
+    /// ```
+    pub fn convert_slice<C: RgbColor + Copy>(&self, src: &[C], dst: &mut [C]) {
+        assert_eq!(
+            src.len(),
+            dst.len(),
+            "source and destination lengths differ"
+        );
+        for (&s, d) in src.iter().zip(dst) {
+            *d = self.convert(s);
+        }
+    }
+}
+
+/// Decodes red, green, and blue components into linear light.
+fn decode(
+    transfer: TransferFunction,
+    (red, green, blue): (impl Component, impl Component, impl Component),
+) -> [f64; 3] {
+    [
+        red.to_normalized_f32(),
+        green.to_normalized_f32(),
+        blue.to_normalized_f32(),
+    ]
+    .map(|c| f64::from(transfer.decode(c)))
+}
+
+/// Encodes linear light into the red, green, and blue components of `color`.
+#[allow(clippy::cast_possible_truncation)]
+fn encode<C: RgbColor>(transfer: TransferFunction, rgb: [f64; 3], mut color: C) -> C {
+    let [red, green, blue] = rgb.map(|c| transfer.encode(c as f32));
+    color.set_red(Component::from_normalized_f32(red));
+    color.set_green(Component::from_normalized_f32(green));
+    color.set_blue(Component::from_normalized_f32(blue));
+    color
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::{
+        cie::Xyzf32,
+        encoding::Srgb,
+        rgb::{HasBlue, HasGreen, HasRed, Rgb888, Rgbf32},
+    };
+
+    fn assert_matrix_eq(actual: Matrix3, expected: Matrix3, tolerance: f64) {
+        for (a, e) in actual.iter().flatten().zip(expected.iter().flatten()) {
+            assert!((a - e).abs() < tolerance, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn published_matrices() {
+        // The matrices of CSS Color 4, and of SMPTE ST 2065-1 and the ACEScg specification.
+        assert_matrix_eq(
+            RgbSpace::DISPLAY_P3.to_xyz_matrix(),
+            [
+                [0.486_570_9, 0.265_667_7, 0.198_217_3],
+                [0.228_974_6, 0.691_738_5, 0.079_286_9],
+                [0.0, 0.045_113_4, 1.043_944_4],
+            ],
+            1e-7,
+        );
+        assert_matrix_eq(
+            RgbSpace::REC2020.to_xyz_matrix(),
+            [
+                [0.636_958_0, 0.144_616_9, 0.168_881_0],
+                [0.262_700_2, 0.677_998_1, 0.059_301_7],
+                [0.0, 0.028_072_7, 1.060_985_1],
+            ],
+            1e-7,
+        );
+        assert_matrix_eq(
+            RgbSpace::ACES2065_1.to_xyz_matrix(),
+            [
+                [0.952_552_4, 0.0, 0.000_093_7],
+                [0.343_966_4, 0.728_166_1, -0.072_132_5],
+                [0.0, 0.0, 1.008_825_2],
+            ],
+            1e-7,
+        );
+        assert_matrix_eq(
+            RgbSpace::ACESCG.to_xyz_matrix(),
+            [
+                [0.662_454_2, 0.134_004_2, 0.156_187_7],
+                [0.272_228_7, 0.674_081_8, 0.053_689_5],
+                [-0.005_574_6, 0.004_060_7, 1.010_339_1],
+            ],
+            1e-7,
+        );
+    }
+
+    #[test]
+    fn srgb_matches_cie() {
+        for value in [0.0, 0.2, 0.5, 1.0] {
+            let color = Rgbf32::from_rgb(value, 0.3, 1.0 - value);
+            let expected = Xyzf32::from(Srgb::new(color));
+            let actual: Xyzf32 = RgbSpace::SRGB.to_xyz(color);
+            assert!((actual.x() - expected.x()).abs() < 1e-6);
+            assert!((actual.y() - expected.y()).abs() < 1e-6);
+            assert!((actual.z() - expected.z()).abs() < 1e-6);
+            let round_trip: Rgbf32 = RgbSpace::SRGB.from_xyz(actual);
+            assert!((round_trip.red() - value).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn identity_is_lossless() {
+        let conversion = Conversion::new(&RgbSpace::PROPHOTO_RGB, &RgbSpace::PROPHOTO_RGB);
+        for value in 0..=u8::MAX {
+            let color = Rgb888::from_rgb(value, 255 - value, value / 2);
+            assert_eq!(conversion.convert(color), color);
+        }
+    }
+
+    #[test]
+    fn round_trips_through_every_space() {
+        let spaces = [
+            RgbSpace::SRGB,
+            RgbSpace::DISPLAY_P3,
+            RgbSpace::REC709,
+            RgbSpace::REC2020,
+            RgbSpace::ADOBE_RGB,
+            RgbSpace::PROPHOTO_RGB,
+            RgbSpace::ACES2065_1,
+            RgbSpace::ACESCG,
+        ];
+        let color = Rgbf32::from_rgb(0.9, 0.4, 0.1);
+        for space in &spaces {
+            let there = Conversion::new(&RgbSpace::SRGB, space).convert(color);
+            let back = Conversion::new(space, &RgbSpace::SRGB).convert(there);
+            assert!((back.red() - color.red()).abs() < 1e-5, "{space:?}");
+            assert!((back.green() - color.green()).abs() < 1e-5, "{space:?}");
+            assert!((back.blue() - color.blue()).abs() < 1e-5, "{space:?}");
+        }
+    }
+
+    #[test]
+    fn integers_are_clamped() {
+        let red = Conversion::new(&RgbSpace::REC2020, &RgbSpace::SRGB)
+            .convert(Rgb888::from_rgb(255, 0, 0));
+        assert_eq!(red, Rgb888::from_rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn white_is_adapted() {
+        // White is white in every space, regardless of its white point.
+        let white = Rgbf32::from_rgb(1.0, 1.0, 1.0);
+        for space in [RgbSpace::PROPHOTO_RGB, RgbSpace::ACES2065_1] {
+            let converted = Conversion::new(&RgbSpace::SRGB, &space).convert(white);
+            assert!((converted.red() - 1.0).abs() < 1e-6, "{space:?}");
+            assert!((converted.green() - 1.0).abs() < 1e-6, "{space:?}");
+            assert!((converted.blue() - 1.0).abs() < 1e-6, "{space:?}");
+        }
+    }
+}
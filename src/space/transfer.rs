@@ -0,0 +1,157 @@
+use crate::{
+    encoding::{linear_to_srgb, srgb_to_linear},
+    math::pow,
+};
+
+/// The function that encodes linear light into the components of an RGB color space.
+///
+/// Values outside of `0.0..=1.0` are extended by mirroring each function around zero, as in the
+/// extended encodings used by CSS Color 4, so that colors outside of the gamut round-trip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferFunction {
+    /// Components are linear light.
+    Linear,
+
+    /// The sRGB transfer function, as defined by IEC 61966-2-1.
+    Srgb,
+
+    /// A pure power function, where linear light is the encoded value raised to the gamma.
+    Gamma(f64),
+
+    /// The opto-electronic transfer function of ITU-R BT.709 and BT.2020.
+    ///
+    /// The constants are those of BT.2020 in full precision, of which the BT.709 values of
+    /// `1.099` and `0.018` are a rounding.
+    Rec709,
+
+    /// The transfer function of ROMM RGB (`ProPhoto` RGB), a gamma of `1.8` with a linear segment
+    /// near black.
+    ProPhoto,
+}
+
+/// The `α` constant of the BT.2020 transfer function, which makes both segments meet.
+pub(crate) const REC709_ALPHA: f64 = 1.099_296_826_809_44;
+
+/// The `β` constant of the BT.2020 transfer function, where the linear segment ends.
+pub(crate) const REC709_BETA: f64 = 0.018_053_968_510_807;
+
+/// The linear value where the linear segment of the `ProPhoto` transfer function ends.
+pub(crate) const PROPHOTO_END: f64 = 1.0 / 512.0;
+
+impl TransferFunction {
+    /// Decodes an encoded component into linear light.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::space::TransferFunction;
+    ///
+    /// assert_eq!(TransferFunction::Gamma(2.0).decode(0.5), 0.25);
+    /// assert_eq!(TransferFunction::Rec709.decode(1.0), 1.0);
+    /// assert_eq!(TransferFunction::Linear.decode(-0.5), -0.5);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn decode(self, value: f32) -> f32 {
+        let abs = f64::from(value.abs());
+        let linear = match self {
+            Self::Linear => return value,
+            Self::Srgb => return srgb_to_linear(value),
+            Self::Gamma(gamma) => pow(abs, gamma),
+            Self::Rec709 => {
+                if abs < 4.5 * REC709_BETA {
+                    abs / 4.5
+                } else {
+                    pow((abs + REC709_ALPHA - 1.0) / REC709_ALPHA, 1.0 / 0.45)
+                }
+            }
+            Self::ProPhoto => {
+                if abs < 16.0 * PROPHOTO_END {
+                    abs / 16.0
+                } else {
+                    pow(abs, 1.8)
+                }
+            }
+        };
+        (linear as f32).copysign(value)
+    }
+
+    /// Encodes a linear light component.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::space::TransferFunction;
+    ///
+    /// assert_eq!(TransferFunction::Gamma(2.0).encode(0.25), 0.5);
+    /// assert!((TransferFunction::ProPhoto.encode(0.5) - 0.680_395).abs() < 1e-6);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn encode(self, value: f32) -> f32 {
+        let abs = f64::from(value.abs());
+        let encoded = match self {
+            Self::Linear => return value,
+            Self::Srgb => return linear_to_srgb(value),
+            Self::Gamma(gamma) => pow(abs, 1.0 / gamma),
+            Self::Rec709 => {
+                if abs < REC709_BETA {
+                    abs * 4.5
+                } else {
+                    REC709_ALPHA * pow(abs, 0.45) - (REC709_ALPHA - 1.0)
+                }
+            }
+            Self::ProPhoto => {
+                if abs < PROPHOTO_END {
+                    abs * 16.0
+                } else {
+                    pow(abs, 1.0 / 1.8)
+                }
+            }
+        };
+        (encoded as f32).copysign(value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    const ALL: [TransferFunction; 5] = [
+        TransferFunction::Linear,
+        TransferFunction::Srgb,
+        TransferFunction::Gamma(563.0 / 256.0),
+        TransferFunction::Rec709,
+        TransferFunction::ProPhoto,
+    ];
+
+    #[test]
+    fn round_trips() {
+        for function in ALL {
+            for i in -10..=30_i8 {
+                let value = f32::from(i) / 20.0;
+                let round_trip = function.decode(function.encode(value));
+                assert!((round_trip - value).abs() < 1e-5, "{function:?} {value}");
+            }
+            assert_eq!(function.decode(0.0), 0.0);
+            assert!((function.decode(1.0) - 1.0).abs() < 1e-6, "{function:?}");
+        }
+    }
+
+    #[test]
+    fn segments_meet() {
+        // Both segments of the piecewise functions are continuous at the breakpoint.
+        let rec709 = REC709_ALPHA * pow(REC709_BETA, 0.45) - (REC709_ALPHA - 1.0);
+        assert!((rec709 - 4.5 * REC709_BETA).abs() < 1e-12);
+        assert!((pow(PROPHOTO_END, 1.0 / 1.8) - 16.0 * PROPHOTO_END).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rec709_reference_values() {
+        // A reflectance of 18% is encoded as about 40.9%.
+        let encoded = TransferFunction::Rec709.encode(0.18);
+        assert!((encoded - 0.408_848).abs() < 1e-6);
+        assert!((TransferFunction::Rec709.encode(0.01) - 0.045).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,481 @@
+//! Simulating 👁️ color vision deficiencies, such as for reviewing the accessibility of a palette.
+//!
+//! This module contains:
+//!
+//! - [`Deficiency`], the cone type that is missing or anomalous
+//! - [`Simulation`], which shows colors as they are seen with a deficiency, using either:
+//!   - [`Simulation::machado`], the model of [Machado, Oliveira, and Fernandes (2009)][machado],
+//!     which also simulates anomalous trichromacy with a severity
+//!   - [`Simulation::brettel`], the model of [Brettel, Viénot, and Mollon (1997)][brettel], which
+//!     is more accurate for dichromacy, and especially for tritanopia
+//! - [`Simulation::daltonize`], which shifts colors to restore the contrast lost to a deficiency
+//!
+//! Every method works on any sRGB-encoded [`RgbColor`], one color at a time, over slices, or over
+//! images, and is applied in linear light. Alpha, if any, is kept as is.
+//!
+//! [machado]: https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html
+//! [brettel]: https://doi.org/10.1364/JOSAA.14.002647
+//! [`RgbColor`]: crate::rgb::RgbColor
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{
+//!     rgb::{HasGreen, HasRed, Rgb888},
+//!     vision::{Deficiency, Simulation},
+//! };
+//!
+//! let red = Rgb888::from_rgb(200, 40, 40);
+//! let green = Rgb888::from_rgb(60, 140, 40);
+//!
+//! // With deuteranopia, red and green look like similar shades of olive.
+//! let deuteranopia = Simulation::brettel(Deficiency::Deutan, 1.0);
+//! let (red, green) = (deuteranopia.simulate(red), deuteranopia.simulate(green));
+//! assert!(red.red().abs_diff(green.red()) <= 5);
+//! assert!(red.green().abs_diff(green.green()) <= 5);
+//! ```
+
+use crate::{
+    encoding::{decode, encode},
+    image::{ImageView, ImageViewMut},
+    math::{Matrix3, mul_vector},
+    rgb::RgbColor,
+};
+
+mod brettel;
+mod machado;
+
+/// A color vision deficiency, named after the cone type that is missing or anomalous.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Deficiency {
+    /// Missing (protanopia) or anomalous (protanomaly) long-wavelength, or red, cones.
+    Protan,
+
+    /// Missing (deuteranopia) or anomalous (deuteranomaly) medium-wavelength, or green, cones.
+    Deutan,
+
+    /// Missing (tritanopia) or anomalous (tritanomaly) short-wavelength, or blue, cones.
+    Tritan,
+}
+
+/// A simulation of how colors are seen with a [`Deficiency`].
+///
+/// Simulations are precomputed matrices, so creating one once and reusing it is cheap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Simulation {
+    deficiency: Deficiency,
+    matrices: [Matrix3; 2],
+    separation: [f64; 3],
+}
+
+impl Simulation {
+    /// Creates a simulation with the model of Machado, Oliveira, and Fernandes (2009).
+    ///
+    /// A `severity` of `0.0` is normal vision, and `1.0` is dichromacy, where the cone type is
+    /// missing; severities in between are anomalous trichromacy.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `severity` is not within `0.0..=1.0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{rgb::Rgb888, vision::{Deficiency, Simulation}};
+    ///
+    /// let protanopia = Simulation::machado(Deficiency::Protan, 1.0);
+    /// assert_eq!(protanopia.simulate(Rgb888::from_rgb(255, 0, 0)), Rgb888::from_rgb(109, 95, 0));
+    /// ```
+    #[must_use]
+    pub fn machado(deficiency: Deficiency, severity: f32) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&severity),
+            "severity must be within 0.0..=1.0"
+        );
+        let matrix = machado::matrix(deficiency, f64::from(severity));
+        Self {
+            deficiency,
+            matrices: [matrix; 2],
+            separation: [0.0; 3],
+        }
+    }
+
+    /// Creates a simulation with the model of Brettel, Viénot, and Mollon (1997).
+    ///
+    /// The model is of dichromacy, where the cone type is missing. A `severity` below `1.0` mixes
+    /// the simulated color with the original color in linear light, which approximates anomalous
+    /// trichromacy; `0.0` is normal vision.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `severity` is not within `0.0..=1.0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{rgb::Rgb888, vision::{Deficiency, Simulation}};
+    ///
+    /// let tritanopia = Simulation::brettel(Deficiency::Tritan, 1.0);
+    /// let blue = tritanopia.simulate(Rgb888::from_rgb(0, 0, 255));
+    /// assert_eq!(blue, Rgb888::from_rgb(0, 98, 136));
+    /// ```
+    #[must_use]
+    pub fn brettel(deficiency: Deficiency, severity: f32) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&severity),
+            "severity must be within 0.0..=1.0"
+        );
+        let severity = f64::from(severity);
+        let brettel::HalfPlanes {
+            matrices,
+            separation,
+        } = brettel::half_planes(deficiency);
+        let matrices = matrices.map(|m| {
+            core::array::from_fn(|i| {
+                core::array::from_fn(|j| {
+                    let identity = if i == j { 1.0 } else { 0.0 };
+                    identity + (m[i][j] - identity) * severity
+                })
+            })
+        });
+        Self {
+            deficiency,
+            matrices,
+            separation,
+        }
+    }
+
+    /// Returns the simulated deficiency.
+    #[must_use]
+    pub const fn deficiency(&self) -> Deficiency {
+        self.deficiency
+    }
+
+    /// Returns `color` as seen with the deficiency.
+    ///
+    /// Colors that are seen outside of the sRGB gamut are clamped.
+    #[must_use]
+    pub fn simulate<C: RgbColor>(&self, color: C) -> C {
+        map_linear(color, |rgb| self.simulate_linear(rgb))
+    }
+
+    /// Simulates each color of `src` into `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{rgb::{HasRed, Rgbf32}, vision::{Deficiency, Simulation}};
+    ///
+    /// let src = [Rgbf32::from_rgb(0.5, 0.5, 0.5); 4];
+    /// let mut dst = [Rgbf32::default(); 4];
+    /// Simulation::machado(Deficiency::Deutan, 0.6).simulate_slice(&src, &mut dst);
+    /// assert!(dst.iter().all(|c| (c.red() - 0.5).abs() < 1e-4));
+    /// ```
+    pub fn simulate_slice<C: RgbColor + Copy>(&self, src: &[C], dst: &mut [C]) {
+        map_slice(src, dst, |color| self.simulate(color));
+    }
+
+    /// Simulates each pixel of `src` into `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different sizes.
+    pub fn simulate_image<C: RgbColor + Copy>(
+        &self,
+        src: ImageView<'_, C>,
+        dst: &mut ImageViewMut<'_, C>,
+    ) {
+        map_image(src, dst, |color| self.simulate(color));
+    }
+
+    /// Returns `color` corrected for the deficiency, so that colors that are confused with it are
+    /// easier to tell apart.
+    ///
+    /// The difference between the color and how it is seen is the information that is lost. It is
+    /// shifted into the components that are still seen, as described by Fidaner, Lin, and Ozguven
+    /// (2005): into green and blue for protans and deutans, and into red and green for tritans.
+    /// Neutral colors, which are seen as they are, are unchanged.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{rgb::{HasBlue, Rgb888}, vision::{Deficiency, Simulation}};
+    ///
+    /// let deuteranopia = Simulation::machado(Deficiency::Deutan, 1.0);
+    /// let gray = Rgb888::from_rgb(128, 128, 128);
+    /// assert_eq!(deuteranopia.daltonize(gray), gray);
+    ///
+    /// // Red gains blue, which is still seen, and green loses it.
+    /// let red = deuteranopia.daltonize(Rgb888::from_rgb(200, 60, 60));
+    /// let green = deuteranopia.daltonize(Rgb888::from_rgb(60, 160, 60));
+    /// assert!(red.blue() > 60 && green.blue() < 60);
+    /// ```
+    #[must_use]
+    pub fn daltonize<C: RgbColor>(&self, color: C) -> C {
+        #[rustfmt::skip]
+        const SHIFT_INTO_GREEN_AND_BLUE: Matrix3 = [
+            [0.0, 0.0, 0.0],
+            [0.7, 1.0, 0.0],
+            [0.7, 0.0, 1.0],
+        ];
+        #[rustfmt::skip]
+        const SHIFT_INTO_RED_AND_GREEN: Matrix3 = [
+            [1.0, 0.0, 0.7],
+            [0.0, 1.0, 0.7],
+            [0.0, 0.0, 0.0],
+        ];
+        let shift = match self.deficiency {
+            Deficiency::Protan | Deficiency::Deutan => &SHIFT_INTO_GREEN_AND_BLUE,
+            Deficiency::Tritan => &SHIFT_INTO_RED_AND_GREEN,
+        };
+        map_linear(color, |rgb| {
+            let seen = self.simulate_linear(rgb);
+            let lost = core::array::from_fn(|i| rgb[i] - seen[i]);
+            let shifted = mul_vector(shift, lost);
+            core::array::from_fn(|i| rgb[i] + shifted[i])
+        })
+    }
+
+    /// Daltonizes each color of `src` into `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    pub fn daltonize_slice<C: RgbColor + Copy>(&self, src: &[C], dst: &mut [C]) {
+        map_slice(src, dst, |color| self.daltonize(color));
+    }
+
+    /// Daltonizes each pixel of `src` into `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` have different sizes.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::{
+    ///     image::{ImageView, ImageViewMut},
+    ///     rgb::Abgr8888,
+    ///     vision::{Deficiency, Simulation},
+    /// };
+    ///
+    /// let src = [Abgr8888::from_abgr(255, 0, 0, 255); 4];
+    /// let mut dst = [Abgr8888::default(); 4];
+    /// Simulation::brettel(Deficiency::Protan, 1.0).daltonize_image(
+    ///     ImageView::new(&src, 2, 2).unwrap(),
+    ///     &mut ImageViewMut::new(&mut dst, 2, 2).unwrap(),
+    /// );
+    /// assert!(dst.iter().all(|&c| c == dst[0] && c != src[0]));
+    /// ```
+    pub fn daltonize_image<C: RgbColor + Copy>(
+        &self,
+        src: ImageView<'_, C>,
+        dst: &mut ImageViewMut<'_, C>,
+    ) {
+        map_image(src, dst, |color| self.daltonize(color));
+    }
+
+    /// Simulates a color in linear sRGB.
+    fn simulate_linear(&self, rgb: [f64; 3]) -> [f64; 3] {
+        let side: f64 = self.separation.iter().zip(rgb).map(|(n, c)| n * c).sum();
+        let matrix = if side >= 0.0 {
+            &self.matrices[0]
+        } else {
+            &self.matrices[1]
+        };
+        mul_vector(matrix, rgb)
+    }
+}
+
+/// Applies `f` to the linear red, green, and blue components of `color`, keeping any others.
+#[allow(clippy::cast_possible_truncation)]
+fn map_linear<C: RgbColor>(mut color: C, f: impl FnOnce([f64; 3]) -> [f64; 3]) -> C {
+    let (red, green, blue) = (color.red(), color.green(), color.blue());
+    let rgb = [decode::<_, f32>(red), decode(green), decode(blue)].map(f64::from);
+    let [red, green, blue] = f(rgb).map(|c| c.clamp(0.0, 1.0) as f32);
+    color.set_red(encode(red));
+    color.set_green(encode(green));
+    color.set_blue(encode(blue));
+    color
+}
+
+fn map_slice<C: Copy>(src: &[C], dst: &mut [C], mut f: impl FnMut(C) -> C) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    );
+    for (&s, d) in src.iter().zip(dst) {
+        *d = f(s);
+    }
+}
+
+fn map_image<C: Copy>(
+    src: ImageView<'_, C>,
+    dst: &mut ImageViewMut<'_, C>,
+    mut f: impl FnMut(C) -> C,
+) {
+    assert_eq!(
+        (src.width(), src.height()),
+        (dst.width(), dst.height()),
+        "source and destination sizes differ"
+    );
+    for (src, dst) in src.rows().zip(dst.rows_mut()) {
+        map_slice(src, dst, &mut f);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::rgb::{Abgr8888, HasBlue, HasGreen, HasRed, Rgb888, Rgbf32};
+
+    const DEFICIENCIES: [Deficiency; 3] =
+        [Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan];
+
+    fn all(severity: f32) -> impl Iterator<Item = Simulation> {
+        DEFICIENCIES.into_iter().flat_map(move |deficiency| {
+            [
+                Simulation::machado(deficiency, severity),
+                Simulation::brettel(deficiency, severity),
+            ]
+        })
+    }
+
+    #[test]
+    fn zero_severity_is_normal_vision() {
+        for simulation in all(0.0) {
+            for value in 0..=u8::MAX {
+                let color = Rgb888::from_rgb(value, value / 3, 255 - value);
+                assert_eq!(simulation.simulate(color), color);
+                assert_eq!(simulation.daltonize(color), color);
+            }
+        }
+    }
+
+    #[test]
+    fn neutral_colors_are_seen_as_they_are() {
+        for simulation in all(1.0).chain(all(0.5)) {
+            for value in [0, 64, 128, 255] {
+                let gray = Rgb888::from_rgb(value, value, value);
+                let (r, g, b) = simulation.simulate(gray).into_rgb();
+                assert!(
+                    [r, g, b].iter().all(|c| c.abs_diff(value) <= 1),
+                    "{simulation:?} {value}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn models_agree_on_dichromacy() {
+        // The models differ more for saturated colors, which are seen outside of the gamut.
+        for deficiency in [Deficiency::Protan, Deficiency::Deutan] {
+            let machado = Simulation::machado(deficiency, 1.0);
+            let brettel = Simulation::brettel(deficiency, 1.0);
+            for color in [
+                Rgbf32::from_rgb(0.7, 0.3, 0.3),
+                Rgbf32::from_rgb(0.3, 0.6, 0.3),
+                Rgbf32::from_rgb(0.4, 0.5, 0.7),
+                Rgbf32::from_rgb(0.8, 0.6, 0.4),
+            ] {
+                let (a, b) = (machado.simulate(color), brettel.simulate(color));
+                assert!(
+                    (a.red() - b.red()).abs() < 0.05,
+                    "{deficiency:?} {a:?} {b:?}"
+                );
+                assert!(
+                    (a.green() - b.green()).abs() < 0.05,
+                    "{deficiency:?} {a:?} {b:?}"
+                );
+                assert!(
+                    (a.blue() - b.blue()).abs() < 0.05,
+                    "{deficiency:?} {a:?} {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn severity_is_gradual() {
+        let red = Rgbf32::from_rgb(1.0, 0.0, 0.0);
+        for deficiency in [Deficiency::Protan, Deficiency::Deutan] {
+            let mut previous = 1.0;
+            for step in 1..=10_u8 {
+                let severity = f32::from(step) / 10.0;
+                let seen = Simulation::machado(deficiency, severity).simulate(red);
+                assert!(seen.red() < previous, "{deficiency:?} {severity}");
+                previous = seen.red();
+            }
+        }
+    }
+
+    #[test]
+    fn daltonize_restores_contrast() {
+        let red = Rgb888::from_rgb(200, 60, 60);
+        let green = Rgb888::from_rgb(90, 130, 60);
+        let distance = |simulation: &Simulation, a: Rgb888, b: Rgb888| {
+            let (a, b) = (simulation.simulate(a), simulation.simulate(b));
+            u32::from(a.red().abs_diff(b.red()))
+                + u32::from(a.green().abs_diff(b.green()))
+                + u32::from(a.blue().abs_diff(b.blue()))
+        };
+        for simulation in [
+            Simulation::machado(Deficiency::Protan, 1.0),
+            Simulation::machado(Deficiency::Deutan, 1.0),
+            Simulation::brettel(Deficiency::Deutan, 1.0),
+        ] {
+            let before = distance(&simulation, red, green);
+            let after = distance(
+                &simulation,
+                simulation.daltonize(red),
+                simulation.daltonize(green),
+            );
+            assert!(after > before * 2, "{simulation:?} {before} {after}");
+        }
+    }
+
+    #[test]
+    fn alpha_is_kept() {
+        let color = Abgr8888::from_abgr(64, 0, 128, 255);
+        let simulation = Simulation::brettel(Deficiency::Tritan, 1.0);
+        assert_eq!(simulation.simulate(color).alpha(), 64);
+        assert_eq!(simulation.daltonize(color).alpha(), 64);
+    }
+
+    #[test]
+    fn simulate_image_with_stride() {
+        let src = [Rgb888::from_rgb(255, 0, 0); 6];
+        let mut dst = [Rgb888::default(); 6];
+        let simulation = Simulation::machado(Deficiency::Protan, 1.0);
+        simulation.simulate_image(
+            ImageView::with_stride(&src, 2, 2, 3).unwrap(),
+            &mut ImageViewMut::with_stride(&mut dst, 2, 2, 3).unwrap(),
+        );
+        let seen = simulation.simulate(src[0]);
+        assert_eq!(
+            dst,
+            [seen, seen, Rgb888::default(), seen, seen, Rgb888::default()]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "source and destination lengths differ")]
+    fn slice_different_lengths() {
+        let src = [Rgb888::default(); 2];
+        let mut dst = [Rgb888::default(); 3];
+        Simulation::machado(Deficiency::Protan, 1.0).simulate_slice(&src, &mut dst);
+    }
+
+    #[test]
+    #[should_panic(expected = "severity must be within 0.0..=1.0")]
+    fn invalid_severity() {
+        let _ = Simulation::brettel(Deficiency::Deutan, 1.5);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{math::Matrix3, vision::Deficiency};
+
+/// The two half-planes of a dichromacy, as matrices that convert linear sRGB into the colors seen
+/// on either side of the plane separating them.
+pub(super) struct HalfPlanes {
+    /// The matrices for colors on the positive and negative side of the separating plane.
+    pub(super) matrices: [Matrix3; 2],
+
+    /// The normal of the plane separating the half-planes, in linear sRGB.
+    pub(super) separation: [f64; 3],
+}
+
+/// Returns the half-planes of Brettel, Viénot, and Mollon (1997) for `deficiency`.
+///
+/// These are projected onto the neutral axis and the anchor wavelengths of 475 and 575 nm for
+/// protanopia and deuteranopia, or 485 and 660 nm for tritanopia, in the LMS space of Viénot,
+/// Brettel, and Mollon (1999), as precomputed for linear sRGB by [DaltonLens].
+///
+/// [DaltonLens]: https://github.com/DaltonLens/libDaltonLens
+#[rustfmt::skip]
+pub(super) const fn half_planes(deficiency: Deficiency) -> HalfPlanes {
+    match deficiency {
+        Deficiency::Protan => HalfPlanes {
+            matrices: [
+                [[0.14980, 1.19548, -0.34528], [0.10764, 0.84864, 0.04372], [0.00384, -0.00540, 1.00156]],
+                [[0.14570, 1.16172, -0.30742], [0.10816, 0.85291, 0.03892], [0.00386, -0.00524, 1.00139]],
+            ],
+            separation: [0.00048, 0.00393, -0.00441],
+        },
+        Deficiency::Deutan => HalfPlanes {
+            matrices: [
+                [[0.36477, 0.86381, -0.22858], [0.26294, 0.64245, 0.09462], [-0.02006, 0.02728, 0.99278]],
+                [[0.37298, 0.88166, -0.25464], [0.25954, 0.63506, 0.10540], [-0.01980, 0.02784, 0.99196]],
+            ],
+            separation: [-0.00281, -0.00611, 0.00892],
+        },
+        Deficiency::Tritan => HalfPlanes {
+            matrices: [
+                [[1.01277, 0.13548, -0.14826], [-0.01243, 0.86812, 0.14431], [0.07589, 0.80500, 0.11911]],
+                [[0.93678, 0.18979, -0.12657], [0.06154, 0.81526, 0.12320], [-0.37562, 1.12767, 0.24796]],
+            ],
+            separation: [0.03901, -0.02788, -0.01113],
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{mul_matrix, mul_vector};
+
+    #[test]
+    fn half_planes_are_projections() {
+        for deficiency in [Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan] {
+            let HalfPlanes {
+                matrices,
+                separation,
+            } = half_planes(deficiency);
+            // White is on the separating plane, and is seen as white.
+            assert!(separation.iter().sum::<f64>().abs() < 1e-12);
+            for m in &matrices {
+                let white = mul_vector(m, [1.0; 3]);
+                assert!(white.iter().all(|c| (c - 1.0).abs() < 2e-5), "{white:?}");
+                // Simulating twice is the same as simulating once.
+                let squared = mul_matrix(m, m);
+                for (a, b) in squared.iter().flatten().zip(m.iter().flatten()) {
+                    assert!((a - b).abs() < 1e-4);
+                }
+            }
+        }
+    }
+}
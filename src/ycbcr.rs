@@ -0,0 +1,396 @@
+//! Luma and chroma 📺 color representations, such as decoded video frames.
+//!
+//! `Y'CbCr` separates the nonlinear `R'G'B'` components of a color into luma `Y'`, the weighted
+//! sum of the three, and the blue and red color differences `Cb` and `Cr`. Video stores colors
+//! this way so that the color differences, to which the eye is less sensitive, can be stored at a
+//! lower resolution.
+//!
+//! This module contains:
+//!
+//! - [`YCbCr<T>`], a generic `Y'CbCr` color
+//! - [`Matrix`], the coefficients of ITU-R BT.601, BT.709, or BT.2020
+//! - [`Range`] and [`Format`], which describe how components are stored as integers, with
+//!   limited or full range and 8 to 16 bits
+//! - [`Frame`], which describes a video frame in a byte buffer, with a planar, semi-planar, or
+//!   packed [`FrameLayout`] such as NV12 or YUYV, and converts it to and from RGB images
+//!
+//! Every conversion works on any [`RgbColor`], whose components are used as they are stored,
+//! which for video is usually encoded with the [BT.709 transfer function][rec709]. `Y'CbCr`
+//! colors do not record their matrix or range, which instead are given to each conversion.
+//!
+//! [`RgbColor`]: crate::rgb::RgbColor
+//! [rec709]: crate::space::TransferFunction::Rec709
+//!
+//! ## Getting Started
+//!
+//! ```rust
+//! use gem::{
+//!     rgb::Rgb888,
+//!     ycbcr::{Format, Matrix, Range, YCbCr8},
+//! };
+//!
+//! const BT709: Format = Format::new(Matrix::Bt709, Range::Limited, 8);
+//!
+//! let red = YCbCr8::from_rgb_color(Rgb888::from_rgb(255, 0, 0), BT709);
+//! assert_eq!(red, YCbCr8::from_ycbcr(63, 102, 240));
+//!
+//! let black = YCbCr8::from_ycbcr(16, 128, 128);
+//! assert_eq!(black.into_rgb_color::<Rgb888>(BT709), Rgb888::from_rgb(0, 0, 0));
+//! ```
+//!
+//! ## Predefined Types
+//!
+//! Type           | Bits per pixel | Description
+//! -------------- | -------------- | -----------
+//! [`YCbCr8`]     | 24             | 8-bit integer components
+//! [`YCbCr16`]    | 48             | 10, 12, or 16-bit integer components, in the low bits
+//! [`YCbCrF32`]   | 96             | 32-bit floating-point, unquantized components
+
+use crate::{component::Component, rgb::RgbColor};
+
+mod format;
+mod frame;
+mod matrix;
+
+pub use format::{Format, Range};
+pub use frame::{ChromaFilter, ChromaSiting, Frame, FrameLayout};
+pub use matrix::Matrix;
+
+/// A color representation that contains luma `Y'` and color differences `Cb` and `Cr`.
+///
+/// ## Layout
+///
+/// ```c
+/// template<typename T>
+/// struct YCbCr {
+///     T y;
+///     T cb;
+///     T cr;
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct YCbCr<T> {
+    y: T,
+    cb: T,
+    cr: T,
+}
+
+impl<T> YCbCr<T> {
+    /// Creates a new color with the given luma and color difference components.
+    #[must_use]
+    pub const fn from_ycbcr(y: T, cb: T, cr: T) -> Self {
+        Self { y, cb, cr }
+    }
+
+    /// Consumes the color and returns its luma and color difference components.
+    #[must_use]
+    pub fn into_ycbcr(self) -> (T, T, T) {
+        (self.y, self.cb, self.cr)
+    }
+
+    /// Returns the luma component, `Y'`.
+    #[must_use]
+    pub const fn y(&self) -> T
+    where
+        T: Copy,
+    {
+        self.y
+    }
+
+    /// Returns the blue color difference component, `Cb`.
+    #[must_use]
+    pub const fn cb(&self) -> T
+    where
+        T: Copy,
+    {
+        self.cb
+    }
+
+    /// Returns the red color difference component, `Cr`.
+    #[must_use]
+    pub const fn cr(&self) -> T
+    where
+        T: Copy,
+    {
+        self.cr
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for YCbCr<T> where T: bytemuck::Zeroable {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for YCbCr<T> where T: bytemuck::Pod {}
+
+/// 8-bit integer `Y'CbCr` color.
+pub type YCbCr8 = YCbCr<u8>;
+
+/// 16-bit integer `Y'CbCr` color.
+///
+/// Components of 10 or 12 bits are stored in the low bits, so that a limited range 10-bit black is
+/// `64`.
+pub type YCbCr16 = YCbCr<u16>;
+
+/// 32-bit floating-point `Y'CbCr` color.
+///
+/// Luma is within `0.0..=1.0`, and color differences are within `-0.5..=0.5`, for colors inside of
+/// the `R'G'B'` cube.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{rgb::Rgbf32, ycbcr::{Matrix, YCbCrF32}};
+///
+/// let blue = YCbCrF32::from_rgb_color(Rgbf32::from_rgb(0.0, 0.0, 1.0), Matrix::Bt601);
+/// assert!((blue.y() - 0.114).abs() < 1e-6);
+/// assert!((blue.cb() - 0.5).abs() < 1e-6);
+/// ```
+pub type YCbCrF32 = YCbCr<f32>;
+
+impl YCbCrF32 {
+    /// Converts an RGB color into `Y'CbCr` with `matrix`.
+    #[must_use]
+    pub fn from_rgb_color<C: RgbColor>(color: C, matrix: Matrix) -> Self {
+        Self::from_f64(matrix.rgb_to_ycbcr(into_f64(color)))
+    }
+
+    /// Converts this color into an RGB color with `matrix`.
+    ///
+    /// Colors outside of the `R'G'B'` cube are clamped if `C` has integer components.
+    #[must_use]
+    pub fn into_rgb_color<C: RgbColor>(self, matrix: Matrix) -> C {
+        from_f64(matrix.ycbcr_to_rgb(self.into_f64()))
+    }
+
+    fn into_f64(self) -> [f64; 3] {
+        [self.y, self.cb, self.cr].map(f64::from)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_f64([y, cb, cr]: [f64; 3]) -> Self {
+        Self::from_ycbcr(y as f32, cb as f32, cr as f32)
+    }
+}
+
+/// Implements conversions for `Y'CbCr` colors with integer components.
+macro_rules! impl_integer {
+    ($ty:ty, $bits:literal) => {
+        impl YCbCr<$ty> {
+            #[doc = concat!("Converts an RGB color into `Y'CbCr` of `format`.")]
+            ///
+            /// ## Panics
+            ///
+            #[doc = concat!("Panics if `format` has more than ", $bits, " bits.")]
+            #[must_use]
+            pub fn from_rgb_color<C: RgbColor>(color: C, format: Format) -> Self {
+                Self::from_f32(YCbCrF32::from_rgb_color(color, format.matrix()), format)
+            }
+
+            /// Converts this color of `format` into an RGB color.
+            ///
+            /// Colors outside of the `R'G'B'` cube are clamped if `C` has integer components.
+            ///
+            /// ## Panics
+            ///
+            #[doc = concat!("Panics if `format` has more than ", $bits, " bits.")]
+            #[must_use]
+            pub fn into_rgb_color<C: RgbColor>(self, format: Format) -> C {
+                self.into_f32(format).into_rgb_color(format.matrix())
+            }
+
+            /// Quantizes an unquantized color into code values of `format`.
+            ///
+            /// ## Panics
+            ///
+            #[doc = concat!("Panics if `format` has more than ", $bits, " bits.")]
+            #[must_use]
+            #[allow(clippy::cast_possible_truncation)]
+            pub fn from_f32(color: YCbCrF32, format: Format) -> Self {
+                assert!(
+                    format.bits() <= $bits,
+                    concat!("format must have at most ", $bits, " bits")
+                );
+                let [y, cb, cr] = format.quantize(color.into_f64()).map(|c| c as $ty);
+                Self::from_ycbcr(y, cb, cr)
+            }
+
+            /// Converts code values of `format` into an unquantized color.
+            ///
+            /// ## Panics
+            ///
+            #[doc = concat!("Panics if `format` has more than ", $bits, " bits.")]
+            #[must_use]
+            pub fn into_f32(self, format: Format) -> YCbCrF32 {
+                assert!(
+                    format.bits() <= $bits,
+                    concat!("format must have at most ", $bits, " bits")
+                );
+                let codes = [self.y, self.cb, self.cr].map(f64::from);
+                YCbCrF32::from_f64(format.dequantize(codes))
+            }
+        }
+    };
+}
+
+impl_integer!(u8, 8);
+impl_integer!(u16, 16);
+
+/// Returns the normalized red, green, and blue components of `color`.
+fn into_f64<C: RgbColor>(color: C) -> [f64; 3] {
+    let (red, green, blue) = color.into_rgb();
+    [
+        red.to_normalized_f32(),
+        green.to_normalized_f32(),
+        blue.to_normalized_f32(),
+    ]
+    .map(f64::from)
+}
+
+/// Creates a color from normalized red, green, and blue components.
+#[allow(clippy::cast_possible_truncation)]
+fn from_f64<C: RgbColor>(rgb: [f64; 3]) -> C {
+    let [red, green, blue] = rgb.map(|c| c as f32);
+    C::from_rgb(
+        Component::from_normalized_f32(red),
+        Component::from_normalized_f32(green),
+        Component::from_normalized_f32(blue),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::rgb::{Rgb888, Rgbf32};
+
+    /// The 100% color bars, in the order of the test pattern.
+    const BARS: [[u8; 3]; 8] = [
+        [255, 255, 255],
+        [255, 255, 0],
+        [0, 255, 255],
+        [0, 255, 0],
+        [255, 0, 255],
+        [255, 0, 0],
+        [0, 0, 255],
+        [0, 0, 0],
+    ];
+
+    fn bars<T: Copy>(format: Format, convert: impl Fn(Rgb888, Format) -> YCbCr<T>) -> [[T; 3]; 8] {
+        BARS.map(|[r, g, b]| {
+            let (y, cb, cr) = convert(Rgb888::from_rgb(r, g, b), format).into_ycbcr();
+            [y, cb, cr]
+        })
+    }
+
+    #[test]
+    fn bt601_color_bars() {
+        // ITU-R BT.601, 8-bit.
+        let format = Format::new(Matrix::Bt601, Range::Limited, 8);
+        assert_eq!(
+            bars(format, YCbCr8::from_rgb_color),
+            [
+                [235, 128, 128],
+                [210, 16, 146],
+                [170, 166, 16],
+                [145, 54, 34],
+                [106, 202, 222],
+                [81, 90, 240],
+                [41, 240, 110],
+                [16, 128, 128],
+            ]
+        );
+    }
+
+    #[test]
+    fn bt709_color_bars() {
+        // ITU-R BT.709, 8-bit, and ITU-R BT.2111, 10-bit.
+        let format = Format::new(Matrix::Bt709, Range::Limited, 8);
+        assert_eq!(
+            bars(format, YCbCr8::from_rgb_color),
+            [
+                [235, 128, 128],
+                [219, 16, 138],
+                [188, 154, 16],
+                [173, 42, 26],
+                [78, 214, 230],
+                [63, 102, 240],
+                [32, 240, 118],
+                [16, 128, 128],
+            ]
+        );
+        assert_eq!(
+            bars(format.with_bits(10), YCbCr16::from_rgb_color),
+            [
+                [940, 512, 512],
+                [877, 64, 553],
+                [754, 615, 64],
+                [691, 167, 105],
+                [313, 857, 919],
+                [250, 409, 960],
+                [127, 960, 471],
+                [64, 512, 512],
+            ]
+        );
+    }
+
+    #[test]
+    fn bt2020_color_bars() {
+        // ITU-R BT.2111, 10-bit, and its 12-bit luma.
+        let format = Format::new(Matrix::Bt2020, Range::Limited, 10);
+        assert_eq!(
+            bars(format, YCbCr16::from_rgb_color),
+            [
+                [940, 512, 512],
+                [888, 64, 548],
+                [710, 637, 64],
+                [658, 189, 100],
+                [346, 835, 924],
+                [294, 387, 960],
+                [116, 960, 476],
+                [64, 512, 512],
+            ]
+        );
+        let luma = bars(format.with_bits(12), YCbCr16::from_rgb_color).map(|[y, _, _]| y);
+        assert_eq!(luma, [3760, 3552, 2839, 2632, 1384, 1177, 464, 256]);
+    }
+
+    #[test]
+    fn full_range() {
+        let format = Format::new(Matrix::Bt601, Range::Full, 8);
+        let white = YCbCr8::from_rgb_color(Rgb888::from_rgb(255, 255, 255), format);
+        assert_eq!(white, YCbCr8::from_ycbcr(255, 128, 128));
+        let red = YCbCr8::from_rgb_color(Rgb888::from_rgb(255, 0, 0), format);
+        assert_eq!(red, YCbCr8::from_ycbcr(76, 85, 255));
+    }
+
+    #[test]
+    fn round_trips_8_bit_grays() {
+        // Full range keeps every gray, while limited range has only 220 levels of luma.
+        for (range, tolerance) in [(Range::Full, 0), (Range::Limited, 1)] {
+            let format = Format::new(Matrix::Bt709, range, 8);
+            for value in 0..=u8::MAX {
+                let gray = Rgb888::from_rgb(value, value, value);
+                let ycbcr = YCbCr8::from_rgb_color(gray, format);
+                let (r, g, b) = ycbcr.into_rgb_color::<Rgb888>(format).into_rgb();
+                assert_eq!((r, g), (b, b));
+                assert!(b.abs_diff(value) <= tolerance, "{range:?} {value}");
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_float() {
+        let color = Rgbf32::from_rgb(0.2, 0.7, 0.4);
+        let ycbcr = YCbCrF32::from_rgb_color(color, Matrix::Bt2020);
+        let (r, g, b) = ycbcr.into_rgb_color::<Rgbf32>(Matrix::Bt2020).into_rgb();
+        assert!((r - 0.2).abs() < 1e-6 && (g - 0.7).abs() < 1e-6 && (b - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "format must have at most 8 bits")]
+    fn too_many_bits() {
+        let format = Format::new(Matrix::Bt709, Range::Limited, 10);
+        let _ = YCbCr8::from_rgb_color(Rgb888::default(), format);
+    }
+}
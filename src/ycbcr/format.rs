@@ -0,0 +1,190 @@
+use crate::ycbcr::Matrix;
+
+/// The range of code values that integer `Y'CbCr` components use.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Range {
+    /// Limited, or "studio", range, as used by broadcast video.
+    ///
+    /// With 8 bits, black is `16` and white is `235`, and color differences are `16..=240` around
+    /// `128`; other bit depths scale these values. The lowest and highest code values are reserved
+    /// for synchronization, so components are clamped to exclude them.
+    #[default]
+    Limited,
+
+    /// Full range, as used by JPEG and screen content.
+    ///
+    /// Components use every code value; with 8 bits, black is `0` and white is `255`, and color
+    /// differences are centered around `128`.
+    Full,
+}
+
+/// How `Y'CbCr` components are derived from `R'G'B'` and stored as integers.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::ycbcr::{Format, Matrix, Range};
+///
+/// let format = Format::new(Matrix::Bt2020, Range::Limited, 10);
+/// assert_eq!(format.matrix(), Matrix::Bt2020);
+/// assert_eq!(format.bits(), 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Format {
+    matrix: Matrix,
+    range: Range,
+    bits: u8,
+}
+
+impl Format {
+    /// Creates a format with the given matrix, range, and bit depth.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `bits` is not within `8..=16`.
+    #[must_use]
+    pub const fn new(matrix: Matrix, range: Range, bits: u8) -> Self {
+        assert!(bits >= 8 && bits <= 16, "bits must be within 8..=16");
+        Self {
+            matrix,
+            range,
+            bits,
+        }
+    }
+
+    /// Returns the matrix coefficients.
+    #[must_use]
+    pub const fn matrix(&self) -> Matrix {
+        self.matrix
+    }
+
+    /// Returns the range of code values.
+    #[must_use]
+    pub const fn range(&self) -> Range {
+        self.range
+    }
+
+    /// Returns the number of bits of each component.
+    #[must_use]
+    pub const fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Returns a copy of this format with a different matrix.
+    #[must_use]
+    pub const fn with_matrix(self, matrix: Matrix) -> Self {
+        Self { matrix, ..self }
+    }
+
+    /// Returns a copy of this format with a different range.
+    #[must_use]
+    pub const fn with_range(self, range: Range) -> Self {
+        Self { range, ..self }
+    }
+
+    /// Returns a copy of this format with a different bit depth.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `bits` is not within `8..=16`.
+    #[must_use]
+    pub const fn with_bits(self, bits: u8) -> Self {
+        Self::new(self.matrix, self.range, bits)
+    }
+
+    /// Quantizes `Y'`, `Cb`, and `Cr` into code values.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_lossless
+    )]
+    pub(crate) fn quantize(self, [y, cb, cr]: [f64; 3]) -> [u16; 3] {
+        let max = f64::from((1_u32 << self.bits) - 1);
+        let (min, max, y, cb, cr) = match self.range {
+            Range::Limited => {
+                let scale = f64::from(1_u32 << (self.bits - 8));
+                let y = (219.0 * y + 16.0) * scale;
+                let (cb, cr) = ((224.0 * cb + 128.0) * scale, (224.0 * cr + 128.0) * scale);
+                (scale, max - scale, y, cb, cr)
+            }
+            Range::Full => {
+                let offset = f64::from(1_u32 << (self.bits - 1));
+                (0.0, max, max * y, max * cb + offset, max * cr + offset)
+            }
+        };
+        [y, cb, cr].map(|c| (c.clamp(min, max) + 0.5) as u16)
+    }
+
+    /// Converts code values, which may be interpolated between integers, into `Y'`, `Cb`, and `Cr`.
+    pub(crate) fn dequantize(self, [y, cb, cr]: [f64; 3]) -> [f64; 3] {
+        match self.range {
+            Range::Limited => {
+                let scale = f64::from(1_u32 << (self.bits - 8));
+                [
+                    (y / scale - 16.0) / 219.0,
+                    (cb / scale - 128.0) / 224.0,
+                    (cr / scale - 128.0) / 224.0,
+                ]
+            }
+            Range::Full => {
+                let max = f64::from((1_u32 << self.bits) - 1);
+                let offset = f64::from(1_u32 << (self.bits - 1));
+                [y / max, (cb - offset) / max, (cr - offset) / max]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limited_range_reference_values() {
+        // Black, white, and the extremes of color differences, scaled by 2^(n - 8).
+        for bits in [8, 10, 12] {
+            let format = Format::new(Matrix::Bt709, Range::Limited, bits);
+            let scale = 1 << (bits - 8);
+            assert_eq!(
+                format.quantize([0.0, 0.0, 0.0]),
+                [16, 128, 128].map(|c| c * scale)
+            );
+            assert_eq!(
+                format.quantize([1.0, -0.5, 0.5]),
+                [235, 16, 240].map(|c| c * scale)
+            );
+            // The reserved code values are excluded.
+            let max = (1 << bits) - 1 - scale;
+            assert_eq!(format.quantize([2.0, -1.0, 1.0]), [max, scale, max]);
+        }
+    }
+
+    #[test]
+    fn full_range_reference_values() {
+        let format = Format::new(Matrix::Bt601, Range::Full, 8);
+        assert_eq!(format.quantize([0.0, 0.0, 0.0]), [0, 128, 128]);
+        assert_eq!(format.quantize([1.0, -0.5, 0.5]), [255, 1, 255]);
+        let format = format.with_bits(10);
+        assert_eq!(format.quantize([1.0, 0.0, 0.0]), [1023, 512, 512]);
+    }
+
+    #[test]
+    fn dequantize_round_trips() {
+        for range in [Range::Limited, Range::Full] {
+            for bits in [8, 10, 12, 16] {
+                let format = Format::new(Matrix::Bt2020, range, bits);
+                let codes = format.quantize([0.25, -0.125, 0.375]);
+                assert_eq!(
+                    format.quantize(format.dequantize(codes.map(f64::from))),
+                    codes
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "bits must be within 8..=16")]
+    fn invalid_bits() {
+        let _ = Format::new(Matrix::Bt709, Range::Full, 6);
+    }
+}
@@ -0,0 +1,814 @@
+use crate::{
+    image::{ImageView, ImageViewMut},
+    rgb::{RgbColor, Rgbf32},
+    ycbcr::{Format, Matrix, Range},
+};
+
+/// How the samples of a frame are arranged in memory.
+///
+/// Layouts are named after their common `FourCC` codes. Planar layouts store each component in its
+/// own plane, semi-planar layouts store `Y'` in one plane and interleaved `Cb` and `Cr` in another,
+/// and packed layouts interleave every component in a single plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameLayout {
+    /// Planar 4:2:0, with a `Y'` plane followed by `Cb` and `Cr` planes of half the width and
+    /// height; also known as `YU12`.
+    I420,
+
+    /// Planar 4:2:2, with a `Y'` plane followed by `Cb` and `Cr` planes of half the width.
+    I422,
+
+    /// Planar 4:4:4, with `Y'`, `Cb`, and `Cr` planes of the same size.
+    I444,
+
+    /// Semi-planar 4:2:0, with a `Y'` plane followed by a plane of `Cb`, `Cr` pairs of half the
+    /// width and height.
+    Nv12,
+
+    /// Semi-planar 4:2:0, like [`FrameLayout::Nv12`] with `Cr` before `Cb` in each pair.
+    Nv21,
+
+    /// Packed 4:2:2, with every two pixels stored as `Y'₀`, `Cb`, `Y'₁`, `Cr`; also known as
+    /// `YUY2`.
+    Yuyv,
+
+    /// Packed 4:2:2, with every two pixels stored as `Cb`, `Y'₀`, `Cr`, `Y'₁`.
+    Uyvy,
+
+    /// Semi-planar 4:2:0, like [`FrameLayout::Nv12`] with 16-bit little-endian samples that store
+    /// 10 bits in their most significant bits.
+    P010,
+}
+
+impl FrameLayout {
+    /// Returns how many luma samples share a chroma sample, horizontally and vertically.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::ycbcr::FrameLayout;
+    ///
+    /// assert_eq!(FrameLayout::Nv12.subsampling(), (2, 2));
+    /// assert_eq!(FrameLayout::Yuyv.subsampling(), (2, 1));
+    /// assert_eq!(FrameLayout::I444.subsampling(), (1, 1));
+    /// ```
+    #[must_use]
+    pub const fn subsampling(self) -> (usize, usize) {
+        match self {
+            Self::I420 | Self::Nv12 | Self::Nv21 | Self::P010 => (2, 2),
+            Self::I422 | Self::Yuyv | Self::Uyvy => (2, 1),
+            Self::I444 => (1, 1),
+        }
+    }
+
+    /// Returns the number of bits of each component.
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        match self {
+            Self::P010 => 10,
+            _ => 8,
+        }
+    }
+
+    /// Returns the size of a sample, in bytes.
+    const fn sample_size(self) -> usize {
+        match self {
+            Self::P010 => 2,
+            _ => 1,
+        }
+    }
+
+    /// Returns the size of a row of `width` pixels in the first plane, in bytes.
+    const fn row_size(self, width: usize) -> Option<usize> {
+        match self {
+            Self::Yuyv | Self::Uyvy => width.div_ceil(2).checked_mul(4),
+            _ => width.checked_mul(self.sample_size()),
+        }
+    }
+}
+
+/// Where chroma samples are located relative to the luma samples they are shared by.
+///
+/// Siting only matters for subsampled layouts, and only in the directions they are subsampled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChromaSiting {
+    /// Horizontally co-sited with the left luma sample, and vertically centered between rows.
+    ///
+    /// This is the siting of MPEG-2, H.264, and HEVC by default, and of every 4:2:2 format.
+    #[default]
+    Left,
+
+    /// Centered between luma samples, both horizontally and vertically, as used by JPEG and
+    /// MPEG-1.
+    Center,
+
+    /// Co-sited with the top-left luma sample, as recommended for 4:2:0 by ITU-R BT.2020.
+    TopLeft,
+}
+
+impl ChromaSiting {
+    /// Returns twice the offset, in luma samples, of the first chroma sample.
+    const fn offsets(self, (horizontal, vertical): (usize, usize)) -> (usize, usize) {
+        match self {
+            Self::Left => (0, vertical - 1),
+            Self::Center => (horizontal - 1, vertical - 1),
+            Self::TopLeft => (0, 0),
+        }
+    }
+}
+
+/// How chroma is resampled between the resolution of a frame and its pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChromaFilter {
+    /// Each pixel uses its nearest chroma sample, and each chroma sample the pixel at its site.
+    Nearest,
+
+    /// Pixels interpolate linearly between chroma samples, and chroma samples are a weighted
+    /// average of the pixels around their site, with a triangle filter.
+    #[default]
+    Bilinear,
+}
+
+/// Describes a video frame of `Y'CbCr` samples stored in a byte buffer.
+///
+/// A frame is only a description; the bytes are passed to [`Frame::decode`], which converts them
+/// into an image of RGB pixels, and [`Frame::encode`], which does the reverse.
+///
+/// The first plane starts at the beginning of the buffer, and each plane immediately follows the
+/// previous one. Rows of the first plane are [`Frame::stride`] bytes apart; rows of chroma planes
+/// have the same stride divided by the horizontal subsampling, so that padding is scaled along
+/// with the samples.
+///
+/// ## Examples
+///
+/// ```rust
+/// use gem::{
+///     image::{ImageView, ImageViewMut},
+///     rgb::Rgb888,
+///     ycbcr::{ChromaFilter, Frame, FrameLayout},
+/// };
+///
+/// let frame = Frame::new(FrameLayout::Nv12, 2, 2);
+/// assert_eq!(frame.buffer_len(), 6);
+///
+/// let pixels = [Rgb888::from_rgb(255, 0, 0); 4];
+/// let mut data = [0; 6];
+/// frame.encode(ImageView::new(&pixels, 2, 2).unwrap(), &mut data, ChromaFilter::Bilinear);
+/// assert_eq!(data, [63, 63, 63, 63, 102, 240]);
+///
+/// // Quantization into limited range loses a little precision.
+/// let mut decoded = [Rgb888::default(); 4];
+/// let mut image = ImageViewMut::new(&mut decoded, 2, 2).unwrap();
+/// frame.decode(&data, &mut image, ChromaFilter::Bilinear);
+/// assert_eq!(decoded, [Rgb888::from_rgb(255, 1, 0); 4]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Frame {
+    layout: FrameLayout,
+    width: usize,
+    height: usize,
+    stride: usize,
+    format: Format,
+    siting: ChromaSiting,
+    planes: Planes,
+}
+
+impl Frame {
+    /// Creates a frame of `width` by `height` pixels, with rows stored without padding.
+    ///
+    /// Components use the BT.709 matrix in limited range, with the bit depth of the layout, and
+    /// chroma is sited as [`ChromaSiting::Left`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the size of the frame in bytes overflows [`usize`].
+    #[must_use]
+    pub fn new(layout: FrameLayout, width: usize, height: usize) -> Self {
+        let stride = layout.row_size(width).expect("frame dimensions overflow");
+        Self {
+            layout,
+            width,
+            height,
+            stride,
+            format: Format::new(Matrix::Bt709, Range::Limited, layout.bits()),
+            siting: ChromaSiting::Left,
+            planes: Planes::new(layout, height, stride).expect("frame dimensions overflow"),
+        }
+    }
+
+    /// Returns how the samples are arranged in memory.
+    #[must_use]
+    pub const fn layout(&self) -> FrameLayout {
+        self.layout
+    }
+
+    /// Returns the width of the frame, in pixels.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the frame, in pixels.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the distance between the start of each row of the first plane, in bytes.
+    #[must_use]
+    pub const fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns how components are derived from `R'G'B'` and stored.
+    #[must_use]
+    pub const fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns where chroma samples are located.
+    #[must_use]
+    pub const fn siting(&self) -> ChromaSiting {
+        self.siting
+    }
+
+    /// Returns the number of bytes that a buffer must contain to store the frame.
+    #[must_use]
+    pub const fn buffer_len(&self) -> usize {
+        self.planes.len
+    }
+
+    /// Returns a copy of this frame with rows of the first plane `stride` bytes apart.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `stride` is smaller than a row, is not a multiple of the size of a sample, or
+    /// makes the size of the frame in bytes overflow [`usize`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::ycbcr::{Frame, FrameLayout};
+    ///
+    /// // Rows aligned to 16 bytes, and chroma rows to 8 bytes.
+    /// let frame = Frame::new(FrameLayout::I420, 12, 2).with_stride(16);
+    /// assert_eq!(frame.buffer_len(), 16 * 2 + 8 * 2);
+    /// ```
+    #[must_use]
+    pub fn with_stride(self, stride: usize) -> Self {
+        assert!(
+            self.layout.row_size(self.width) <= Some(stride),
+            "stride is smaller than a row"
+        );
+        assert!(
+            stride.is_multiple_of(self.layout.sample_size()),
+            "stride must be a multiple of the sample size"
+        );
+        let planes = Planes::new(self.layout, self.height, stride);
+        Self {
+            stride,
+            planes: planes.expect("frame dimensions overflow"),
+            ..self
+        }
+    }
+
+    /// Returns a copy of this frame with a different format.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the bit depth of `format` is not that of the layout.
+    #[must_use]
+    pub fn with_format(self, format: Format) -> Self {
+        assert_eq!(
+            format.bits(),
+            self.layout.bits(),
+            "format must have the bit depth of the layout"
+        );
+        Self { format, ..self }
+    }
+
+    /// Returns a copy of this frame with a different chroma siting.
+    #[must_use]
+    pub const fn with_siting(self, siting: ChromaSiting) -> Self {
+        Self { siting, ..self }
+    }
+
+    /// Converts the frame stored in `data` into RGB pixels in `dst`, upsampling chroma with
+    /// `filter`.
+    ///
+    /// Pixels with an alpha channel are fully opaque.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `dst` is not the size of the frame, or `data` is shorter than
+    /// [`Frame::buffer_len`].
+    pub fn decode<P: From<Rgbf32>>(
+        &self,
+        data: &[u8],
+        dst: &mut ImageViewMut<'_, P>,
+        filter: ChromaFilter,
+    ) {
+        self.check(dst.width(), dst.height(), data.len());
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+        let subsampling = self.layout.subsampling();
+        let offsets = self.siting.offsets(subsampling);
+        let (chroma_width, chroma_height) = self.chroma_size();
+        for (y, row) in dst.rows_mut().enumerate() {
+            let ty = upsample_tap(y, subsampling.1, offsets.1, chroma_height, filter);
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let tx = upsample_tap(x, subsampling.0, offsets.0, chroma_width, filter);
+                let [cb, cr] = [self.planes.cb, self.planes.cr].map(|plane| {
+                    let sample = |cx, cy| self.read(data, plane, cx, cy);
+                    let top = lerp(sample(tx.0, ty.0), sample(tx.1, ty.0), tx.2);
+                    let bottom = lerp(sample(tx.0, ty.1), sample(tx.1, ty.1), tx.2);
+                    lerp(top, bottom, ty.2)
+                });
+                let luma = self.read(data, self.planes.luma, x, y);
+                let ycbcr = self.format.dequantize([luma, cb, cr]);
+                *pixel = P::from(into_rgbf32(self.format.matrix().ycbcr_to_rgb(ycbcr)));
+            }
+        }
+    }
+
+    /// Converts the RGB pixels in `src` into the frame stored in `data`, downsampling chroma with
+    /// `filter`.
+    ///
+    /// Packed layouts with an odd width repeat the last pixel to fill their last pair.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` is not the size of the frame, or `data` is shorter than
+    /// [`Frame::buffer_len`].
+    pub fn encode<P: RgbColor + Copy>(
+        &self,
+        src: ImageView<'_, P>,
+        data: &mut [u8],
+        filter: ChromaFilter,
+    ) {
+        self.check(src.width(), src.height(), data.len());
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+        let matrix = self.format.matrix();
+        let rgb = |x, y| super::into_f64(src.get(x, y).copied().unwrap_or_default());
+
+        let luma_width = match self.layout {
+            FrameLayout::Yuyv | FrameLayout::Uyvy => self.width.next_multiple_of(2),
+            _ => self.width,
+        };
+        for y in 0..self.height {
+            for x in 0..luma_width {
+                let ycbcr = matrix.rgb_to_ycbcr(rgb(x.min(self.width - 1), y));
+                let [luma, _, _] = self.format.quantize(ycbcr);
+                self.write(data, self.planes.luma, x, y, luma);
+            }
+        }
+
+        let subsampling = self.layout.subsampling();
+        let offsets = self.siting.offsets(subsampling);
+        let (chroma_width, chroma_height) = self.chroma_size();
+        for cy in 0..chroma_height {
+            for cx in 0..chroma_width {
+                let mut sum = [0.0; 3];
+                let mut total = 0.0;
+                downsample_taps(
+                    cy,
+                    subsampling.1,
+                    offsets.1,
+                    self.height,
+                    filter,
+                    |y, wy| {
+                        downsample_taps(
+                            cx,
+                            subsampling.0,
+                            offsets.0,
+                            self.width,
+                            filter,
+                            |x, wx| {
+                                for (sum, c) in sum.iter_mut().zip(rgb(x, y)) {
+                                    *sum += c * wx * wy;
+                                }
+                                total += wx * wy;
+                            },
+                        );
+                    },
+                );
+                let ycbcr = matrix.rgb_to_ycbcr(sum.map(|c| c / total));
+                let [_, cb, cr] = self.format.quantize(ycbcr);
+                self.write(data, self.planes.cb, cx, cy, cb);
+                self.write(data, self.planes.cr, cx, cy, cr);
+            }
+        }
+    }
+
+    /// Checks that an image and a buffer can hold the frame.
+    fn check(&self, width: usize, height: usize, len: usize) {
+        assert_eq!(
+            (width, height),
+            (self.width, self.height),
+            "frame and image sizes differ"
+        );
+        assert!(len >= self.planes.len, "buffer is smaller than the frame");
+    }
+
+    /// Returns the number of chroma samples in each row and column.
+    const fn chroma_size(&self) -> (usize, usize) {
+        let (horizontal, vertical) = self.layout.subsampling();
+        (
+            self.width.div_ceil(horizontal),
+            self.height.div_ceil(vertical),
+        )
+    }
+
+    /// Reads the code value of a sample.
+    fn read(&self, data: &[u8], plane: Plane, x: usize, y: usize) -> f64 {
+        let index = plane.index(x, y);
+        if self.layout.sample_size() == 1 {
+            f64::from(data[index])
+        } else {
+            let sample = u16::from_le_bytes([data[index], data[index + 1]]);
+            f64::from(sample >> (16 - self.format.bits()))
+        }
+    }
+
+    /// Writes the code value of a sample.
+    #[allow(clippy::cast_possible_truncation)]
+    fn write(&self, data: &mut [u8], plane: Plane, x: usize, y: usize, code: u16) {
+        let index = plane.index(x, y);
+        if self.layout.sample_size() == 1 {
+            data[index] = code as u8;
+        } else {
+            let sample = code << (16 - self.format.bits());
+            data[index..index + 2].copy_from_slice(&sample.to_le_bytes());
+        }
+    }
+}
+
+/// Where the samples of each component are in a frame buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Planes {
+    luma: Plane,
+    cb: Plane,
+    cr: Plane,
+    len: usize,
+}
+
+/// Where the samples of a component are, as the byte offset of the first sample and the distance
+/// between rows and between samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Plane {
+    offset: usize,
+    stride: usize,
+    step: usize,
+}
+
+impl Plane {
+    const fn index(self, x: usize, y: usize) -> usize {
+        self.offset + y * self.stride + x * self.step
+    }
+}
+
+impl Planes {
+    /// Returns the planes of a frame, or `None` if its size in bytes overflows.
+    fn new(layout: FrameLayout, height: usize, stride: usize) -> Option<Self> {
+        let size = layout.sample_size();
+        let (horizontal, vertical) = layout.subsampling();
+        let chroma_height = height.div_ceil(vertical);
+        let luma_len = stride.checked_mul(height)?;
+        let luma = Plane {
+            offset: 0,
+            stride,
+            step: size,
+        };
+        let planes = match layout {
+            FrameLayout::I420 | FrameLayout::I422 | FrameLayout::I444 => {
+                let chroma_stride = stride.div_ceil(horizontal);
+                let chroma_len = chroma_stride.checked_mul(chroma_height)?;
+                let len = luma_len.checked_add(chroma_len.checked_mul(2)?)?;
+                let plane = |offset| Plane {
+                    offset,
+                    stride: chroma_stride,
+                    step: 1,
+                };
+                Self {
+                    luma,
+                    cb: plane(luma_len),
+                    cr: plane(luma_len + chroma_len),
+                    len,
+                }
+            }
+            FrameLayout::Nv12 | FrameLayout::Nv21 | FrameLayout::P010 => {
+                let chroma_stride = (stride / size).div_ceil(horizontal).checked_mul(2 * size)?;
+                let len = luma_len.checked_add(chroma_stride.checked_mul(chroma_height)?)?;
+                let (cb, cr) = match layout {
+                    FrameLayout::Nv21 => (size, 0),
+                    _ => (0, size),
+                };
+                let plane = |offset| Plane {
+                    offset: luma_len + offset,
+                    stride: chroma_stride,
+                    step: 2 * size,
+                };
+                Self {
+                    luma,
+                    cb: plane(cb),
+                    cr: plane(cr),
+                    len,
+                }
+            }
+            FrameLayout::Yuyv | FrameLayout::Uyvy => {
+                let (luma, cb, cr) = match layout {
+                    FrameLayout::Yuyv => (0, 1, 3),
+                    _ => (1, 0, 2),
+                };
+                let plane = |offset, step| Plane {
+                    offset,
+                    stride,
+                    step,
+                };
+                Self {
+                    luma: plane(luma, 2),
+                    cb: plane(cb, 4),
+                    cr: plane(cr, 4),
+                    len: luma_len,
+                }
+            }
+        };
+        Some(planes)
+    }
+}
+
+/// Returns the chroma samples that the luma sample at `x` interpolates between, and the weight of
+/// the second, given the subsampling `factor` and twice the `offset` of the first chroma sample.
+#[allow(clippy::cast_precision_loss)]
+fn upsample_tap(
+    x: usize,
+    factor: usize,
+    offset: usize,
+    len: usize,
+    filter: ChromaFilter,
+) -> (usize, usize, f64) {
+    // Positions are in halves of a luma sample, shifted by a chroma sample to stay positive.
+    let scale = 2 * factor;
+    let position = 2 * x + scale - offset;
+    match filter {
+        ChromaFilter::Nearest => {
+            let nearest = ((position + factor - 1) / scale - 1).min(len - 1);
+            (nearest, nearest, 0.0)
+        }
+        ChromaFilter::Bilinear => {
+            let next = position / scale;
+            let weight = (position % scale) as f64 / scale as f64;
+            (
+                next.saturating_sub(1).min(len - 1),
+                next.min(len - 1),
+                weight,
+            )
+        }
+    }
+}
+
+/// Calls `f` with each of the `len` luma samples that contribute to the chroma sample at `c`, and
+/// its weight, given the subsampling `factor` and twice the `offset` of the first chroma sample.
+#[allow(clippy::cast_precision_loss)]
+fn downsample_taps(
+    c: usize,
+    factor: usize,
+    offset: usize,
+    len: usize,
+    filter: ChromaFilter,
+    mut f: impl FnMut(usize, f64),
+) {
+    // The site of the chroma sample, in halves of a luma sample.
+    let site = 2 * c * factor + offset;
+    match filter {
+        ChromaFilter::Nearest => f((site / 2).min(len - 1), 1.0),
+        ChromaFilter::Bilinear => {
+            let scale = 2 * factor;
+            for x in (site / 2).saturating_sub(factor)..=(site / 2 + factor).min(len - 1) {
+                let distance = (2 * x).abs_diff(site);
+                if distance < scale {
+                    f(x, (scale - distance) as f64);
+                }
+            }
+        }
+    }
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn into_rgbf32([red, green, blue]: [f64; 3]) -> Rgbf32 {
+    Rgbf32::from_rgb(red as f32, green as f32, blue as f32)
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::{rgb::Rgb888, ycbcr::YCbCrF32};
+
+    const RED: Rgb888 = Rgb888::from_rgb(255, 0, 0);
+
+    fn encode<const N: usize>(frame: Frame, pixels: &[Rgb888], filter: ChromaFilter) -> [u8; N] {
+        let mut data = [0; N];
+        let src = ImageView::new(pixels, frame.width(), frame.height()).unwrap();
+        frame.encode(src, &mut data, filter);
+        data
+    }
+
+    fn decode<const N: usize>(frame: Frame, data: &[u8], filter: ChromaFilter) -> [Rgbf32; N] {
+        let mut pixels = [Rgbf32::default(); N];
+        let mut dst = ImageViewMut::new(&mut pixels, frame.width(), frame.height()).unwrap();
+        frame.decode(data, &mut dst, filter);
+        pixels
+    }
+
+    #[test]
+    fn buffer_lens() {
+        let len = |layout, width, height| Frame::new(layout, width, height).buffer_len();
+        assert_eq!(len(FrameLayout::I420, 4, 4), 16 + 4 + 4);
+        assert_eq!(len(FrameLayout::I420, 3, 3), 9 + 4 + 4);
+        assert_eq!(len(FrameLayout::I422, 4, 4), 16 + 8 + 8);
+        assert_eq!(len(FrameLayout::I444, 4, 4), 16 * 3);
+        assert_eq!(len(FrameLayout::Nv12, 3, 3), 9 + 8);
+        assert_eq!(len(FrameLayout::Yuyv, 3, 1), 8);
+        assert_eq!(len(FrameLayout::P010, 2, 2), 8 + 4);
+        assert_eq!(len(FrameLayout::Nv21, 0, 0), 0);
+    }
+
+    #[test]
+    fn planar_and_semi_planar_bytes() {
+        // ITU-R BT.709 red, in 8-bit limited range.
+        let pixels = [RED; 4];
+        let frame = Frame::new(FrameLayout::I420, 2, 2);
+        let i420: [u8; 6] = encode(frame, &pixels, ChromaFilter::Bilinear);
+        assert_eq!(i420, [63, 63, 63, 63, 102, 240]);
+        let frame = Frame::new(FrameLayout::Nv21, 2, 2);
+        let nv21: [u8; 6] = encode(frame, &pixels, ChromaFilter::Bilinear);
+        assert_eq!(nv21, [63, 63, 63, 63, 240, 102]);
+    }
+
+    #[test]
+    fn packed_bytes() {
+        // An odd width repeats the last pixel.
+        let pixels = [RED, RED, Rgb888::from_rgb(0, 0, 0)];
+        let frame = Frame::new(FrameLayout::Yuyv, 3, 1);
+        let yuyv: [u8; 8] = encode(frame, &pixels, ChromaFilter::Nearest);
+        assert_eq!(yuyv, [63, 102, 63, 240, 16, 128, 16, 128]);
+        let frame = Frame::new(FrameLayout::Uyvy, 3, 1);
+        let uyvy: [u8; 8] = encode(frame, &pixels, ChromaFilter::Nearest);
+        assert_eq!(uyvy, [102, 63, 240, 63, 128, 16, 128, 16]);
+    }
+
+    #[test]
+    fn p010_is_msb_aligned() {
+        // White is 940 and neutral chroma 512, shifted into the high 10 bits.
+        let frame = Frame::new(FrameLayout::P010, 2, 2);
+        assert_eq!(frame.format().bits(), 10);
+        let white = Rgb888::from_rgb(255, 255, 255);
+        let data: [u8; 12] = encode(frame, &[white; 4], ChromaFilter::Bilinear);
+        let [y0, y1] = (940_u16 << 6).to_le_bytes();
+        let [c0, c1] = (512_u16 << 6).to_le_bytes();
+        assert_eq!(data, [y0, y1, y0, y1, y0, y1, y0, y1, c0, c1, c0, c1]);
+
+        let decoded: [Rgbf32; 4] = decode(frame, &data, ChromaFilter::Bilinear);
+        for pixel in decoded {
+            let (r, g, b) = pixel.into_rgb();
+            assert!((r - 1.0).abs() < 1e-6 && (g - 1.0).abs() < 1e-6 && (b - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn stride_pads_every_plane() {
+        let frame = Frame::new(FrameLayout::Nv12, 2, 2).with_stride(4);
+        let data: [u8; 12] = encode(frame, &[RED; 4], ChromaFilter::Bilinear);
+        assert_eq!(data, [63, 63, 0, 0, 63, 63, 0, 0, 102, 240, 0, 0]);
+        let frame = Frame::new(FrameLayout::I420, 2, 2).with_stride(4);
+        let data: [u8; 12] = encode(frame, &[RED; 4], ChromaFilter::Bilinear);
+        assert_eq!(data, [63, 63, 0, 0, 63, 63, 0, 0, 102, 0, 240, 0]);
+    }
+
+    #[test]
+    fn upsampling_respects_siting() {
+        // Blue color differences of 0 and `c` in full range, under four pixels.
+        let format = Format::new(Matrix::Bt601, Range::Full, 8);
+        let frame = Frame::new(FrameLayout::I422, 4, 1).with_format(format);
+        let data = [128, 128, 128, 128, 128, 192, 128, 128];
+        let c = 64.0 / 255.0;
+        let cases = [
+            (ChromaSiting::Left, ChromaFilter::Nearest, [0.0, 0.0, c, c]),
+            (
+                ChromaSiting::Left,
+                ChromaFilter::Bilinear,
+                [0.0, c / 2.0, c, c],
+            ),
+            (
+                ChromaSiting::Center,
+                ChromaFilter::Nearest,
+                [0.0, 0.0, c, c],
+            ),
+            (
+                ChromaSiting::Center,
+                ChromaFilter::Bilinear,
+                [0.0, c / 4.0, c * 0.75, c],
+            ),
+        ];
+        for (siting, filter, expected) in cases {
+            let decoded: [Rgbf32; 4] = decode(frame.with_siting(siting), &data, filter);
+            for (pixel, expected) in decoded.into_iter().zip(expected) {
+                let cb = YCbCrF32::from_rgb_color(pixel, Matrix::Bt601).cb();
+                assert!(
+                    (f64::from(cb) - expected).abs() < 1e-6,
+                    "{siting:?} {filter:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn downsampling_respects_siting() {
+        // Black has no color difference, and blue a blue difference of 0.5, coded as 255.
+        let format = Format::new(Matrix::Bt601, Range::Full, 8);
+        let frame = Frame::new(FrameLayout::I422, 4, 1).with_format(format);
+        let black = Rgb888::from_rgb(0, 0, 0);
+        let blue = Rgb888::from_rgb(0, 0, 255);
+        let pixels = [black, black, blue, blue];
+        let cases = [
+            (ChromaSiting::Left, ChromaFilter::Nearest, [128, 255]),
+            // Weights of 1, 2, 1 around each site: 0.5 × 3 / 4 = 0.375.
+            (ChromaSiting::Left, ChromaFilter::Bilinear, [128, 224]),
+            (ChromaSiting::Center, ChromaFilter::Nearest, [128, 255]),
+            // Weights of 1, 3, 3, 1 around each site, without those outside of the image.
+            (ChromaSiting::Center, ChromaFilter::Bilinear, [146, 237]),
+        ];
+        for (siting, filter, expected) in cases {
+            let data: [u8; 8] = encode(frame.with_siting(siting), &pixels, filter);
+            assert_eq!([data[4], data[5]], expected, "{siting:?} {filter:?}");
+        }
+    }
+
+    #[test]
+    fn round_trips_4_2_0() {
+        // A flat color survives subsampling, apart from quantization.
+        let color = Rgb888::from_rgb(200, 120, 40);
+        for layout in [
+            FrameLayout::I420,
+            FrameLayout::Nv12,
+            FrameLayout::Nv21,
+            FrameLayout::P010,
+        ] {
+            let frame = Frame::new(layout, 3, 3).with_siting(ChromaSiting::TopLeft);
+            let data: [u8; 34] = encode(frame, &[color; 9], ChromaFilter::Bilinear);
+            let mut decoded = [Rgb888::default(); 9];
+            let mut dst = ImageViewMut::new(&mut decoded, 3, 3).unwrap();
+            frame.decode(&data, &mut dst, ChromaFilter::Bilinear);
+            for pixel in decoded {
+                let (r, g, b) = pixel.into_rgb();
+                assert!(r.abs_diff(200) <= 1 && g.abs_diff(120) <= 1 && b.abs_diff(40) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn full_range_4_4_4_is_lossless_for_grays() {
+        let format = Format::new(Matrix::Bt709, Range::Full, 8);
+        let frame = Frame::new(FrameLayout::I444, 16, 16).with_format(format);
+        let mut pixels = [Rgb888::default(); 256];
+        for (value, pixel) in (0..=u8::MAX).zip(&mut pixels) {
+            *pixel = Rgb888::from_rgb(value, value, value);
+        }
+        let data: [u8; 768] = encode(frame, &pixels, ChromaFilter::Nearest);
+        let mut decoded = [Rgb888::default(); 256];
+        let mut dst = ImageViewMut::new(&mut decoded, 16, 16).unwrap();
+        frame.decode(&data, &mut dst, ChromaFilter::Nearest);
+        assert_eq!(decoded, pixels);
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer is smaller than the frame")]
+    fn buffer_too_small() {
+        let frame = Frame::new(FrameLayout::Nv12, 2, 2);
+        let _: [u8; 5] = encode(frame, &[RED; 4], ChromaFilter::Nearest);
+    }
+
+    #[test]
+    #[should_panic(expected = "stride is smaller than a row")]
+    fn stride_too_small() {
+        let _ = Frame::new(FrameLayout::Yuyv, 3, 1).with_stride(6);
+    }
+
+    #[test]
+    #[should_panic(expected = "format must have the bit depth of the layout")]
+    fn format_bits_differ() {
+        let _ = Frame::new(FrameLayout::P010, 2, 2).with_format(Format::new(
+            Matrix::Bt2020,
+            Range::Limited,
+            8,
+        ));
+    }
+}
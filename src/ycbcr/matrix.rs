@@ -0,0 +1,99 @@
+/// The matrix coefficients that derive luma and color differences from nonlinear `R'G'B'`.
+///
+/// Each standard defines the weights `Kr` and `Kb` of red and blue in luma, from which the weight
+/// of green, `1 - Kr - Kb`, and the scale of the color differences follow. All are the
+/// non-constant luminance forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Matrix {
+    /// ITU-R BT.601, used by standard definition video and JPEG.
+    Bt601,
+
+    /// ITU-R BT.709, used by high definition video.
+    Bt709,
+
+    /// ITU-R BT.2020, used by ultra high definition video.
+    Bt2020,
+}
+
+impl Matrix {
+    /// Returns the weights `Kr` and `Kb` of red and blue in luma.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gem::ycbcr::Matrix;
+    ///
+    /// assert_eq!(Matrix::Bt709.kr_kb(), (0.2126, 0.0722));
+    /// ```
+    #[must_use]
+    pub const fn kr_kb(self) -> (f64, f64) {
+        match self {
+            Self::Bt601 => (0.299, 0.114),
+            Self::Bt709 => (0.2126, 0.0722),
+            Self::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+
+    /// Converts nonlinear `R'G'B'`, within `0.0..=1.0`, into `Y'`, within `0.0..=1.0`, and `Cb` and
+    /// `Cr`, within `-0.5..=0.5`.
+    pub(crate) fn rgb_to_ycbcr(self, [r, g, b]: [f64; 3]) -> [f64; 3] {
+        let (kr, kb) = self.kr_kb();
+        let y = kr * r + (1.0 - kr - kb) * g + kb * b;
+        [
+            y,
+            (b - y) / (2.0 * (1.0 - kb)),
+            (r - y) / (2.0 * (1.0 - kr)),
+        ]
+    }
+
+    /// Converts `Y'CbCr` into nonlinear `R'G'B'`.
+    pub(crate) fn ycbcr_to_rgb(self, [y, cb, cr]: [f64; 3]) -> [f64; 3] {
+        let (kr, kb) = self.kr_kb();
+        let r = y + 2.0 * (1.0 - kr) * cr;
+        let b = y + 2.0 * (1.0 - kb) * cb;
+        let g = (y - kr * r - kb * b) / (1.0 - kr - kb);
+        [r, g, b]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_difference_coefficients() {
+        // The coefficients of Cb and Cr as written in ITU-R BT.601 and BT.709.
+        let cases = [
+            (
+                Matrix::Bt601,
+                [-0.168_736, -0.331_264, 0.5],
+                [0.5, -0.418_688, -0.081_312],
+            ),
+            (
+                Matrix::Bt709,
+                [-0.114_572, -0.385_428, 0.5],
+                [0.5, -0.454_153, -0.045_847],
+            ),
+        ];
+        for (matrix, cb, cr) in cases {
+            for (i, (cb, cr)) in cb.into_iter().zip(cr).enumerate() {
+                let mut rgb = [0.0; 3];
+                rgb[i] = 1.0;
+                let [_, blue, red] = matrix.rgb_to_ycbcr(rgb);
+                assert!((blue - cb).abs() < 1e-6, "{matrix:?} {i}");
+                assert!((red - cr).abs() < 1e-6, "{matrix:?} {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        for matrix in [Matrix::Bt601, Matrix::Bt709, Matrix::Bt2020] {
+            let rgb = [0.9, 0.3, 0.6];
+            let round_trip = matrix.ycbcr_to_rgb(matrix.rgb_to_ycbcr(rgb));
+            for (a, b) in rgb.iter().zip(round_trip) {
+                assert!((a - b).abs() < 1e-12);
+            }
+        }
+    }
+}